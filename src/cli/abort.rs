@@ -0,0 +1,106 @@
+//! Graceful abort on Ctrl-C (SIGINT) and SIGTERM.
+//!
+//! The first signal asks the run to stop: no new timed operation is started,
+//! the one in flight gets [`GRACE_PERIOD`] to finish, and the release then
+//! fails with [`CliError::Aborted`], which saves the release state, prints
+//! how to continue and cleans up the temp clone like any other failure.
+//! Child processes are spawned with `kill_on_drop`, so dropping an operation
+//! stops them. A second signal exits immediately.
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How long an in-flight operation may finish after an abort was requested
+pub const GRACE_PERIOD: Duration = Duration::from_secs(15);
+
+/// Exit code of a run stopped by a second signal
+const FORCED_EXIT_CODE: i32 = 130;
+
+static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+static SIGNAL: OnceLock<&'static str> = OnceLock::new();
+
+fn token() -> &'static CancellationToken {
+    TOKEN.get_or_init(CancellationToken::new)
+}
+
+/// Start listening for SIGINT / SIGTERM
+///
+/// Must be called inside the Tokio runtime.
+pub fn install() {
+    tokio::spawn(async {
+        let signal = next_signal().await;
+        let _ = SIGNAL.set(signal);
+        token().cancel();
+        eprintln!(
+            "\n⏹  {} received: stopping after the current operation (up to {}s). Press Ctrl-C again to exit immediately.",
+            signal,
+            GRACE_PERIOD.as_secs()
+        );
+
+        next_signal().await;
+        eprintln!("\n⏹  Exiting immediately; the temp clone and draft release are left as they are.");
+        std::process::exit(FORCED_EXIT_CODE);
+    });
+}
+
+/// Signal that requested the abort, if any
+pub fn requested() -> Option<&'static str> {
+    token().is_cancelled().then(|| SIGNAL.get().copied().unwrap_or("signal"))
+}
+
+/// Completes once an abort is requested
+pub async fn cancelled() -> &'static str {
+    token().cancelled().await;
+    SIGNAL.get().copied().unwrap_or("signal")
+}
+
+/// Fail with [`CliError::Aborted`] if an abort was requested
+pub fn check(operation: &str) -> Result<()> {
+    match requested() {
+        Some(_) => Err(error(operation)),
+        None => Ok(()),
+    }
+}
+
+/// [`CliError::Aborted`] for `operation`
+pub fn error(operation: &str) -> ReleaseError {
+    ReleaseError::Cli(CliError::Aborted {
+        operation: operation.to_string(),
+        signal: requested().unwrap_or("signal").to_string(),
+    })
+}
+
+/// Turn the failure of an operation interrupted by the abort into [`CliError::Aborted`]
+///
+/// Ctrl-C in a terminal also reaches child processes, which then fail on
+/// their own before the grace period is over.
+pub fn or_aborted(operation: &str, err: ReleaseError) -> ReleaseError {
+    match requested() {
+        Some(_) if !matches!(err, ReleaseError::Cli(CliError::Aborted { .. })) => error(operation),
+        _ => err,
+    }
+}
+
+#[cfg(unix)]
+async fn next_signal() -> &'static str {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => tokio::select! {
+            _ = tokio::signal::ctrl_c() => "SIGINT",
+            _ = terminate.recv() => "SIGTERM",
+        },
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+            "SIGINT"
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn next_signal() -> &'static str {
+    let _ = tokio::signal::ctrl_c().await;
+    "Ctrl-C"
+}
@@ -3,7 +3,8 @@
 //! This module provides minimal CLI argument parsing.
 //! The tool is designed to "just work" - point it at a repo, it releases.
 
-use clap::Parser;
+use crate::github::{ExistingAssetPolicy, PrereleasePolicy};
+use clap::{Parser, Subcommand};
 
 /// Simple release tool for single Rust packages
 #[derive(Parser, Debug)]
@@ -17,12 +18,510 @@ Usage:
   kodegen_bundler_release <source>
   kodegen_bundler_release cyrup-ai/kodegen-tools-filesystem
   kodegen_bundler_release /path/to/local/repo
-  kodegen_bundler_release https://github.com/cyrup-ai/kodegen-tools-filesystem"
+  kodegen_bundler_release https://github.com/cyrup-ai/kodegen-tools-filesystem
+  kodegen_bundler_release rebuild v1.2.3 cyrup-ai/kodegen-tools-filesystem
+  kodegen_bundler_release bundle inspect target/release/bundle/deb/app_1.2.3_amd64.deb",
+    subcommand_negates_reqs = true
 )]
 pub struct Args {
+    /// Command to run instead of a full release
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Repository source: local path, GitHub URL, or org/repo
-    #[arg(index = 1, value_name = "SOURCE")]
-    pub source: String,
+    #[arg(index = 1, value_name = "SOURCE", required = true)]
+    pub source: Option<String>,
+
+    /// Non-interactive CI mode: never prompt, skip shell rc/.env sourcing,
+    /// fail on warnings, and group log output for GitHub Actions
+    #[arg(long, env = "KODEGEN_CI", global = true)]
+    pub ci: bool,
+
+    /// Run release phases directly in the local repository instead of a temp clone
+    /// (uses uncommitted changes; a backup ref is recorded first)
+    #[arg(long)]
+    pub in_place: bool,
+
+    /// What to do when the release already has an asset with the same name
+    /// (identical checksums are always skipped)
+    #[arg(long, value_enum, default_value_t = ExistingAssetPolicy::Skip, value_name = "POLICY")]
+    pub on_existing_asset: ExistingAssetPolicy,
+
+    /// Which releases are marked as a GitHub prerelease: `zero-major` (0.x and
+    /// -rc/-beta versions), `pre-tag` (only -rc/-beta), `always` or `never`
+    /// [default: `prerelease` in release.toml, else zero-major]
+    #[arg(long, value_enum, value_name = "POLICY", env = "KODEGEN_PRERELEASE")]
+    pub prerelease: Option<PrereleasePolicy>,
+
+    /// Maximum number of release assets uploaded at the same time
+    #[arg(long, value_name = "N", default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=16), env = "KODEGEN_UPLOAD_CONCURRENCY")]
+    pub upload_concurrency: u16,
+
+    /// Release channel: `stable` releases the tagged Cargo.toml version,
+    /// `nightly` builds `<version>-nightly.<YYYYMMDD>` from main into the rolling `nightly` release
+    #[arg(long, value_enum, default_value_t = ReleaseChannel::Stable, value_name = "CHANNEL", env = "KODEGEN_CHANNEL")]
+    pub channel: ReleaseChannel,
+
+    /// Skip checking publishable crates with their published feature set and
+    /// `--all-features` before the release
+    #[arg(long)]
+    pub skip_feature_check: bool,
+
+    /// Skip the `cargo fmt` / `cargo clippy` gates configured in
+    /// `[package.metadata.release.lints]`
+    #[arg(long)]
+    pub skip_lint: bool,
+
+    /// Skip the RustSec advisory check configured in
+    /// `[package.metadata.release.audit]`
+    #[arg(long)]
+    pub skip_audit: bool,
+
+    /// Skip the cargo-deny license and ban checks configured in
+    /// `[package.metadata.release.deny]`
+    #[arg(long)]
+    pub skip_deny: bool,
+
+    /// Run the test suite in the release clone before releasing (see
+    /// `[package.metadata.release.tests]`; defaults to its `run` setting)
+    #[arg(long, conflicts_with = "no_run_tests")]
+    pub run_tests: bool,
+
+    /// Do not run the test suite, even if `[package.metadata.release.tests]` sets `run = true`
+    #[arg(long)]
+    pub no_run_tests: bool,
+
+    /// Skip waiting for the published crates to appear in the crates.io index
+    #[arg(long)]
+    pub skip_publish_verification: bool,
+
+    /// Skip building and uploading the Mac App Store package
+    /// (configured in `[package.metadata.release.mac-app-store]`)
+    #[arg(long)]
+    pub skip_mac_app_store: bool,
+
+    /// Skip submitting MSIX packages to the Microsoft Store
+    /// (configured in `[package.metadata.release.store]`)
+    #[arg(long)]
+    pub skip_store_submission: bool,
+
+    /// Attach the log of a failed bundler run to the draft release (as
+    /// bundle-log-<platform>-<id>.log; removed again before publishing)
+    #[arg(long)]
+    pub attach_bundle_logs: bool,
+
+    /// Upload artifacts to the GitHub release only, not to the configured mirrors
+    /// (`[[package.metadata.release.mirrors]]`)
+    #[arg(long)]
+    pub skip_mirrors: bool,
+
+    /// Skip a release phase: github-release (reuse the tag's existing release),
+    /// build, bundles, upload or github-publish (repeatable)
+    #[arg(long = "skip", value_name = "PHASE", value_parser = super::phase_selection::parse_release_phase)]
+    pub skip_phases: Vec<crate::state::ReleasePhase>,
+
+    /// Run only these release phases (repeatable; see --skip)
+    #[arg(long = "only", value_name = "PHASE", value_parser = super::phase_selection::parse_release_phase)]
+    pub only_phases: Vec<crate::state::ReleasePhase>,
+
+    /// Release even if local HEAD and the remote release branch have diverged
+    #[arg(long)]
+    pub allow_diverged: bool,
+
+    /// Release an existing tag (e.g. one pushed by CI) instead of main:
+    /// the clone is checked out at TAG, whose Cargo.toml version must match it
+    #[arg(long, value_name = "TAG")]
+    pub from_tag: Option<String>,
+
+    /// Delete an already published release for TAG and recreate it
+    /// (must name the release's tag; asks for confirmation unless in CI mode)
+    #[arg(long, value_name = "TAG")]
+    pub force_recreate_release: Option<String>,
+
+    /// Adopt what an earlier run of this version already did on GitHub (the
+    /// draft, or uploads recorded in its release manifest) without asking
+    #[arg(long)]
+    pub adopt_existing: bool,
+
+    /// Where the changelog in the release notes comes from: `commits` since
+    /// the previous tag, or the merged pull requests they belong to
+    #[arg(long, value_enum, default_value_t = NotesSource::Commits, value_name = "SOURCE")]
+    pub notes_source: NotesSource,
+
+    /// Import a Developer ID .p12 into a temporary keychain for this release
+    /// (macOS only; password from APPLE_CERTIFICATE_PASSWORD)
+    #[arg(long, value_name = "P12", global = true)]
+    pub import_p12: Option<std::path::PathBuf>,
+
+    /// Release during a freeze window from release.toml (`[[freeze]]`);
+    /// the reason is recorded in the release report
+    #[arg(long, value_name = "REASON")]
+    pub override_freeze: Option<String>,
+
+    /// Release profile from release.toml (`[profile.NAME]`), e.g. staging:
+    /// overrides the GitHub repository, draft-only publishing and signing
+    #[arg(long, value_name = "NAME", env = "KODEGEN_PROFILE", global = true)]
+    pub profile: Option<String>,
+
+    /// Git remote(s) to release from, in preference order
+    /// (default: origin, then upstream; comma-separated or repeated)
+    #[arg(long = "remote", value_name = "NAME", value_delimiter = ',', env = "KODEGEN_REMOTES", global = true)]
+    pub remotes: Vec<String>,
+
+    /// How git authenticates to GitHub: `auto` uses SSH when a key or agent
+    /// is available and otherwise HTTPS with GH_TOKEN/GITHUB_TOKEN
+    #[arg(long, value_enum, default_value_t = crate::git_auth::GitAuthMode::Auto, value_name = "MODE", env = "KODEGEN_GIT_AUTH", global = true)]
+    pub git_auth: crate::git_auth::GitAuthMode,
+
+    /// Override a phase timeout, e.g. `--timeout bundle=90m`
+    /// (phases: build, bundle, upload, github; repeatable)
+    #[arg(long = "timeout", value_name = "PHASE=DURATION", value_parser = super::timeouts::parse_phase_timeout, global = true)]
+    pub timeouts: Vec<(super::timeouts::TimedPhase, std::time::Duration)>,
+
+    /// Also write the release manifest (release-manifest.json) to this path
+    #[arg(long, value_name = "PATH", global = true)]
+    pub manifest_out: Option<std::path::PathBuf>,
+
+    /// Write a Markdown report of the run (phase timings, artifacts,
+    /// published crates, warnings and cleanup actions) to this path
+    #[arg(long, value_name = "PATH", global = true)]
+    pub report: Option<std::path::PathBuf>,
+
+    /// Also write the report as HTML to this path
+    #[arg(long, value_name = "PATH", global = true)]
+    pub report_html: Option<std::path::PathBuf>,
+
+    /// Attach the report to the GitHub release (release-report.md, plus
+    /// release-report.html with --report-html)
+    #[arg(long, global = true)]
+    pub attach_report: bool,
+
+    /// Print nothing but the result: the release URL (and the --manifest-out
+    /// path) on success; failures still go to stderr
+    #[arg(long, short = 'q', global = true)]
+    pub quiet: bool,
+
+    /// How failures are reported on stderr: readable text, or one JSON object
+    /// with a stable error code (exit codes are the same either way)
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Human, value_name = "FORMAT", env = "KODEGEN_ERROR_FORMAT", global = true)]
+    pub error_format: ErrorFormat,
+
+    /// Abort the whole run after this long (e.g. 45m, 1h30m), saving state first
+    #[arg(long, value_name = "DURATION", value_parser = super::timeouts::parse_duration, env = "KODEGEN_DEADLINE", global = true)]
+    pub deadline: Option<std::time::Duration>,
+
+    /// Report a build, bundle or test command that prints nothing for this
+    /// long (default 10m)
+    #[arg(long, value_name = "DURATION", value_parser = super::timeouts::parse_duration, env = "KODEGEN_STALL_TIMEOUT", global = true)]
+    pub stall_timeout: Option<std::time::Duration>,
+
+    /// What to do with a stalled command: warn, kill it, or kill and retry it once
+    #[arg(long, value_enum, default_value_t = super::watchdog::StallAction::Warn, value_name = "ACTION", env = "KODEGEN_ON_STALL", global = true)]
+    pub on_stall: super::watchdog::StallAction,
+
+    /// How tags are created or moved on GitHub: `git` pushes them, `api`
+    /// writes the ref through the REST API (for tags protected by rulesets
+    /// the token may bypass)
+    #[arg(long, value_enum, default_value_t = crate::github::TagMethod::Git, value_name = "METHOD", env = "KODEGEN_TAG_VIA", global = true)]
+    pub tag_via: crate::github::TagMethod,
+
+    /// Post a commit status per release phase (`release/build`, `release/upload`,
+    /// ...) on the released commit
+    #[arg(long, env = "KODEGEN_COMMIT_STATUS")]
+    pub commit_status: bool,
+}
+
+/// Output format for failures
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// Colored message with recovery suggestions
+    #[default]
+    Human,
+    /// Single-line JSON object (code, category, exit_code, message, ...)
+    Json,
+}
+
+/// Source of the changelog in the release notes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum NotesSource {
+    /// Commits since the previous tag (grouped by crate in a workspace)
+    #[default]
+    Commits,
+    /// Merged pull requests since the previous tag, grouped by label
+    Prs,
+}
+
+/// What a release run publishes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReleaseChannel {
+    /// The version tagged by `just publish`
+    #[default]
+    Stable,
+    /// A dated snapshot of main, replacing the assets of the `nightly` release
+    Nightly,
+}
+
+/// Commands other than the default release
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Rebuild and re-upload artifacts for an existing tag
+    ///
+    /// Checks out the tag in a temp clone, rebuilds and re-bundles, then
+    /// replaces the assets on the existing GitHub release.
+    Rebuild {
+        /// Existing release tag (e.g. v1.2.3)
+        #[arg(value_name = "TAG")]
+        tag: String,
+
+        /// Repository source: local path, GitHub URL, or org/repo
+        #[arg(value_name = "SOURCE", default_value = ".")]
+        source: String,
+    },
+
+    /// Download the assets of a release listed in its release manifest
+    ///
+    /// Every asset is checked against the SHA-256 in the manifest. With
+    /// --decrypt, encrypted assets are decrypted and checked again.
+    Download {
+        /// Release tag (e.g. v1.2.3)
+        #[arg(value_name = "TAG")]
+        tag: String,
+
+        /// Repository source: local path, GitHub URL, or org/repo
+        #[arg(value_name = "SOURCE", default_value = ".")]
+        source: String,
+
+        /// Directory to download into
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        out: std::path::PathBuf,
+
+        /// Decrypt encrypted assets (`[package.metadata.release.encryption]`)
+        #[arg(long)]
+        decrypt: bool,
+
+        /// age identity file for decrypting .age assets
+        #[arg(long, value_name = "FILE", requires = "decrypt")]
+        identity: Option<std::path::PathBuf>,
+    },
+
+    /// Sign a release's assets again with the current credentials
+    ///
+    /// Downloads the platform's assets listed in the release manifest,
+    /// re-signs (and for dmg, notarizes) them, replaces them on the release
+    /// and uploads the updated manifest.
+    Resign {
+        /// Release tag (e.g. v1.2.3)
+        #[arg(value_name = "TAG")]
+        tag: String,
+
+        /// Repository source: local path, GitHub URL, or org/repo
+        #[arg(value_name = "SOURCE", default_value = ".")]
+        source: String,
+
+        /// Platforms whose assets to re-sign (dmg on macOS; exe/nsis, msi, msix on Windows)
+        #[arg(
+            long = "platform",
+            value_name = "PLATFORM",
+            value_delimiter = ',',
+            required = true,
+            value_parser = ["dmg", "exe", "nsis", "msi", "msix"]
+        )]
+        platforms: Vec<String>,
+    },
+
+    /// Compare the bundles of two releases file by file
+    ///
+    /// Downloads the platform's artifacts of both releases and reports added,
+    /// removed and changed files, with their sizes.
+    Diff {
+        /// Older release tag (e.g. v1.2.0)
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// Newer release tag (e.g. v1.3.0)
+        #[arg(value_name = "NEW")]
+        new: String,
+
+        /// Repository source: local path, GitHub URL, or org/repo
+        #[arg(value_name = "SOURCE", default_value = ".")]
+        source: String,
+
+        /// Platform whose artifacts to compare (deb, rpm, appimage, dmg, exe, msi, msix)
+        #[arg(long, value_name = "PLATFORM")]
+        platform: String,
+
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show download counts per asset and platform across recent releases
+    Stats {
+        /// Repository source: local path, GitHub URL, or org/repo
+        #[arg(value_name = "SOURCE", default_value = ".")]
+        source: String,
+
+        /// Number of most recent published releases to include
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        releases: usize,
+
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a short-lived crates.io token from trusted publishing
+    ///
+    /// Exchanges the OIDC token of a GitHub Actions job (with `permissions:
+    /// id-token: write`) for a 30-minute crates.io publish token. Works as a
+    /// `cargo:token-from-stdout` credential provider.
+    RegistryToken {
+        /// Export the token as CARGO_REGISTRY_TOKEN to the later steps of the job
+        /// (via GITHUB_ENV) instead of printing it
+        #[arg(long, conflicts_with = "revoke")]
+        github_env: bool,
+
+        /// Revoke the token in CARGO_REGISTRY_TOKEN
+        #[arg(long)]
+        revoke: bool,
+    },
+
+    /// Store a token in the OS keyring (read from stdin)
+    ///
+    /// Stored tokens are used when GH_TOKEN / CARGO_REGISTRY_TOKEN are not set
+    /// in the environment, e.g. `gh auth token | kodegen_bundler_release login github`.
+    Login {
+        /// Which token to store
+        #[arg(value_enum, default_value_t = StoredToken::Github)]
+        token: StoredToken,
+    },
+
+    /// Delete tokens stored with `login`
+    Logout {
+        /// Which token to delete (default: all)
+        #[arg(value_enum)]
+        token: Option<StoredToken>,
+    },
+
+    /// Stop a release running elsewhere on this machine and clean up after it
+    ///
+    /// Sends the release process SIGTERM (a graceful abort) and kills it if it
+    /// does not stop, kills Docker containers using its temp clone, releases
+    /// the state lock and marks the state aborted.
+    Abort {
+        /// Process ID of the release to stop (required when several are running)
+        #[arg(long, value_name = "PID")]
+        pid: Option<u32>,
+
+        /// Also delete the draft GitHub release the run created
+        #[arg(long)]
+        delete_draft: bool,
+    },
+
+    /// Work with bundles produced by this tool
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommand,
+    },
+
+    /// Print the workspace dependency graph and publish tiers
+    ///
+    /// Packages are grouped into the tiers they are published in, and each
+    /// edge points from a dependency to the package depending on it.
+    /// Packages that are not published are drawn dashed.
+    Graph {
+        /// Workspace directory
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: std::path::PathBuf,
+
+        /// Graph description language
+        #[arg(long, value_enum, default_value = "dot")]
+        format: crate::workspace::GraphFormat,
+    },
+
+    /// Show which workspace members a version bump of one crate affects
+    ///
+    /// Lists the members depending on the crate (transitively), the version
+    /// requirements that reject the new version and the members that have to
+    /// be published again because of them.
+    Impact {
+        /// Workspace member to bump
+        #[arg(value_name = "CRATE")]
+        package: String,
+
+        /// Workspace directory
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: std::path::PathBuf,
+
+        /// Version component to bump
+        #[arg(long, value_enum, default_value = "minor")]
+        bump: crate::version::Bump,
+
+        /// Exact new version instead of --bump
+        #[arg(long, value_name = "VERSION", conflicts_with = "bump")]
+        to: Option<semver::Version>,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the release configuration without releasing
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+/// Tokens `login` can store in the OS keyring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StoredToken {
+    /// GitHub token (GH_TOKEN)
+    Github,
+    /// crates.io token (CARGO_REGISTRY_TOKEN)
+    Crates,
+}
+
+impl StoredToken {
+    /// Environment variable the token stands in for
+    pub fn var(self) -> &'static str {
+        match self {
+            Self::Github => "GH_TOKEN",
+            Self::Crates => "CARGO_REGISTRY_TOKEN",
+        }
+    }
+}
+
+/// `bundle` subcommands
+#[derive(Subcommand, Debug)]
+pub enum BundleCommand {
+    /// Print metadata, file listing, version, signature status and checksum of a bundle
+    ///
+    /// Supports .deb, .rpm, .dmg, .AppImage, .exe, .msi and .msix files; nothing is
+    /// installed, mounted or executed.
+    Inspect {
+        /// Bundle file to inspect
+        #[arg(value_name = "ARTIFACT")]
+        artifact: std::path::PathBuf,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// `config` subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Check `[package.metadata.bundle]` and release.toml against their schema
+    ///
+    /// Reports unknown keys, values of the wrong type and referenced files
+    /// (icons, scripts, templates) that do not exist, with file, line and column.
+    Validate {
+        /// Package directory (holding Cargo.toml and release.toml)
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: std::path::PathBuf,
+    },
 }
 
 impl Args {
@@ -31,13 +530,132 @@ impl Args {
         Self::parse()
     }
 
+    /// Repository source for the selected command
+    pub fn source(&self) -> &str {
+        match &self.command {
+            Some(
+                Command::Rebuild { source, .. }
+                | Command::Download { source, .. }
+                | Command::Resign { source, .. }
+                | Command::Diff { source, .. }
+                | Command::Stats { source, .. },
+            ) => source,
+            Some(
+                Command::Bundle { .. }
+                | Command::Config { .. }
+                | Command::Graph { .. }
+                | Command::Impact { .. }
+                | Command::RegistryToken { .. }
+                | Command::Login { .. }
+                | Command::Logout { .. }
+                | Command::Abort { .. },
+            ) => "",
+            None => self.source.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// Whether the selected command works without a repository source
+    fn without_source(&self) -> bool {
+        matches!(
+            self.command,
+            Some(
+                Command::Bundle { .. }
+                    | Command::Config { .. }
+                    | Command::Graph { .. }
+                    | Command::Impact { .. }
+                    | Command::RegistryToken { .. }
+                    | Command::Login { .. }
+                    | Command::Logout { .. }
+                    | Command::Abort { .. }
+            )
+        )
+    }
+
     /// Validate arguments for consistency
     pub fn validate(&self) -> Result<(), String> {
-        // Validate source argument
-        if self.source.is_empty() {
+        // Validate source argument (bundle commands work on local files only)
+        if self.source().is_empty() && !self.without_source() {
             return Err("Source repository is required".to_string());
         }
 
+        if self.in_place && self.command.is_some() {
+            return Err("--in-place cannot be combined with a subcommand".to_string());
+        }
+
+        if let Some(Command::Stats { releases: 0, .. }) = &self.command {
+            return Err("--releases must be at least 1".to_string());
+        }
+
+        if let Some(Command::Rebuild { tag, .. }) = &self.command
+            && tag.trim().is_empty()
+        {
+            return Err("Tag is required for rebuild".to_string());
+        }
+
+        if self.channel == ReleaseChannel::Nightly && self.command.is_some() {
+            return Err("--channel nightly cannot be combined with a subcommand".to_string());
+        }
+
+        if self.channel == ReleaseChannel::Nightly && self.force_recreate_release.is_some() {
+            return Err("--force-recreate-release has no effect with --channel nightly".to_string());
+        }
+
+        if self.allow_diverged && self.command.is_some() {
+            return Err("--allow-diverged cannot be combined with a subcommand".to_string());
+        }
+
+        if self.from_tag.is_some() {
+            if self.command.is_some() || self.channel == ReleaseChannel::Nightly {
+                return Err("--from-tag only applies to a stable release".to_string());
+            }
+            if self.in_place {
+                return Err("--from-tag cannot be combined with --in-place".to_string());
+            }
+        }
+
+        if self.force_recreate_release.is_some() && self.command.is_some() {
+            return Err("--force-recreate-release cannot be combined with a subcommand".to_string());
+        }
+
+        if self.adopt_existing {
+            if self.command.is_some() || self.channel == ReleaseChannel::Nightly {
+                return Err("--adopt-existing only applies to a stable release".to_string());
+            }
+            if self.force_recreate_release.is_some() {
+                return Err("--adopt-existing cannot be combined with --force-recreate-release".to_string());
+            }
+            if !self.skip_phases.is_empty() || !self.only_phases.is_empty() {
+                return Err("--adopt-existing cannot be combined with --skip or --only".to_string());
+            }
+        }
+
+        if self.notes_source == NotesSource::Prs && (self.command.is_some() || self.channel == ReleaseChannel::Nightly) {
+            return Err("--notes-source only applies to a stable release".to_string());
+        }
+
+        if self.prerelease.is_some() && (self.command.is_some() || self.channel == ReleaseChannel::Nightly) {
+            return Err("--prerelease only applies to a stable release".to_string());
+        }
+
+        if !self.skip_phases.is_empty() || !self.only_phases.is_empty() {
+            if self.command.is_some() || self.channel == ReleaseChannel::Nightly {
+                return Err("--skip and --only only apply to a stable release".to_string());
+            }
+            super::phase_selection::PhaseSelection::from_flags(&self.skip_phases, &self.only_phases)?;
+        }
+
+        if (self.run_tests || self.no_run_tests)
+            && (self.command.is_some() || self.channel == ReleaseChannel::Nightly)
+        {
+            return Err("--run-tests and --no-run-tests only apply to a stable release".to_string());
+        }
+
+        if let Some(p12) = &self.import_p12
+            && !p12.is_file()
+        {
+            return Err(format!("Certificate not found: {}", p12.display()));
+        }
+
         Ok(())
     }
 }
@@ -47,6 +665,65 @@ impl Args {
 pub struct RuntimeConfig {
     /// Output manager for colored terminal output
     output: super::OutputManager,
+    /// Non-interactive CI mode with strict failure semantics
+    ci: bool,
+    /// Policy for same-named assets already on the release
+    on_existing_asset: ExistingAssetPolicy,
+    /// Prerelease policy from `--prerelease`
+    prerelease: Option<PrereleasePolicy>,
+    /// Phase timeouts given on the command line
+    timeout_overrides: Vec<(super::timeouts::TimedPhase, std::time::Duration)>,
+    /// Point in time after which the run aborts
+    deadline: Option<std::time::Instant>,
+    /// Silence after which a subprocess counts as stalled
+    stall_timeout: std::time::Duration,
+    /// What to do with a stalled subprocess
+    on_stall: super::watchdog::StallAction,
+    tag_via: crate::github::TagMethod,
+    /// Post a commit status per release phase
+    commit_status: bool,
+    /// Local copy of the release manifest
+    manifest_out: Option<std::path::PathBuf>,
+    /// Markdown report of the run
+    report_out: Option<std::path::PathBuf>,
+    /// HTML report of the run
+    report_html_out: Option<std::path::PathBuf>,
+    /// Attach the report to the GitHub release
+    attach_report: bool,
+    /// Phase timings, warnings and actions for the report (shared between clones)
+    journal: std::sync::Arc<std::sync::Mutex<super::journal::RunJournal>>,
+    /// Tag of a published release that may be deleted and recreated
+    force_recreate_release: Option<String>,
+    /// Adopt an earlier run's release without asking
+    adopt_existing: bool,
+    /// Source of the changelog in the release notes
+    notes_source: NotesSource,
+    /// Maximum number of parallel asset uploads
+    upload_concurrency: usize,
+    /// Skip the published feature-set checks
+    skip_feature_check: bool,
+    /// Skip the lint gates
+    skip_lint: bool,
+    /// Skip the advisory check
+    skip_audit: bool,
+    /// Skip the policy check
+    skip_deny: bool,
+    /// `--run-tests` (`Some(true)`) or `--no-run-tests` (`Some(false)`)
+    run_tests: Option<bool>,
+    /// Skip waiting for published crates on crates.io
+    skip_publish_verification: bool,
+    /// Skip the Mac App Store package
+    skip_mac_app_store: bool,
+    /// Skip the Microsoft Store submission
+    skip_store_submission: bool,
+    /// Skip the artifact mirrors
+    skip_mirrors: bool,
+    /// Attach failed bundler logs to the draft release
+    attach_bundle_logs: bool,
+    /// Release phases selected with `--skip` / `--only`
+    phases: super::phase_selection::PhaseSelection,
+    /// Output format for failures
+    error_format: ErrorFormat,
 }
 
 impl RuntimeConfig {
@@ -54,6 +731,88 @@ impl RuntimeConfig {
     pub fn new() -> Self {
         Self {
             output: super::OutputManager::new(false, false),
+            ci: false,
+            on_existing_asset: ExistingAssetPolicy::default(),
+            prerelease: None,
+            timeout_overrides: Vec::new(),
+            deadline: None,
+            stall_timeout: super::watchdog::DEFAULT_STALL_TIMEOUT,
+            on_stall: super::watchdog::StallAction::default(),
+            tag_via: crate::github::TagMethod::default(),
+            commit_status: false,
+            manifest_out: None,
+            report_out: None,
+            report_html_out: None,
+            attach_report: false,
+            journal: Default::default(),
+            force_recreate_release: None,
+            adopt_existing: false,
+            notes_source: NotesSource::default(),
+            upload_concurrency: 4,
+            skip_feature_check: false,
+            skip_lint: false,
+            skip_audit: false,
+            skip_deny: false,
+            run_tests: None,
+            skip_publish_verification: false,
+            skip_mac_app_store: false,
+            skip_store_submission: false,
+            skip_mirrors: false,
+            attach_bundle_logs: false,
+            phases: Default::default(),
+            error_format: ErrorFormat::default(),
+        }
+    }
+
+    /// Create runtime configuration from parsed arguments
+    pub fn from_args(args: &Args) -> Self {
+        Self {
+            output: super::OutputManager::new(false, args.quiet).with_ci_groups(args.ci),
+            ci: args.ci,
+            on_existing_asset: args.on_existing_asset,
+            prerelease: args.prerelease,
+            timeout_overrides: args.timeouts.clone(),
+            deadline: args.deadline.map(|d| std::time::Instant::now() + d),
+            stall_timeout: args.stall_timeout.unwrap_or(super::watchdog::DEFAULT_STALL_TIMEOUT),
+            on_stall: args.on_stall,
+            tag_via: args.tag_via,
+            commit_status: args.commit_status,
+            manifest_out: args
+                .manifest_out
+                .as_ref()
+                .map(|p| std::path::absolute(p).unwrap_or_else(|_| p.clone())),
+            report_out: args
+                .report
+                .as_ref()
+                .map(|p| std::path::absolute(p).unwrap_or_else(|_| p.clone())),
+            report_html_out: args
+                .report_html
+                .as_ref()
+                .map(|p| std::path::absolute(p).unwrap_or_else(|_| p.clone())),
+            attach_report: args.attach_report,
+            journal: Default::default(),
+            force_recreate_release: args.force_recreate_release.clone(),
+            adopt_existing: args.adopt_existing,
+            notes_source: args.notes_source,
+            upload_concurrency: usize::from(args.upload_concurrency),
+            skip_feature_check: args.skip_feature_check,
+            skip_lint: args.skip_lint,
+            skip_audit: args.skip_audit,
+            skip_deny: args.skip_deny,
+            run_tests: match (args.run_tests, args.no_run_tests) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            skip_publish_verification: args.skip_publish_verification,
+            skip_mac_app_store: args.skip_mac_app_store,
+            skip_store_submission: args.skip_store_submission,
+            skip_mirrors: args.skip_mirrors,
+            attach_bundle_logs: args.attach_bundle_logs,
+            // Invalid selections are rejected by `Args::validate`
+            phases: super::phase_selection::PhaseSelection::from_flags(&args.skip_phases, &args.only_phases)
+                .unwrap_or_default(),
+            error_format: args.error_format,
         }
     }
 
@@ -88,6 +847,7 @@ impl RuntimeConfig {
 
     /// Print warning message
     pub fn warning_println(&self, message: &str) -> std::io::Result<()> {
+        self.journal().warn(message);
         self.output.warn(message)
     }
 
@@ -98,6 +858,16 @@ impl RuntimeConfig {
 
 
 
+    /// Print a result line for scripts
+    ///
+    /// Shown only with `--quiet`, where it is the only output on stdout; the
+    /// normal output already reports the same result.
+    pub fn result_println(&self, message: &str) {
+        if self.output.is_quiet() {
+            println!("{}", message);
+        }
+    }
+
     /// Print indented text
     pub fn indent(&self, message: &str) -> std::io::Result<()> {
         self.output.indent(message)
@@ -107,4 +877,210 @@ impl RuntimeConfig {
     pub fn is_verbose(&self) -> bool {
         true
     }
+
+    /// Check if running in non-interactive CI mode
+    pub fn is_ci(&self) -> bool {
+        self.ci
+    }
+
+    /// Policy for same-named assets already on the release
+    pub fn on_existing_asset(&self) -> ExistingAssetPolicy {
+        self.on_existing_asset
+    }
+
+    /// Prerelease policy from `--prerelease`, `None` to use release.toml's
+    pub fn prerelease(&self) -> Option<PrereleasePolicy> {
+        self.prerelease
+    }
+
+    /// Phase timeouts given with `--timeout`
+    pub fn timeout_overrides(&self) -> &[(super::timeouts::TimedPhase, std::time::Duration)] {
+        &self.timeout_overrides
+    }
+
+    /// Silence after which a subprocess counts as stalled (`--stall-timeout`)
+    pub fn stall_timeout(&self) -> std::time::Duration {
+        self.stall_timeout
+    }
+
+    /// What to do with a stalled subprocess (`--on-stall`)
+    pub fn on_stall(&self) -> super::watchdog::StallAction {
+        self.on_stall
+    }
+
+    /// How tags are written to GitHub (`--tag-via`)
+    pub fn tag_via(&self) -> crate::github::TagMethod {
+        self.tag_via
+    }
+
+    /// Whether release phases are reported as commit statuses (`--commit-status`)
+    pub fn commit_status(&self) -> bool {
+        self.commit_status
+    }
+
+    /// Time left before `--deadline`, or `None` without a deadline
+    pub fn deadline_remaining(&self) -> Option<std::time::Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(std::time::Instant::now()))
+    }
+
+    /// Where to write a local copy of the release manifest, if requested
+    pub fn manifest_out(&self) -> Option<&std::path::Path> {
+        self.manifest_out.as_deref()
+    }
+
+    /// Where to write the Markdown report, if requested
+    pub fn report_out(&self) -> Option<&std::path::Path> {
+        self.report_out.as_deref()
+    }
+
+    /// Where to write the HTML report, if requested
+    pub fn report_html_out(&self) -> Option<&std::path::Path> {
+        self.report_html_out.as_deref()
+    }
+
+    /// Whether the report is attached to the GitHub release
+    pub fn attach_report(&self) -> bool {
+        self.attach_report
+    }
+
+    /// Record of the run for the release report
+    pub fn journal(&self) -> std::sync::MutexGuard<'_, super::journal::RunJournal> {
+        self.journal.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a destructive cleanup action for the release report
+    pub fn record_action(&self, description: &str) {
+        self.journal().action(description);
+    }
+
+    /// Tag given with `--force-recreate-release`, if any
+    pub fn force_recreate_release(&self) -> Option<&str> {
+        self.force_recreate_release.as_deref()
+    }
+
+    /// Whether an earlier run's release is adopted without asking
+    pub fn adopt_existing(&self) -> bool {
+        self.adopt_existing
+    }
+
+    /// Source of the changelog in the release notes
+    pub fn notes_source(&self) -> NotesSource {
+        self.notes_source
+    }
+
+    /// Maximum number of release assets uploaded at the same time
+    pub fn upload_concurrency(&self) -> usize {
+        self.upload_concurrency
+    }
+
+    /// Whether the published feature-set checks are skipped
+    pub fn skip_feature_check(&self) -> bool {
+        self.skip_feature_check
+    }
+
+    /// Whether the lint gates are skipped
+    pub fn skip_lint(&self) -> bool {
+        self.skip_lint
+    }
+
+    /// Whether the advisory check is skipped
+    pub fn skip_audit(&self) -> bool {
+        self.skip_audit
+    }
+
+    /// Whether the cargo-deny policy check is skipped
+    pub fn skip_deny(&self) -> bool {
+        self.skip_deny
+    }
+
+    /// Whether the test suite runs, overriding `[package.metadata.release.tests]`
+    pub fn run_tests(&self) -> Option<bool> {
+        self.run_tests
+    }
+
+    /// Whether waiting for the published crates on crates.io is skipped
+    pub fn skip_publish_verification(&self) -> bool {
+        self.skip_publish_verification
+    }
+
+    /// Whether the Mac App Store package is skipped
+    pub fn skip_mac_app_store(&self) -> bool {
+        self.skip_mac_app_store
+    }
+
+    /// Whether the Microsoft Store submission is skipped
+    pub fn skip_store_submission(&self) -> bool {
+        self.skip_store_submission
+    }
+
+    /// Whether the artifact mirrors are skipped
+    pub fn skip_mirrors(&self) -> bool {
+        self.skip_mirrors
+    }
+
+    /// Whether failed bundler logs are attached to the draft release
+    pub fn attach_bundle_logs(&self) -> bool {
+        self.attach_bundle_logs
+    }
+
+    /// Release phases selected with `--skip` / `--only`
+    pub fn phases(&self) -> &super::phase_selection::PhaseSelection {
+        &self.phases
+    }
+
+    /// Report a failed command on stderr and return its exit code
+    ///
+    /// `context` prefixes the human-readable message (e.g. "Release failed").
+    pub fn report_failure(&self, context: &str, error: &crate::error::ReleaseError) -> i32 {
+        if self.error_format == ErrorFormat::Json {
+            let report = serde_json::to_string(&error.report())
+                .unwrap_or_else(|_| format!("{{\"code\":\"{}\"}}", error.code()));
+            eprintln!("{}", report);
+            return error.exit_code();
+        }
+
+        self.error_println(&format!("{} [{}]: {}", context, error.code(), error));
+
+        // Show recovery suggestions if available
+        if self.is_verbose() {
+            let suggestions = error.recovery_suggestions();
+            if !suggestions.is_empty() {
+                let _ = self.println("\n💡 Recovery suggestions:");
+                for suggestion in suggestions {
+                    let _ = self.println(&format!("  • {}", suggestion));
+                }
+            }
+        }
+
+        error.exit_code()
+    }
+
+    /// Report a non-fatal problem
+    ///
+    /// Prints a warning normally; in CI mode the problem becomes a hard failure.
+    pub fn soft_failure(&self, message: &str) -> crate::error::Result<()> {
+        if self.ci {
+            return Err(crate::error::ReleaseError::Cli(
+                crate::error::CliError::ExecutionFailed {
+                    command: "ci_strict_mode".to_string(),
+                    reason: message.to_string(),
+                },
+            ));
+        }
+        self.journal().warn(message);
+        self.output.warn(message)?;
+        Ok(())
+    }
+
+    /// Start a collapsible log group (GitHub Actions `::group::` in CI mode)
+    pub fn group(&self, title: &str) -> std::io::Result<()> {
+        self.journal().begin_phase(title);
+        self.output.group(title)
+    }
+
+    /// End the current log group, if any
+    pub fn end_group(&self) -> std::io::Result<()> {
+        self.output.end_group()
+    }
 }
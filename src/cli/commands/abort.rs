@@ -0,0 +1,267 @@
+//! `abort`: stop a release started elsewhere on this machine.
+//!
+//! Running releases are found through the tracking files written when their
+//! temp clone is created. The release process gets SIGTERM, which takes the
+//! graceful abort path (state saved, temp clone cleaned up), and is killed if
+//! it has not exited after the grace period. Docker containers that mount its
+//! temp clone (`cross`, bundler builds) are killed, since stopping the docker
+//! client does not stop them. A temp clone left behind keeps its state,
+//! marked aborted and unlocked, for inspection; the next release removes it.
+//!
+//! With `--delete-draft` the draft GitHub release recorded in the state is
+//! deleted too. The state is read before the process is stopped, because a
+//! graceful abort deletes it with the temp clone.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use crate::state::ReleaseState;
+use crate::EnvConfig;
+use std::path::Path;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+
+use super::temp_clone::{ReleaseTracking, tracked_releases};
+
+/// How long the release may take to stop after SIGTERM before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(crate::cli::abort::GRACE_PERIOD.as_secs() + 30);
+
+/// Stop the release with `pid` (or the only one running) and clean up after it
+pub(super) async fn execute_abort(
+    pid: Option<u32>,
+    delete_draft: bool,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let release = select_release(pid)?;
+    let temp_path = release.temp_path.clone();
+    config
+        .println(&format!(
+            "⏹  Aborting release {} (pid {}, started {})",
+            temp_path.display(),
+            release.pid,
+            release.started_at
+        ))
+        .expect("Failed to write to stdout");
+
+    // Read first: a graceful abort removes the temp clone with the state
+    let state = match crate::state::load_release_state(&temp_path).await {
+        Ok(state) => state,
+        Err(e) => {
+            config
+                .warning_println(&format!("⚠️  Could not read the release state: {}", e))
+                .expect("Failed to write to stdout");
+            None
+        }
+    };
+
+    stop_process(release.pid, config).await;
+    kill_containers(&temp_path, config).await;
+
+    if temp_path.exists() {
+        mark_aborted(&temp_path, config).await?;
+    } else {
+        config
+            .verbose_println("✓ The release removed its temp clone")
+            .expect("Failed to write to stdout");
+    }
+
+    if delete_draft {
+        delete_draft_release(state.as_ref(), config, env_config).await?;
+    } else if let Some(url) = state
+        .as_ref()
+        .and_then(|state| state.github_state.as_ref())
+        .filter(|github| github.draft)
+        .and_then(|github| github.html_url.as_deref())
+    {
+        config
+            .println(&format!("ℹ️  The draft release was kept (delete it with --delete-draft): {}", url))
+            .expect("Failed to write to stdout");
+    }
+
+    config
+        .success_println("✓ Release aborted")
+        .expect("Failed to write to stdout");
+    Ok(0)
+}
+
+/// The tracked release with `pid`, or the only running one
+fn select_release(pid: Option<u32>) -> Result<ReleaseTracking> {
+    let releases = tracked_releases();
+    if let Some(pid) = pid {
+        return releases.into_iter().find(|release| release.pid == pid).ok_or_else(|| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("No release with pid {} is tracked on this machine", pid),
+            })
+        });
+    }
+
+    let mut running: Vec<ReleaseTracking> = releases.into_iter().filter(|release| is_running(release.pid)).collect();
+    match running.len() {
+        0 => Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: "No running release found on this machine (pass --pid for one whose process already exited)"
+                .to_string(),
+        })),
+        1 => Ok(running.remove(0)),
+        _ => Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Several releases are running; pick one with --pid: {}",
+                running
+                    .iter()
+                    .map(|release| format!("{} ({})", release.pid, release.temp_path.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        })),
+    }
+}
+
+/// Whether a process with `pid` exists
+fn is_running(pid: u32) -> bool {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+    sys.process(Pid::from_u32(pid)).is_some()
+}
+
+/// SIGTERM the release, then kill it if it is still running after [`STOP_TIMEOUT`]
+async fn stop_process(pid: u32, config: &RuntimeConfig) {
+    let mut sys = System::new();
+    let pid = Pid::from_u32(pid);
+    sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    let Some(process) = sys.process(pid) else {
+        config
+            .verbose_println("   The release process already exited")
+            .expect("Failed to write to stdout");
+        return;
+    };
+
+    // Without SIGTERM (Windows) the process can only be killed
+    if process.kill_with(Signal::Term).is_none() {
+        process.kill();
+    }
+    config
+        .println(&format!("   Waiting up to {}s for the release to stop...", STOP_TIMEOUT.as_secs()))
+        .expect("Failed to write to stdout");
+
+    let deadline = std::time::Instant::now() + STOP_TIMEOUT;
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        if sys.process(pid).is_none() {
+            config
+                .success_println("✓ Release process stopped")
+                .expect("Failed to write to stdout");
+            return;
+        }
+    }
+
+    if let Some(process) = sys.process(pid) {
+        process.kill();
+    }
+    config
+        .warning_println("⚠️  The release did not stop in time and was killed; its temp clone was left behind")
+        .expect("Failed to write to stdout");
+}
+
+/// Kill Docker containers that mount the release's temp clone
+async fn kill_containers(temp_path: &Path, config: &RuntimeConfig) {
+    if which::which("docker").is_err() {
+        return;
+    }
+    let filter = format!("volume={}", temp_path.display());
+    let ids = match Exec::new("docker").args(["ps", "--quiet", "--filter", &filter]).stdout().await {
+        Ok(ids) => ids,
+        Err(e) => {
+            config
+                .verbose_println(&format!("   Could not list Docker containers: {}", e))
+                .expect("Failed to write to stdout");
+            return;
+        }
+    };
+    let ids: Vec<&str> = ids.lines().filter(|id| !id.is_empty()).collect();
+    if ids.is_empty() {
+        return;
+    }
+
+    match Exec::new("docker").arg("kill").args(&ids).run().await {
+        Ok(_) => config
+            .success_println(&format!("✓ Killed {} Docker container(s)", ids.len()))
+            .expect("Failed to write to stdout"),
+        Err(e) => config
+            .warning_println(&format!("⚠️  Failed to kill Docker containers {}: {}", ids.join(", "), e))
+            .expect("Failed to write to stdout"),
+    }
+}
+
+/// Mark the state in a leftover temp clone aborted and release its lock
+async fn mark_aborted(temp_path: &Path, config: &RuntimeConfig) -> Result<()> {
+    crate::state::force_unlock_release_state(temp_path)?;
+    match crate::state::load_release_state(temp_path).await? {
+        Some(mut state) => {
+            state.mark_aborted("abort command");
+            crate::state::save_release_state(temp_path, &mut state).await?;
+            crate::state::force_unlock_release_state(temp_path)?;
+            config
+                .println(&format!(
+                    "   State marked aborted; the temp clone is kept for inspection until the next release: {}",
+                    temp_path.display()
+                ))
+                .expect("Failed to write to stdout");
+        }
+        None => config
+            .verbose_println("   No release state was saved yet")
+            .expect("Failed to write to stdout"),
+    }
+    Ok(())
+}
+
+/// Delete the draft release recorded in `state`
+///
+/// A release that is no longer a draft (it was published) is never deleted.
+async fn delete_draft_release(
+    state: Option<&ReleaseState>,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<()> {
+    let Some((github, release_id)) = state
+        .and_then(|state| state.github_state.as_ref())
+        .and_then(|github| github.release_id.map(|id| (github, id)))
+    else {
+        config
+            .println("ℹ️  The release had not created a GitHub release; nothing to delete")
+            .expect("Failed to write to stdout");
+        return Ok(());
+    };
+
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github.owner.clone(),
+        repo: github.repo.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
+    if !github_manager.verify_release_is_draft(release_id).await? {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Release {} of {}/{} is published, not a draft; it was not deleted",
+                release_id, github.owner, github.repo
+            ),
+        }));
+    }
+
+    crate::state::append_audit_entry(
+        "abort_delete_draft",
+        serde_json::json!({
+            "repository": format!("{}/{}", github.owner, github.repo),
+            "release_id": release_id,
+            "html_url": github.html_url,
+        }),
+    )?;
+    github_manager.delete_release(release_id).await?;
+    config
+        .success_println(&format!(
+            "✓ Deleted draft release {}",
+            github.html_url.as_deref().unwrap_or_default()
+        ))
+        .expect("Failed to write to stdout");
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! `config validate`: check the bundle settings and `release.toml` without releasing.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use std::path::Path;
+
+/// Validate the settings of the package in `path`; fails when there are problems
+pub(super) fn execute_validate(path: &Path, config: &RuntimeConfig) -> Result<i32> {
+    if !path.join("Cargo.toml").is_file() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("No Cargo.toml in {}", path.display()),
+        }));
+    }
+
+    let problems = crate::settings_schema::validate_settings(path, path);
+    if problems.is_empty() {
+        config
+            .success_println("✓ Bundle settings and release.toml are valid")
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    for problem in &problems {
+        config.error_println(&problem.to_string());
+    }
+    Err(ReleaseError::Cli(CliError::InvalidArguments {
+        reason: format!("{} problem(s) in the release configuration", problems.len()),
+    }))
+}
@@ -0,0 +1,282 @@
+//! `diff`: what changed inside a platform's bundles between two releases.
+//!
+//! Both releases' manifests are read as for `download`, and the artifacts of
+//! the chosen platform are paired by name with the version taken out
+//! (`app_1.2.0_amd64.deb` with `app_1.3.0_amd64.deb`). Each pair is
+//! downloaded to a temporary directory, checked against the manifest's
+//! SHA-256, inspected offline (see [`crate::inspect`]) and compared file by
+//! file. Encrypted artifacts are skipped.
+
+use crate::cli::{Args, RuntimeConfig};
+use crate::error::{CliError, ReleaseError, Result};
+use crate::github::ReleaseAssets;
+use crate::inspect::BundleDiff;
+use crate::manifest::{ManifestArtifact, ReleaseManifest};
+use crate::EnvConfig;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::download::release_manifest;
+
+/// Differences between the platform's bundles of two releases
+#[derive(Debug, Serialize)]
+struct ReleaseDiff {
+    old: String,
+    new: String,
+    platform: String,
+    artifacts: Vec<ArtifactDiff>,
+    /// Artifacts without a counterpart in the newer release
+    only_in_old: Vec<String>,
+    /// Artifacts without a counterpart in the older release
+    only_in_new: Vec<String>,
+    /// Encrypted artifacts, which cannot be inspected
+    skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactDiff {
+    old: String,
+    new: String,
+    #[serde(flatten)]
+    diff: BundleDiff,
+}
+
+/// Compare the `platform` bundles of releases `old` and `new`
+pub(super) async fn execute_diff(
+    args: &Args,
+    old: &str,
+    new: &str,
+    platform: &str,
+    json: bool,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let git_auth = crate::git_auth::GitAuth::resolve(args.git_auth, env_config)?;
+    let resolved = crate::source::RepositorySource::parse(args.source())?
+        .resolve(&git_auth)
+        .await?;
+
+    let mut env_config = env_config.clone();
+    if !config.is_ci()
+        && let Some(dotenv) = resolved.dotenv_path()
+    {
+        env_config.load_dotenv(&dotenv)?;
+    }
+    env_config.require(crate::env::EnvRequirement::GitHubRelease)?;
+
+    let github_remote = super::github_remote::detect_github_repo(&resolved.path, &args.remotes).await?;
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_remote.owner.clone(),
+        repo: github_remote.repo.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, &env_config)?;
+    let repository = format!("{}/{}", github_remote.owner, github_remote.repo);
+
+    let (old_id, old_manifest) = release_manifest(&github_manager, &repository, old).await?;
+    let (new_id, new_manifest) = release_manifest(&github_manager, &repository, new).await?;
+    let (old_artifacts, mut skipped) = platform_artifacts(&old_manifest, platform);
+    let (new_artifacts, new_skipped) = platform_artifacts(&new_manifest, platform);
+    skipped.extend(new_skipped);
+    if old_artifacts.is_empty() && new_artifacts.is_empty() && skipped.is_empty() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Neither {} nor {} has {} artifacts (platforms: {})",
+                old,
+                new,
+                platform,
+                platforms(&[&old_manifest, &new_manifest]).join(", ")
+            ),
+        }));
+    }
+
+    let mut result = ReleaseDiff {
+        old: old.to_string(),
+        new: new.to_string(),
+        platform: platform.to_string(),
+        artifacts: Vec::new(),
+        only_in_old: Vec::new(),
+        only_in_new: new_artifacts
+            .iter()
+            .filter(|a| !old_artifacts.iter().any(|b| pairs(&old_manifest, b, &new_manifest, a)))
+            .map(|a| a.name.clone())
+            .collect(),
+        skipped,
+    };
+
+    let workdir = std::env::temp_dir().join(format!("kodegen-diff-{}", uuid::Uuid::new_v4()));
+    let compared = async {
+        for before in &old_artifacts {
+            let Some(after) = new_artifacts
+                .iter()
+                .find(|a| pairs(&old_manifest, before, &new_manifest, a))
+            else {
+                result.only_in_old.push(before.name.clone());
+                continue;
+            };
+            crate::cli::abort::check("diff")?;
+
+            let old_path = download(&github_manager, old_id, before, &workdir.join("old")).await?;
+            let new_path = download(&github_manager, new_id, after, &workdir.join("new")).await?;
+            let diff = crate::inspect::diff(
+                &crate::inspect::inspect(&old_path)?,
+                &crate::inspect::inspect(&new_path)?,
+            );
+            result.artifacts.push(ArtifactDiff {
+                old: before.name.clone(),
+                new: after.name.clone(),
+                diff,
+            });
+        }
+        Ok::<_, ReleaseError>(())
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&workdir);
+    compared?;
+
+    if json {
+        config
+            .println(&serde_json::to_string_pretty(&result)?)
+            .expect("Failed to write to stdout");
+    } else {
+        print_diff(&result, config);
+    }
+    Ok(0)
+}
+
+/// Artifacts of `platform`, and the names of its encrypted ones
+fn platform_artifacts<'a>(manifest: &'a ReleaseManifest, platform: &str) -> (Vec<&'a ManifestArtifact>, Vec<String>) {
+    let (encrypted, plain): (Vec<&ManifestArtifact>, Vec<&ManifestArtifact>) = manifest
+        .artifacts
+        .iter()
+        .filter(|artifact| artifact.platform.eq_ignore_ascii_case(platform))
+        .partition(|artifact| artifact.encryption.is_some());
+    (plain, encrypted.into_iter().map(|artifact| artifact.name.clone()).collect())
+}
+
+/// Platforms with artifacts in any of `manifests`, sorted
+fn platforms(manifests: &[&ReleaseManifest]) -> Vec<String> {
+    let mut platforms: Vec<String> = manifests
+        .iter()
+        .flat_map(|manifest| manifest.artifacts.iter().map(|a| a.platform.clone()))
+        .collect();
+    platforms.sort();
+    platforms.dedup();
+    platforms
+}
+
+/// Whether two artifacts have the same name apart from their release's version
+fn pairs(old: &ReleaseManifest, before: &ManifestArtifact, new: &ReleaseManifest, after: &ManifestArtifact) -> bool {
+    before.name.replace(&old.version.to_string(), "{version}") == after.name.replace(&new.version.to_string(), "{version}")
+}
+
+/// Download `artifact` into `dir` and check its SHA-256
+async fn download(
+    assets: &impl ReleaseAssets,
+    release_id: u64,
+    artifact: &ManifestArtifact,
+    dir: &Path,
+) -> Result<PathBuf> {
+    let content = assets
+        .download_verified(release_id, &artifact.name, &artifact.sha256)
+        .await?;
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(&artifact.name);
+    std::fs::write(&path, &content)?;
+    Ok(path)
+}
+
+fn print_diff(result: &ReleaseDiff, config: &RuntimeConfig) {
+    let output = config.output();
+    for artifact in &result.artifacts {
+        let diff = &artifact.diff;
+        output
+            .section(&format!("{} → {}", artifact.old, artifact.new))
+            .expect("Failed to write to stdout");
+        config
+            .println(&format!(
+                "Size: {} → {} bytes ({})",
+                diff.old_size,
+                diff.new_size,
+                delta(diff.old_size, diff.new_size)
+            ))
+            .expect("Failed to write to stdout");
+
+        if diff.is_empty() {
+            config
+                .success_println("✓ No differences besides the version")
+                .expect("Failed to write to stdout");
+        }
+        for change in &diff.metadata {
+            config
+                .println(&format!(
+                    "~ {}: {} → {}",
+                    change.key,
+                    change.old.as_deref().unwrap_or("(none)"),
+                    change.new.as_deref().unwrap_or("(none)")
+                ))
+                .expect("Failed to write to stdout");
+        }
+        for file in diff.added.iter().flatten() {
+            config
+                .success_println(&format!("+ {:>12}  {}", file.size, file.path))
+                .expect("Failed to write to stdout");
+        }
+        for file in diff.removed.iter().flatten() {
+            config
+                .warning_println(&format!("- {:>12}  {}", file.size, file.path))
+                .expect("Failed to write to stdout");
+        }
+        for file in diff.changed.iter().flatten() {
+            config
+                .println(&format!(
+                    "~ {:>12}  {} ({} → {})",
+                    delta(file.old_size, file.new_size),
+                    file.path,
+                    file.old_size,
+                    file.new_size
+                ))
+                .expect("Failed to write to stdout");
+        }
+
+        if diff.changed.is_some() {
+            config
+                .indent(&format!(
+                    "{} added, {} removed, {} changed, {} unchanged",
+                    diff.added.as_ref().map_or(0, Vec::len),
+                    diff.removed.as_ref().map_or(0, Vec::len),
+                    diff.changed.as_ref().map_or(0, Vec::len),
+                    diff.unchanged
+                ))
+                .expect("Failed to write to stdout");
+        }
+        for note in &diff.notes {
+            config.indent(&format!("ℹ️  {}", note)).expect("Failed to write to stdout");
+        }
+    }
+
+    for name in &result.only_in_old {
+        config
+            .warning_println(&format!("⚠️  {} has no counterpart in {}", name, result.new))
+            .expect("Failed to write to stdout");
+    }
+    for name in &result.only_in_new {
+        config
+            .warning_println(&format!("⚠️  {} has no counterpart in {}", name, result.old))
+            .expect("Failed to write to stdout");
+    }
+    for name in &result.skipped {
+        config
+            .warning_println(&format!("⚠️  Skipped {} (encrypted)", name))
+            .expect("Failed to write to stdout");
+    }
+}
+
+/// Signed size difference, e.g. `+1204`
+fn delta(old: u64, new: u64) -> String {
+    if new >= old {
+        format!("+{}", new - old)
+    } else {
+        format!("-{}", old - new)
+    }
+}
@@ -0,0 +1,145 @@
+//! `download`: fetch a release's assets as listed in its release manifest.
+//!
+//! The manifest (`release-manifest.json`) is read from the release of the
+//! tag, drafts included, and each listed asset is downloaded into the output
+//! directory and checked against its SHA-256. With `--decrypt`, encrypted
+//! assets are decrypted next to it, checked against the plaintext SHA-256 and
+//! the ciphertext is removed. age assets need `--identity`; gpg assets take
+//! the passphrase from the package's `passphrase-env` variable.
+
+use crate::cli::{Args, RuntimeConfig};
+use crate::encryption::EncryptionMethod;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::github::{ReleaseAssets, verify_sha256};
+use crate::manifest::{MANIFEST_FILE_NAME, ReleaseManifest};
+use crate::EnvConfig;
+use std::path::Path;
+
+/// Download the assets of `tag` into `out`
+pub(super) async fn execute_download(
+    args: &Args,
+    tag: &str,
+    out: &Path,
+    decrypt: bool,
+    identity: Option<&Path>,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let git_auth = crate::git_auth::GitAuth::resolve(args.git_auth, env_config)?;
+    let resolved = crate::source::RepositorySource::parse(args.source())?
+        .resolve(&git_auth)
+        .await?;
+
+    let mut env_config = env_config.clone();
+    if !config.is_ci()
+        && let Some(dotenv) = resolved.dotenv_path()
+    {
+        env_config.load_dotenv(&dotenv)?;
+    }
+    env_config.require(crate::env::EnvRequirement::GitHubRelease)?;
+
+    let github_remote = super::github_remote::detect_github_repo(&resolved.path, &args.remotes).await?;
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_remote.owner.clone(),
+        repo: github_remote.repo.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, &env_config)?;
+
+    let repository = format!("{}/{}", github_remote.owner, github_remote.repo);
+    let (release_id, manifest) = release_manifest(&github_manager, &repository, tag).await?;
+
+    // Passphrase of gpg-encrypted assets, from the package's configuration
+    let needs_passphrase = decrypt
+        && manifest
+            .artifacts
+            .iter()
+            .any(|a| a.encryption.as_ref().is_some_and(|e| e.method == EncryptionMethod::Gpg));
+    let passphrase = if needs_passphrase {
+        let metadata = crate::metadata::load_manifest(&resolved.path.join("Cargo.toml"), config).await?.metadata;
+        let env = metadata
+            .encryption
+            .as_ref()
+            .and_then(|e| e.passphrase_env())
+            .ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: "Assets are gpg-encrypted but [package.metadata.release.encryption] sets no passphrase-env"
+                        .to_string(),
+                })
+            })?;
+        Some(crate::encryption::passphrase(&env_config, env)?)
+    } else {
+        None
+    };
+
+    std::fs::create_dir_all(out)?;
+    config
+        .println(&format!(
+            "⬇️  Downloading {} asset(s) of {} into {}",
+            manifest.artifacts.len(),
+            tag,
+            out.display()
+        ))
+        .expect("Failed to write to stdout");
+
+    for artifact in &manifest.artifacts {
+        crate::cli::abort::check("download")?;
+        let content = github_manager
+            .download_verified(release_id, &artifact.name, &artifact.sha256)
+            .await?;
+        let path = out.join(&artifact.name);
+        std::fs::write(&path, &content)?;
+
+        match &artifact.encryption {
+            Some(encryption) if decrypt => {
+                let plaintext = out.join(&encryption.plaintext_name);
+                crate::encryption::decrypt(encryption.method, &path, &plaintext, identity, passphrase.as_deref())
+                    .await?;
+                verify_sha256(&encryption.plaintext_name, &std::fs::read(&plaintext)?, &encryption.plaintext_sha256)?;
+                std::fs::remove_file(&path)?;
+                config
+                    .indent(&format!("✓ {} (decrypted)", encryption.plaintext_name))
+                    .expect("Failed to write to stdout");
+            }
+            Some(_) => config
+                .indent(&format!("✓ {} (encrypted)", artifact.name))
+                .expect("Failed to write to stdout"),
+            None => config
+                .indent(&format!("✓ {}", artifact.name))
+                .expect("Failed to write to stdout"),
+        }
+    }
+
+    config
+        .success_println(&format!("✓ Downloaded {} asset(s) of {}", manifest.artifacts.len(), tag))
+        .expect("Failed to write to stdout");
+    Ok(0)
+}
+
+/// ID of the release of `tag` and its release manifest
+///
+/// A published release wins over leftover drafts.
+pub(super) async fn release_manifest(
+    github_manager: &crate::github::GitHubReleaseManager,
+    repository: &str,
+    tag: &str,
+) -> Result<(u64, ReleaseManifest)> {
+    let mut releases = github_manager.releases_for_tag(tag).await?;
+    releases.sort_by_key(|release| release.draft);
+    let release = releases.into_iter().next().ok_or_else(|| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("{} has no release for {}", repository, tag),
+        })
+    })?;
+
+    let content = github_manager
+        .download_named(release.release_id, MANIFEST_FILE_NAME)
+        .await?
+        .ok_or_else(|| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "download".to_string(),
+                reason: format!("{} has no {}", release.html_url, MANIFEST_FILE_NAME),
+            })
+        })?;
+    Ok((release.release_id, serde_json::from_slice(&content)?))
+}
@@ -0,0 +1,245 @@
+//! GitHub repository detection from git remotes.
+//!
+//! Looks at every remote of the source repository rather than only `origin`,
+//! and understands the URL forms people actually use:
+//!
+//! - SCP-like SSH: `git@github.com:owner/repo.git`
+//! - SSH URLs: `ssh://git@github.com[:port]/owner/repo.git`
+//! - HTTPS / git protocol: `https://github.com/owner/repo`
+//! - SSH aliases resolved through `~/.ssh/config` (`Host gh-work` + `HostName github.com`)
+//!
+//! Remotes are tried in preference order (`origin`, then `upstream` by default,
+//! configurable with `--remote`). When no preferred remote points at GitHub and
+//! the remaining candidates disagree, detection fails with the list of candidates.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::github::wildcard_match;
+use std::path::Path;
+
+/// Remote preference order when none is configured
+pub(super) const DEFAULT_REMOTE_ORDER: [&str; 2] = ["origin", "upstream"];
+
+/// GitHub repository behind a git remote
+#[derive(Debug, Clone)]
+pub(super) struct GitHubRemote {
+    /// Remote name (e.g. `origin`)
+    pub remote: String,
+    /// Remote URL as configured (used for cloning)
+    pub url: String,
+    /// Repository owner
+    pub owner: String,
+    /// Repository name
+    pub repo: String,
+}
+
+impl GitHubRemote {
+    fn describe(&self) -> String {
+        format!("{} → {}/{} ({})", self.remote, self.owner, self.repo, self.url)
+    }
+}
+
+/// Detect the GitHub repository to release from the remotes of `repo_path`
+///
+/// `preferred` lists remote names in priority order; an empty list uses
+/// [`DEFAULT_REMOTE_ORDER`]. An explicitly preferred remote that is missing or
+/// does not point at GitHub is skipped in favour of the next one.
+pub(super) async fn detect_github_repo(repo_path: &Path, preferred: &[String]) -> Result<GitHubRemote> {
+    let remotes = list_remotes(repo_path).await?;
+    let ssh_hosts = SshConfig::load();
+
+    let candidates: Vec<GitHubRemote> = remotes
+        .iter()
+        .filter_map(|(name, url)| {
+            let (owner, repo) = parse_github_url(url, &ssh_hosts)?;
+            Some(GitHubRemote {
+                remote: name.clone(),
+                url: url.clone(),
+                owner,
+                repo,
+            })
+        })
+        .collect();
+
+    let order: Vec<&str> = if preferred.is_empty() {
+        DEFAULT_REMOTE_ORDER.to_vec()
+    } else {
+        preferred.iter().map(String::as_str).collect()
+    };
+
+    if let Some(found) = order
+        .iter()
+        .find_map(|name| candidates.iter().find(|c| c.remote == *name))
+    {
+        return Ok(found.clone());
+    }
+
+    // No preferred remote; accept the remaining candidates only if they agree
+    match candidates.as_slice() {
+        [] => Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: if remotes.is_empty() {
+                format!("{} has no git remotes", repo_path.display())
+            } else {
+                format!(
+                    "No remote of {} points at a GitHub repository: {}",
+                    repo_path.display(),
+                    remotes
+                        .iter()
+                        .map(|(name, url)| format!("{} ({})", name, url))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+        })),
+        [first, rest @ ..]
+            if rest
+                .iter()
+                .all(|c| c.owner == first.owner && c.repo == first.repo) =>
+        {
+            Ok(first.clone())
+        }
+        _ => Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Ambiguous GitHub repository; none of the preferred remotes ({}) point at GitHub. \
+                 Choose one with --remote NAME. Candidates: {}",
+                order.join(", "),
+                candidates
+                    .iter()
+                    .map(GitHubRemote::describe)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+        })),
+    }
+}
+
+/// `(name, url)` for every remote with a URL, in git config order
+async fn list_remotes(repo_path: &Path) -> Result<Vec<(String, String)>> {
+    let mut command = crate::exec::Exec::new("git");
+    command
+        .args(["config", "--get-regexp", r"^remote\..*\.url$"])
+        .current_dir(repo_path);
+    let output = command.output().await?;
+
+    // Exit code 1 means no matching keys, i.e. no remotes
+    if output.status.code() == Some(1) {
+        return Ok(Vec::new());
+    }
+    let output = command.check(output)?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (key, url) = line.split_once(' ')?;
+            let name = key.strip_prefix("remote.")?.strip_suffix(".url")?;
+            Some((name.to_string(), url.trim().to_string()))
+        })
+        .collect())
+}
+
+/// Parse GitHub owner/repo from a remote URL, resolving SSH aliases
+///
+/// Hosts other than GitHub are rejected unless `~/.ssh/config` maps them to it.
+fn parse_github_url(url: &str, ssh_hosts: &SshConfig) -> Option<(String, String)> {
+    let (host, path, is_ssh) = split_remote_url(url)?;
+    let host = if is_ssh {
+        ssh_hosts.hostname_for(&host).unwrap_or(host)
+    } else {
+        host
+    };
+
+    if !is_github_host(&host) {
+        return None;
+    }
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let mut parts = path.split('/');
+    match (parts.next(), parts.next()) {
+        (Some(owner), Some(repo)) if !owner.is_empty() && !repo.is_empty() => {
+            Some((owner.to_string(), repo.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Split a remote URL into `(host, path, is_ssh)`; local paths yield `None`
+fn split_remote_url(url: &str) -> Option<(String, String, bool)> {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+        let host = host_port.split(':').next().unwrap_or(host_port);
+        let is_ssh = matches!(scheme, "ssh" | "git+ssh" | "ssh+git");
+        return Some((host.to_lowercase(), path.to_string(), is_ssh));
+    }
+
+    // SCP-like `[user@]host:path`; a '/' before the ':' means a local path
+    let (host_part, path) = url.split_once(':')?;
+    if host_part.contains('/') || host_part.is_empty() {
+        return None;
+    }
+    let host = host_part.rsplit_once('@').map_or(host_part, |(_, h)| h);
+    Some((host.to_lowercase(), path.to_string(), true))
+}
+
+fn is_github_host(host: &str) -> bool {
+    host == "github.com" || host == "ssh.github.com" || host == "www.github.com"
+}
+
+/// `Host` → `HostName` mappings from `~/.ssh/config`
+///
+/// Only the top-level file is read (`Include` is not followed). Host patterns
+/// support `*` and `?` wildcards; the first matching `HostName` wins, as in ssh.
+#[derive(Debug, Default)]
+struct SshConfig {
+    entries: Vec<(Vec<String>, String)>,
+}
+
+impl SshConfig {
+    fn load() -> Self {
+        dirs::home_dir()
+            .and_then(|home| std::fs::read_to_string(home.join(".ssh").join("config")).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut patterns: Vec<String> = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (keyword, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+                Some((k, v)) => (k, v.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim()),
+                None => continue,
+            };
+
+            if keyword.eq_ignore_ascii_case("host") {
+                patterns = value.split_whitespace().map(str::to_lowercase).collect();
+            } else if keyword.eq_ignore_ascii_case("match") {
+                patterns.clear();
+            } else if keyword.eq_ignore_ascii_case("hostname") && !patterns.is_empty() {
+                entries.push((patterns.clone(), value.to_lowercase()));
+            }
+        }
+
+        Self { entries }
+    }
+
+    fn hostname_for(&self, alias: &str) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|(patterns, _)| {
+                let negated = patterns
+                    .iter()
+                    .any(|p| p.strip_prefix('!').is_some_and(|p| wildcard_match(p, alias)));
+                !negated
+                    && patterns
+                        .iter()
+                        .any(|p| !p.starts_with('!') && wildcard_match(p, alias))
+            })
+            .map(|(_, hostname)| hostname.replace("%h", alias))
+    }
+}
@@ -0,0 +1,16 @@
+//! `graph`: the workspace dependency graph and publish tiers as DOT or Mermaid.
+
+use crate::cli::RuntimeConfig;
+use crate::error::Result;
+use crate::workspace::{GraphFormat, PublishPlan, WorkspaceInfo, render_graph};
+use std::path::Path;
+
+/// Print the dependency graph of the workspace at `path`
+pub(super) fn execute_graph(path: &Path, format: GraphFormat, config: &RuntimeConfig) -> Result<i32> {
+    let workspace = WorkspaceInfo::analyze(path)?;
+    let plan = PublishPlan::build(&workspace, &[])?;
+    config
+        .println(render_graph(&workspace, &plan, format).trim_end())
+        .expect("Failed to write to stdout");
+    Ok(0)
+}
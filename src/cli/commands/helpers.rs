@@ -1,5 +1,6 @@
 //! Shared helper functions for command execution.
 
+use crate::cli::RuntimeConfig;
 use crate::error::{CliError, ReleaseError, Result};
 
 /// Parse GitHub repository string into owner/repo tuple
@@ -25,44 +26,19 @@ pub(super) fn parse_github_repo(repo_str: Option<&str>) -> Result<(String, Strin
     Ok((parts[0].to_string(), parts[1].to_string()))
 }
 
-/// Parse GitHub owner/repo from git remote URL
+/// Prompt user for confirmation with y/n input
 ///
-/// Supports Git URL formats:
-/// - SSH SCP-like: git@github.com:owner/repo.git
-/// - HTTPS: https://github.com/owner/repo.git
-pub(super) fn parse_github_url(url: &str) -> Result<(String, String)> {
-    // Handle SSH SCP-like format: git@github.com:owner/repo.git
-    if url.contains('@') && url.contains(':') && !url.contains("://") {
-        let parts: Vec<&str> = url.split(':').collect();
-        if parts.len() == 2 {
-            let path = parts[1].trim_end_matches(".git");
-            let path_parts: Vec<&str> = path.split('/').collect();
-            if path_parts.len() == 2 {
-                return Ok((path_parts[0].to_string(), path_parts[1].to_string()));
-            }
-        }
-    }
+/// Refuses to prompt in CI mode, where there is nobody to answer.
+pub(super) fn prompt_confirmation(config: &RuntimeConfig, prompt: &str) -> std::io::Result<bool> {
+    use std::io::Write;
 
-    // Handle HTTPS/SSH protocol URLs
-    if let Some(path_start) = url.find("github.com/") {
-        let path = &url[path_start + 11..];
-        let path = path.trim_end_matches(".git");
-        let parts: Vec<&str> = path.split('/').collect();
-        if parts.len() >= 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
-        }
+    if config.is_ci() {
+        return Err(std::io::Error::other(format!(
+            "Cannot prompt in CI mode: {}",
+            prompt
+        )));
     }
 
-    Err(ReleaseError::Cli(CliError::InvalidArguments {
-        reason: format!("Could not parse GitHub owner/repo from URL: '{}'", url),
-    }))
-}
-
-/// Prompt user for confirmation with y/n input
-#[allow(dead_code)]
-pub(super) fn prompt_confirmation(prompt: &str) -> std::io::Result<bool> {
-    use std::io::Write;
-
     print!("{} [y/N]: ", prompt);
     std::io::stdout().flush()?;
 
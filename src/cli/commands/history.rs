@@ -0,0 +1,69 @@
+//! History needed to find the previous release.
+//!
+//! Release notes list the commits since the previous tag, and the
+//! `version-files` check looks for that tag's version. Both go through
+//! `git describe`, which in a shallow clone (CI checkouts default to a depth
+//! of 1) finds no earlier tag and makes every release look like the first.
+//! Before either runs, a shallow repository is deepened from the remote and
+//! missing tags are fetched; when that fails the release stops with
+//! instructions instead of publishing wrong notes.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use std::path::Path;
+
+/// Make sure `repo` has the full history and tags of `remote_url`
+///
+/// `tag_pattern` selects the release tags (see `VersionScheme::tag_pattern`).
+pub(super) async fn ensure_full_history(
+    repo: &Path,
+    remote_url: &str,
+    tag_pattern: &str,
+    auth: &crate::git_auth::GitAuth,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    let shallow = git(Exec::new("git"), repo, &["rev-parse", "--is-shallow-repository"]).await?;
+    if shallow == "true" {
+        config
+            .println("📜 Shallow clone: fetching the full history and tags...")
+            .expect("Failed to write to stdout");
+        if let Err(e) = git(auth.git(), repo, &["fetch", "--quiet", "--unshallow", "--tags", remote_url]).await {
+            return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "git fetch --unshallow --tags".to_string(),
+                reason: format!(
+                    "{} is a shallow clone and could not be deepened ({}). Release notes and the previous \
+                     version need the history since the last tag: fetch it first with \
+                     `git fetch --unshallow --tags`, or check out with `fetch-depth: 0` (actions/checkout)",
+                    repo.display(),
+                    e
+                ),
+            }));
+        }
+        config
+            .verbose_println("✓ Fetched the full history")
+            .expect("Failed to write to stdout");
+        return Ok(());
+    }
+
+    // A clone made with --no-tags has the history but no release tags
+    let tags = git(Exec::new("git"), repo, &["tag", "--list", tag_pattern]).await?;
+    if tags.is_empty() {
+        match git(auth.git(), repo, &["fetch", "--quiet", "--tags", remote_url]).await {
+            Ok(_) => config
+                .verbose_println("✓ Fetched tags (none matching the tag format were present)")
+                .expect("Failed to write to stdout"),
+            // Without tags on the remote either, this is the first release
+            Err(e) => config
+                .verbose_println(&format!("   Could not fetch tags: {}", e))
+                .expect("Failed to write to stdout"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `git` (a prepared git command) in `repo` and return trimmed stdout
+async fn git(mut git: Exec, repo: &Path, args: &[&str]) -> Result<String> {
+    git.args(args).current_dir(repo).stdout().await
+}
@@ -0,0 +1,101 @@
+//! `impact`: which workspace members a crate bump affects.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::version::{Bump, VersionScheme};
+use crate::workspace::{ImpactReport, WorkspaceInfo};
+use semver::Version;
+use std::path::Path;
+
+/// Report the impact of bumping `package` in the workspace at `path`
+///
+/// The new version is `to`, or `bump` applied to the current version.
+pub(super) fn execute_impact(
+    package: &str,
+    path: &Path,
+    bump: Bump,
+    to: Option<&Version>,
+    json: bool,
+    config: &RuntimeConfig,
+) -> Result<i32> {
+    let workspace = WorkspaceInfo::analyze(path)?;
+    let current = workspace.get_package(package)?.version.clone();
+    let current = Version::parse(&current).map_err(|e| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("{} has an invalid version {}: {}", package, current, e),
+        })
+    })?;
+    let new_version = match to {
+        Some(version) if *version <= current => {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("--to {} is not newer than {} {}", version, package, current),
+            }));
+        }
+        Some(version) => version.clone(),
+        None => VersionScheme::default().next(&current, bump, chrono::Local::now().date_naive())?,
+    };
+
+    let report = ImpactReport::analyze(&workspace, package, &new_version)?;
+    if json {
+        config
+            .println(&serde_json::to_string_pretty(&report)?)
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    let output = config.output();
+    output
+        .section(&format!(
+            "Impact of {} {} → {}",
+            report.package, report.current_version, report.new_version
+        ))
+        .expect("Failed to write to stdout");
+    if report.dependents.is_empty() {
+        config
+            .println("No workspace member depends on it")
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    let width = report.dependents.iter().map(|d| d.name.len()).max().unwrap_or(0);
+    for dependent in &report.dependents {
+        let via = if dependent.path.len() > 2 {
+            format!("via {}", dependent.path[1..dependent.path.len() - 1].join(" → "))
+        } else {
+            "direct".to_string()
+        };
+        let action = match (&dependent.republish_version, dependent.needs_update()) {
+            (Some(version), _) => format!("republish as {}", version),
+            (None, true) => "update manifest (publish = false)".to_string(),
+            (None, false) => "no changes".to_string(),
+        };
+        config
+            .println(&format!("{:width$}  {:<24}  {}", dependent.name, via, action, width = width))
+            .expect("Failed to write to stdout");
+        for requirement in &dependent.requirements {
+            let line = match (&requirement.requirement, &requirement.update) {
+                (Some(current), Some(update)) => {
+                    format!("{} = \"{}\" → \"{}\"", requirement.dependency, current, update)
+                }
+                (Some(current), None) => format!("{} = \"{}\" (still matches)", requirement.dependency, current),
+                (None, _) => format!("{} (path only, no version requirement)", requirement.dependency),
+            };
+            config.indent(&line).expect("Failed to write to stdout");
+        }
+    }
+
+    let republished: Vec<&str> = report.republished().map(|d| d.name.as_str()).collect();
+    config
+        .println(&format!(
+            "\n{} affected, {} to republish{}",
+            report.dependents.len(),
+            republished.len(),
+            if republished.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", republished.join(", "))
+            }
+        ))
+        .expect("Failed to write to stdout");
+    Ok(0)
+}
@@ -0,0 +1,70 @@
+//! `bundle inspect`: print what a produced bundle contains.
+
+use crate::cli::RuntimeConfig;
+use crate::error::Result;
+use crate::inspect::{SignatureStatus, inspect};
+use std::path::Path;
+
+/// Inspect `artifact` and print the report (or JSON with `json`)
+pub(super) fn execute_inspect(artifact: &Path, json: bool, config: &RuntimeConfig) -> Result<i32> {
+    let report = inspect(artifact)?;
+
+    if json {
+        config
+            .println(&serde_json::to_string_pretty(&report)?)
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    let output = config.output();
+    output.section(&report.path.display().to_string()).expect("Failed to write to stdout");
+    config.println(&format!("Format:     {}", report.format)).expect("Failed to write to stdout");
+    config
+        .println(&format!(
+            "Version:    {}",
+            report.version.as_deref().unwrap_or("unknown")
+        ))
+        .expect("Failed to write to stdout");
+    config
+        .println(&format!("Size:       {} bytes", report.size))
+        .expect("Failed to write to stdout");
+    config.println(&format!("SHA-256:    {}", report.sha256)).expect("Failed to write to stdout");
+    match &report.signature {
+        SignatureStatus::Signed(kind) => config
+            .success_println(&format!("Signature:  {}", kind))
+            .expect("Failed to write to stdout"),
+        SignatureStatus::Unsigned => config
+            .warning_println("Signature:  none")
+            .expect("Failed to write to stdout"),
+    }
+
+    if !report.metadata.is_empty() {
+        output.section("Metadata").expect("Failed to write to stdout");
+        let width = report.metadata.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        for (key, value) in &report.metadata {
+            config
+                .println(&format!("{:width$}  {}", key, value, width = width))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    if let Some(files) = &report.files {
+        output
+            .section(&format!("Files ({})", files.len()))
+            .expect("Failed to write to stdout");
+        for file in files {
+            config
+                .println(&format!("{:>12}  {}", file.size, file.path))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    if !report.notes.is_empty() {
+        config.println("").expect("Failed to write to stdout");
+        for note in &report.notes {
+            config.indent(&format!("ℹ️  {}", note)).expect("Failed to write to stdout");
+        }
+    }
+
+    Ok(0)
+}
@@ -0,0 +1,65 @@
+//! `login` / `logout`: tokens kept in the OS keyring instead of shell rc files.
+
+use crate::cli::RuntimeConfig;
+use crate::cli::args::StoredToken;
+use crate::env::keyring;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::EnvConfig;
+use std::io::{BufRead, IsTerminal};
+
+/// Read a token from stdin and store it in the keyring
+pub(super) fn execute_login(token: StoredToken, config: &RuntimeConfig, env_config: &EnvConfig) -> Result<i32> {
+    let name = token.var();
+    if std::io::stdin().is_terminal() {
+        config
+            .println(&format!("Paste the token to store as {} and press Enter:", name))
+            .expect("Failed to write to stdout");
+    }
+
+    let mut value = String::new();
+    std::io::stdin().lock().read_line(&mut value)?;
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("No token given for {}", name),
+        }));
+    }
+
+    let service = env_config.keychain_service();
+    keyring::store(&service, name, value)?;
+    config
+        .success_println(&format!("✓ Stored {} in the OS keyring (service {})", name, service))
+        .expect("Failed to write to stdout");
+    if env_config.is_set(name) {
+        config
+            .warning_println(&format!("⚠️  {} is also set in the environment, which takes precedence", name))
+            .expect("Failed to write to stdout");
+    }
+    Ok(0)
+}
+
+/// Delete `token`, or every stored token
+pub(super) fn execute_logout(
+    token: Option<StoredToken>,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let service = env_config.keychain_service();
+    let names = match token {
+        Some(token) => vec![token.var()],
+        None => keyring::TOKEN_VARS.to_vec(),
+    };
+
+    for name in names {
+        if keyring::delete(&service, name)? {
+            config
+                .success_println(&format!("✓ Deleted {} from the OS keyring", name))
+                .expect("Failed to write to stdout");
+        } else {
+            config
+                .println(&format!("   {} was not stored", name))
+                .expect("Failed to write to stdout");
+        }
+    }
+    Ok(0)
+}
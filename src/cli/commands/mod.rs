@@ -4,51 +4,144 @@
 //! all modules and providing comprehensive error handling and user feedback.
 
 // Submodules
+mod abort;
+mod config;
+mod diff;
+mod download;
+mod github_remote;
+mod graph;
 mod helpers;
+mod history;
+mod impact;
+mod inspect;
+mod login;
+mod registry_token;
 mod release;
+mod remote_sync;
+mod resign;
+mod stats;
 mod temp_clone;
 
-use crate::cli::{Args, RuntimeConfig};
-use crate::error::Result;
+use crate::cli::{Args, BundleCommand, Command, ConfigCommand, RuntimeConfig};
+use crate::error::{CliError, ReleaseError, Result};
 use crate::EnvConfig;
 
 // Import command executors
 use release::execute_release;
 
 /// Execute the main command based on parsed arguments
+///
+/// Failures are reported here and mapped to their category's exit code.
 pub async fn execute_command(args: Args, env_config: EnvConfig) -> Result<i32> {
+    let config = RuntimeConfig::from_args(&args);
+
     // Validate arguments
     if let Err(validation_error) = args.validate() {
-        let output = super::OutputManager::new(false, false);
-        output.error(&format!("Invalid arguments: {}", validation_error));
-        return Ok(1);
+        let error = ReleaseError::Cli(CliError::InvalidArguments {
+            reason: validation_error,
+        });
+        return Ok(config.report_failure("Invalid arguments", &error));
+    }
+
+    if let Some(Command::Bundle { command }) = &args.command {
+        let result = match command {
+            BundleCommand::Inspect { artifact, json } => {
+                inspect::execute_inspect(artifact, *json, &config)
+            }
+        };
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Inspection failed", &e)));
+    }
+
+    if let Some(Command::Config { command }) = &args.command {
+        let result = match command {
+            ConfigCommand::Validate { path } => config::execute_validate(path, &config),
+        };
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Validation failed", &e)));
+    }
+
+    if let Some(Command::Graph { path, format }) = &args.command {
+        let result = graph::execute_graph(path, *format, &config);
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Graph export failed", &e)));
+    }
+
+    if let Some(Command::Impact { package, path, bump, to, json }) = &args.command {
+        let result = impact::execute_impact(package, path, *bump, to.as_ref(), *json, &config);
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Impact analysis failed", &e)));
+    }
+
+    if let Some(Command::Download { tag, out, decrypt, identity, .. }) = &args.command {
+        let result =
+            download::execute_download(&args, tag, out, *decrypt, identity.as_deref(), &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Download failed", &e)));
+    }
+
+    if let Some(Command::Resign { tag, platforms, .. }) = &args.command {
+        let result = resign::execute_resign(&args, tag, platforms, &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Re-signing failed", &e)));
+    }
+
+    if let Some(Command::Diff { old, new, platform, json, .. }) = &args.command {
+        let result = diff::execute_diff(&args, old, new, platform, *json, &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Diff failed", &e)));
+    }
+
+    if let Some(Command::Stats { releases, json, .. }) = &args.command {
+        let result = stats::execute_stats(&args, *releases, *json, &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Statistics failed", &e)));
+    }
+
+    if let Some(Command::Login { token }) = &args.command {
+        let result = login::execute_login(*token, &config, &env_config);
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Login failed", &e)));
+    }
+
+    if let Some(Command::Logout { token }) = &args.command {
+        let result = login::execute_logout(*token, &config, &env_config);
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Logout failed", &e)));
     }
 
-    let config = RuntimeConfig::new();
+    if let Some(Command::Abort { pid, delete_draft }) = &args.command {
+        let result = abort::execute_abort(*pid, *delete_draft, &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Abort failed", &e)));
+    }
+
+    if let Some(Command::RegistryToken { github_env, revoke }) = &args.command {
+        let result = registry_token::execute_registry_token(*github_env, *revoke, &config, &env_config).await;
+        return Ok(result.unwrap_or_else(|e| config.report_failure("Registry token failed", &e)));
+    }
 
     // Execute release command
     let result = execute_release(&args, &config, &env_config).await;
+    let _ = config.end_group();
+    write_report(&args, &config, result.as_ref().err());
 
-    match result {
-        Ok(exit_code) => {
-            // Don't print success message here - release command already did
-            Ok(exit_code)
-        }
-        Err(e) => {
-            config.error_println(&format!("Release failed: {}", e));
-
-            // Show recovery suggestions if available
-            if config.is_verbose() {
-                let suggestions = e.recovery_suggestions();
-                if !suggestions.is_empty() {
-                    let _ = config.println("\n💡 Recovery suggestions:");
-                    for suggestion in suggestions {
-                        let _ = config.println(&format!("  • {}", suggestion));
-                    }
-                }
-            }
+    // Don't print success message here - release command already did
+    Ok(result.unwrap_or_else(|e| config.report_failure("Release failed", &e)))
+}
+
+/// Write the run report to `--report` / `--report-html`, if requested
+///
+/// Failing to write it only warns; the outcome of the run is already decided.
+fn write_report(args: &Args, config: &RuntimeConfig, error: Option<&ReleaseError>) {
+    let outputs: Vec<(&std::path::Path, bool)> = [(config.report_out(), false), (config.report_html_out(), true)]
+        .into_iter()
+        .filter_map(|(path, html)| path.map(|path| (path, html)))
+        .collect();
+    if outputs.is_empty() {
+        return;
+    }
 
-            Ok(1)
+    let failure = error.map(|e| e.to_string());
+    let report = crate::report::ReleaseReport::new(&config.journal(), args.source(), failure.as_deref());
+    for (path, html) in outputs {
+        let content = if html { report.to_html() } else { report.to_markdown() };
+        match std::fs::write(path, content) {
+            Ok(()) => config
+                .println(&format!("📝 Release report written to {}", path.display()))
+                .expect("Failed to write to stdout"),
+            Err(e) => config
+                .warning_println(&format!("⚠️  Failed to write release report {}: {}", path.display(), e))
+                .expect("Failed to write to stdout"),
         }
     }
 }
@@ -0,0 +1,152 @@
+//! `registry-token`: short-lived crates.io tokens from trusted publishing.
+//!
+//! crates.io can trust a GitHub Actions workflow (configured on the crate's
+//! settings page) and exchange the workflow's OIDC token for a publish token
+//! that expires after 30 minutes. `just publish` then needs no long-lived
+//! `CARGO_REGISTRY_TOKEN` secret. The job needs `permissions: id-token: write`.
+//!
+//! The token is printed on stdout alone, so the command works as a cargo
+//! credential provider:
+//!
+//! ```sh
+//! CARGO_REGISTRY_CREDENTIAL_PROVIDER="cargo:token-from-stdout kodegen_bundler_release registry-token"
+//! ```
+//!
+//! With `--github-env` it is masked and exported as `CARGO_REGISTRY_TOKEN` to
+//! the later steps of the job instead, and `--revoke` revokes that token once
+//! publishing is done.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::EnvConfig;
+use std::io::Write;
+use std::time::Duration;
+
+/// crates.io endpoint that exchanges OIDC tokens and revokes the result
+const TRUSTED_PUBLISHING_URL: &str = "https://crates.io/api/v1/trusted_publishing/tokens";
+
+/// Audience crates.io expects in the OIDC token
+const AUDIENCE: &str = "crates.io";
+
+/// Mint a token (or revoke one with `revoke`)
+pub(super) async fn execute_registry_token(
+    github_env: bool,
+    revoke: bool,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let client = reqwest::Client::builder()
+        .user_agent("kodegen_bundler_release")
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| failed(e.to_string()))?;
+
+    if revoke {
+        let token = env_config.get("CARGO_REGISTRY_TOKEN").filter(|t| !t.is_empty()).ok_or_else(|| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "--revoke needs the token in CARGO_REGISTRY_TOKEN".to_string(),
+            })
+        })?;
+        let response = client
+            .delete(TRUSTED_PUBLISHING_URL)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(failed(error_body(response).await));
+        }
+        config
+            .success_println("✓ Revoked the crates.io token")
+            .expect("Failed to write to stdout");
+        return Ok(0);
+    }
+
+    let jwt = github_oidc_token(&client, env_config).await?;
+    let response = client
+        .post(TRUSTED_PUBLISHING_URL)
+        .json(&serde_json::json!({ "jwt": jwt }))
+        .send()
+        .await
+        .map_err(|e| failed(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(failed(error_body(response).await));
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| failed(e.to_string()))?;
+    let token = body
+        .get("token")
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| failed("crates.io returned no token".to_string()))?;
+
+    if github_env {
+        let path = env_config.get("GITHUB_ENV").ok_or_else(|| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "--github-env needs GITHUB_ENV (only set in GitHub Actions)".to_string(),
+            })
+        })?;
+        // Masked before it can reach any log
+        config
+            .println(&format!("::add-mask::{}", token))
+            .expect("Failed to write to stdout");
+        let mut file = std::fs::OpenOptions::new().append(true).create(true).open(&path)?;
+        writeln!(file, "CARGO_REGISTRY_TOKEN={}", token)?;
+        config
+            .success_println("✓ Exported a crates.io token (valid for 30 minutes) as CARGO_REGISTRY_TOKEN")
+            .expect("Failed to write to stdout");
+    } else {
+        // Credential providers read the token from stdout, so nothing else goes there
+        println!("{}", token);
+    }
+    Ok(0)
+}
+
+/// OIDC token of the running GitHub Actions job, for the crates.io audience
+async fn github_oidc_token(client: &reqwest::Client, env_config: &EnvConfig) -> Result<String> {
+    let (Some(url), Some(request_token)) = (
+        env_config.get("ACTIONS_ID_TOKEN_REQUEST_URL"),
+        env_config.get("ACTIONS_ID_TOKEN_REQUEST_TOKEN"),
+    ) else {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: "Trusted publishing needs a GitHub Actions job with `permissions: id-token: write` \
+                     (ACTIONS_ID_TOKEN_REQUEST_URL is not set)"
+                .to_string(),
+        }));
+    };
+
+    let response = client
+        .get(&url)
+        .query(&[("audience", AUDIENCE)])
+        .bearer_auth(request_token)
+        .send()
+        .await
+        .map_err(|e| failed(format!("OIDC token request failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(failed(format!("OIDC token request failed: {}", response.status())));
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| failed(e.to_string()))?;
+    body.get("value")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| failed("GitHub returned no OIDC token".to_string()))
+}
+
+/// Status and crates.io's error detail of a failed request
+async fn error_body(response: reqwest::Response) -> String {
+    let status = response.status();
+    let detail = response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|body| body["errors"][0]["detail"].as_str().map(String::from));
+    match detail {
+        Some(detail) => format!("{}: {}", status, detail),
+        None => status.to_string(),
+    }
+}
+
+fn failed(reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "crates.io trusted publishing".to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,210 @@
+//! Adoption of what an earlier run already did on GitHub.
+//!
+//! The release state lives in the temp clone, so a run that failed or was
+//! interrupted leaves nothing for the next run to resume from. Before the
+//! gates, a full run looks at the releases of the tag instead: a release
+//! carrying `release-manifest.json` went through the upload phase, and a
+//! draft without one still has its release. When the manifest was built from
+//! the checked-out commit, those phases can be adopted as completed instead
+//! of refusing the published release or deleting and recreating the draft.
+//! Adoption is confirmed interactively, or accepted with `--adopt-existing`.
+
+use crate::error::Result;
+use crate::github::{GitHubReleaseResult, ReleaseAssets};
+use crate::manifest::{MANIFEST_FILE_NAME, ReleaseManifest};
+use crate::state::ReleasePhase;
+
+use super::super::super::helpers::prompt_confirmation;
+use super::context::ReleasePhaseContext;
+use super::registry::is_published;
+
+/// What an earlier run of the same version left on GitHub
+pub(super) enum Adoption {
+    /// Published, with the manifest of its uploads: nothing is left to do
+    Complete {
+        release: GitHubReleaseResult,
+        manifest: ReleaseManifest,
+    },
+    /// A draft with the manifest of its uploads: only publishing is left
+    Publish {
+        release: GitHubReleaseResult,
+        manifest: ReleaseManifest,
+    },
+    /// A draft without a manifest: build, bundle and upload into it
+    Draft { release: GitHubReleaseResult },
+}
+
+impl Adoption {
+    /// The adopted release
+    pub(super) fn release(&self) -> &GitHubReleaseResult {
+        match self {
+            Self::Complete { release, .. } | Self::Publish { release, .. } | Self::Draft { release } => release,
+        }
+    }
+
+    /// Phases whose outputs already exist
+    pub(super) fn completed_phases(&self) -> &'static [ReleasePhase] {
+        match self {
+            Self::Complete { .. } => &ReleasePhase::SELECTABLE,
+            Self::Publish { .. } => &[
+                ReleasePhase::GitHubRelease,
+                ReleasePhase::Building,
+                ReleasePhase::Bundling,
+                ReleasePhase::Uploading,
+            ],
+            Self::Draft { .. } => &[ReleasePhase::GitHubRelease],
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Complete { manifest, .. } => format!(
+                "published with {} artifact(s); nothing is left to do",
+                manifest.artifacts.len()
+            ),
+            Self::Publish { manifest, .. } => format!(
+                "draft with {} uploaded artifact(s); only publishing is left",
+                manifest.artifacts.len()
+            ),
+            Self::Draft { .. } => "draft without a release manifest; build, bundle and upload into it".to_string(),
+        }
+    }
+}
+
+/// Look for an earlier run's release of the tag and ask whether to adopt it
+///
+/// Returns `None` when there is nothing to adopt, when the earlier run built
+/// another commit or when adoption is declined; the release then handles the
+/// existing releases as usual.
+pub(super) async fn detect_existing_release(
+    ctx: &ReleasePhaseContext<'_>,
+    tag_name: &str,
+) -> Result<Option<Adoption>> {
+    let existing = ctx.github_manager.releases_for_tag(tag_name).await?;
+    if existing.is_empty() {
+        return Ok(None);
+    }
+
+    // A published release wins over leftover drafts
+    let mut candidates = existing;
+    candidates.sort_by_key(|release| release.draft);
+
+    let mut adoption = None;
+    for release in candidates {
+        let manifest = match ctx
+            .github_manager
+            .download_named(release.release_id, MANIFEST_FILE_NAME)
+            .await?
+        {
+            Some(content) => match serde_json::from_slice::<ReleaseManifest>(&content) {
+                Ok(manifest) if manifest.version == *ctx.new_version && manifest.package == ctx.package_name => {
+                    Some(manifest)
+                }
+                Ok(_) => None,
+                Err(e) => {
+                    ctx.config
+                        .verbose_println(&format!("   Ignoring unreadable {} on {}: {}", MANIFEST_FILE_NAME, release.html_url, e))
+                        .expect("Failed to write to stdout");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        match (release.draft, manifest) {
+            (false, Some(manifest)) => {
+                adoption = Some(Adoption::Complete { release, manifest });
+                break;
+            }
+            (true, Some(manifest)) => {
+                adoption = Some(Adoption::Publish { release, manifest });
+                break;
+            }
+            (true, None) if adoption.is_none() => adoption = Some(Adoption::Draft { release }),
+            // A published release without a manifest was not made by this tool
+            _ => {}
+        }
+    }
+    let Some(adoption) = adoption else {
+        return Ok(None);
+    };
+
+    ctx.config.group("Existing release").expect("Failed to write to stdout");
+    ctx.config
+        .println(&format!("🔁 {} already has a release: {}", tag_name, adoption.release().html_url))
+        .expect("Failed to write to stdout");
+    ctx.config
+        .indent(&adoption.describe())
+        .expect("Failed to write to stdout");
+
+    let head = head_commit(ctx.release_clone_path).await?;
+    if let Adoption::Complete { manifest, .. } | Adoption::Publish { manifest, .. } = &adoption {
+        match &manifest.commit {
+            Some(commit) if *commit != head => {
+                ctx.config
+                    .warning_println(&format!(
+                        "⚠️  It was built from {}, but {} is now at {}; not adopting it",
+                        short(commit),
+                        tag_name,
+                        short(&head)
+                    ))
+                    .expect("Failed to write to stdout");
+                return Ok(None);
+            }
+            Some(_) => ctx
+                .config
+                .indent(&format!("Built from {} (the tag's commit)", short(&head)))
+                .expect("Failed to write to stdout"),
+            None => ctx
+                .config
+                .indent("Its manifest does not record the commit it was built from")
+                .expect("Failed to write to stdout"),
+        }
+    }
+
+    let version = ctx.new_version.to_string();
+    match is_published(ctx.package_name, &version).await {
+        Ok(true) => ctx
+            .config
+            .indent(&format!("{} {} is on crates.io", ctx.package_name, version))
+            .expect("Failed to write to stdout"),
+        Ok(false) => ctx
+            .config
+            .indent(&format!("{} {} is not on crates.io", ctx.package_name, version))
+            .expect("Failed to write to stdout"),
+        Err(e) => ctx
+            .config
+            .verbose_println(&format!("   crates.io index request failed: {}", e))
+            .expect("Failed to write to stdout"),
+    }
+
+    let adopt = if ctx.config.adopt_existing() {
+        true
+    } else if ctx.config.is_ci() {
+        ctx.config
+            .warning_println("⚠️  Not adopting it without --adopt-existing in CI mode")
+            .expect("Failed to write to stdout");
+        false
+    } else {
+        prompt_confirmation(ctx.config, "Adopt it and skip the phases it already completed?")?
+    };
+    if !adopt {
+        return Ok(None);
+    }
+
+    ctx.config.record_action(&format!("Adopted the existing release {}", adoption.release().html_url));
+    Ok(Some(adoption))
+}
+
+/// Commit checked out in the release clone
+pub(super) async fn head_commit(repo_path: &std::path::Path) -> Result<String> {
+    crate::exec::Exec::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(repo_path)
+        .stdout()
+        .await
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
@@ -0,0 +1,170 @@
+//! RustSec advisory check of the release clone's `Cargo.lock`.
+//!
+//! Runs `cargo audit --json` (from `cargo-audit`) before anything is created
+//! on GitHub. Each vulnerable dependency blocks the release, or only warns
+//! with `warn-only = true`. Advisories in the ignore list are accepted until
+//! their `until` date; after it they count again, so accepted risks are
+//! revisited instead of being forgotten.
+
+use crate::cli::AdvisoryFinding;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::AuditGate;
+use serde_json::Value;
+
+use super::context::ReleasePhaseContext;
+
+/// Check `Cargo.lock` and fail on vulnerabilities that are neither ignored nor advisory
+pub(super) async fn run_advisory_check(ctx: &ReleasePhaseContext<'_>, gate: &AuditGate) -> Result<()> {
+    use crate::cli::timeouts::TimedPhase;
+
+    // Libraries often do not commit a lock file; audit what a fresh build would resolve
+    if !ctx.release_clone_path.join("Cargo.lock").exists() {
+        ctx.config
+            .verbose_println("   No Cargo.lock in the release; generating one")
+            .expect("Failed to write to stdout");
+        let (success, _, stderr) = ctx
+            .timeouts
+            .run(
+                TimedPhase::Build,
+                "cargo generate-lockfile",
+                ctx.config,
+                run_cargo(ctx, &["generate-lockfile"]),
+            )
+            .await?;
+        if !success {
+            return Err(audit_error("cargo generate-lockfile", stderr.trim().to_string()));
+        }
+    }
+
+    let (_, stdout, stderr) = ctx
+        .timeouts
+        .run(TimedPhase::Build, "cargo audit", ctx.config, run_cargo(ctx, &["audit", "--json"]))
+        .await?;
+    // cargo audit exits non-zero when it finds vulnerabilities, so only its JSON tells the outcome
+    let report: Value = serde_json::from_str(&stdout).map_err(|_| {
+        let reason = if stderr.contains("no such command") {
+            "cargo-audit is not installed (cargo install cargo-audit --locked)".to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        audit_error("cargo audit --json", reason)
+    })?;
+
+    let today = chrono::Utc::now().date_naive();
+    let vulnerabilities = report
+        .pointer("/vulnerabilities/list")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut blocking = Vec::new();
+
+    for vulnerability in &vulnerabilities {
+        let field = |pointer: &str| {
+            vulnerability
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut finding = AdvisoryFinding {
+            id: field("/advisory/id"),
+            package: field("/package/name"),
+            version: field("/package/version"),
+            title: field("/advisory/title"),
+            patched: vulnerability
+                .pointer("/versions/patched")
+                .and_then(Value::as_array)
+                .map(|patched| patched.iter().filter_map(Value::as_str).map(String::from).collect())
+                .unwrap_or_default(),
+            status: "",
+            note: String::new(),
+        };
+        let label = format!("{} ({} {}): {}", finding.id, finding.package, finding.version, finding.title);
+
+        match gate.ignored(&finding.id) {
+            Some(ignored) if ignored.is_active(today) => {
+                finding.status = "ignored";
+                finding.note = match (&ignored.until, &ignored.reason) {
+                    (Some(until), Some(reason)) => format!("until {}: {}", until, reason),
+                    (Some(until), None) => format!("until {}", until),
+                    (None, Some(reason)) => reason.clone(),
+                    (None, None) => String::new(),
+                };
+                ctx.config
+                    .verbose_println(&format!("   Ignoring {}", label))
+                    .expect("Failed to write to stdout");
+            }
+            expired => {
+                if let Some(until) = expired.and_then(|ignored| ignored.until) {
+                    finding.note = format!("ignore entry expired on {}", until);
+                }
+                if gate.warn_only {
+                    finding.status = "warned";
+                    ctx.config
+                        .warning_println(&format!("⚠️  {}", label))
+                        .expect("Failed to write to stdout");
+                } else {
+                    finding.status = "blocked";
+                    ctx.config
+                        .indent(&format!("✗ {}", label))
+                        .expect("Failed to write to stdout");
+                    blocking.push(finding.id.clone());
+                }
+                if !finding.note.is_empty() {
+                    ctx.config
+                        .indent(&format!("  {} {}", finding.id, finding.note))
+                        .expect("Failed to write to stdout");
+                }
+            }
+        }
+        ctx.config.journal().add_advisory(finding);
+    }
+
+    if !blocking.is_empty() {
+        return Err(audit_error(
+            "cargo audit",
+            format!(
+                "vulnerable dependencies: {}; update them, or accept them in [[package.metadata.release.audit.ignore]]",
+                blocking.join(", ")
+            ),
+        ));
+    }
+
+    let dependencies = report
+        .pointer("/lockfile/dependency-count")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    let summary = if vulnerabilities.is_empty() {
+        format!("✓ {} dependencies checked, no vulnerabilities", dependencies)
+    } else {
+        format!(
+            "✓ {} dependencies checked, {} advisory(ies) ignored or accepted",
+            dependencies,
+            vulnerabilities.len()
+        )
+    };
+    ctx.config.success_println(&summary).expect("Failed to write to stdout");
+    Ok(())
+}
+
+/// Run cargo in the clone, returning success, stdout and stderr
+async fn run_cargo(ctx: &ReleasePhaseContext<'_>, args: &[&str]) -> Result<(bool, String, String)> {
+    let output = crate::exec::Exec::new("cargo")
+        .args(args)
+        .current_dir(ctx.release_clone_path)
+        .output_watched(ctx.config)
+        .await?;
+
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    ))
+}
+
+fn audit_error(command: &str, reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: command.to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,131 @@
+//! Release branches deleted after a successful release.
+//!
+//! With `keep-release-branches = N` in `[package.metadata.release.retention]`,
+//! a branch named like the tag of a published release (`v1.2.3`) is deleted
+//! on GitHub and in the local checkout, except for the N newest releases. A
+//! branch with commits its tag does not contain is kept, with a warning.
+
+use crate::cli::RuntimeConfig;
+use crate::error::Result;
+use crate::github::GitHubReleaseManager;
+use std::path::Path;
+
+/// Delete the release branches of all but the `keep` newest published releases
+///
+/// `local_repo` is the source checkout whose branches are deleted too. Each
+/// deleted branch is recorded as a cleanup action; failures only warn.
+pub async fn prune_release_branches(
+    github_manager: &GitHubReleaseManager,
+    keep: usize,
+    local_repo: Option<&Path>,
+    config: &RuntimeConfig,
+) -> Result<usize> {
+    let mut published: Vec<_> = github_manager
+        .list_releases()
+        .await?
+        .into_iter()
+        .filter(|release| !release.draft)
+        .collect();
+    published.sort_by_key(|release| std::cmp::Reverse(release.published_at.or(release.created_at)));
+    let remote_branches = github_manager.list_branches().await?;
+
+    let mut deleted = 0;
+    for release in published.iter().skip(keep) {
+        let branch = release.tag_name.as_str();
+        let on_remote = remote_branches.iter().any(|name| name == branch);
+        let on_local = match local_repo {
+            Some(repo) => local_branch_exists(repo, branch).await,
+            None => false,
+        };
+        if !on_remote && !on_local {
+            continue;
+        }
+
+        if on_remote {
+            let outcome = match github_manager.commits_beyond_tag(branch, branch).await {
+                Ok(0) => github_manager.delete_branch(branch).await,
+                Ok(ahead) => {
+                    config
+                        .warning_println(&format!(
+                            "⚠️  Keeping release branch {}: {} commit(s) are not in tag {}",
+                            branch, ahead, branch
+                        ))
+                        .expect("Failed to write to stdout");
+                    continue;
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = outcome {
+                config
+                    .warning_println(&format!("⚠️  Failed to delete release branch {}: {}", branch, e))
+                    .expect("Failed to write to stdout");
+                continue;
+            }
+            config.record_action(&format!("Deleted release branch {} on GitHub", branch));
+            config
+                .indent(&format!("🗑  Deleted release branch {} on GitHub", branch))
+                .expect("Failed to write to stdout");
+            deleted += 1;
+        }
+
+        if let Some(repo) = local_repo.filter(|_| on_local) {
+            match delete_local_branch(repo, branch).await {
+                Ok(true) => {
+                    config.record_action(&format!("Deleted local release branch {}", branch));
+                    config
+                        .indent(&format!("🗑  Deleted local release branch {}", branch))
+                        .expect("Failed to write to stdout");
+                    deleted += 1;
+                }
+                Ok(false) => config
+                    .warning_println(&format!(
+                        "⚠️  Keeping local release branch {}: it is checked out or has commits not in tag {}",
+                        branch, branch
+                    ))
+                    .expect("Failed to write to stdout"),
+                Err(e) => config
+                    .warning_println(&format!("⚠️  Failed to delete local release branch {}: {}", branch, e))
+                    .expect("Failed to write to stdout"),
+            }
+        }
+    }
+    Ok(deleted)
+}
+
+async fn local_branch_exists(repo: &Path, branch: &str) -> bool {
+    crate::exec::Exec::new("git")
+        .args(["show-ref", "--verify", "--quiet", &format!("refs/heads/{}", branch)])
+        .current_dir(repo)
+        .output()
+        .await
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Delete a local branch that is not checked out and is contained in its tag
+///
+/// Returns `false` when the branch is kept.
+async fn delete_local_branch(repo: &Path, branch: &str) -> Result<bool> {
+    let git = |args: &[&str]| {
+        let mut exec = crate::exec::Exec::new("git");
+        exec.args(args).current_dir(repo);
+        exec
+    };
+
+    let head = git(&["symbolic-ref", "--quiet", "--short", "HEAD"]).output().await?;
+    if String::from_utf8_lossy(&head.stdout).trim() == branch {
+        return Ok(false);
+    }
+    let contained = git(&[
+        "merge-base",
+        "--is-ancestor",
+        &format!("refs/heads/{}", branch),
+        &format!("refs/tags/{}", branch),
+    ])
+    .output()
+    .await?;
+    if !contained.status.success() {
+        return Ok(false);
+    }
+    git(&["branch", "-D", branch]).run().await?;
+    Ok(true)
+}
@@ -0,0 +1,176 @@
+//! Tarball (and optional FreeBSD package) artifacts for BSD targets.
+//!
+//! The external bundler has no BSD support, so BSD targets configured in
+//! `[package.metadata.release.bsd]` are built here: with `cargo` on a matching
+//! BSD host, otherwise with `cross` (Docker). Each target becomes
+//!
+//! ```text
+//! <bin>-<version>-<target>.tar.gz   # <bin>-<version>-<target>/{<bin>, README*, LICENSE*}
+//! <bin>-<version>-<target>.pkg      # FreeBSD only, with `pkg = true` and `pkg` installed
+//! ```
+//!
+//! With `pkg = true` the tarball also carries the FreeBSD `+MANIFEST`, so a
+//! package can be created later on a FreeBSD machine with `pkg create`.
+
+use crate::compression::ArtifactKind;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use crate::metadata::Builder;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::compress::compression_variants;
+use super::context::ReleasePhaseContext;
+use super::targets::{compile, default_builder, stage, tarball};
+
+/// Build every configured BSD target
+///
+/// Returns `(target, artifacts)` per target, in configuration order.
+pub async fn build_bsd_artifacts(ctx: &ReleasePhaseContext<'_>) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    let mut built = Vec::new();
+
+    for target in &ctx.bsd.targets {
+        ctx.config
+            .verbose_println(&format!("\n   Building {} (tarball)...", target))
+            .expect("Failed to write to stdout");
+
+        let binary = build_target(ctx, target).await?;
+        let mut artifacts = Vec::new();
+
+        let name = format!("{}-{}-{}", ctx.binary_name, ctx.new_version, target);
+        let staging = stage(ctx, &name, &binary)?;
+
+        let freebsd_pkg = ctx.bsd.pkg && target.ends_with("-freebsd");
+        let pkg_manifest = if freebsd_pkg {
+            let manifest = pkg_manifest(ctx, target, &binary)?;
+            std::fs::write(staging.join("+MANIFEST"), &manifest)?;
+            Some(manifest)
+        } else {
+            None
+        };
+
+        artifacts.push(tarball(ctx, &name, &staging).await?);
+
+        if let Some(manifest) = pkg_manifest {
+            match create_pkg(ctx, &name, &binary, &manifest).await? {
+                Some(pkg) => artifacts.push(pkg),
+                None => ctx
+                    .config
+                    .warning_println(&format!(
+                        "⚠️  `pkg` not found; {} ships +MANIFEST for `pkg create` on FreeBSD",
+                        name
+                    ))
+                    .expect("Failed to write to stdout"),
+            }
+        }
+
+        built.push((target.clone(), compression_variants(ctx, ArtifactKind::Bsd, artifacts).await?));
+    }
+
+    Ok(built)
+}
+
+/// Compile the release binary for `target` and return its path
+async fn build_target(ctx: &ReleasePhaseContext<'_>, target: &str) -> Result<PathBuf> {
+    let builder = default_builder(target);
+    if builder == Builder::Cross && target.ends_with("-openbsd") {
+        // cross ships no OpenBSD images
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("{} can only be built on an OpenBSD host", target),
+        }));
+    }
+
+    compile(ctx, target, builder, None).await
+}
+
+/// FreeBSD `+MANIFEST` installing the binary as `/usr/local/bin/<bin>`
+fn pkg_manifest(ctx: &ReleasePhaseContext<'_>, target: &str, binary: &Path) -> Result<String> {
+    let arch = match target.split('-').next().unwrap_or_default() {
+        "x86_64" => "amd64",
+        "i686" => "i386",
+        other => other,
+    };
+    let bsd = ctx.bsd;
+    let description = bsd.description.clone().unwrap_or_else(|| ctx.package_name.to_string());
+
+    // SPDX "A OR B" / "A AND B" map onto pkg's license logic
+    let license = bsd.license.as_deref().unwrap_or_default();
+    let (license_logic, licenses): (&str, Vec<&str>) = if license.contains(" OR ") {
+        ("or", license.split(" OR ").map(str::trim).collect())
+    } else if license.contains(" AND ") {
+        ("and", license.split(" AND ").map(str::trim).collect())
+    } else {
+        ("single", [license].into_iter().filter(|l| !l.is_empty()).collect())
+    };
+
+    let sha256 = format!("{:x}", Sha256::digest(std::fs::read(binary)?));
+    let manifest = serde_json::json!({
+        "name": ctx.package_name,
+        // pkg versions cannot contain '-'
+        "version": ctx.new_version.to_string().replace('-', "."),
+        "origin": format!("misc/{}", ctx.package_name),
+        "comment": description.lines().next().unwrap_or_default(),
+        "desc": description,
+        "maintainer": bsd.maintainer.as_deref().unwrap_or("unknown"),
+        "www": bsd.homepage.as_deref().unwrap_or_default(),
+        "prefix": "/usr/local",
+        // Packages built against FreeBSD 13 also install on 14
+        "abi": format!("FreeBSD:13:{}", arch),
+        "licenselogic": license_logic,
+        "licenses": licenses,
+        "files": { format!("/usr/local/bin/{}", ctx.binary_name): sha256 },
+    });
+
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+/// Run `pkg create`; returns `None` when `pkg` is not installed
+async fn create_pkg(
+    ctx: &ReleasePhaseContext<'_>,
+    name: &str,
+    binary: &Path,
+    manifest: &str,
+) -> Result<Option<PathBuf>> {
+    let available = Exec::new("pkg").arg("--version").run().await.is_ok();
+    if !available {
+        return Ok(None);
+    }
+
+    let work = ctx.release_clone_path.join("artifacts").join(format!("{}-pkg", name));
+    let root = work.join("root");
+    let out = work.join("out");
+    std::fs::create_dir_all(root.join("usr/local/bin"))?;
+    std::fs::create_dir_all(&out)?;
+    std::fs::copy(binary, root.join("usr/local/bin").join(ctx.binary_name))?;
+    let manifest_path = work.join("+MANIFEST");
+    std::fs::write(&manifest_path, manifest)?;
+
+    Exec::new("pkg")
+        .arg("create")
+        .arg("-M")
+        .arg(&manifest_path)
+        .arg("-r")
+        .arg(&root)
+        .arg("-o")
+        .arg(&out)
+        .run_watched(ctx.config)
+        .await?;
+
+    let created = std::fs::read_dir(&out)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|path| path.extension().is_some_and(|ext| ext == "pkg"))
+        .ok_or_else(|| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "pkg create".to_string(),
+                reason: format!("No package written to {}", out.display()),
+            })
+        })?;
+
+    let pkg = ctx.release_clone_path.join("artifacts").join(format!("{}.pkg", name));
+    std::fs::rename(created, &pkg)?;
+    ctx.config
+        .indent(&format!("✓ {}.pkg", name))
+        .expect("Failed to write to stdout");
+
+    Ok(Some(pkg))
+}
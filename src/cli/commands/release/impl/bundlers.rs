@@ -0,0 +1,129 @@
+//! Custom package formats from `[[package.metadata.release.bundlers]]`.
+//!
+//! Formats the external bundler does not know (internal tarball layouts,
+//! proprietary installers, ...) are produced by a configured command after
+//! the built-in bundles, without changes to this tool. Each command runs in
+//! the release clone with an empty output directory,
+//! `artifacts/<name>/<arch>/`, and the files it leaves there that match the
+//! bundler's `artifacts` globs are released like any other bundle.
+//!
+//! Arguments may use `{binary}` (binary name), `{binary_path}` (the built
+//! release binary), `{version}`, `{arch}` and `{out_dir}`; the same values are
+//! exported as `KODEGEN_BUNDLE_*` environment variables.
+
+use crate::compression::ArtifactKind;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use crate::metadata::CustomBundler;
+use std::path::{Path, PathBuf};
+
+use super::compress::compression_variants;
+use super::context::ReleasePhaseContext;
+use super::platform::{bundle_output_dir, detect_target_architecture};
+use super::shell_integration::built_binary_path;
+
+/// Run every custom bundler that supports this host
+///
+/// Returns `(name, artifacts)` per bundler, in configuration order.
+pub async fn build_custom_artifacts(ctx: &ReleasePhaseContext<'_>) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let mut built = Vec::new();
+    for bundler in ctx.bundlers {
+        if !bundler.supports_host() {
+            ctx.config
+                .verbose_println(&format!(
+                    "   Skipping {} (runs on {}, not {})",
+                    bundler.name,
+                    bundler.hosts.join(", "),
+                    std::env::consts::OS
+                ))
+                .expect("Failed to write to stdout");
+            continue;
+        }
+
+        ctx.config
+            .verbose_println(&format!("\n   Building {} (custom bundler)...", bundler.name))
+            .expect("Failed to write to stdout");
+        let artifacts = ctx
+            .timeouts
+            .run(
+                TimedPhase::Bundle,
+                &format!("bundle {}", bundler.name),
+                ctx.config,
+                run_bundler(ctx, bundler),
+            )
+            .await?;
+        built.push((bundler.name.clone(), compression_variants(ctx, ArtifactKind::Bundlers, artifacts).await?));
+    }
+
+    Ok(built)
+}
+
+/// Run one bundler and collect its artifacts
+async fn run_bundler(ctx: &ReleasePhaseContext<'_>, bundler: &CustomBundler) -> Result<Vec<PathBuf>> {
+    let arch = detect_target_architecture()?;
+    let out_dir = bundle_output_dir(ctx.release_clone_path, &bundler.name, arch);
+    // Start empty, so only files from this run are picked up
+    if out_dir.exists() {
+        std::fs::remove_dir_all(&out_dir)?;
+    }
+    std::fs::create_dir_all(&out_dir)?;
+
+    let binary_path = built_binary_path(ctx.release_clone_path, ctx.binary_name);
+    let version = ctx.new_version.to_string();
+    let values = [
+        ("binary", ctx.binary_name.to_string()),
+        ("binary_path", binary_path.display().to_string()),
+        ("version", version),
+        ("arch", arch.to_string()),
+        ("out_dir", out_dir.display().to_string()),
+    ];
+    let substitute = |arg: &str| {
+        values
+            .iter()
+            .fold(arg.to_string(), |arg, (key, value)| arg.replace(&format!("{{{}}}", key), value))
+    };
+
+    let mut command = Exec::new(substitute(&bundler.command[0]));
+    command
+        .args(bundler.command[1..].iter().map(|arg| substitute(arg)))
+        .current_dir(ctx.release_clone_path);
+    for (key, value) in &values {
+        command.env(format!("KODEGEN_BUNDLE_{}", key.to_ascii_uppercase()), value);
+    }
+    command.run_watched(ctx.config).await?;
+
+    let artifacts = collect_artifacts(&out_dir, &bundler.artifacts)?;
+    if artifacts.is_empty() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: bundler.command.join(" "),
+            reason: format!(
+                "bundler {} left no files matching {} in {}",
+                bundler.name,
+                bundler.artifacts.join(", "),
+                out_dir.display()
+            ),
+        }));
+    }
+    for artifact in &artifacts {
+        ctx.config
+            .indent(&format!("✓ {}", artifact.display()))
+            .expect("Failed to write to stdout");
+    }
+    Ok(artifacts)
+}
+
+/// Files directly in `dir` whose names match one of `globs`, sorted
+fn collect_artifacts(dir: &Path, globs: &[String]) -> Result<Vec<PathBuf>> {
+    let mut artifacts = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if path.is_file() && globs.iter().any(|glob| crate::github::wildcard_match(glob, &name)) {
+            artifacts.push(path);
+        }
+    }
+    artifacts.sort();
+    Ok(artifacts)
+}
@@ -0,0 +1,45 @@
+//! Compressed variants of the tarball artifacts (`[package.metadata.release.compression]`).
+//!
+//! Runs in each builder before its artifacts are returned, so the variants
+//! are claimed, recorded in the manifest, encrypted and uploaded with them.
+
+use crate::compression::ArtifactKind;
+use crate::error::{CliError, ReleaseError, Result};
+use std::path::PathBuf;
+
+use super::context::ReleasePhaseContext;
+
+/// `artifacts` followed by their compressed variants
+pub(super) async fn compression_variants(
+    ctx: &ReleasePhaseContext<'_>,
+    kind: ArtifactKind,
+    artifacts: Vec<PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    let settings = ctx.compression.settings(kind);
+    if settings.is_noop() || artifacts.is_empty() {
+        return Ok(artifacts);
+    }
+
+    let mut all = artifacts.clone();
+    for path in artifacts {
+        crate::cli::abort::check("artifact compression")?;
+        let variants = tokio::task::spawn_blocking(move || crate::compression::compress_variants(&path, settings))
+            .await
+            .map_err(|e| {
+                ReleaseError::Cli(CliError::ExecutionFailed {
+                    command: "compress".to_string(),
+                    reason: e.to_string(),
+                })
+            })??;
+        for variant in variants {
+            ctx.config
+                .indent(&format!(
+                    "✓ {}",
+                    variant.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+                ))
+                .expect("Failed to write to stdout");
+            all.push(variant);
+        }
+    }
+    Ok(all)
+}
@@ -6,8 +6,14 @@ use crate::cli::RuntimeConfig;
 pub struct ReleasePhaseContext<'a> {
     /// Temporary directory for isolated execution
     pub release_clone_path: &'a std::path::Path,
-    /// Binary name to build and release
+    /// Package name (recorded in the release manifest)
+    pub package_name: &'a str,
+    /// Binary name to build and release (the package name for a library crate)
     pub binary_name: &'a str,
+    /// Features the binary requires (`required-features`); passed to `cargo build`
+    pub binary_features: &'a [String],
+    /// Library crate without a binary: build and bundle phases are skipped
+    pub library_only: bool,
     /// Target version for this release
     pub new_version: &'a semver::Version,
     /// Runtime configuration for output and settings
@@ -18,4 +24,43 @@ pub struct ReleasePhaseContext<'a> {
     pub github_owner: &'a str,
     /// GitHub repository name
     pub github_repo_name: &'a str,
+    /// Bundle from the release clone instead of the GitHub default branch
+    /// (used when rebuilding an existing tag)
+    pub bundle_from_clone: bool,
+    /// Leave the GitHub release as a draft (release profile with `draft = true`)
+    pub keep_draft: bool,
+    /// Build with split debug info and release the symbols per target
+    pub debug_symbols: bool,
+    /// Shell completion / man page generation settings
+    pub shell_integration: &'a crate::metadata::ShellIntegration,
+    /// BSD targets built with `cross` and released as tarballs
+    pub bsd: &'a crate::metadata::BsdTargets,
+    /// Build matrix entries released as tarballs
+    pub targets: &'a [crate::metadata::BuildTarget],
+    /// Custom package formats built after the built-in bundles
+    pub bundlers: &'a [crate::metadata::CustomBundler],
+    /// Test suite to run before the release (`None` when tests are off)
+    pub tests: Option<&'a crate::metadata::TestGate>,
+    /// Lint gates to run before the release (`None` when none are enabled)
+    pub lints: Option<&'a crate::metadata::LintGates>,
+    /// RustSec advisory check (`None` when not configured or skipped)
+    pub audit: Option<&'a crate::metadata::AuditGate>,
+    /// cargo-deny license and ban checks (`None` when not configured or skipped)
+    pub deny: Option<&'a crate::metadata::DenyGate>,
+    /// Mac App Store package settings (stable releases only)
+    pub mac_app_store: Option<&'a crate::metadata::MacAppStore>,
+    /// Microsoft Store product to submit MSIX packages to (stable releases only)
+    pub store: Option<&'a crate::store::StoreConfig>,
+    /// Mirrors that receive the artifacts after GitHub (stable releases only)
+    pub mirrors: &'a [crate::mirror::MirrorConfig],
+    /// Encryption applied to the artifacts before upload
+    pub encryption: Option<&'a crate::encryption::EncryptionConfig>,
+    /// Compression levels and zstd variants of the tarball artifacts
+    pub compression: &'a crate::compression::CompressionConfig,
+    /// Environment configuration (signing credentials are forwarded to the bundler)
+    pub env_config: &'a crate::EnvConfig,
+    /// Per-phase timeouts (bounded by the release deadline)
+    pub timeouts: &'a crate::cli::timeouts::PhaseTimeouts,
+    /// Retry limits, read from the `KODEGEN_RETRY_*` variables
+    pub retries: &'a crate::cli::retry_config::RetryConfig,
 }
@@ -0,0 +1,150 @@
+//! Debug symbol archives from the release build.
+//!
+//! With `[package.metadata.release] debug-symbols = true` the release binaries
+//! are built with debug info split out of the executable, so the installers
+//! stay as small as before, and each build target's symbols are released as
+//! a separate asset:
+//!
+//! ```text
+//! <bin>-<version>-<target>-debug-symbols.zip      # macOS: <bin>.dSYM
+//! <bin>-<version>-<target>-debug-symbols.zip      # Windows: <bin>.pdb
+//! <bin>-<version>-<target>-debug-symbols.tar.gz   # Linux: <bin>.dwp (split DWARF)
+//! ```
+//!
+//! The same cargo settings are passed to the bundler, so a bundle built on
+//! this host from the same source and toolchain matches the symbols.
+
+use crate::compression::ArtifactKind;
+use crate::error::Result;
+use crate::exec::Exec;
+use std::path::{Path, PathBuf};
+
+use super::compress::compression_variants;
+use super::context::ReleasePhaseContext;
+
+/// Cargo settings that build the release profile with split debug info
+pub(super) fn build_env(ctx: &ReleasePhaseContext<'_>) -> Vec<(&'static str, &'static str)> {
+    if !ctx.debug_symbols {
+        return Vec::new();
+    }
+    vec![
+        ("CARGO_PROFILE_RELEASE_DEBUG", "true"),
+        ("CARGO_PROFILE_RELEASE_SPLIT_DEBUGINFO", "packed"),
+    ]
+}
+
+/// Pack the symbols of every release build target
+///
+/// Returns `(label, archives)` per target, like the tarball builders. A
+/// missing symbol file is a soft failure: the release goes on without it.
+pub async fn collect_debug_symbols(ctx: &ReleasePhaseContext<'_>) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    if !ctx.debug_symbols || ctx.library_only {
+        return Ok(Vec::new());
+    }
+
+    let mut archives = Vec::new();
+    for (target, release_dir) in build_dirs(ctx.release_clone_path).await? {
+        let Some(symbols) = symbol_file(&release_dir, &target, ctx.binary_name) else {
+            ctx.config.soft_failure(&format!(
+                "⚠️  No debug symbols for {} in {} (is the profile overriding split-debuginfo?)",
+                target,
+                release_dir.display()
+            ))?;
+            continue;
+        };
+
+        let name = format!("{}-{}-{}-debug-symbols", ctx.binary_name, ctx.new_version, target);
+        let archive = archive(ctx, &symbols, &name).await?;
+        ctx.config
+            .indent(&format!("✓ {}", archive.file_name().unwrap_or_default().to_string_lossy()))
+            .expect("Failed to write to stdout");
+        let variants = compression_variants(ctx, ArtifactKind::DebugSymbols, vec![archive]).await?;
+        archives.push((format!("{}-debug-symbols", target), variants));
+    }
+
+    Ok(archives)
+}
+
+/// `(target, target/.../release)` for each target the release build produced
+///
+/// macOS builds both architectures per target triple; other hosts build the
+/// host target into `target/release`.
+async fn build_dirs(clone_path: &Path) -> Result<Vec<(String, PathBuf)>> {
+    if cfg!(target_os = "macos") {
+        return Ok(["x86_64-apple-darwin", "aarch64-apple-darwin"]
+            .into_iter()
+            .map(|target| (target.to_string(), clone_path.join("target").join(target).join("release")))
+            .collect());
+    }
+    Ok(vec![(host_target().await?, clone_path.join("target").join("release"))])
+}
+
+/// Host target triple, from `rustc -vV`
+async fn host_target() -> Result<String> {
+    let version = Exec::new("rustc").arg("-vV").stdout().await?;
+    Ok(version
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+        .unwrap_or_else(|| format!("{}-unknown-{}", std::env::consts::ARCH, std::env::consts::OS)))
+}
+
+/// The symbol file cargo places next to the binary for `target`
+///
+/// PDB files are named after the crate, with `-` replaced by `_`.
+fn symbol_file(release_dir: &Path, target: &str, binary_name: &str) -> Option<PathBuf> {
+    let candidate = if target.contains("-apple-") {
+        release_dir.join(format!("{}.dSYM", binary_name))
+    } else if target.contains("-windows-msvc") {
+        release_dir.join(format!("{}.pdb", binary_name.replace('-', "_")))
+    } else {
+        release_dir.join(format!("{}.dwp", binary_name))
+    };
+    candidate.exists().then_some(candidate)
+}
+
+/// Pack `symbols` into `artifacts/<name>.zip` (dSYM, PDB) or `.tar.gz` (DWARF)
+async fn archive(ctx: &ReleasePhaseContext<'_>, symbols: &Path, name: &str) -> Result<PathBuf> {
+    let artifacts_dir = ctx.release_clone_path.join("artifacts");
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let dir = symbols.parent().unwrap_or(symbols);
+    let file_name = symbols.file_name().unwrap_or_default();
+
+    let archive = if symbols.extension().is_some_and(|ext| ext == "dSYM") {
+        // ditto keeps the bundle's structure and extended attributes
+        let archive = artifacts_dir.join(format!("{}.zip", name));
+        Exec::new("ditto")
+            .args(["-c", "-k", "--keepParent"])
+            .arg(symbols)
+            .arg(&archive)
+            .run_watched(ctx.config)
+            .await?;
+        archive
+    } else if symbols.extension().is_some_and(|ext| ext == "pdb") {
+        // bsdtar (tar.exe on Windows 10+) picks the zip format from the name
+        let archive = artifacts_dir.join(format!("{}.zip", name));
+        Exec::new("tar")
+            .arg("-a")
+            .arg("-cf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir)
+            .arg(file_name)
+            .run_watched(ctx.config)
+            .await?;
+        archive
+    } else {
+        let archive = artifacts_dir.join(format!("{}.tar.gz", name));
+        Exec::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir)
+            .arg(file_name)
+            .run_watched(ctx.config)
+            .await?;
+        archive
+    };
+
+    Ok(archive)
+}
@@ -0,0 +1,170 @@
+//! License and crate-ban policy check of the release clone (`cargo deny`).
+//!
+//! Runs `cargo deny --format json check licenses bans` (from `cargo-deny`)
+//! before anything is created on GitHub. The policy is the repository's
+//! `deny.toml`, another file named by `config`, or one written from
+//! `allow-licenses` / `deny-crates` in Cargo.toml. Each error diagnostic
+//! blocks the release, or only warns with `warn-only = true`; cargo-deny's own
+//! warnings never block.
+
+use crate::cli::PolicyFinding;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::DenyGate;
+use serde_json::Value;
+use std::path::PathBuf;
+
+use super::context::ReleasePhaseContext;
+
+/// Check the dependency graph and fail on violations unless `warn-only` is set
+pub(super) async fn run_policy_check(ctx: &ReleasePhaseContext<'_>, gate: &DenyGate) -> Result<()> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let config = policy_file(ctx, gate)?;
+    let mut args: Vec<String> = ["deny", "--format", "json", "check", "--config"].map(String::from).to_vec();
+    args.push(config.display().to_string());
+    args.extend(gate.checks.iter().cloned());
+
+    let output = ctx
+        .timeouts
+        .run(
+            TimedPhase::Build,
+            "cargo deny",
+            ctx.config,
+            crate::exec::Exec::new("cargo")
+                .args(&args)
+                .current_dir(ctx.release_clone_path)
+                .output_watched(ctx.config),
+        )
+        .await?;
+    // cargo deny exits non-zero on violations; its JSON lines on stderr tell the outcome
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let events: Vec<Value> = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|event| event.get("type").is_some())
+        .collect();
+    if events.is_empty() && !output.status.success() {
+        let reason = if stderr.contains("no such command") {
+            "cargo-deny is not installed (cargo install cargo-deny --locked)".to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        return Err(deny_error(reason));
+    }
+
+    let mut blocking = Vec::new();
+    let mut warnings = 0;
+    for fields in events
+        .iter()
+        .filter(|event| event.get("type").and_then(Value::as_str) == Some("diagnostic"))
+        .filter_map(|event| event.get("fields"))
+    {
+        let severity = fields.get("severity").and_then(Value::as_str).unwrap_or_default();
+        if !matches!(severity, "error" | "warning") {
+            continue;
+        }
+        let text = |pointer: &str| {
+            fields
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut message = text("/message");
+        let span = text("/labels/0/span");
+        if !span.is_empty() && !message.contains(&span) {
+            message = format!("{} ({})", message, span);
+        }
+        let mut finding = PolicyFinding {
+            code: text("/code"),
+            package: text("/graphs/0/Krate/name"),
+            version: text("/graphs/0/Krate/version"),
+            message,
+            status: "",
+        };
+        let label = if finding.package.is_empty() {
+            finding.message.clone()
+        } else {
+            format!("{} {}: {}", finding.package, finding.version, finding.message)
+        };
+
+        if severity == "error" && !gate.warn_only {
+            finding.status = "blocked";
+            ctx.config
+                .indent(&format!("✗ {}", label))
+                .expect("Failed to write to stdout");
+            blocking.push(label);
+        } else {
+            finding.status = "warned";
+            warnings += 1;
+            ctx.config
+                .warning_println(&format!("⚠️  {}", label))
+                .expect("Failed to write to stdout");
+        }
+        ctx.config.journal().add_policy_finding(finding);
+    }
+
+    if !blocking.is_empty() {
+        return Err(deny_error(format!(
+            "{} dependency policy violation(s); change the dependencies or the policy in {}",
+            blocking.len(),
+            if gate.is_inline() {
+                "[package.metadata.release.deny]".to_string()
+            } else {
+                config.display().to_string()
+            }
+        )));
+    }
+
+    let summary = if warnings == 0 {
+        format!("✓ Dependency policy satisfied ({})", gate.checks.join(", "))
+    } else {
+        format!("✓ Dependency policy satisfied with {} warning(s)", warnings)
+    };
+    ctx.config.success_println(&summary).expect("Failed to write to stdout");
+    Ok(())
+}
+
+/// Policy file for cargo deny, written from the inline policy when there is one
+fn policy_file(ctx: &ReleasePhaseContext<'_>, gate: &DenyGate) -> Result<PathBuf> {
+    if gate.is_inline() {
+        let quoted = |items: &[String]| {
+            items
+                .iter()
+                .map(|item| toml::Value::String(item.clone()).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let path = ctx.release_clone_path.join("target").join("release-deny.toml");
+        std::fs::create_dir_all(path.parent().unwrap_or(ctx.release_clone_path))?;
+        std::fs::write(
+            &path,
+            format!(
+                "[licenses]\nallow = [{}]\n\n[bans]\ndeny = [{}]\n",
+                quoted(&gate.allow_licenses),
+                quoted(&gate.deny_crates)
+            ),
+        )?;
+        return Ok(path);
+    }
+
+    let path = ctx
+        .release_clone_path
+        .join(gate.config.as_deref().unwrap_or(std::path::Path::new("deny.toml")));
+    if !path.is_file() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "{} not found; create it with `cargo deny init`, or set allow-licenses / deny-crates in [package.metadata.release.deny]",
+                path.display()
+            ),
+        }));
+    }
+    Ok(path)
+}
+
+fn deny_error(reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "cargo deny".to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,191 @@
+//! Disk-space preflight before cloning, building and bundling.
+//!
+//! A release needs room for the temp clone, a release `target/` directory
+//! (two on macOS), the bundler's Docker build directory and the bundles
+//! themselves, which together can reach tens of GB. Running out halfway leaves
+//! a draft release with partial assets, so the estimate is checked against the
+//! free space of each volume involved before any work starts.
+//!
+//! The estimate is deliberately conservative:
+//! - clone: size of the repository (including `.git`, excluding `target/`)
+//! - build: the existing `target/release` size, or the source size × [`BUILD_FACTOR`]
+//! - Docker: one more build directory on the system temp volume
+//! - bundles: previous bundles in `artifacts/`, or [`BUNDLE_ESTIMATE`] per platform
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::util::format_bytes;
+use std::path::{Path, PathBuf};
+
+use super::platform::{get_docker_platforms, get_platforms_to_build};
+
+/// Release build output relative to source size when no previous build exists
+const BUILD_FACTOR: u64 = 20;
+
+/// Lower bound for a release build directory
+const MIN_BUILD_ESTIMATE: u64 = 2 * GIB;
+
+/// Per-platform bundle size when no previous bundles exist
+const BUNDLE_ESTIMATE: u64 = 256 * MIB;
+
+/// Free space kept in reserve on every volume
+const SAFETY_MARGIN: u64 = GIB;
+
+const MIB: u64 = 1024 * 1024;
+const GIB: u64 = 1024 * MIB;
+
+/// Space needed on one volume
+struct VolumeRequirement {
+    mount_point: PathBuf,
+    available: u64,
+    parts: Vec<(&'static str, u64)>,
+}
+
+impl VolumeRequirement {
+    fn required(&self) -> u64 {
+        self.parts.iter().map(|(_, bytes)| bytes).sum::<u64>() + SAFETY_MARGIN
+    }
+}
+
+/// Check that the work and temp volumes can hold the release
+///
+/// `work_dir` is the directory the release runs in (the repository itself for
+/// in-place and already-cloned sources), or `None` when a temp clone will be
+/// created under the system temp directory. Set `KODEGEN_SKIP_DISK_CHECK` to
+/// skip the check.
+pub fn check_disk_space(
+    repo_path: &Path,
+    work_dir: Option<&Path>,
+    config: &RuntimeConfig,
+    env_config: &crate::EnvConfig,
+) -> Result<()> {
+    if env_config.is_set("KODEGEN_SKIP_DISK_CHECK") {
+        config
+            .verbose_println("ℹ️  Disk space check skipped (KODEGEN_SKIP_DISK_CHECK)")
+            .expect("Failed to write to stdout");
+        return Ok(());
+    }
+
+    let temp_root = std::env::temp_dir();
+    let work_volume = work_dir.map(Path::to_path_buf).unwrap_or_else(|| temp_root.clone());
+
+    let repo_bytes = dir_size(repo_path, &["target"]);
+    let source_bytes = repo_bytes.saturating_sub(dir_size(&repo_path.join(".git"), &[]));
+    let targets = if cfg!(target_os = "macos") { 2 } else { 1 };
+    let build_bytes = match dir_size(&repo_path.join("target").join("release"), &[]) {
+        0 => (source_bytes * BUILD_FACTOR).max(MIN_BUILD_ESTIMATE),
+        previous => previous,
+    };
+
+    let platforms = get_platforms_to_build();
+    let bundle_bytes = match dir_size(&repo_path.join("artifacts"), &[]) {
+        0 => BUNDLE_ESTIMATE * platforms.len() as u64,
+        previous => previous,
+    };
+
+    let mut parts: Vec<(&Path, &'static str, u64)> = Vec::new();
+    if work_dir.is_none() {
+        parts.push((&work_volume, "temp clone", repo_bytes));
+    }
+    parts.push((&work_volume, "release build", build_bytes * targets));
+    parts.push((&work_volume, "bundles", bundle_bytes));
+    if !get_docker_platforms(&platforms).is_empty() {
+        parts.push((&temp_root, "Docker build", build_bytes));
+    }
+
+    // Group by volume so a shared temp/work volume is checked once for the sum
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let mut volumes: Vec<VolumeRequirement> = Vec::new();
+    for (path, label, bytes) in parts {
+        let Some(disk) = disk_for_path(&disks, path) else {
+            config
+                .verbose_println(&format!("ℹ️  Could not determine the volume for {}; skipping its disk check", path.display()))
+                .expect("Failed to write to stdout");
+            continue;
+        };
+        match volumes.iter_mut().find(|v| v.mount_point == disk.mount_point()) {
+            Some(volume) => volume.parts.push((label, bytes)),
+            None => volumes.push(VolumeRequirement {
+                mount_point: disk.mount_point().to_path_buf(),
+                available: disk.available_space(),
+                parts: vec![(label, bytes)],
+            }),
+        }
+    }
+
+    let mut short = false;
+    for volume in &volumes {
+        let breakdown = volume
+            .parts
+            .iter()
+            .map(|(label, bytes)| format!("{} {}", label, format_bytes(*bytes)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if volume.available >= volume.required() {
+            config
+                .verbose_println(&format!(
+                    "✓ Disk space on {}: {} free, ~{} needed ({})",
+                    volume.mount_point.display(),
+                    format_bytes(volume.available),
+                    format_bytes(volume.required()),
+                    breakdown
+                ))
+                .expect("Failed to write to stdout");
+        } else {
+            short = true;
+            config.error_println(&format!(
+                "Not enough disk space on {}: {} free, ~{} needed ({} + {} reserve)",
+                volume.mount_point.display(),
+                format_bytes(volume.available),
+                format_bytes(volume.required()),
+                breakdown,
+                format_bytes(SAFETY_MARGIN)
+            ));
+        }
+    }
+
+    if short {
+        config
+            .indent("Free up space (e.g. `cargo clean`, `docker system prune`), point TMPDIR at a larger volume,")
+            .expect("Failed to write to stdout");
+        config
+            .indent("use --in-place to skip the temp clone, or set KODEGEN_SKIP_DISK_CHECK=1 to override")
+            .expect("Failed to write to stdout");
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "disk_space_preflight".to_string(),
+            reason: "Insufficient disk space for the release".to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Disk whose mount point is the longest prefix of `path`
+fn disk_for_path<'a>(disks: &'a sysinfo::Disks, path: &Path) -> Option<&'a sysinfo::Disk> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+}
+
+/// Total size of regular files under `dir`, skipping top-level `exclude` entries
+///
+/// Symlinks are not followed. Missing directories count as zero.
+fn dir_size(dir: &Path, exclude: &[&str]) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| !exclude.iter().any(|name| entry.file_name() == *name))
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path(), &[]),
+            Ok(meta) if meta.is_file() => meta.len(),
+            _ => 0,
+        })
+        .sum()
+}
@@ -0,0 +1,49 @@
+//! Encryption of the artifacts before upload (`[package.metadata.release.encryption]`).
+//!
+//! The plaintext stays in the release clone; only the encrypted copies are
+//! uploaded. Each manifest entry is switched to its encrypted copy and keeps
+//! the plaintext's name and SHA-256 so `download --decrypt` can verify it.
+
+use crate::error::Result;
+use crate::manifest::ReleaseManifest;
+use std::path::PathBuf;
+
+use super::context::ReleasePhaseContext;
+
+/// Encrypt `artifacts` if encryption is configured and return the files to upload
+pub(super) async fn encrypt_artifacts(
+    ctx: &ReleasePhaseContext<'_>,
+    manifest: &mut ReleaseManifest,
+    artifacts: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let Some(encryption) = ctx.encryption else {
+        return Ok(artifacts.to_vec());
+    };
+    if artifacts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let method = encryption.method();
+    ctx.config
+        .println(&format!(
+            "🔒 Encrypting {} artifact(s) with {}...",
+            artifacts.len(),
+            method.extension()
+        ))
+        .expect("Failed to write to stdout");
+
+    let mut encrypted = Vec::with_capacity(artifacts.len());
+    for path in artifacts {
+        crate::cli::abort::check("artifact encryption")?;
+        let output = encryption.encrypt(path, ctx.env_config).await?;
+        manifest.record_encryption(path, &output, method)?;
+        ctx.config
+            .verbose_println(&format!(
+                "   ✓ {}",
+                output.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+            ))
+            .expect("Failed to write to stdout");
+        encrypted.push(output);
+    }
+    Ok(encrypted)
+}
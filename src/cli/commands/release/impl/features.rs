@@ -0,0 +1,146 @@
+//! Feature-set checks for published crates.
+//!
+//! `cargo publish` verifies a crate with one feature set only, so code behind
+//! other features can be broken without anyone noticing until a user enables
+//! them. Before the GitHub release is created, every publishable crate in the
+//! release clone is checked with the feature set it is published with
+//! (`[package.metadata.release.publish]`) and with `--all-features`.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::PublishFeatures;
+
+use super::context::ReleasePhaseContext;
+
+/// Check every publishable crate with its published features and `--all-features`
+///
+/// Returns the number of `cargo check` runs.
+pub(super) async fn check_publish_features(ctx: &ReleasePhaseContext<'_>) -> Result<usize> {
+    let crates = publishable_crates(ctx.release_clone_path)?;
+    let mut checks = 0;
+
+    for PublishableCrate { package, features, .. } in &crates {
+        let published = features.cargo_args();
+        let description = if published.is_empty() {
+            "default features".to_string()
+        } else {
+            published.join(" ")
+        };
+        cargo_check(ctx, package.as_deref(), &published, &description).await?;
+        checks += 1;
+
+        if features.check_all_features {
+            cargo_check(ctx, package.as_deref(), &["--all-features".to_string()], "--all-features").await?;
+            checks += 1;
+        }
+    }
+
+    Ok(checks)
+}
+
+/// A crate that is published to crates.io
+pub(super) struct PublishableCrate {
+    /// Workspace member name; `None` for a single-crate repository
+    pub package: Option<String>,
+    /// Workspace member version; `None` for a single-crate repository,
+    /// which is published at the release version
+    pub version: Option<String>,
+    pub features: PublishFeatures,
+    /// Whether `publish` allows crates.io, rather than only other registries
+    pub crates_io: bool,
+}
+
+/// Publishable, non-skipped crates of the repository, sorted by name
+pub(super) fn publishable_crates(repo: &std::path::Path) -> Result<Vec<PublishableCrate>> {
+    if crate::workspace::WorkspaceInfo::is_workspace_root(repo)? {
+        let workspace = crate::workspace::WorkspaceInfo::analyze(repo)?;
+        let skipped = workspace.workspace_config.skip_publish();
+        let mut crates: Vec<_> = workspace
+            .packages
+            .values()
+            .filter(|package| package.config.is_publishable() && !skipped.contains(&package.name))
+            .map(|package| {
+                Ok(PublishableCrate {
+                    package: Some(package.name.clone()),
+                    version: Some(package.version.clone()),
+                    features: package.config.publish_features()?,
+                    crates_io: allows_crates_io(package.config.publish.as_ref()),
+                })
+            })
+            .collect::<Result<_>>()?;
+        crates.sort_by(|a: &PublishableCrate, b| a.package.cmp(&b.package));
+        return Ok(crates);
+    }
+
+    let content = std::fs::read_to_string(repo.join("Cargo.toml"))?;
+    let manifest: toml::Value = toml::from_str(&content)?;
+    let Some(package) = manifest.get("package") else {
+        return Ok(Vec::new());
+    };
+    if package.get("publish").and_then(|p| p.as_bool()) == Some(false) {
+        return Ok(Vec::new());
+    }
+    Ok(vec![PublishableCrate {
+        package: None,
+        version: None,
+        features: PublishFeatures::from_package(package)?,
+        crates_io: allows_crates_io(package.get("publish")),
+    }])
+}
+
+/// Whether a `publish` value lets the crate go to crates.io
+///
+/// `publish = ["my-registry"]` publishes elsewhere only; `crates-io` is the
+/// name cargo uses for crates.io in that list.
+fn allows_crates_io(publish: Option<&toml::Value>) -> bool {
+    match publish {
+        Some(toml::Value::Boolean(publish)) => *publish,
+        Some(toml::Value::Array(registries)) => registries.iter().any(|r| r.as_str() == Some("crates-io")),
+        _ => true,
+    }
+}
+
+async fn cargo_check(
+    ctx: &ReleasePhaseContext<'_>,
+    package: Option<&str>,
+    feature_args: &[String],
+    description: &str,
+) -> Result<()> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let mut args = vec!["check".to_string()];
+    if let Some(package) = package {
+        args.push("-p".to_string());
+        args.push(package.to_string());
+    }
+    args.extend(feature_args.iter().cloned());
+
+    let command = format!("cargo {}", args.join(" "));
+    let output = ctx
+        .timeouts
+        .run(
+            TimedPhase::Build,
+            &command,
+            ctx.config,
+            crate::exec::Exec::new("cargo")
+                .args(&args)
+                .current_dir(ctx.release_clone_path)
+                .output_watched(ctx.config),
+        )
+        .await?;
+
+    if !output.status.success() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command,
+            reason: String::from_utf8_lossy(&output.stderr).to_string(),
+        }));
+    }
+
+    ctx.config
+        .indent(&format!(
+            "✓ {} ({})",
+            package.unwrap_or(ctx.package_name),
+            description
+        ))
+        .expect("Failed to write to stdout");
+    Ok(())
+}
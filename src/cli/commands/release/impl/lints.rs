@@ -0,0 +1,168 @@
+//! `cargo fmt` / `cargo clippy` gates run in the release clone.
+//!
+//! Each publishable crate is checked with the gates enabled in
+//! `[package.metadata.release.lints]`. Every crate is checked before the
+//! outcome is decided, so the report lists all findings; failures of an
+//! advisory gate are warnings, any other failure stops the release.
+
+use crate::cli::LintResult;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::LintGates;
+
+use super::context::ReleasePhaseContext;
+use super::features::{PublishableCrate, publishable_crates};
+
+/// Run the enabled lint gates for every publishable crate
+pub(super) async fn run_lint_gates(ctx: &ReleasePhaseContext<'_>, gates: &LintGates) -> Result<()> {
+    let crates = publishable_crates(ctx.release_clone_path)?;
+    let mut blocking = Vec::new();
+
+    for PublishableCrate { package, .. } in &crates {
+        let name = package.as_deref().unwrap_or(ctx.package_name);
+        let mut results = Vec::new();
+        if gates.fmt {
+            results.push(fmt_check(ctx, package.as_deref(), name, gates).await?);
+        }
+        if gates.clippy {
+            results.push(clippy_check(ctx, package.as_deref(), name, gates).await?);
+        }
+
+        for result in results {
+            if result.passed {
+                ctx.config
+                    .indent(&format!("✓ {} ({})", name, result.gate))
+                    .expect("Failed to write to stdout");
+            } else if result.advisory {
+                ctx.config
+                    .warning_println(&format!("⚠️  {} ({}, advisory): {}", name, result.gate, result.detail))
+                    .expect("Failed to write to stdout");
+            } else {
+                ctx.config
+                    .indent(&format!("✗ {} ({}): {}", name, result.gate, result.detail))
+                    .expect("Failed to write to stdout");
+                blocking.push(format!("{} ({})", name, result.gate));
+            }
+            ctx.config.journal().add_lint(result);
+        }
+    }
+
+    if !blocking.is_empty() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "lint gates".to_string(),
+            reason: format!(
+                "{} failed; fix the findings or mark the gate advisory in [package.metadata.release.lints]",
+                blocking.join(", ")
+            ),
+        }));
+    }
+    Ok(())
+}
+
+/// `cargo fmt --check`, counting the files that need formatting
+async fn fmt_check(
+    ctx: &ReleasePhaseContext<'_>,
+    package: Option<&str>,
+    name: &str,
+    gates: &LintGates,
+) -> Result<LintResult> {
+    let mut args = vec!["fmt".to_string()];
+    if let Some(package) = package {
+        args.extend(["-p".to_string(), package.to_string()]);
+    }
+    args.extend(["--".to_string(), "--check".to_string(), "-l".to_string()]);
+
+    let (passed, stdout, stderr) = run_cargo(ctx, &args).await?;
+    // With -l, every file that needs formatting is listed on its own line
+    let files = stdout.lines().filter(|line| !line.trim().is_empty()).count();
+    let detail = if passed {
+        String::new()
+    } else if files > 0 {
+        format!("{} file(s) need formatting", files)
+    } else {
+        first_line(&stderr)
+    };
+
+    Ok(LintResult {
+        package: name.to_string(),
+        gate: "fmt",
+        passed,
+        advisory: gates.is_advisory("fmt"),
+        detail,
+    })
+}
+
+/// `cargo clippy --all-targets -- -D warnings`, counting the diagnostics
+async fn clippy_check(
+    ctx: &ReleasePhaseContext<'_>,
+    package: Option<&str>,
+    name: &str,
+    gates: &LintGates,
+) -> Result<LintResult> {
+    let mut args = vec!["clippy".to_string()];
+    if let Some(package) = package {
+        args.extend(["-p".to_string(), package.to_string()]);
+    }
+    args.extend(["--all-targets", "--message-format=short", "--", "-D", "warnings"].map(String::from));
+    args.extend(gates.clippy_args.iter().cloned());
+
+    let (passed, _, stderr) = run_cargo(ctx, &args).await?;
+    // Short diagnostics look like `src/lib.rs:3:5: error: ...`
+    let diagnostics: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.contains(": error: ") || line.contains(": warning: "))
+        .collect();
+    for line in &diagnostics {
+        ctx.config.verbose_println(&format!("   {}", line)).expect("Failed to write to stdout");
+    }
+    let detail = if passed {
+        String::new()
+    } else if !diagnostics.is_empty() {
+        format!("{} diagnostic(s)", diagnostics.len())
+    } else {
+        first_line(&stderr)
+    };
+
+    Ok(LintResult {
+        package: name.to_string(),
+        gate: "clippy",
+        passed,
+        advisory: gates.is_advisory("clippy"),
+        detail,
+    })
+}
+
+/// Run cargo in the clone, bounded by the build timeout
+async fn run_cargo(ctx: &ReleasePhaseContext<'_>, args: &[String]) -> Result<(bool, String, String)> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let command = format!("cargo {}", args.join(" "));
+    let output = ctx
+        .timeouts
+        .run(
+            TimedPhase::Build,
+            &command,
+            ctx.config,
+            crate::exec::Exec::new("cargo")
+                .args(args)
+                .current_dir(ctx.release_clone_path)
+                .output_watched(ctx.config),
+        )
+        .await?;
+
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    ))
+}
+
+/// First error line of cargo's output, for failures without diagnostics
+fn first_line(stderr: &str) -> String {
+    stderr
+        .lines()
+        .find(|line| line.starts_with("error"))
+        .or_else(|| stderr.lines().find(|line| !line.trim().is_empty()))
+        .unwrap_or("failed")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,144 @@
+//! Mac App Store package, separate from the Developer ID bundles.
+//!
+//! The bundler builds the `mas` platform: an app bundle signed with the App
+//! Store application identity, the sandbox entitlements and the embedded
+//! provisioning profile, wrapped in a pkg signed with the installer identity.
+//! The pkg is not notarized and never attached to the GitHub release; it is
+//! uploaded to App Store Connect with `xcrun altool` and the App Store Connect
+//! API key (`APPLE_API_KEY`, `APPLE_API_ISSUER`).
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::manifest::AppStorePackage;
+use crate::metadata::MacAppStore;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::context::ReleasePhaseContext;
+use super::platform::{bundle_with_log, ensure_bundler_installed};
+
+/// Build the App Store pkg and upload it unless `upload = false`
+pub(super) async fn build_app_store_package(
+    ctx: &ReleasePhaseContext<'_>,
+    mas: &MacAppStore,
+) -> Result<AppStorePackage> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let bundler_binary = ensure_bundler_installed(ctx).await?;
+    // The pkg is not a release asset, so its failure log stays local
+    let pkg = bundle_with_log(ctx, &bundler_binary, "mas", None)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| upload_error("the bundler produced no pkg".to_string()))?;
+
+    let content = std::fs::read(&pkg)?;
+    let name = pkg
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut package = AppStorePackage {
+        name,
+        size: content.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&content)),
+        uploaded_at: None,
+    };
+
+    if !mas.upload {
+        ctx.config
+            .warning_println(&format!(
+                "⚠️  Not uploading {} (mac-app-store upload = false): {}",
+                package.name,
+                pkg.display()
+            ))
+            .expect("Failed to write to stdout");
+        return Ok(package);
+    }
+
+    ctx.config
+        .println("☁️  Uploading to App Store Connect...")
+        .expect("Failed to write to stdout");
+    ctx.timeouts
+        .run(
+            TimedPhase::Upload,
+            "App Store Connect upload",
+            ctx.config,
+            upload_to_app_store_connect(ctx, &pkg),
+        )
+        .await?;
+    package.uploaded_at = Some(chrono::Utc::now());
+
+    Ok(package)
+}
+
+/// Upload a pkg with `xcrun altool --upload-app`
+async fn upload_to_app_store_connect(ctx: &ReleasePhaseContext<'_>, pkg: &Path) -> Result<()> {
+    let var = |name: &str| {
+        ctx.env_config
+            .get(name)
+            .ok_or_else(|| upload_error(format!("{} is not set", name)))
+    };
+    let api_key = var("APPLE_API_KEY")?;
+    let api_issuer = var("APPLE_API_ISSUER")?;
+
+    // altool looks for AuthKey_<id>.p8 in API_PRIVATE_KEYS_DIR, else in ~/.appstoreconnect/private_keys
+    let keys_dir = private_keys_dir(ctx, &api_key)?;
+
+    let mut altool = crate::exec::Exec::new("xcrun");
+    altool
+        .args(["altool", "--upload-app", "--type", "macos", "--file"])
+        .arg(pkg)
+        .args(["--apiKey", &api_key, "--apiIssuer", &api_issuer]);
+    if let Some(dir) = &keys_dir {
+        altool.env("API_PRIVATE_KEYS_DIR", dir);
+    }
+    let output = altool.output_watched(ctx.config).await;
+    if let Some(dir) = &keys_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    let output = output?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines().filter(|line| !line.trim().is_empty()) {
+        ctx.config.verbose_println(&format!("   {}", line)).expect("Failed to write to stdout");
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(upload_error(format!(
+            "altool exited with {:?}: {}",
+            output.status.code(),
+            if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() }
+        )));
+    }
+
+    ctx.config
+        .indent(&format!("✓ Uploaded {}", pkg.display()))
+        .expect("Failed to write to stdout");
+    Ok(())
+}
+
+/// Temporary directory holding the API key from `APPLE_API_KEY_PATH` or
+/// `APPLE_API_KEY_CONTENT`, or `None` to use the key altool already has
+fn private_keys_dir(ctx: &ReleasePhaseContext<'_>, api_key: &str) -> Result<Option<PathBuf>> {
+    let key = if let Some(path) = ctx.env_config.get("APPLE_API_KEY_PATH") {
+        std::fs::read(path)?
+    } else if let Some(content) = ctx.env_config.get("APPLE_API_KEY_CONTENT") {
+        base64::engine::general_purpose::STANDARD
+            .decode(content.trim())
+            .map_err(|e| upload_error(format!("APPLE_API_KEY_CONTENT is not valid base64: {}", e)))?
+    } else {
+        return Ok(None);
+    };
+
+    let dir = std::env::temp_dir().join(format!("kodegen-release-asc-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(format!("AuthKey_{}.p8", api_key)), key)?;
+    Ok(Some(dir))
+}
+
+fn upload_error(reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "xcrun altool --upload-app".to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,147 @@
+//! Artifact uploads to the mirrors in `[[package.metadata.release.mirrors]]`.
+//!
+//! Runs after the artifacts are on the GitHub release. Every mirror receives
+//! every artifact, and all mirrors are tried before the outcome is decided,
+//! so the manifest records what each destination got. A failed mirror stops
+//! the release unless it is configured with `on-failure = "warn"`.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::manifest::{MirrorArtifact, MirrorFailure, MirrorUpload};
+use crate::mirror::{FailurePolicy, MirrorClient, MirrorConfig};
+use std::path::PathBuf;
+
+use super::context::ReleasePhaseContext;
+use super::retry::retry_with_backoff;
+
+/// Upload `artifacts` to every mirror and return what each received
+pub(super) async fn upload_to_mirrors(
+    ctx: &ReleasePhaseContext<'_>,
+    tag: &str,
+    artifacts: &[PathBuf],
+) -> Result<Vec<MirrorUpload>> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let mut uploads = Vec::with_capacity(ctx.mirrors.len());
+    let mut blocking = Vec::new();
+
+    for mirror in ctx.mirrors {
+        let operation = format!("upload to mirror {}", mirror.name);
+        let upload = match ctx
+            .timeouts
+            .run(TimedPhase::Upload, &operation, ctx.config, upload_to(ctx, mirror, tag, artifacts))
+            .await
+        {
+            Ok(upload) => upload,
+            // A timeout or setup error means nothing is known to have arrived
+            Err(e) => MirrorUpload {
+                name: mirror.name.clone(),
+                destination: mirror.describe(&ctx.new_version.to_string(), tag),
+                status: "failed".to_string(),
+                artifacts: Vec::new(),
+                failures: artifacts
+                    .iter()
+                    .map(|path| MirrorFailure {
+                        name: file_name(path),
+                        error: e.to_string(),
+                    })
+                    .collect(),
+            },
+        };
+
+        if upload.failures.is_empty() {
+            ctx.config
+                .indent(&format!("✓ {}: {} artifact(s)", mirror.name, upload.artifacts.len()))
+                .expect("Failed to write to stdout");
+        } else {
+            let summary = format!(
+                "{}: {} of {} artifact(s) failed ({})",
+                mirror.name,
+                upload.failures.len(),
+                artifacts.len(),
+                upload.failures[0].error
+            );
+            match mirror.on_failure {
+                FailurePolicy::Warn => ctx
+                    .config
+                    .warning_println(&format!("⚠️  {}", summary))
+                    .expect("Failed to write to stdout"),
+                FailurePolicy::Fail => {
+                    ctx.config
+                        .indent(&format!("✗ {}", summary))
+                        .expect("Failed to write to stdout");
+                    blocking.push(mirror.name.clone());
+                }
+            }
+        }
+        uploads.push(upload);
+    }
+
+    if !blocking.is_empty() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "mirror upload".to_string(),
+            reason: format!(
+                "mirror(s) {} did not receive every artifact; fix them, set on-failure = \"warn\", or release with --skip-mirrors",
+                blocking.join(", ")
+            ),
+        }));
+    }
+    Ok(uploads)
+}
+
+/// Upload every artifact to one mirror, recording failures instead of stopping
+async fn upload_to(
+    ctx: &ReleasePhaseContext<'_>,
+    mirror: &MirrorConfig,
+    tag: &str,
+    artifacts: &[PathBuf],
+) -> Result<MirrorUpload> {
+    let version = ctx.new_version.to_string();
+    let client = MirrorClient::new(mirror, ctx.env_config, &version, tag)?;
+    let retries = ctx.retries.file_uploads;
+    let mut upload = MirrorUpload {
+        name: mirror.name.clone(),
+        destination: mirror.describe(&version, tag),
+        status: String::new(),
+        artifacts: Vec::new(),
+        failures: Vec::new(),
+    };
+
+    for path in artifacts {
+        let name = file_name(path);
+        let content = std::fs::read(path)?;
+        let result = retry_with_backoff(
+            || client.put(&name, content.clone()),
+            retries,
+            &format!("{} upload of {}", mirror.name, name),
+            ctx.config,
+            None,
+        )
+        .await;
+        match result {
+            Ok(url) => {
+                ctx.config
+                    .verbose_println(&format!("   ✓ {} → {}", name, url))
+                    .expect("Failed to write to stdout");
+                upload.artifacts.push(MirrorArtifact { name, url });
+            }
+            Err(e) => upload.failures.push(MirrorFailure {
+                name,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    upload.status = match (upload.artifacts.is_empty(), upload.failures.is_empty()) {
+        (_, true) => "complete",
+        (false, false) => "partial",
+        (true, false) => "failed",
+    }
+    .to_string();
+    Ok(upload)
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
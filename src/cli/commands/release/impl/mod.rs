@@ -1,17 +1,66 @@
 //! Release implementation modules.
 //!
 //! This module contains the decomposed release implementation logic:
+//! - `audit`: RustSec advisory check of `Cargo.lock` in the release clone
+//! - `branches`: Release branches deleted after a successful release
+//! - `bsd`: Tarball and FreeBSD package artifacts for BSD targets
+//! - `bundlers`: Custom package formats built by configured commands
+//! - `compress`: zstd variants and gzip levels of the tarball artifacts
 //! - `context`: Context structure for phase execution
+//! - `debug_symbols`: Per-target debug symbol archives from the release build
+//! - `deny`: cargo-deny license and crate-ban checks of the release clone
+//! - `disk_space`: Disk-space preflight before cloning and building
+//! - `encrypt`: Encryption of the artifacts before upload
+//! - `features`: Published feature-set checks for publishable crates
+//! - `lints`: `cargo fmt` / `cargo clippy` gates run in the release clone
+//! - `mac_app_store`: App Store pkg built and uploaded to App Store Connect
+//! - `mirrors`: Artifact uploads to S3 and HTTP mirrors after GitHub
+//! - `nightly`: Dated snapshot builds published to the rolling `nightly` release
+//! - `registry`: Waiting for published crates to appear in the crates.io index
 //! - `retry`: Retry logic with exponential backoff
 //! - `platform`: Platform detection and bundling operations
 //! - `phases`: Release phase execution (phases 2-8)
 //! - `release`: Main release orchestration logic
+//! - `rebuild`: Artifacts-only rebuild of an existing tag
+//! - `shell_integration`: Shell completions and man pages from the built binary
+//! - `statuses`: Commit statuses reporting each release phase
+//! - `store`: Microsoft Store submission of MSIX packages
+//! - `tags`: Release tags written to GitHub under tag protection rulesets
+//! - `targets`: Per-target build matrix (cargo or cross) and shared tarball helpers
+//! - `test_suite`: Test suite gate run in the release clone before releasing
 
+mod adopt;
+mod audit;
+mod branches;
+mod bsd;
+mod bundlers;
+mod compress;
 mod context;
+mod debug_symbols;
+mod deny;
+mod disk_space;
+mod encrypt;
+mod features;
+mod lints;
+mod mac_app_store;
+mod mirrors;
+mod nightly;
 mod retry;
 mod platform;
 mod phases;
+mod rebuild;
+mod registry;
 mod release;
+mod shell_integration;
+mod statuses;
+mod store;
+mod tags;
+mod targets;
+mod test_suite;
 
 // Re-export the main entry point
+pub use branches::prune_release_branches;
+pub use disk_space::check_disk_space;
+pub use nightly::perform_nightly_single_repo;
+pub use rebuild::perform_rebuild_single_repo;
 pub use release::perform_release_single_repo;
@@ -0,0 +1,293 @@
+//! Nightly channel: dated snapshot builds published to a rolling release.
+//!
+//! `--channel nightly` builds the release clone (main) as
+//! `<major>.<minor>.<patch>-nightly.<YYYYMMDD>`, force-moves the `nightly` tag
+//! to it and replaces the assets of the `nightly` GitHub release, which is
+//! created as a prerelease the first time. Nothing is published to crates.io
+//! and no version bump is committed; the version is only rewritten in the
+//! clone so bundles carry it.
+
+use crate::cli::RuntimeConfig;
+use crate::cli::timeouts::{PhaseTimeouts, TimedPhase};
+use crate::error::{CliError, ReleaseError, Result};
+use crate::git_auth::GitAuth;
+use crate::github::ReleaseAssets;
+use crate::manifest::{MANIFEST_FILE_NAME, ReleaseManifest};
+use crate::state::ReleaseState;
+use crate::EnvConfig;
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::super::super::github_remote::GitHubRemote;
+use super::context::ReleasePhaseContext;
+use super::encrypt::encrypt_artifacts;
+use super::phases::{build_release_binaries, create_bundles, upload_artifacts_concurrently, upload_manifest};
+use super::release::{after_publish, print_result, write_manifest_copy};
+use super::retry::retry_with_backoff;
+use super::tags::move_tag;
+
+/// Rolling tag (and release) the nightly channel publishes to
+pub const NIGHTLY_TAG: &str = "nightly";
+
+/// Build a nightly snapshot and replace the assets of the `nightly` release.
+pub async fn perform_nightly_single_repo(
+    temp_dir: &Path,
+    metadata: crate::metadata::PackageMetadata,
+    binary_name: String,
+    github_remote: &GitHubRemote,
+    git_auth: &GitAuth,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    use crate::cli::retry_config::RetryConfig;
+    let retry_config = RetryConfig::from_env(env_config);
+
+    let base_version = semver::Version::parse(&metadata.version).map_err(|e| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Invalid version in Cargo.toml: {}", e),
+        })
+    })?;
+    let nightly_version = nightly_version(&base_version, chrono::Utc::now().date_naive())?;
+
+    config
+        .println(&format!("🌙 Starting nightly release {}", nightly_version))
+        .expect("Failed to write to stdout");
+
+    // Bundles read the version from the clone's Cargo.toml
+    set_package_version(&temp_dir.join("Cargo.toml"), &nightly_version)?;
+
+    let commit = git(git_auth, temp_dir, &["rev-parse", "HEAD"]).await?;
+
+    let github_owner = github_remote.owner.clone();
+    let github_repo_name = github_remote.repo.clone();
+    let body = format!(
+        "Nightly build {} of {}/{}@{}.\n\nThis release is replaced by every nightly run.",
+        nightly_version, github_owner, github_repo_name, commit
+    );
+
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_owner.clone(),
+        repo: github_repo_name.clone(),
+        token: None, // Will be read from env_config in new()
+        notes: Some(body.clone()),
+        // A second run on the same day replaces that day's assets
+        on_existing_asset: crate::github::ExistingAssetPolicy::Replace,
+        version_scheme: metadata.version_scheme.clone(),
+        asset_labels: metadata.asset_labels.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
+    config
+        .success_println("✓ GitHub API authenticated")
+        .expect("Failed to write to stdout");
+
+    // ===== ROLLING TAG =====
+    config.group("Nightly tag").expect("Failed to write to stdout");
+    git(git_auth, temp_dir, &["tag", "--force", NIGHTLY_TAG, "HEAD"]).await?;
+    move_tag(&github_manager, git_auth, temp_dir, &github_remote.url, NIGHTLY_TAG, &commit, config).await?;
+    config
+        .success_println(&format!(
+            "✓ Moved tag {} to {}",
+            NIGHTLY_TAG,
+            &commit[..commit.len().min(12)]
+        ))
+        .expect("Failed to write to stdout");
+
+    let timeouts =
+        PhaseTimeouts::resolve(env_config, &metadata.timeouts, config.timeout_overrides());
+
+    // ===== ROLLING RELEASE =====
+    // Reuse the published nightly release; drafts left by interrupted runs are stale
+    config.group("Nightly release").expect("Failed to write to stdout");
+    let existing = github_manager.releases_for_tag(NIGHTLY_TAG).await?;
+    for draft in existing.iter().filter(|release| release.draft) {
+        github_manager.delete_release(draft.release_id).await?;
+        config.record_action(&format!("Deleted stale nightly draft {}", draft.html_url));
+        config
+            .verbose_println(&format!("✓ Deleted stale nightly draft {}", draft.html_url))
+            .expect("Failed to write to stdout");
+    }
+    let (release, created) = match existing.into_iter().find(|release| !release.draft) {
+        Some(release) => {
+            config
+                .println(&format!("✓ Updating release: {}", release.html_url))
+                .expect("Failed to write to stdout");
+            (release, false)
+        }
+        None => {
+            // Created as a draft (and, being a pre-release version, a prerelease)
+            let release = timeouts
+                .run(
+                    TimedPhase::Github,
+                    "GitHub release creation",
+                    config,
+                    retry_with_backoff(
+                        || github_manager.create_release_from_tag(&nightly_version, NIGHTLY_TAG, None),
+                        retry_config.github_api,
+                        "GitHub release creation",
+                        config,
+                        None,
+                    ),
+                )
+                .await?;
+            config
+                .success_println(&format!("✓ Created draft release: {}", release.html_url))
+                .expect("Failed to write to stdout");
+            (release, true)
+        }
+    };
+    github_manager
+        .update_release_details(release.release_id, &format!("Nightly {}", nightly_version), &body)
+        .await?;
+
+    let ctx = ReleasePhaseContext {
+        release_clone_path: temp_dir,
+        package_name: &metadata.name,
+        binary_name: &binary_name,
+        binary_features: &metadata.binary_features,
+        library_only: false,
+        new_version: &nightly_version,
+        config,
+        github_manager: &github_manager,
+        github_owner: &github_owner,
+        github_repo_name: &github_repo_name,
+        // The version rewrite only exists in the clone
+        bundle_from_clone: true,
+        keep_draft: false,
+        debug_symbols: metadata.debug_symbols,
+        shell_integration: &metadata.shell_integration,
+        bsd: &metadata.bsd,
+        targets: &metadata.targets,
+        bundlers: &metadata.bundlers,
+        tests: None,
+        lints: None,
+        audit: None,
+        deny: None,
+        mac_app_store: None,
+        store: None,
+        mirrors: &[],
+        encryption: metadata.encryption.as_ref(),
+        compression: &metadata.compression,
+        env_config,
+        timeouts: &timeouts,
+        retries: &retry_config,
+    };
+
+    // ===== BUILD RELEASE BINARIES =====
+    config.group("Build release binaries").expect("Failed to write to stdout");
+    config
+        .println("🔨 Building release binaries...")
+        .expect("Failed to write to stdout");
+    build_release_binaries(&ctx, env_config).await?;
+
+    let mut manifest = ReleaseManifest::new(
+        &metadata.name,
+        &nightly_version,
+        NIGHTLY_TAG,
+        &format!("{}/{}", github_owner, github_repo_name),
+        &release,
+    );
+
+    // ===== BUNDLE AND REPLACE ASSETS =====
+    let pending_uploads = create_bundles(&ctx, &mut manifest, Some(release.release_id)).await?;
+
+    config.group("Upload artifacts").expect("Failed to write to stdout");
+    let uploaded = encrypt_artifacts(&ctx, &mut manifest, &pending_uploads).await?;
+    // Nothing resumes a nightly, so its upload progress is not kept
+    let mut upload_state = ReleaseState::new(nightly_version.clone(), crate::state::ReleaseConfig::default());
+    upload_artifacts_concurrently(&ctx, &mut upload_state, release.release_id, &uploaded).await?;
+    upload_manifest(&ctx, &mut manifest, release.release_id).await?;
+
+    // Yesterday's assets carry yesterday's version in their names
+    let keep: HashSet<String> = uploaded
+        .iter()
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .chain([MANIFEST_FILE_NAME.to_string()])
+        .collect();
+    let pruned = github_manager
+        .prune_assets(release.release_id, |name| keep.contains(name))
+        .await?;
+    for name in &pruned {
+        config.record_action(&format!("Removed previous nightly asset {}", name));
+        config
+            .verbose_println(&format!("🗑  Removed previous nightly asset {}", name))
+            .expect("Failed to write to stdout");
+    }
+
+    if created {
+        config.group("Publish GitHub release").expect("Failed to write to stdout");
+        github_manager.publish_draft_release(release.release_id).await?;
+    }
+    let release_url = manifest.html_url.clone();
+    write_manifest_copy(&mut manifest, config).map_err(|e| after_publish(true, &release_url, e))?;
+
+    config
+        .success_println(&format!(
+            "🎉 Nightly {} released: {} asset(s) uploaded, {} previous asset(s) removed",
+            nightly_version,
+            uploaded.len(),
+            pruned.len()
+        ))
+        .expect("Failed to write to stdout");
+    print_result(&manifest, config);
+
+    Ok(0)
+}
+
+/// `<major>.<minor>.<patch>-nightly.<YYYYMMDD>` of `base`
+fn nightly_version(base: &semver::Version, date: chrono::NaiveDate) -> Result<semver::Version> {
+    let mut version = semver::Version::new(base.major, base.minor, base.patch);
+    version.pre = semver::Prerelease::new(&format!("nightly.{}", date.format("%Y%m%d"))).map_err(|e| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Invalid nightly version: {}", e),
+        })
+    })?;
+    Ok(version)
+}
+
+/// Rewrite `version = "..."` in the `[package]` table of `cargo_toml`
+///
+/// Only that line changes, so the rest of the file keeps its formatting.
+fn set_package_version(cargo_toml: &Path, version: &semver::Version) -> Result<()> {
+    let content = std::fs::read_to_string(cargo_toml)?;
+    let mut in_package = false;
+    let mut replaced = false;
+
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('[') {
+                in_package = trimmed.starts_with("[package]");
+            } else if in_package
+                && !replaced
+                && trimmed.split('=').next().is_some_and(|key| key.trim() == "version")
+            {
+                replaced = true;
+                let indent = &line[..line.len() - trimmed.len()];
+                return format!("{}version = \"{}\"", indent, version);
+            }
+            line.to_string()
+        })
+        .collect();
+
+    if !replaced {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("No version in [package] of {}", cargo_toml.display()),
+        }));
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    std::fs::write(cargo_toml, updated)?;
+    Ok(())
+}
+
+/// Run git in `repo`, returning trimmed stdout
+///
+/// Runs with `auth`, so pushing works with a token where SSH is unavailable.
+async fn git(auth: &GitAuth, repo: &Path, args: &[&str]) -> Result<String> {
+    auth.git().args(args).current_dir(repo).stdout().await
+}
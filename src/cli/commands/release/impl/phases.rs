@@ -4,15 +4,36 @@
 //! Git operations and cargo publish are handled by `just publish` before this runs.
 
 use crate::error::{CliError, ReleaseError, Result};
-use crate::state::ReleaseState;
+use crate::manifest::{MANIFEST_FILE_NAME, NotarizationStatus, ReleaseManifest, notarization_for};
+use crate::state::{ReleasePhase, ReleaseState};
+use crate::util::format_bytes;
 use crate::EnvConfig;
 
+use super::super::super::helpers::prompt_confirmation;
+use super::bsd::build_bsd_artifacts;
+use super::bundlers::build_custom_artifacts;
+use super::debug_symbols::{self, collect_debug_symbols};
 use super::context::ReleasePhaseContext;
+use super::encrypt::encrypt_artifacts;
+use super::features::check_publish_features;
+use super::adopt::{Adoption, detect_existing_release, head_commit};
+use super::audit::run_advisory_check;
+use super::deny::run_policy_check;
+use super::lints::run_lint_gates;
+use super::mac_app_store::build_app_store_package;
+use super::mirrors::upload_to_mirrors;
 use super::platform::{
-    bundle_platform, ensure_bundler_installed, get_docker_platforms, get_native_platforms,
-    get_platforms_to_build,
+    ArtifactNames, bundle_with_log, ensure_bundler_installed, get_docker_platforms, get_native_platforms,
+    get_platforms_to_build, is_native_platform,
 };
+use super::registry::verify_published_crates;
 use super::retry::retry_with_backoff;
+use super::shell_integration::generate_shell_integration;
+use super::statuses::PhaseStatuses;
+use super::store::submit_to_store;
+use super::tags::check_release_tag;
+use super::targets::build_matrix_artifacts;
+use super::test_suite::run_test_suite;
 
 /// Get environment variables needed for native cross-compilation to the specified target.
 /// Extracts OpenSSL, pkg-config, and other build-related vars from EnvConfig.
@@ -65,22 +86,167 @@ fn get_cross_compile_env(target: &str, env_config: &EnvConfig) -> Vec<(String, S
 /// Execute release phases with retry logic
 ///
 /// Phases:
+/// 0. Check publishable crates with their published features and `--all-features`
 /// 1. Create GitHub draft release (using existing tag)
 /// 2. Build release binaries
 /// 3. Create platform bundles
-/// 4. Upload artifacts incrementally
-/// 5. Publish GitHub release
+/// 4. Encrypt the artifacts if configured, upload them incrementally, then to
+///    the configured mirrors
+/// 5. Wait for the published crates to appear on crates.io
+/// 6. Upload the release manifest and publish the GitHub release (left as a
+///    draft when the release profile sets `draft = true`)
+///
+/// Phases left out with `--skip` / `--only` are reported and skipped; without
+/// the github-release phase the tag's existing release is reused. A full run
+/// first offers to adopt what an earlier run left on the tag's release. With
+/// `--commit-status` every phase is reported on the released commit.
+///
+/// Returns the manifest of everything uploaded.
 pub async fn execute_phases_with_retry(
     ctx: &ReleasePhaseContext<'_>,
     release_state: &mut ReleaseState,
     env_config: &crate::EnvConfig,
-) -> Result<()> {
-    use crate::cli::retry_config::{CargoTimeoutConfig, RetryConfig};
-    let retry_config = RetryConfig::default();
-    let timeout_config = CargoTimeoutConfig::default();
+) -> Result<ReleaseManifest> {
+    let statuses = PhaseStatuses::new(ctx).await;
+    let result = run_phases(ctx, release_state, env_config, &statuses).await;
+    if let Err(e) = &result {
+        statuses.fail(ctx, e).await;
+    }
+    result
+}
+
+/// The phases of [`execute_phases_with_retry`], reporting to `statuses`
+async fn run_phases(
+    ctx: &ReleasePhaseContext<'_>,
+    release_state: &mut ReleaseState,
+    env_config: &crate::EnvConfig,
+    statuses: &PhaseStatuses,
+) -> Result<ReleaseManifest> {
+    use crate::cli::timeouts::TimedPhase;
+    let retry_config = ctx.retries;
+
+    // Use the existing tag (created by `just publish`)
+    let tag_name = ctx.github_manager.tag_name(ctx.new_version);
+    let mut phases = if ctx.library_only {
+        ctx.config.phases().without_binaries()
+    } else {
+        ctx.config.phases().clone()
+    };
+    if !ctx.config.phases().is_complete() {
+        ctx.config
+            .println(&format!("🎯 Phases: {}", phases.describe()))
+            .expect("Failed to write to stdout");
+    }
+
+    // ===== EARLIER RUN (state lost with its temp clone) =====
+    let adoption = if ctx.config.phases().is_complete()
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        detect_existing_release(ctx, &tag_name).await?
+    } else {
+        None
+    };
+    if let Some(Adoption::Complete { release, manifest }) = &adoption {
+        release_state.set_github_state(
+            ctx.github_owner.to_string(),
+            ctx.github_repo_name.to_string(),
+            Some(release),
+        );
+        release_state.set_phase(crate::state::ReleasePhase::Completed);
+        crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+        ctx.config
+            .success_println(&format!("✓ Release already complete: {}", release.html_url))
+            .expect("Failed to write to stdout");
+        return Ok(manifest.clone());
+    }
+    if let Some(adoption) = &adoption {
+        phases = phases.without(adoption.completed_phases());
+        ctx.config
+            .println(&format!("🎯 Phases: {}", phases.describe()))
+            .expect("Failed to write to stdout");
+    }
+
+    // The gates below belong to the github-release phase: they run before it creates anything
+    let creates_release = phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+        && adoption.is_none();
+    if creates_release {
+        statuses.start(ctx, ReleasePhase::GitHubRelease).await;
+    }
+
+    // ===== TEST SUITE (before anything is created on GitHub) =====
+    if let Some(gate) = ctx.tests
+        && phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        ctx.config.group("Run tests").expect("Failed to write to stdout");
+        ctx.config
+            .println(&format!("🧪 Running {}...", gate.command.join(" ")))
+            .expect("Failed to write to stdout");
+        let tests = run_test_suite(ctx, gate).await?;
+        ctx.config
+            .success_println(&format!(
+                "✓ {} test(s) passed, {} ignored",
+                tests.passed, tests.ignored
+            ))
+            .expect("Failed to write to stdout");
+    }
+
+    // ===== LINT GATES (before anything is created on GitHub) =====
+    if let Some(gates) = ctx.lints
+        && phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        ctx.config.group("Lint").expect("Failed to write to stdout");
+        ctx.config
+            .println("🧹 Running lint gates...")
+            .expect("Failed to write to stdout");
+        run_lint_gates(ctx, gates).await?;
+    }
+
+    // ===== ADVISORY CHECK (before anything is created on GitHub) =====
+    if let Some(gate) = ctx.audit
+        && phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        ctx.config.group("Audit dependencies").expect("Failed to write to stdout");
+        ctx.config
+            .println("🛡️  Checking Cargo.lock against the RustSec advisory database...")
+            .expect("Failed to write to stdout");
+        run_advisory_check(ctx, gate).await?;
+    }
+
+    // ===== POLICY CHECK (before anything is created on GitHub) =====
+    if let Some(gate) = ctx.deny
+        && phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        ctx.config.group("Check dependency policy").expect("Failed to write to stdout");
+        ctx.config
+            .println(&format!("⚖️  Checking {} with cargo deny...", gate.checks.join(" and ")))
+            .expect("Failed to write to stdout");
+        run_policy_check(ctx, gate).await?;
+    }
+
+    // ===== FEATURE CHECKS (before anything is created on GitHub) =====
+    if !ctx.config.skip_feature_check()
+        && phases.runs(ReleasePhase::GitHubRelease)
+        && !release_state.has_completed(crate::state::ReleasePhase::GitHubRelease)
+    {
+        ctx.config.group("Check feature sets").expect("Failed to write to stdout");
+        ctx.config
+            .println("🧪 Checking published feature sets...")
+            .expect("Failed to write to stdout");
+        let checks = check_publish_features(ctx).await?;
+        ctx.config
+            .success_println(&format!("✓ {} feature-set check(s) passed", checks))
+            .expect("Failed to write to stdout");
+    }
 
     // ===== PHASE 1: CREATE GITHUB DRAFT RELEASE =====
-    let release_id = if release_state.has_completed(crate::state::ReleasePhase::GitHubRelease) {
+    crate::cli::abort::check("GitHub draft release")?;
+    ctx.config.group("GitHub draft release").expect("Failed to write to stdout");
+    let release = if release_state.has_completed(crate::state::ReleasePhase::GitHubRelease) {
         ctx.config
             .println("✓ Skipping GitHub release creation (already completed)")
             .expect("Failed to write to stdout");
@@ -91,32 +257,87 @@ pub async fn execute_phases_with_retry(
                     github_state.html_url.as_ref().unwrap_or(&"N/A".to_string())
                 ))
                 .expect("Failed to write to stdout");
-            github_state.release_id.ok_or_else(|| {
-                ReleaseError::State(crate::error::StateError::Corrupted {
-                    reason: "GitHubRelease checkpoint exists but release_id is None".to_string(),
-                })
-            })?
+            statuses
+                .finish(ctx, ReleasePhase::GitHubRelease, "Completed by an earlier run")
+                .await;
+            crate::github::GitHubReleaseResult {
+                release_id: github_state.release_id.ok_or_else(|| {
+                    ReleaseError::State(crate::error::StateError::Corrupted {
+                        reason: "GitHubRelease checkpoint exists but release_id is None".to_string(),
+                    })
+                })?,
+                html_url: github_state.html_url.clone().unwrap_or_default(),
+                draft: github_state.draft,
+                prerelease: github_state.prerelease,
+            }
         } else {
             return Err(ReleaseError::State(crate::error::StateError::Corrupted {
                 reason: "GitHubRelease checkpoint exists but github_state is None".to_string(),
             }));
         }
+    } else if let Some(adoption) = &adoption {
+        let release = adoption.release().clone();
+        ctx.config
+            .println(&format!("⏭  Adopted {}", release.html_url))
+            .expect("Failed to write to stdout");
+        statuses
+            .finish(ctx, ReleasePhase::GitHubRelease, "Adopted an earlier run's release")
+            .await;
+        release_state.set_github_state(
+            ctx.github_owner.to_string(),
+            ctx.github_repo_name.to_string(),
+            Some(&release),
+        );
+        release
+    } else if !phases.runs(ReleasePhase::GitHubRelease) {
+        // Prefer a draft; a published release can still receive assets
+        let existing = ctx.github_manager.releases_for_tag(&tag_name).await?;
+        let release = existing
+            .iter()
+            .find(|release| release.draft)
+            .or(existing.first())
+            .cloned()
+            .ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: format!(
+                        "Skipping github-release needs an existing release for {}, but there is none",
+                        tag_name
+                    ),
+                })
+            })?;
+        ctx.config
+            .println(&format!("⏭  Skipping github-release; using {}", release.html_url))
+            .expect("Failed to write to stdout");
+        statuses.finish(ctx, ReleasePhase::GitHubRelease, "Skipped").await;
+        release_state.set_github_state(
+            ctx.github_owner.to_string(),
+            ctx.github_repo_name.to_string(),
+            Some(&release),
+        );
+        release
     } else {
+        remove_existing_releases(ctx, release_state, &tag_name).await?;
+        check_release_tag(ctx, &tag_name).await?;
+
         ctx.config
             .println("🚀 Creating GitHub draft release...")
             .expect("Failed to write to stdout");
 
-        // Use the existing tag (created by `just publish`)
-        let tag_name = format!("v{}", ctx.new_version);
-
-        let release_result = retry_with_backoff(
-            || ctx.github_manager.create_release_from_tag(ctx.new_version, &tag_name, None),
-            retry_config.github_api,
-            "GitHub release creation",
-            ctx.config,
-            None,
-        )
-        .await?;
+        let release_result = ctx
+            .timeouts
+            .run(
+                TimedPhase::Github,
+                "GitHub release creation",
+                ctx.config,
+                retry_with_backoff(
+                    || ctx.github_manager.create_release_from_tag(ctx.new_version, &tag_name, None),
+                    retry_config.github_api,
+                    "GitHub release creation",
+                    ctx.config,
+                    None,
+                ),
+            )
+            .await?;
 
         ctx.config
             .success_println(&format!(
@@ -131,15 +352,13 @@ pub async fn execute_phases_with_retry(
             ctx.github_repo_name.to_string(),
             Some(&release_result),
         );
-        let release_id = release_result.release_id;
-
         // Save state
         release_state.set_phase(crate::state::ReleasePhase::GitHubRelease);
         release_state.add_checkpoint(
             "github_release_created".to_string(),
             crate::state::ReleasePhase::GitHubRelease,
             Some(serde_json::json!({
-                "release_id": release_id,
+                "release_id": release_result.release_id,
                 "html_url": &release_result.html_url,
             })),
         );
@@ -148,112 +367,321 @@ pub async fn execute_phases_with_retry(
             .verbose_println("ℹ️  Saved progress checkpoint (GitHub release)")
             .expect("Failed to write to stdout");
 
-        release_id
+        // Notes over GitHub's body limit were shortened; attach the full text
+        if let Some(overflow) = ctx.github_manager.notes_overflow(ctx.new_version, &tag_name) {
+            ctx.timeouts
+                .run(
+                    TimedPhase::Upload,
+                    "full release notes upload",
+                    ctx.config,
+                    retry_with_backoff(
+                        || ctx.github_manager.upload_full_notes(release_result.release_id, &overflow),
+                        retry_config.github_api,
+                        "full release notes upload",
+                        ctx.config,
+                        None,
+                    ),
+                )
+                .await?;
+            ctx.config
+                .warning_println(&format!(
+                    "⚠️  Release notes exceed GitHub's body limit; the body was shortened and links to {}",
+                    overflow.asset_name
+                ))
+                .expect("Failed to write to stdout");
+        }
+
+        statuses
+            .finish(ctx, ReleasePhase::GitHubRelease, "Draft release created")
+            .await;
+        release_result
+    };
+    let release_id = release.release_id;
+    let mut manifest = match adoption {
+        // Its uploads are what gets published
+        Some(Adoption::Publish { manifest, .. }) => manifest,
+        _ => {
+            let mut manifest = ReleaseManifest::new(
+                ctx.package_name,
+                ctx.new_version,
+                &tag_name,
+                &format!("{}/{}", ctx.github_owner, ctx.github_repo_name),
+                &release,
+            );
+            manifest.commit = Some(head_commit(ctx.release_clone_path).await?);
+            manifest
+        }
     };
 
     // ===== PHASE 2: BUILD RELEASE BINARIES =====
-    ctx.config
-        .println("🔨 Building release binaries...")
-        .expect("Failed to write to stdout");
+    crate::cli::abort::check("build")?;
+    ctx.config.group("Build release binaries").expect("Failed to write to stdout");
+    if phases.runs(ReleasePhase::Building) {
+        ctx.config
+            .println("🔨 Building release binaries...")
+            .expect("Failed to write to stdout");
+        statuses.start(ctx, ReleasePhase::Building).await;
 
-    use tokio::process::Command;
-    use tokio::time::{timeout, Duration};
+        build_release_binaries(ctx, env_config).await?;
 
-    let build_timeout = Duration::from_secs(timeout_config.build_timeout_secs);
+        release_state.set_phase(crate::state::ReleasePhase::Building);
+        crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+        statuses.finish(ctx, ReleasePhase::Building, "Release binaries built").await;
+    } else {
+        skip_phase(ctx, statuses, ReleasePhase::Building).await;
+    }
 
-    // On macOS, build for both architectures to enable universal binaries
-    #[cfg(target_os = "macos")]
-    let build_targets = vec!["x86_64-apple-darwin", "aarch64-apple-darwin"];
+    // ===== PHASE 3: CREATE PLATFORM BUNDLES =====
+    crate::cli::abort::check("bundling")?;
+    // Artifacts are uploaded together once every platform is bundled
+    let pending_uploads = if phases.runs(ReleasePhase::Bundling) {
+        statuses.start(ctx, ReleasePhase::Bundling).await;
+        let pending_uploads = create_bundles(ctx, &mut manifest, Some(release_id)).await?;
+        statuses
+            .finish(ctx, ReleasePhase::Bundling, &format!("{} artifact(s) created", pending_uploads.len()))
+            .await;
+        pending_uploads
+    } else {
+        ctx.config.group("Create platform bundles").expect("Failed to write to stdout");
+        skip_phase(ctx, statuses, ReleasePhase::Bundling).await;
+        Vec::new()
+    };
 
-    #[cfg(not(target_os = "macos"))]
-    let build_targets: Vec<&str> = vec![];
+    // ===== UPLOAD ARTIFACTS =====
+    crate::cli::abort::check("upload")?;
+    ctx.config.group("Upload artifacts").expect("Failed to write to stdout");
+    if phases.runs(ReleasePhase::Uploading) {
+        statuses.start(ctx, ReleasePhase::Uploading).await;
+        // A library crate has no artifacts; only the manifest is uploaded
+        if !pending_uploads.is_empty() {
+            // Stores receive the plaintext packages; the release and mirrors the encrypted copies
+            let uploads = encrypt_artifacts(ctx, &mut manifest, &pending_uploads).await?;
+            let total_artifacts_uploaded =
+                upload_artifacts_concurrently(ctx, release_state, release_id, &uploads).await?;
 
-    if build_targets.is_empty() {
-        // Single-target build (non-macOS)
-        let build_output = timeout(
-            build_timeout,
-            Command::new("cargo")
-                .arg("build")
-                .arg("--release")
-                .current_dir(ctx.release_clone_path)
-                .output(),
-        )
-        .await
-        .map_err(|_| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "cargo build --release".to_string(),
-                reason: format!(
-                    "Build timed out after {} seconds",
-                    timeout_config.build_timeout_secs
-                ),
-            })
-        })?
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "cargo build --release".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
+            ctx.config
+                .success_println(&format!(
+                    "✓ Uploaded {} artifact(s) to GitHub release",
+                    total_artifacts_uploaded
+                ))
+                .expect("Failed to write to stdout");
 
-        if !build_output.status.success() {
-            return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "cargo build --release".to_string(),
-                reason: String::from_utf8_lossy(&build_output.stderr).to_string(),
-            }));
+            if !ctx.mirrors.is_empty() {
+                ctx.config
+                    .println(&format!("🪞 Uploading to {} mirror(s)...", ctx.mirrors.len()))
+                    .expect("Failed to write to stdout");
+                manifest.mirrors = upload_to_mirrors(ctx, &tag_name, &uploads).await?;
+            }
         }
-    } else {
-        // Multi-target build (macOS) - propagate cross-compile env vars
-        for target in &build_targets {
+
+        release_state.set_phase(crate::state::ReleasePhase::Uploading);
+        crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+
+        // ===== CRATES.IO VERIFICATION =====
+        if !ctx.config.skip_publish_verification() {
+            ctx.config.group("Verify crates.io publication").expect("Failed to write to stdout");
             ctx.config
-                .verbose_println(&format!("   Building for {}...", target))
+                .println("🔎 Waiting for published crates on crates.io...")
                 .expect("Failed to write to stdout");
+            manifest.crates_io = verify_published_crates(ctx).await?;
+            ctx.config
+                .success_println(&format!("✓ {} crate(s) visible on crates.io", manifest.crates_io.len()))
+                .expect("Failed to write to stdout");
+        }
 
-            let cross_env = get_cross_compile_env(target, env_config);
-            let build_output = timeout(
-                build_timeout,
-                Command::new("cargo")
-                    .arg("build")
-                    .arg("--release")
-                    .arg("--target")
-                    .arg(target)
-                    .current_dir(ctx.release_clone_path)
-                    .envs(cross_env)
-                    .output(),
-            )
-            .await
-            .map_err(|_| {
-                ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: format!("cargo build --release --target {}", target),
+        // ===== MAC APP STORE =====
+        if let Some(mas) = ctx.mac_app_store
+            && phases.runs(ReleasePhase::Bundling)
+            && !ctx.config.skip_mac_app_store()
+        {
+            ctx.config.group("Mac App Store package").expect("Failed to write to stdout");
+            if is_native_platform("mas") {
+                ctx.config
+                    .println("🍎 Building the Mac App Store package...")
+                    .expect("Failed to write to stdout");
+                manifest.mac_app_store = Some(build_app_store_package(ctx, mas).await?);
+            } else {
+                ctx.config
+                    .soft_failure("⚠️  The Mac App Store package needs a macOS host; skipping it")?;
+            }
+        }
+
+        // ===== MICROSOFT STORE =====
+        if let Some(store) = ctx.store
+            && phases.runs(ReleasePhase::Bundling)
+            && !ctx.config.skip_store_submission()
+        {
+            ctx.config.group("Submit to Microsoft Store").expect("Failed to write to stdout");
+            let packages: Vec<std::path::PathBuf> = pending_uploads
+                .iter()
+                .filter(|path| path.extension().is_some_and(|ext| ext == "msix"))
+                .cloned()
+                .collect();
+            if packages.is_empty() {
+                ctx.config.soft_failure(
+                    "⚠️  No .msix bundle was built (MSIX needs a Windows host); skipping the Microsoft Store submission",
+                )?;
+            } else {
+                ctx.config
+                    .println(&format!("🏪 Submitting {} MSIX package(s) to the Microsoft Store...", packages.len()))
+                    .expect("Failed to write to stdout");
+                let submission = submit_to_store(ctx, store, &packages).await?;
+                ctx.config
+                    .success_println(&format!(
+                        "✓ Store submission {}: {}",
+                        submission.submission_id, submission.status
+                    ))
+                    .expect("Failed to write to stdout");
+                manifest.microsoft_store = Some(submission);
+            }
+        }
+
+        // ===== RELEASE MANIFEST =====
+        upload_manifest(ctx, &mut manifest, release_id).await?;
+        statuses.finish(ctx, ReleasePhase::Uploading, "Artifacts uploaded").await;
+    } else {
+        skip_phase(ctx, statuses, ReleasePhase::Uploading).await;
+    }
+
+    // ===== PHASE 4: PUBLISH GITHUB RELEASE =====
+    crate::cli::abort::check("publishing")?;
+    ctx.config.group("Publish GitHub release").expect("Failed to write to stdout");
+    if release_state.has_completed(crate::state::ReleasePhase::GitHubPublish) {
+        ctx.config
+            .println("✓ Skipping release publishing (already published)")
+            .expect("Failed to write to stdout");
+        statuses
+            .finish(ctx, ReleasePhase::GitHubPublish, "Completed by an earlier run")
+            .await;
+    } else if !phases.runs(ReleasePhase::GitHubPublish) {
+        ctx.config
+            .warning_println(&format!(
+                "⚠️  Leaving the release unpublished (--skip/--only): {}",
+                release.html_url
+            ))
+            .expect("Failed to write to stdout");
+        statuses.finish(ctx, ReleasePhase::GitHubPublish, "Skipped").await;
+    } else if !release.draft {
+        ctx.config
+            .println(&format!("✓ Release already published: {}", release.html_url))
+            .expect("Failed to write to stdout");
+        statuses
+            .finish(ctx, ReleasePhase::GitHubPublish, "Release already published")
+            .await;
+    } else if ctx.keep_draft {
+        ctx.config
+            .warning_println(&format!(
+                "⚠️  Leaving the release as a draft (profile sets draft = true): {}",
+                release.html_url
+            ))
+            .expect("Failed to write to stdout");
+        statuses
+            .finish(ctx, ReleasePhase::GitHubPublish, "Left as a draft (release profile)")
+            .await;
+    } else {
+        statuses.start(ctx, ReleasePhase::GitHubPublish).await;
+        // Logs of failed bundler runs were for debugging the draft only
+        match ctx.github_manager.delete_bundle_logs(release_id).await {
+            Ok(deleted) if !deleted.is_empty() => ctx
+                .config
+                .verbose_println(&format!("   Removed {} bundler log(s) from the draft", deleted.len()))
+                .expect("Failed to write to stdout"),
+            Ok(_) => {}
+            Err(e) => ctx
+                .config
+                .soft_failure(&format!("⚠️  Failed to remove bundler logs from the draft: {}", e))?,
+        }
+
+        ctx.config
+            .println("🔍 Verifying release is ready to publish...")
+            .expect("Failed to write to stdout");
+
+        match ctx.github_manager.verify_release_is_draft(release_id).await {
+            Ok(true) => {
+                ctx.config
+                    .success_println("✓ Release verified as draft")
+                    .expect("Failed to write to stdout");
+            }
+            Ok(false) => {
+                return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+                    command: "publish_release".to_string(),
                     reason: format!(
-                        "Build timed out after {} seconds",
-                        timeout_config.build_timeout_secs
+                        "Release {} is not a draft (already published)",
+                        release_id
                     ),
-                })
-            })?
-            .map_err(|e| {
-                ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: format!("cargo build --release --target {}", target),
-                    reason: e.to_string(),
-                })
-            })?;
-
-            if !build_output.status.success() {
+                }));
+            }
+            Err(e) => {
                 return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: format!("cargo build --release --target {}", target),
-                    reason: String::from_utf8_lossy(&build_output.stderr).to_string(),
+                    command: "verify_release_draft_status".to_string(),
+                    reason: format!("Failed to verify release {} draft status: {}", release_id, e),
                 }));
             }
         }
-    }
 
-    ctx.config
-        .success_println("✓ Built release binaries")
-        .expect("Failed to write to stdout");
+        ctx.config
+            .println("✅ Publishing GitHub release...")
+            .expect("Failed to write to stdout");
+
+        ctx.timeouts
+            .run(
+                TimedPhase::Github,
+                "Publish GitHub release",
+                ctx.config,
+                retry_with_backoff(
+                    || ctx.github_manager.publish_draft_release(release_id),
+                    retry_config.release_publishing,
+                    "Publish GitHub release",
+                    ctx.config,
+                    None,
+                ),
+            )
+            .await?;
+
+        ctx.config
+            .success_println(&format!("✓ Published release {}", ctx.github_manager.tag_name(ctx.new_version)))
+            .expect("Failed to write to stdout");
 
-    release_state.set_phase(crate::state::ReleasePhase::Building);
+        release_state.set_phase(crate::state::ReleasePhase::GitHubPublish);
+        release_state.add_checkpoint(
+            "release_published".to_string(),
+            crate::state::ReleasePhase::GitHubPublish,
+            None,
+        );
+        crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+        statuses.finish(ctx, ReleasePhase::GitHubPublish, "Release published").await;
+    }
+
+    release_state.set_phase(crate::state::ReleasePhase::Completed);
     crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
 
-    // ===== PHASE 3: CREATE PLATFORM BUNDLES =====
+    Ok(manifest)
+}
+
+/// Shell integration, platform bundles, BSD and matrix tarballs, custom bundles
+///
+/// Records every artifact in the manifest and returns them for upload. The
+/// log of a failed bundler run is attached to `log_release`, if given.
+pub(super) async fn create_bundles(
+    ctx: &ReleasePhaseContext<'_>,
+    manifest: &mut ReleaseManifest,
+    log_release: Option<u64>,
+) -> Result<Vec<std::path::PathBuf>> {
+    let mut pending_uploads: Vec<std::path::PathBuf> = Vec::new();
+    let mut asset_names = ArtifactNames::default();
+
+    // ===== SHELL COMPLETIONS AND MAN PAGES =====
+    if let Some(archive) = generate_shell_integration(ctx).await? {
+        asset_names.claim("shell-integration", std::slice::from_ref(&archive))?;
+        manifest.add_artifacts(
+            "shell-integration",
+            std::slice::from_ref(&archive),
+            NotarizationStatus::NotApplicable,
+        )?;
+        pending_uploads.push(archive);
+    }
+
+    ctx.config.group("Create platform bundles").expect("Failed to write to stdout");
     ctx.config
         .println("📦 Creating platform bundles...")
         .expect("Failed to write to stdout");
@@ -278,7 +706,6 @@ pub async fn execute_phases_with_retry(
         .expect("Failed to write to stdout");
 
     let mut total_artifacts_created = 0;
-    let mut total_artifacts_uploaded = 0;
 
     if !all_platforms.is_empty() {
         let bundler_binary = ensure_bundler_installed(ctx).await?;
@@ -291,24 +718,26 @@ pub async fn execute_phases_with_retry(
                 .verbose_println(&format!("\n   Building {} ({})...", platform, platform_type))
                 .expect("Failed to write to stdout");
 
-            let artifacts = bundle_platform(ctx, &bundler_binary, platform).await?;
+            let artifacts = bundle_with_log(ctx, &bundler_binary, platform, log_release).await?;
 
+            asset_names.claim(platform, &artifacts)?;
             total_artifacts_created += artifacts.len();
-
-            // Upload immediately after bundling
-            let uploaded = upload_artifacts_incrementally(
-                ctx,
-                release_state,
-                release_id,
-                &artifacts,
-                platform,
-            )
-            .await?;
-
-            total_artifacts_uploaded += uploaded;
+            manifest.add_artifacts(platform, &artifacts, notarization_for(platform, ctx.env_config))?;
+            pending_uploads.extend(artifacts);
         }
     }
 
+    let mut tarballs = build_bsd_artifacts(ctx).await?;
+    tarballs.extend(build_matrix_artifacts(ctx).await?);
+    tarballs.extend(build_custom_artifacts(ctx).await?);
+    tarballs.extend(collect_debug_symbols(ctx).await?);
+    for (target, artifacts) in tarballs {
+        asset_names.claim(&target, &artifacts)?;
+        total_artifacts_created += artifacts.len();
+        manifest.add_artifacts(&target, &artifacts, NotarizationStatus::NotApplicable)?;
+        pending_uploads.extend(artifacts);
+    }
+
     if total_artifacts_created == 0 {
         return Err(ReleaseError::Cli(CliError::ExecutionFailed {
             command: "bundle".to_string(),
@@ -323,91 +752,277 @@ pub async fn execute_phases_with_retry(
             all_platforms.len()
         ))
         .expect("Failed to write to stdout");
+
+    Ok(pending_uploads)
+}
+
+/// Report a phase left out by `--skip` / `--only` (or for a library crate)
+async fn skip_phase(ctx: &ReleasePhaseContext<'_>, statuses: &PhaseStatuses, phase: ReleasePhase) {
+    let reason = if ctx.library_only && matches!(phase, ReleasePhase::Building | ReleasePhase::Bundling) {
+        "library crate"
+    } else {
+        "--skip/--only"
+    };
     ctx.config
-        .success_println(&format!(
-            "✓ Uploaded {} artifact(s) to GitHub release",
-            total_artifacts_uploaded
-        ))
+        .println(&format!("⏭  Skipping {} ({})", phase.cli_name(), reason))
         .expect("Failed to write to stdout");
+    statuses.finish(ctx, phase, &format!("Skipped ({})", reason)).await;
+}
 
-    release_state.set_phase(crate::state::ReleasePhase::Uploading);
-    crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+/// Remove releases already attached to the tag before creating the draft
+///
+/// Stale drafts (left by an interrupted run whose state was lost) are deleted.
+/// A published release is never deleted automatically: it requires
+/// `--force-recreate-release <tag>` plus an interactive confirmation (the
+/// matching tag is the confirmation in CI mode), and an audit entry is written
+/// before anything is deleted.
+async fn remove_existing_releases(
+    ctx: &ReleasePhaseContext<'_>,
+    release_state: &mut ReleaseState,
+    tag_name: &str,
+) -> Result<()> {
+    let forced = ctx.config.force_recreate_release();
+    if let Some(forced_tag) = forced
+        && forced_tag != tag_name
+    {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "--force-recreate-release {} does not match the release tag {}",
+                forced_tag, tag_name
+            ),
+        }));
+    }
 
-    // ===== PHASE 4: PUBLISH GITHUB RELEASE =====
-    if release_state.has_completed(crate::state::ReleasePhase::GitHubPublish) {
-        ctx.config
-            .println("✓ Skipping release publishing (already published)")
-            .expect("Failed to write to stdout");
-    } else {
-        ctx.config
-            .println("🔍 Verifying release is ready to publish...")
-            .expect("Failed to write to stdout");
+    let existing = ctx.github_manager.releases_for_tag(tag_name).await?;
+    if existing.is_empty() {
+        return Ok(());
+    }
 
-        match ctx.github_manager.verify_release_is_draft(release_id).await {
-            Ok(true) => {
-                ctx.config
-                    .success_println("✓ Release verified as draft")
-                    .expect("Failed to write to stdout");
-            }
-            Ok(false) => {
-                return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: "publish_release".to_string(),
-                    reason: format!(
-                        "Release {} is not a draft (already published)",
-                        release_id
-                    ),
-                }));
-            }
-            Err(e) => {
+    let published: Vec<_> = existing.iter().filter(|release| !release.draft).collect();
+    let allow_published = !published.is_empty() && forced.is_some();
+
+    if allow_published {
+        for release in &published {
+            ctx.config
+                .warning_println(&format!("⚠️  {} is already published: {}", tag_name, release.html_url))
+                .expect("Failed to write to stdout");
+        }
+
+        if !ctx.config.is_ci() {
+            let confirmed = prompt_confirmation(
+                ctx.config,
+                &format!("Delete the published release {} and recreate it? This cannot be undone", tag_name),
+            )?;
+            if !confirmed {
                 return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: "verify_release_draft_status".to_string(),
-                    reason: format!("Failed to verify release {} draft status: {}", release_id, e),
+                    command: "force_recreate_release".to_string(),
+                    reason: format!("Deletion of published release {} was not confirmed", tag_name),
                 }));
             }
         }
 
+        for release in &published {
+            let details = serde_json::json!({
+                "repository": format!("{}/{}", ctx.github_owner, ctx.github_repo_name),
+                "tag": tag_name,
+                "release_id": release.release_id,
+                "html_url": &release.html_url,
+            });
+            let log = crate::state::append_audit_entry("delete_published_release", details.clone())?;
+            release_state.add_checkpoint(
+                "published_release_deleted".to_string(),
+                crate::state::ReleasePhase::GitHubRelease,
+                Some(details),
+            );
+            ctx.config
+                .verbose_println(&format!("   Audit entry written to {}", log.display()))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    // Refuses published releases unless allowed above
+    let deleted = ctx
+        .github_manager
+        .cleanup_existing_release(ctx.new_version, allow_published)
+        .await?;
+
+    for release in &deleted {
+        let kind = if release.draft { "stale draft" } else { "published" };
+        let description = format!("Deleted {} release {}", kind, release.html_url);
+        ctx.config.record_action(&description);
         ctx.config
-            .println("✅ Publishing GitHub release...")
+            .indent(&format!("🗑  {}", description))
             .expect("Failed to write to stdout");
+    }
+
+    Ok(())
+}
 
-        retry_with_backoff(
-            || ctx.github_manager.publish_draft_release(release_id),
-            retry_config.release_publishing,
-            "Publish GitHub release",
+/// Write the release manifest under `target/` and upload it to the release,
+/// then refresh the checksum table in the release body from it
+///
+/// The manifest asset is always replaced, since it is regenerated on every run.
+pub(super) async fn upload_manifest(
+    ctx: &ReleasePhaseContext<'_>,
+    manifest: &mut ReleaseManifest,
+    release_id: u64,
+) -> Result<()> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let path = ctx
+        .release_clone_path
+        .join("target")
+        .join(MANIFEST_FILE_NAME);
+    manifest.write(&path)?;
+
+    ctx.timeouts
+        .run(
+            TimedPhase::Upload,
+            "upload release manifest",
             ctx.config,
-            None,
+            retry_with_backoff(
+                || {
+                    ctx.github_manager.replace_artifacts(
+                        release_id,
+                        std::slice::from_ref(&path),
+                        ctx.config,
+                    )
+                },
+                ctx.retries.file_uploads,
+                "Upload release manifest",
+                ctx.config,
+                None,
+            ),
         )
         .await?;
 
-        ctx.config
-            .success_println(&format!("✓ Published release v{}", ctx.new_version))
-            .expect("Failed to write to stdout");
+    // The release itself is complete; a stale table is only a warning
+    match ctx.github_manager.update_checksum_table(release_id, &manifest.artifacts).await {
+        Ok(true) => ctx
+            .config
+            .verbose_println("✓ Checksum table in the release body updated")
+            .expect("Failed to write to stdout"),
+        Ok(false) => {}
+        Err(e) => ctx
+            .config
+            .warning_println(&format!("⚠️  Failed to update the checksum table in the release body: {}", e))
+            .expect("Failed to write to stdout"),
+    }
 
-        release_state.set_phase(crate::state::ReleasePhase::GitHubPublish);
-        release_state.add_checkpoint(
-            "release_published".to_string(),
-            crate::state::ReleasePhase::GitHubPublish,
-            None,
-        );
-        crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+    Ok(())
+}
+
+/// Build release binaries in the release clone
+///
+/// On macOS, builds both architectures to enable universal binaries.
+pub(super) async fn build_release_binaries(
+    ctx: &ReleasePhaseContext<'_>,
+    env_config: &EnvConfig,
+) -> Result<()> {
+    use crate::cli::timeouts::TimedPhase;
+    use crate::exec::Exec;
+
+    // On macOS, build for both architectures to enable universal binaries
+    #[cfg(target_os = "macos")]
+    let build_targets = vec!["x86_64-apple-darwin", "aarch64-apple-darwin"];
+
+    #[cfg(not(target_os = "macos"))]
+    let build_targets: Vec<&str> = vec![];
+
+    // A binary with `required-features` is skipped by cargo unless they are enabled
+    let features = if ctx.binary_features.is_empty() {
+        Vec::new()
+    } else {
+        vec!["--features".to_string(), ctx.binary_features.join(",")]
+    };
+
+    if build_targets.is_empty() {
+        // Single-target build (non-macOS)
+        let build_output = ctx
+            .timeouts
+            .run(
+                TimedPhase::Build,
+                "cargo build --release",
+                ctx.config,
+                Exec::new("cargo")
+                    .arg("build")
+                    .arg("--release")
+                    .args(&features)
+                    .current_dir(ctx.release_clone_path)
+                    .envs(debug_symbols::build_env(ctx))
+                    .output_watched(ctx.config),
+            )
+            .await?;
+
+        if !build_output.status.success() {
+            return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "cargo build --release".to_string(),
+                reason: String::from_utf8_lossy(&build_output.stderr).to_string(),
+            }));
+        }
+    } else {
+        // Multi-target build (macOS) - propagate cross-compile env vars
+        for target in &build_targets {
+            ctx.config
+                .verbose_println(&format!("   Building for {}...", target))
+                .expect("Failed to write to stdout");
+
+            let command = format!("cargo build --release --target {}", target);
+            let cross_env = get_cross_compile_env(target, env_config);
+            let build_output = ctx
+                .timeouts
+                .run(
+                    TimedPhase::Build,
+                    &command,
+                    ctx.config,
+                    Exec::new("cargo")
+                        .arg("build")
+                        .arg("--release")
+                        .arg("--target")
+                        .arg(target)
+                        .args(&features)
+                        .current_dir(ctx.release_clone_path)
+                        .envs(cross_env)
+                        .envs(debug_symbols::build_env(ctx))
+                        .output_watched(ctx.config),
+                )
+                .await?;
+
+            if !build_output.status.success() {
+                return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+                    command,
+                    reason: String::from_utf8_lossy(&build_output.stderr).to_string(),
+                }));
+            }
+        }
     }
 
-    release_state.set_phase(crate::state::ReleasePhase::Completed);
-    crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+    ctx.config
+        .success_println("✓ Built release binaries")
+        .expect("Failed to write to stdout");
 
     Ok(())
 }
 
-/// Upload artifacts incrementally with state tracking for resume capability
-async fn upload_artifacts_incrementally(
+/// Upload artifacts in parallel with state tracking for resume capability
+///
+/// Up to `--upload-concurrency` uploads run at once. Each artifact is retried
+/// on its own (with its own upload timeout), so one flaky upload never
+/// restarts the others. Every completed upload is checkpointed immediately,
+/// and if some uploads still fail, the rest are finished before the error is
+/// returned so that a resumed release only retries the failures.
+pub(super) async fn upload_artifacts_concurrently(
     ctx: &ReleasePhaseContext<'_>,
     release_state: &mut ReleaseState,
     release_id: u64,
     artifacts: &[std::path::PathBuf],
-    platform: &str,
 ) -> Result<usize> {
-    let mut uploaded_count = 0;
+    use crate::cli::timeouts::TimedPhase;
+    use futures::stream::{self, StreamExt};
 
+    let retries = ctx.retries.file_uploads;
+
+    let mut queued = Vec::new();
     for artifact_path in artifacts {
         let filename = artifact_path
             .file_name()
@@ -417,13 +1032,14 @@ async fn upload_artifacts_incrementally(
                     command: "get filename".to_string(),
                     reason: format!("Invalid filename in path: {}", artifact_path.display()),
                 })
-            })?;
+            })?
+            .to_string();
 
         // Check if already uploaded
         let already_uploaded = release_state
             .github_state
             .as_ref()
-            .map(|gh| gh.uploaded_artifacts.contains(&filename.to_string()))
+            .map(|gh| gh.uploaded_artifacts.contains(&filename))
             .unwrap_or(false);
 
         if already_uploaded {
@@ -433,39 +1049,103 @@ async fn upload_artifacts_incrementally(
             continue;
         }
 
-        ctx.config
-            .indent(&format!("☁️  Uploading {}...", filename))
-            .expect("Failed to write to stdout");
+        let size = std::fs::metadata(artifact_path).map(|m| m.len()).unwrap_or(0);
+        queued.push((artifact_path, filename, size));
+    }
 
-        let uploaded_urls = ctx
-            .github_manager
-            .upload_artifacts(
-                release_id,
-                std::slice::from_ref(artifact_path),
-                ctx.new_version,
-                ctx.config,
-            )
-            .await
-            .map_err(|e| {
-                ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: format!("upload_{}", platform),
-                    reason: e.to_string(),
-                })
-            })?;
+    if queued.is_empty() {
+        return Ok(0);
+    }
 
-        if !uploaded_urls.is_empty() {
-            if let Some(github_state) = &mut release_state.github_state {
-                github_state.uploaded_artifacts.push(filename.to_string());
-            }
+    let total = queued.len();
+    let total_bytes: u64 = queued.iter().map(|(_, _, size)| size).sum();
+    let concurrency = ctx.config.upload_concurrency().min(total);
+    ctx.config
+        .println(&format!(
+            "☁️  Uploading {} artifact(s), {}, {} at a time...",
+            total,
+            format_bytes(total_bytes),
+            concurrency
+        ))
+        .expect("Failed to write to stdout");
 
-            crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+    let mut uploads = stream::iter(queued)
+        .map(|(artifact_path, filename, size)| async move {
+            let result = ctx
+                .timeouts
+                .run(
+                    TimedPhase::Upload,
+                    &format!("upload {}", filename),
+                    ctx.config,
+                    retry_with_backoff(
+                        || {
+                            ctx.github_manager.upload_artifacts(
+                                release_id,
+                                std::slice::from_ref(artifact_path),
+                                ctx.new_version,
+                                ctx.config,
+                            )
+                        },
+                        retries,
+                        &format!("Upload {}", filename),
+                        ctx.config,
+                        None,
+                    ),
+                )
+                .await;
+            (filename, size, result)
+        })
+        .buffer_unordered(concurrency);
 
-            ctx.config
-                .indent(&format!("✓ Uploaded {}", filename))
-                .expect("Failed to write to stdout");
-            uploaded_count += 1;
+    let mut uploaded_count = 0;
+    let mut finished = 0;
+    let mut finished_bytes = 0;
+    let mut failures = Vec::new();
+
+    while let Some((filename, size, result)) = uploads.next().await {
+        finished += 1;
+        finished_bytes += size;
+
+        match result {
+            Ok(uploaded_urls) => {
+                if !uploaded_urls.is_empty() {
+                    if let Some(github_state) = &mut release_state.github_state {
+                        github_state.uploaded_artifacts.push(filename.clone());
+                    }
+                    crate::state::save_release_state(ctx.release_clone_path, release_state).await?;
+                    uploaded_count += 1;
+                }
+                ctx.config
+                    .indent(&format!(
+                        "[{}/{}] ✓ {} ({} of {} done)",
+                        finished,
+                        total,
+                        filename,
+                        format_bytes(finished_bytes),
+                        format_bytes(total_bytes)
+                    ))
+                    .expect("Failed to write to stdout");
+            }
+            Err(e) => {
+                ctx.config
+                    .indent(&format!("[{}/{}] ✗ {}: {}", finished, total, filename, e))
+                    .expect("Failed to write to stdout");
+                failures.push(format!("{}: {}", filename, e));
+            }
         }
     }
 
+    if !failures.is_empty() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "upload artifacts".to_string(),
+            reason: format!(
+                "{} of {} upload(s) failed: {}",
+                failures.len(),
+                total,
+                failures.join("; ")
+            ),
+        }));
+    }
+
     Ok(uploaded_count)
 }
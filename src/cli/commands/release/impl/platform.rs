@@ -1,8 +1,9 @@
 //! Platform detection and bundling logic for release artifacts.
 
 use crate::error::{CliError, ReleaseError, Result};
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::exec::Exec;
 
 use super::context::ReleasePhaseContext;
 
@@ -10,7 +11,14 @@ use super::context::ReleasePhaseContext;
 pub fn get_platforms_to_build() -> Vec<&'static str> {
     // Return all supported platforms
     // The bundler will automatically use Docker for cross-platform builds
-    vec!["deb", "rpm", "appimage", "dmg", "exe"]
+    let mut platforms = vec!["deb", "rpm", "appimage", "dmg", "exe"];
+
+    // MSI (WiX) and MSIX (makeappx) only build natively on a Windows host
+    if std::env::consts::OS == "windows" {
+        platforms.extend(["msi", "msix"]);
+    }
+
+    platforms
 }
 
 /// Get platforms that can be built natively on current OS
@@ -37,14 +45,14 @@ pub fn get_docker_platforms<'a>(all_platforms: &'a [&'a str]) -> Vec<&'a str> {
 /// compile-time cfg attributes, enabling universal binaries.
 pub fn is_native_platform(platform: &str) -> bool {
     match (std::env::consts::OS, platform) {
-        // macOS native packages
-        ("macos", "dmg") => true,
+        // macOS native packages (Developer ID disk image, App Store pkg)
+        ("macos", "dmg" | "mas") => true,
 
         // Linux native packages  
         ("linux", "deb" | "rpm" | "appimage") => true,
 
-        // Windows native packages
-        ("windows", "exe") => true,
+        // Windows native packages (NSIS installer, MSI and MSIX)
+        ("windows", "exe" | "msi" | "msix") => true,
 
         // Everything else requires Docker
         _ => false,
@@ -108,6 +116,9 @@ pub fn construct_output_filename(
         "rpm" => format!("{}-{}-1.{}.rpm", binary_name, version, arch),
         "dmg" => format!("{}-{}-{}.dmg", binary_name, version, arch),
         "exe" => format!("{}_{}_{}_setup.exe", binary_name, version, arch),
+        "msi" => format!("{}_{}_{}_en-US.msi", binary_name, version, arch),
+        "msix" => format!("{}_{}_{}.msix", binary_name, version, arch),
+        "mas" => format!("{}-{}-{}-appstore.pkg", binary_name, version, arch),
         "appimage" => format!("{}-{}-{}.AppImage", binary_name, version, arch),
         _ => {
             return Err(ReleaseError::Cli(CliError::InvalidArguments {
@@ -119,19 +130,56 @@ pub fn construct_output_filename(
     Ok(filename)
 }
 
-/// Get installed binary version by running `binary --version`
-async fn get_installed_version(binary_name: &str) -> Option<String> {
-    let output = tokio::process::Command::new(binary_name)
-        .arg("--version")
-        .output()
-        .await
-        .ok()?;
+/// Directory a platform bundle is written to: `artifacts/<platform>/<arch>/`
+///
+/// Bundles of one type for different architectures land in separate
+/// directories, so a rerun or a second architecture never overwrites them.
+pub fn bundle_output_dir(release_clone_path: &Path, platform: &str, arch: &str) -> PathBuf {
+    release_clone_path.join("artifacts").join(platform).join(arch)
+}
 
-    if !output.status.success() {
-        return None;
+/// Release asset names claimed so far in a run
+///
+/// GitHub release assets share one flat namespace, so two artifacts with the
+/// same file name (e.g. a `.deb` for amd64 and one for arm64 without an
+/// architecture suffix) would silently replace each other on upload. Every
+/// producer claims its artifacts here before anything is uploaded.
+#[derive(Debug, Default)]
+pub struct ArtifactNames {
+    claimed: HashMap<String, (String, PathBuf)>,
+}
+
+impl ArtifactNames {
+    /// Claim the file names of `paths` for `source` (a platform or target label)
+    pub fn claim(&mut self, source: &str, paths: &[PathBuf]) -> Result<()> {
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if let Some((owner, existing)) = self.claimed.get(&name) {
+                return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+                    command: "bundle".to_string(),
+                    reason: format!(
+                        "Artifact name collision: {} is produced by both {} ({}) and {} ({}). \
+                         Release assets must have unique names; give matrix entries for the same target distinct `name`s.",
+                        name,
+                        owner,
+                        existing.display(),
+                        source,
+                        path.display()
+                    ),
+                }));
+            }
+            self.claimed.insert(name, (source.to_string(), path.clone()));
+        }
+        Ok(())
     }
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+/// Get installed binary version by running `binary --version`
+async fn get_installed_version(binary_name: &str) -> Option<String> {
+    let stdout = Exec::new(binary_name).arg("--version").stdout().await.ok()?;
     // Parse version using regex (matches semantic versions like 0.10.0)
     let re = regex::Regex::new(r"\b(\d+\.\d+\.\d+(?:-[a-zA-Z0-9.-]+)?)\b").ok()?;
     re.captures(&stdout)
@@ -178,7 +226,7 @@ async fn get_crates_io_version(crate_name: &str) -> Option<String> {
 /// 3. Only install/update if local version is missing or older
 ///
 /// This avoids unnecessary reinstalls during development.
-pub async fn ensure_bundler_installed(ctx: &ReleasePhaseContext<'_>) -> Result<std::path::PathBuf> {
+pub async fn ensure_bundler_installed(ctx: &ReleasePhaseContext<'_>) -> Result<PathBuf> {
     let binary_name = "kodegen_bundler_bundle";
 
     // Get installed version
@@ -193,7 +241,7 @@ pub async fn ensure_bundler_installed(ctx: &ReleasePhaseContext<'_>) -> Result<s
             true
         }
         (Some(_), None) => {
-            ctx.config.verbose_println("   Could not check crates.io, using installed version").expect("Failed to write to stdout");
+            ctx.config.soft_failure("   Could not check crates.io, using installed version")?;
             false // Can't check crates.io, assume installed is OK
         }
         (Some(installed), Some(latest)) => {
@@ -214,50 +262,132 @@ pub async fn ensure_bundler_installed(ctx: &ReleasePhaseContext<'_>) -> Result<s
     };
 
     if !needs_install {
-        return Ok(std::path::PathBuf::from(binary_name));
+        return Ok(PathBuf::from(binary_name));
     }
 
     // Install from crates.io
     ctx.config.verbose_println(&format!("   Installing {} from crates.io...", binary_name)).expect("Failed to write to stdout");
 
-    let install_status = std::process::Command::new("cargo")
+    Exec::new("cargo")
         .arg("install")
         .arg(binary_name)
-        .status()
-        .map_err(|e| ReleaseError::Cli(CliError::ExecutionFailed {
-            command: "cargo install bundler".to_string(),
-            reason: e.to_string(),
-        }))?;
-
-    if !install_status.success() {
-        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-            command: "cargo install bundler".to_string(),
-            reason: format!("Install failed with exit code: {:?}", install_status.code()),
-        }));
-    }
+        .run_watched(ctx.config)
+        .await?;
 
     ctx.config.verbose_println("   ✓ Bundler installed successfully").expect("Failed to write to stdout");
 
-    Ok(std::path::PathBuf::from(binary_name))
+    Ok(PathBuf::from(binary_name))
+}
+
+/// Extra bundler environment for the Mac App Store pkg
+///
+/// The App Store build is signed with its own identities, the sandbox
+/// entitlements and the embedded provisioning profile instead of Developer ID.
+fn mac_app_store_vars(ctx: &ReleasePhaseContext<'_>, platform: &str) -> Vec<(&'static str, String)> {
+    let Some(mas) = ctx.mac_app_store.filter(|_| platform == "mas") else {
+        return Vec::new();
+    };
+    let mut vars = ctx.env_config.vars_for(crate::env::EnvRequirement::MacAppStore);
+    vars.push((
+        "KODEGEN_MAS_ENTITLEMENTS",
+        ctx.release_clone_path.join(&mas.entitlements).display().to_string(),
+    ));
+    vars.push((
+        "KODEGEN_MAS_PROVISIONING_PROFILE",
+        ctx.release_clone_path.join(&mas.provisioning_profile).display().to_string(),
+    ));
+    vars
+}
+
+/// Bundle a platform under the bundle timeout, keeping the bundler's output on failure
+///
+/// Everything the bundler prints is also written to
+/// `target/bundle-logs/<platform>-<uuid>.log`, so a failed containerized
+/// build can be read in full after the streamed output scrolled away. The log
+/// is removed when bundling succeeds. With `--attach-bundle-logs`, the log of
+/// a failure is also attached to `draft_release`.
+pub async fn bundle_with_log(
+    ctx: &ReleasePhaseContext<'_>,
+    bundler_binary: &PathBuf,
+    platform: &str,
+    draft_release: Option<u64>,
+) -> Result<Vec<PathBuf>> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let log_dir = std::env::current_dir()?.join("target").join("bundle-logs");
+    std::fs::create_dir_all(&log_dir)?;
+    let log_path = log_dir.join(format!("{}-{}.log", platform, uuid::Uuid::new_v4()));
+
+    let result = ctx
+        .timeouts
+        .run(
+            TimedPhase::Bundle,
+            &format!("bundle {}", platform),
+            ctx.config,
+            bundle_platform(ctx, bundler_binary, platform, &log_path),
+        )
+        .await;
+    if result.is_ok() {
+        let _ = std::fs::remove_file(&log_path);
+        return result;
+    }
+    if !log_path.exists() {
+        return result;
+    }
+
+    // A timeout drops the bundler mid-run, so its error cannot name the log
+    ctx.config
+        .warning_println(&format!("⚠️  Full {} bundler output: {}", platform, log_path.display()))
+        .expect("Failed to write to stdout");
+    if let Some(release_id) = draft_release
+        && ctx.config.attach_bundle_logs()
+    {
+        match ctx.github_manager.upload_bundle_log(release_id, &log_path).await {
+            Ok(name) => ctx
+                .config
+                .indent(&format!("Attached {} to the draft release", name))
+                .expect("Failed to write to stdout"),
+            Err(e) => ctx
+                .config
+                .warning_println(&format!("⚠️  Failed to attach the bundler log: {}", e))
+                .expect("Failed to write to stdout"),
+        }
+    }
+    result
 }
 
 /// Bundle a platform by invoking kodegen_bundler_bundle binary
 ///
 /// Uses ONLY the 3 required arguments with proper stdout/stderr streaming.
-pub async fn bundle_platform(
+/// The output is also written line by line to `log_path`.
+async fn bundle_platform(
     ctx: &ReleasePhaseContext<'_>,
-    bundler_binary: &std::path::PathBuf,
+    bundler_binary: &PathBuf,
     platform: &str,
-) -> Result<Vec<std::path::PathBuf>> {
+    log_path: &Path,
+) -> Result<Vec<PathBuf>> {
     // Determine architecture for filename construction
     let arch = match platform {
+        // WiX and makeappx have no container image; there is no path or
+        // uid/gid mapping for them, so they never go through Docker
+        "msi" | "msix" if !is_native_platform(platform) => {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!(
+                    "{} packages can only be built on a Windows host (this host is {})",
+                    platform.to_uppercase(),
+                    std::env::consts::OS
+                ),
+            }));
+        }
+
+        // Native platforms use detected architecture
+        "dmg" | "mas" | "msi" | "msix" => detect_target_architecture()?,
+        "exe" if is_native_platform(platform) => detect_target_architecture()?,
+
         // Docker platforms have fixed architectures
         "deb" | "rpm" => "amd64",
         "appimage" => "x86_64",
         "exe" => "x64",
-
-        // Native platforms use detected architecture
-        "dmg" => detect_target_architecture()?,
         
         _ => {
             return Err(ReleaseError::Cli(CliError::InvalidArguments {
@@ -273,8 +403,10 @@ pub async fn bundle_platform(
         platform,
         arch,
     )?;
-    let output_path = ctx.release_clone_path.join("artifacts").join(&filename);
-    
+    let output_dir = bundle_output_dir(ctx.release_clone_path, platform, arch);
+    std::fs::create_dir_all(&output_dir)?;
+    let output_path = output_dir.join(&filename);
+
     ctx.config.verbose_println(&format!(
         "   Target architecture: {}\n   Output path: {}",
         arch,
@@ -282,63 +414,60 @@ pub async fn bundle_platform(
     )).expect("Failed to write to stdout");
 
     // Determine source argument
-    // Bundler needs GitHub URL to clone - construct from metadata
-    let github_url = format!(
-        "https://github.com/{}/{}",
-        ctx.github_owner,
-        ctx.github_repo_name
-    );
+    // Bundler needs GitHub URL to clone - construct from metadata. Rebuilds
+    // bundle the release clone, which is checked out at the requested tag.
+    let github_url = if ctx.bundle_from_clone {
+        ctx.release_clone_path.display().to_string()
+    } else {
+        format!(
+            "https://github.com/{}/{}",
+            ctx.github_owner,
+            ctx.github_repo_name
+        )
+    };
     
-    // Invoke bundler with ONLY 3 arguments
-    let mut child = Command::new(bundler_binary)
+    // Invoke bundler with ONLY 3 arguments. Signing credentials from the
+    // shell rc, .env or keychain are not in the process environment, so pass them on
+    // (or strip them from the inherited environment for an unsigned profile).
+    // The bundler clones from GitHub, so it keeps the tokens
+    let mut bundler = Exec::new(bundler_binary);
+    bundler.with_credentials();
+    for name in ctx.env_config.masked_signing_vars() {
+        bundler.env_remove(name);
+    }
+    bundler
         .arg("--source")
         .arg(&github_url)
         .arg("--platform")
         .arg(platform)
         .arg("--output-binary")
         .arg(&output_path)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: format!("bundle_{}", platform),
-                reason: e.to_string(),
-            })
-        })?;
-
-    // Stream stdout and stderr concurrently through OutputManager
-    let runtime_config = ctx.config.clone();
-    let runtime_config2 = ctx.config.clone();
-    
-    tokio::join!(
-        async {
-            if let Some(stdout) = child.stdout.take() {
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config.indent(&line).expect("Failed to write to stdout");
-                }
-            }
-        },
-        async {
-            if let Some(stderr) = child.stderr.take() {
-                let reader = BufReader::new(stderr);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
-                    runtime_config2.indent(&line).expect("Failed to write to stdout");
-                }
-            }
-        }
-    );
-
-    // Wait for process to complete
-    let status = child.wait().await.map_err(|e| {
-        ReleaseError::Cli(CliError::ExecutionFailed {
-            command: format!("bundle_{}", platform),
-            reason: e.to_string(),
+        .envs(ctx.env_config.signing_vars())
+        .envs(mac_app_store_vars(ctx, platform))
+        .envs(super::debug_symbols::build_env(ctx));
+
+    // Stream stdout and stderr through OutputManager, watched for stalls. The
+    // log is line-buffered, so it is complete up to the last line even if the
+    // bundler is killed by the timeout or the watchdog.
+    let mut log = std::io::LineWriter::new(std::fs::File::create(log_path)?);
+    let mut write_log = |line: &str| {
+        use std::io::Write;
+        let _ = writeln!(log, "{}", line);
+    };
+    let status = bundler
+        .stream(ctx.config, |_, line| {
+            write_log(line);
+            ctx.config.indent(line).expect("Failed to write to stdout");
         })
-    })?;
+        .await;
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            write_log(&format!("--- bundler failed: {} ---", e));
+            return Err(e);
+        }
+    };
+    write_log(&format!("--- bundler exited with {} ---", status));
     
     // Contract enforcement: exit code 0 = file guaranteed to exist
     if status.success() {
@@ -359,8 +488,9 @@ pub async fn bundle_platform(
         Err(ReleaseError::Cli(CliError::ExecutionFailed {
             command: format!("bundle_{}", platform),
             reason: format!(
-                "Bundling failed with exit code {:?}",
-                status.code()
+                "Bundling failed with exit code {:?} (full output: {})",
+                status.code(),
+                log_path.display()
             ),
         }))
     }
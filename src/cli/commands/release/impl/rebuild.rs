@@ -0,0 +1,167 @@
+//! Artifacts-only rebuild of an existing release.
+//!
+//! Rebuilds and re-bundles a tag that already has a published GitHub release,
+//! then replaces the release assets in place. Used when a packaging bug is
+//! found after the tag exists.
+
+use crate::cli::RuntimeConfig;
+use crate::cli::retry_config::RetryConfig;
+use crate::cli::timeouts::PhaseTimeouts;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::manifest::ReleaseManifest;
+use crate::state::ReleaseState;
+use crate::EnvConfig;
+
+use super::super::super::github_remote::GitHubRemote;
+use super::context::ReleasePhaseContext;
+use super::encrypt::encrypt_artifacts;
+use super::phases::{build_release_binaries, create_bundles, upload_artifacts_concurrently, upload_manifest};
+use super::release::{print_result, write_manifest_copy};
+
+/// Rebuild artifacts for an existing tag and replace them on its release.
+///
+/// Expects `temp_dir` to already be checked out at `tag`.
+pub async fn perform_rebuild_single_repo(
+    temp_dir: &std::path::Path,
+    tag: &str,
+    metadata: crate::metadata::PackageMetadata,
+    binary_name: String,
+    github_remote: &GitHubRemote,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    config
+        .println(&format!("🔁 Rebuilding artifacts for {}", tag))
+        .expect("Failed to write to stdout");
+
+    let release_version = semver::Version::parse(&metadata.version).map_err(|e| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Invalid version in Cargo.toml at {}: {}", tag, e),
+        })
+    })?;
+
+    metadata.version_scheme.validate(&release_version)?;
+
+    // Artifact names embed the version, so it must match the tag
+    if tag != metadata.version_scheme.tag_name(&release_version) {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Tag {} does not match Cargo.toml version {} at that tag",
+                tag, release_version
+            ),
+        }));
+    }
+
+    let github_owner = github_remote.owner.clone();
+    let github_repo_name = github_remote.repo.clone();
+
+    config
+        .verbose_println(&format!(
+            "   Repository: {}/{} (remote {})",
+            github_owner, github_repo_name, github_remote.remote
+        ))
+        .expect("Failed to write to stdout");
+
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_owner.clone(),
+        repo: github_repo_name.clone(),
+        token: None, // Will be read from env_config in new()
+        version_scheme: metadata.version_scheme.clone(),
+        asset_labels: metadata.asset_labels.clone(),
+        on_existing_asset: crate::github::ExistingAssetPolicy::Replace,
+        ..Default::default()
+    };
+
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
+    config
+        .success_println("✓ GitHub API authenticated")
+        .expect("Failed to write to stdout");
+
+    let release = github_manager.find_release_by_tag(tag).await?.ok_or_else(|| {
+        ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "rebuild".to_string(),
+            reason: format!(
+                "No published GitHub release found for tag {}. Run a full release instead.",
+                tag
+            ),
+        })
+    })?;
+
+    config
+        .indent(&format!("   Release: {}", release.html_url))
+        .expect("Failed to write to stdout");
+
+    let timeouts =
+        PhaseTimeouts::resolve(env_config, &metadata.timeouts, config.timeout_overrides());
+    let retries = RetryConfig::from_env(env_config);
+
+    let ctx = ReleasePhaseContext {
+        release_clone_path: temp_dir,
+        package_name: &metadata.name,
+        binary_name: &binary_name,
+        binary_features: &metadata.binary_features,
+        library_only: false,
+        new_version: &release_version,
+        config,
+        github_manager: &github_manager,
+        github_owner: &github_owner,
+        github_repo_name: &github_repo_name,
+        bundle_from_clone: true,
+        keep_draft: false,
+        debug_symbols: metadata.debug_symbols,
+        shell_integration: &metadata.shell_integration,
+        bsd: &metadata.bsd,
+        targets: &metadata.targets,
+        bundlers: &metadata.bundlers,
+        tests: None,
+        lints: None,
+        audit: None,
+        deny: None,
+        mac_app_store: None,
+        store: None,
+        mirrors: &[],
+        encryption: metadata.encryption.as_ref(),
+        compression: &metadata.compression,
+        env_config,
+        timeouts: &timeouts,
+        retries: &retries,
+    };
+
+    // ===== BUILD RELEASE BINARIES =====
+    config.group("Build release binaries").expect("Failed to write to stdout");
+    config
+        .println("🔨 Building release binaries...")
+        .expect("Failed to write to stdout");
+    build_release_binaries(&ctx, env_config).await?;
+
+    let mut manifest = ReleaseManifest::new(
+        &metadata.name,
+        &release_version,
+        tag,
+        &format!("{}/{}", github_owner, github_repo_name),
+        &release,
+    );
+
+    // ===== BUNDLE AND REPLACE ASSETS =====
+    // Everything is bundled before the first asset is replaced; the release is
+    // already published, so bundler failure logs stay local
+    let pending_uploads = create_bundles(&ctx, &mut manifest, None).await?;
+
+    config.group("Upload artifacts").expect("Failed to write to stdout");
+    let uploads = encrypt_artifacts(&ctx, &mut manifest, &pending_uploads).await?;
+    let mut upload_state = ReleaseState::new(release_version.clone(), crate::state::ReleaseConfig::default());
+    upload_artifacts_concurrently(&ctx, &mut upload_state, release.release_id, &uploads).await?;
+
+    upload_manifest(&ctx, &mut manifest, release.release_id).await?;
+    write_manifest_copy(&mut manifest, config)?;
+
+    config
+        .success_println(&format!(
+            "🎉 Replaced {} asset(s) on {} ({})",
+            uploads.len(), tag, metadata.name
+        ))
+        .expect("Failed to write to stdout");
+    print_result(&manifest, config);
+
+    Ok(0)
+}
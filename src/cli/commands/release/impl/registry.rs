@@ -0,0 +1,188 @@
+//! Post-publish verification against the crates.io sparse index.
+//!
+//! `cargo publish` returns once crates.io accepted the upload, but the
+//! version can take a while to reach the index `cargo install` reads. Before
+//! the release manifest is uploaded, every publishable crate is polled at
+//! `https://index.crates.io/` until its exact version is listed, bounded by
+//! the `registry` phase timeout. The time each version was first seen is
+//! recorded in the manifest. Crates with `publish = false`, or whose `publish`
+//! list names only other registries, never reach crates.io and are not waited
+//! for.
+//!
+//! `KODEGEN_REGISTRY_INDEX` points the checks at another sparse index, such
+//! as the local registry of the `test-harness` feature.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::manifest::PublishedCrate;
+use crate::sparse_index::index_path;
+use std::time::Duration;
+
+use super::context::ReleasePhaseContext;
+use super::features::{PublishableCrate, publishable_crates};
+
+/// Sparse index of crates.io
+const SPARSE_INDEX: &str = "https://index.crates.io";
+
+/// Sparse index to check, without a trailing slash
+fn index_url() -> String {
+    std::env::var("KODEGEN_REGISTRY_INDEX")
+        .ok()
+        .map(|url| url.trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| SPARSE_INDEX.to_string())
+}
+
+/// Delay between polls of a crate that is not visible yet
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Wait until every publishable crate's version is in the crates.io index
+pub(super) async fn verify_published_crates(ctx: &ReleasePhaseContext<'_>) -> Result<Vec<PublishedCrate>> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let crates = crates_to_verify(ctx.release_clone_path)?;
+    let client = index_client()?;
+
+    let mut verified = Vec::new();
+    for PublishableCrate { package, version, .. } in crates {
+        let name = package.unwrap_or_else(|| ctx.package_name.to_string());
+        let version = version.unwrap_or_else(|| ctx.new_version.to_string());
+        let operation = format!("wait for {} {} on crates.io", name, version);
+
+        let verified_at = ctx
+            .timeouts
+            .run(TimedPhase::Registry, &operation, ctx.config, async {
+                loop {
+                    match index_has_version(&client, &name, &version).await {
+                        Ok(true) => return Ok(chrono::Utc::now()),
+                        Ok(false) => ctx
+                            .config
+                            .verbose_println(&format!("   {} {} not in the index yet", name, version))
+                            .expect("Failed to write to stdout"),
+                        Err(e) => ctx
+                            .config
+                            .verbose_println(&format!("   crates.io index request failed: {}", e))
+                            .expect("Failed to write to stdout"),
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            })
+            .await?;
+
+        ctx.config
+            .indent(&format!("✓ {} {}", name, version))
+            .expect("Failed to write to stdout");
+        verified.push(PublishedCrate {
+            name,
+            version,
+            verified_at,
+        });
+    }
+
+    Ok(verified)
+}
+
+/// Crates of `repo` that `cargo publish` sends to crates.io
+fn crates_to_verify(repo: &std::path::Path) -> Result<Vec<PublishableCrate>> {
+    Ok(publishable_crates(repo)?
+        .into_iter()
+        .filter(|krate| krate.crates_io)
+        .collect())
+}
+
+/// Whether `version` of `name` is already in the crates.io index
+pub(super) async fn is_published(name: &str, version: &str) -> Result<bool> {
+    index_has_version(&index_client()?, name, version)
+        .await
+        .map_err(|e| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "crates.io index".to_string(),
+                reason: e.to_string(),
+            })
+        })
+}
+
+fn index_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent("kodegen_bundler_release")
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "crates.io index".to_string(),
+                reason: e.to_string(),
+            })
+        })
+}
+
+/// Whether the sparse index lists `version` of `name`
+///
+/// A crate that was never published is a 404, i.e. not visible yet.
+async fn index_has_version(client: &reqwest::Client, name: &str, version: &str) -> reqwest::Result<bool> {
+    let response = client
+        .get(format!("{}/{}", index_url(), index_path(name)))
+        // The index is served through a CDN; ask for a fresh copy
+        .header(reqwest::header::CACHE_CONTROL, "no-cache")
+        .send()
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    let body = response.error_for_status()?.text().await?;
+
+    // One JSON object per published version
+    Ok(body.lines().any(|line| {
+        serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|entry| entry.get("vers").and_then(|v| v.as_str()).map(|v| v == version))
+            .unwrap_or(false)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &std::path::Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn package(name: &str, publish: &str) -> String {
+        format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n{}\n", name, publish)
+    }
+
+    #[test]
+    fn skips_crates_not_published_to_crates_io() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "Cargo.toml", "[workspace]\nmembers = [\"public\", \"private\", \"internal\", \"listed\"]\n");
+        write(dir.path(), "public/Cargo.toml", &package("public", ""));
+        write(dir.path(), "private/Cargo.toml", &package("private", "publish = false"));
+        write(dir.path(), "internal/Cargo.toml", &package("internal", "publish = [\"my-registry\"]"));
+        write(dir.path(), "listed/Cargo.toml", &package("listed", "publish = [\"my-registry\", \"crates-io\"]"));
+        for member in ["public", "private", "internal", "listed"] {
+            write(dir.path(), &format!("{}/src/lib.rs", member), "");
+        }
+
+        let names: Vec<_> = crates_to_verify(dir.path())
+            .unwrap()
+            .into_iter()
+            .filter_map(|krate| krate.package)
+            .collect();
+        assert_eq!(names, ["listed", "public"]);
+    }
+
+    #[test]
+    fn skips_single_crate_published_elsewhere() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "Cargo.toml", &package("tool", "publish = [\"my-registry\"]"));
+        write(dir.path(), "src/main.rs", "fn main() {}");
+        assert!(crates_to_verify(dir.path()).unwrap().is_empty());
+
+        write(dir.path(), "Cargo.toml", &package("tool", "publish = false"));
+        assert!(crates_to_verify(dir.path()).unwrap().is_empty());
+
+        write(dir.path(), "Cargo.toml", &package("tool", ""));
+        assert_eq!(crates_to_verify(dir.path()).unwrap().len(), 1);
+    }
+}
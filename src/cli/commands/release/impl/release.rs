@@ -3,12 +3,14 @@
 //! Coordinates GitHub release + platform bundling.
 //! Version bumping and git tagging are handled by `just publish` before this runs.
 
-use crate::cli::RuntimeConfig;
+use crate::cli::{NotesSource, RuntimeConfig};
+use crate::cli::retry_config::RetryConfig;
+use crate::cli::timeouts::PhaseTimeouts;
 use crate::error::{CliError, ReleaseError, Result};
 use crate::state::ReleaseState;
 use crate::EnvConfig;
 
-use super::super::super::helpers::parse_github_url;
+use super::super::super::github_remote::GitHubRemote;
 use super::context::ReleasePhaseContext;
 use super::phases::execute_phases_with_retry;
 
@@ -16,10 +18,14 @@ use super::phases::execute_phases_with_retry;
 ///
 /// Expects version already bumped and tagged by `just publish`.
 /// This function creates GitHub release and uploads platform bundles.
+/// Without a binary (a library-only crate) there is nothing to build or
+/// bundle, and only the GitHub release is created and published.
 pub async fn perform_release_single_repo(
     temp_dir: &std::path::Path,
     metadata: crate::metadata::PackageMetadata,
-    binary_name: String,
+    binary_name: Option<String>,
+    github_remote: &GitHubRemote,
+    profile: &crate::profile::ReleaseProfile,
     config: &RuntimeConfig,
     env_config: &EnvConfig,
 ) -> Result<i32> {
@@ -33,54 +39,203 @@ pub async fn perform_release_single_repo(
             reason: format!("Invalid version in Cargo.toml: {}", e),
         })
     })?;
+    metadata.version_scheme.validate(&release_version)?;
+    let tag_name = metadata.version_scheme.tag_name(&release_version);
 
     config
-        .println(&format!("   Version: v{}", release_version))
+        .println(&format!("   Version: {}", tag_name))
         .expect("Failed to write to stdout");
 
     // Create release state
     let mut release_state =
         ReleaseState::new(release_version.clone(), crate::state::ReleaseConfig::default());
 
-    // Detect GitHub repository from origin remote
-    let origin_url = detect_origin_url(temp_dir).await?;
-    let (github_owner, github_repo_name) = parse_github_url(&origin_url)?;
+    // GitHub repository detected from the source repository's remotes (or the
+    // release profile's repository)
+    let github_owner = github_remote.owner.clone();
+    let github_repo_name = github_remote.repo.clone();
 
     config
         .verbose_println(&format!(
-            "   Repository: {}/{}",
-            &github_owner, &github_repo_name
+            "   Repository: {}/{} (remote {})",
+            github_owner, github_repo_name, github_remote.remote
         ))
         .expect("Failed to write to stdout");
 
+    // Localized release notes from templates, if configured
+    let notes = match &metadata.release_notes {
+        Some(notes_config) => {
+            let notes = notes_config.render(temp_dir, &metadata.name, &release_version, &tag_name)?;
+            if notes.is_none() {
+                config
+                    .warning_println(&format!(
+                        "⚠️  No release notes templates found in {}",
+                        notes_config.templates.display()
+                    ))
+                    .expect("Failed to write to stdout");
+            }
+            notes
+        }
+        None => None,
+    };
+
+    // Workspace changelog grouped by crate, after the templated notes
+    // (pull request notes need the GitHub API and are added further down)
+    let tag_pattern = metadata.version_scheme.tag_pattern();
+    let crate_notes = match config.notes_source() {
+        NotesSource::Commits => crate::release_notes::crate_sections(temp_dir, &tag_name, &tag_pattern)?,
+        NotesSource::Prs => None,
+    };
+    let notes = match (notes, crate_notes) {
+        (Some(notes), Some(crate_notes)) => Some(format!("{}\n\n{}", notes, crate_notes)),
+        (notes, crate_notes) => notes.or(crate_notes),
+    };
+
     // Initialize GitHub manager
     let github_config = crate::github::GitHubReleaseConfig {
         owner: github_owner.clone(),
         repo: github_repo_name.clone(),
-        draft: false,
-        prerelease_for_zero_versions: true,
-        notes: None,
+        draft: profile.draft,
+        prerelease: config.prerelease().or(profile.prerelease).unwrap_or_default(),
+        notes,
         token: None, // Will be read from env_config in new()
+        on_existing_asset: config.on_existing_asset(),
+        version_scheme: metadata.version_scheme.clone(),
+        asset_labels: metadata.asset_labels.clone(),
     };
 
-    let github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
+    let mut github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
     config
         .success_println("✓ GitHub API authenticated")
         .expect("Failed to write to stdout");
 
+    if config.notes_source() == NotesSource::Prs {
+        let range = crate::release_notes::change_range(temp_dir, &tag_name, &tag_pattern)?;
+        let pull_requests = github_manager.merged_pull_requests(&range).await?;
+        config
+            .verbose_println(&format!(
+                "   {} merged pull request(s) in {} commit(s) since the previous tag",
+                pull_requests.len(),
+                range.commits.len()
+            ))
+            .expect("Failed to write to stdout");
+        match crate::release_notes::pull_request_sections(&pull_requests) {
+            Some(section) => github_manager.append_notes(section),
+            None => config
+                .warning_println("⚠️  No merged pull requests found since the previous tag")
+                .expect("Failed to write to stdout"),
+        }
+    }
+
+    let timeouts =
+        PhaseTimeouts::resolve(env_config, &metadata.timeouts, config.timeout_overrides());
+    let retries = RetryConfig::from_env(env_config);
+
     // Build context for phase execution
+    let library_only = binary_name.is_none();
+    let binary_name = binary_name.unwrap_or_else(|| metadata.name.clone());
     let ctx = ReleasePhaseContext {
         release_clone_path: temp_dir,
+        package_name: &metadata.name,
         binary_name: &binary_name,
+        binary_features: &metadata.binary_features,
+        library_only,
         new_version: &release_version,
         config,
         github_manager: &github_manager,
         github_owner: &github_owner,
         github_repo_name: &github_repo_name,
+        // A profile's repository may not carry the source, so bundle the clone
+        bundle_from_clone: profile.repository.is_some(),
+        keep_draft: profile.draft,
+        debug_symbols: metadata.debug_symbols,
+        shell_integration: &metadata.shell_integration,
+        bsd: &metadata.bsd,
+        targets: &metadata.targets,
+        bundlers: &metadata.bundlers,
+        tests: config
+            .run_tests()
+            .unwrap_or(metadata.tests.run)
+            .then_some(&metadata.tests),
+        lints: (metadata.lints.is_enabled() && !config.skip_lint()).then_some(&metadata.lints),
+        audit: metadata.audit.as_ref().filter(|_| !config.skip_audit()),
+        deny: metadata.deny.as_ref().filter(|_| !config.skip_deny()),
+        mac_app_store: metadata.mac_app_store.as_ref(),
+        store: metadata.store.as_ref(),
+        mirrors: if config.skip_mirrors() { &[] } else { &metadata.mirrors },
+        encryption: metadata.encryption.as_ref(),
+        compression: &metadata.compression,
+        env_config,
+        timeouts: &timeouts,
+        retries: &retries,
     };
 
-    // Execute release phases (GitHub release + bundling)
-    execute_phases_with_retry(&ctx, &mut release_state, env_config).await?;
+    // Execute release phases (GitHub release + bundling). On failure (including
+    // a phase timeout or --deadline) record the error and save state before aborting.
+    let mut manifest = match execute_phases_with_retry(&ctx, &mut release_state, env_config).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let phase = release_state.current_phase;
+            release_state.add_error(e.to_string(), phase, e.is_recoverable(), None);
+            if let ReleaseError::Cli(CliError::Aborted { signal, .. }) = &e {
+                release_state.mark_aborted(signal);
+            }
+            if let Err(save_err) = crate::state::save_release_state(temp_dir, &mut release_state).await {
+                config
+                    .warning_println(&format!("Failed to save release state: {}", save_err))
+                    .expect("Failed to write to stdout");
+            }
+            if let ReleaseError::Cli(CliError::Aborted { .. }) = &e {
+                // The temp clone goes away on exit; keep a copy of the state outside it
+                let kept = match crate::state::aborted_state_dir(&metadata.name, &release_version) {
+                    Ok(dir) => crate::state::save_release_state(&dir, &mut release_state)
+                        .await
+                        .map(|_| crate::state::state_file_path(&dir)),
+                    Err(e) => Err(e),
+                };
+                let kept = kept
+                    .inspect_err(|save_err| {
+                        config
+                            .warning_println(&format!("Failed to keep the release state: {}", save_err))
+                            .expect("Failed to write to stdout");
+                    })
+                    .ok();
+                print_resume_instructions(&release_state, kept.as_deref(), config);
+            }
+            return Err(e);
+        }
+    };
+    // From here on a failure leaves a live release behind
+    let published = release_state.has_completed(crate::state::ReleasePhase::GitHubPublish);
+    let release_url = manifest.html_url.clone();
+    write_manifest_copy(&mut manifest, config)
+        .map_err(|e| after_publish(published, &release_url, e))?;
+
+    // Old drafts, prerelease assets and superseded nightlies. The release
+    // itself succeeded, so failures here are only warnings.
+    if metadata.retention.is_enabled() {
+        config.group("Retention").expect("Failed to write to stdout");
+        config
+            .println("🧹 Applying retention policy...")
+            .expect("Failed to write to stdout");
+        match crate::github::apply_retention(&github_manager, &metadata.retention, manifest.release_id, config).await {
+            Ok(summary) => config
+                .success_println(&format!(
+                    "✓ Retention: {} release(s) and {} asset(s) deleted{}",
+                    summary.releases_deleted,
+                    summary.assets_deleted,
+                    if summary.failures > 0 {
+                        format!(", {} failed", summary.failures)
+                    } else {
+                        String::new()
+                    }
+                ))
+                .expect("Failed to write to stdout"),
+            Err(e) => config
+                .warning_println(&format!("⚠️  Retention skipped: {}", e))
+                .expect("Failed to write to stdout"),
+        }
+    }
 
     // Success
     config
@@ -90,8 +245,9 @@ pub async fn perform_release_single_repo(
         .success_println(&format!("   Package: {}", metadata.name))
         .expect("Failed to write to stdout");
     config
-        .success_println(&format!("   Version: v{}", release_version))
+        .success_println(&format!("   Version: {}", tag_name))
         .expect("Failed to write to stdout");
+    print_result(&manifest, config);
 
     // Cleanup release state file
     match crate::state::cleanup_release_state(temp_dir) {
@@ -110,26 +266,74 @@ pub async fn perform_release_single_repo(
     Ok(0)
 }
 
-/// Detect origin URL from git config
-async fn detect_origin_url(repo_path: &std::path::Path) -> Result<String> {
-    let output = tokio::process::Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .current_dir(repo_path)
-        .output()
-        .await
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "git remote get-url origin".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
-
-    if !output.status.success() {
-        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-            command: "git remote get-url origin".to_string(),
-            reason: String::from_utf8_lossy(&output.stderr).to_string(),
-        }));
+/// Tell the user where the state was kept and how to continue a release stopped by a signal
+fn print_resume_instructions(release_state: &ReleaseState, state_file: Option<&std::path::Path>, config: &RuntimeConfig) {
+    use crate::state::ReleasePhase;
+
+    if let Some(path) = state_file {
+        config
+            .println(&format!("\n💾 Release state saved to {}", path.display()))
+            .expect("Failed to write to stdout");
+    }
+    config.println("\n⏹  Release aborted. To continue:").expect("Failed to write to stdout");
+    let draft = release_state
+        .github_state
+        .as_ref()
+        .and_then(|github| github.html_url.as_deref())
+        .filter(|_| release_state.current_phase >= ReleasePhase::GitHubRelease);
+    match draft {
+        None => config
+            .indent("Nothing was created on GitHub; run the release again to start over")
+            .expect("Failed to write to stdout"),
+        Some(url) => {
+            config.indent(&format!("Draft release: {}", url)).expect("Failed to write to stdout");
+            if release_state.current_phase >= ReleasePhase::Uploading {
+                config
+                    .indent("Every artifact was uploaded: re-run with --only github-publish to publish the draft")
+                    .expect("Failed to write to stdout");
+            } else {
+                config
+                    .indent("Re-run with --skip github-release to build and upload into this draft")
+                    .expect("Failed to write to stdout");
+            }
+            config
+                .indent("Or run the release again to replace the draft and start over")
+                .expect("Failed to write to stdout");
+        }
     }
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Write the release manifest to `--manifest-out`, if given, and keep it for
+/// the release report
+pub(super) fn write_manifest_copy(
+    manifest: &mut crate::manifest::ReleaseManifest,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    if let Some(path) = config.manifest_out() {
+        manifest.write(path)?;
+        config
+            .println(&format!("📄 Release manifest written to {}", path.display()))
+            .expect("Failed to write to stdout");
+    }
+    config.journal().set_manifest(manifest);
+    Ok(())
+}
+
+/// Print what `--quiet` keeps: the release URL, then the `--manifest-out` path
+pub(super) fn print_result(manifest: &crate::manifest::ReleaseManifest, config: &RuntimeConfig) {
+    config.result_println(&manifest.html_url);
+    if let Some(path) = config.manifest_out() {
+        config.result_println(&path.display().to_string());
+    }
+}
+
+/// Report a failure after publishing as a partial publish (exit code 12)
+pub(super) fn after_publish(published: bool, url: &str, error: ReleaseError) -> ReleaseError {
+    if !published {
+        return error;
+    }
+    ReleaseError::Cli(CliError::PartiallyPublished {
+        url: url.to_string(),
+        reason: error.to_string(),
+    })
 }
@@ -0,0 +1,130 @@
+//! Shell completion and man page generation from the built binary.
+//!
+//! Runs the commands configured in `[package.metadata.release.shell-integration]`
+//! and packs the output into a tarball laid out like an install prefix:
+//!
+//! ```text
+//! share/bash-completion/completions/<bin>
+//! share/zsh/site-functions/_<bin>
+//! share/fish/vendor_completions.d/<bin>.fish
+//! share/man/man1/<bin>.1
+//! ```
+//!
+//! The tarball is uploaded alongside the platform bundles so packagers and
+//! Homebrew formulas can install the files into their standard locations.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use std::path::{Path, PathBuf};
+
+use super::context::ReleasePhaseContext;
+
+/// Shells to generate completions for, with their install path under `share/`
+const COMPLETION_SHELLS: [(&str, &str); 3] = [
+    ("bash", "bash-completion/completions/{bin}"),
+    ("zsh", "zsh/site-functions/_{bin}"),
+    ("fish", "fish/vendor_completions.d/{bin}.fish"),
+];
+
+/// Generate shell integration files and pack them into a tarball
+///
+/// Returns `None` when nothing is configured.
+pub async fn generate_shell_integration(ctx: &ReleasePhaseContext<'_>) -> Result<Option<PathBuf>> {
+    let shell_integration = ctx.shell_integration;
+    if !shell_integration.is_enabled() {
+        return Ok(None);
+    }
+
+    let binary = built_binary_path(ctx.release_clone_path, ctx.binary_name);
+    let archive_name = format!(
+        "{}-{}-shell-integration",
+        ctx.binary_name, ctx.new_version
+    );
+    let artifacts_dir = ctx.release_clone_path.join("artifacts");
+    let staging = artifacts_dir.join(&archive_name);
+    let share = staging.join("share");
+
+    if let Some(args) = &shell_integration.completions {
+        for (shell, relative) in COMPLETION_SHELLS {
+            let args: Vec<String> = args.iter().map(|a| a.replace("{shell}", shell)).collect();
+            let output = run_binary(&binary, &args).await?;
+            write_file(&share.join(relative.replace("{bin}", ctx.binary_name)), &output)?;
+            ctx.config
+                .indent(&format!("✓ Generated {} completions", shell))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    if let Some(args) = &shell_integration.man {
+        let output = run_binary(&binary, args).await?;
+        write_file(
+            &share.join("man/man1").join(format!("{}.1", ctx.binary_name)),
+            &output,
+        )?;
+        ctx.config
+            .indent("✓ Generated man page")
+            .expect("Failed to write to stdout");
+    }
+
+    let archive = artifacts_dir.join(format!("{}.tar.gz", archive_name));
+    Exec::new("tar")
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&staging)
+        .arg("share")
+        .run()
+        .await?;
+
+    Ok(Some(archive))
+}
+
+/// Locate the host-runnable binary produced by the release build
+///
+/// macOS builds per target triple; the host architecture's build is used.
+pub(super) fn built_binary_path(clone_path: &Path, binary_name: &str) -> PathBuf {
+    let file_name = format!("{}{}", binary_name, std::env::consts::EXE_SUFFIX);
+
+    if cfg!(target_os = "macos") {
+        let host_target = format!("{}-apple-darwin", std::env::consts::ARCH);
+        let candidate = clone_path
+            .join("target")
+            .join(host_target)
+            .join("release")
+            .join(&file_name);
+        if candidate.exists() {
+            return candidate;
+        }
+    }
+
+    clone_path.join("target").join("release").join(file_name)
+}
+
+/// Run the built binary and capture stdout
+async fn run_binary(binary: &Path, args: &[String]) -> Result<Vec<u8>> {
+    let mut command = Exec::new(binary);
+    command.args(args);
+    let output = command.output().await?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: command.display(),
+            reason: format!(
+                "Expected output on stdout (exit code {:?}): {}",
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        }));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Write a generated file, creating parent directories
+fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
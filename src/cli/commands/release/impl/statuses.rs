@@ -0,0 +1,102 @@
+//! Commit statuses for the release phases (`--commit-status`).
+//!
+//! Each selectable phase reports under `release/<phase>` on the released
+//! commit: `pending` when it starts, `success` when it completes, is skipped
+//! or was completed by an earlier run, and `failure` with the error when the
+//! release stops in it. In GitHub Actions the statuses link to the run.
+//!
+//! Statuses are informational: failing to post one only warns.
+
+use crate::github::CommitState;
+use crate::state::ReleasePhase;
+use std::sync::Mutex;
+
+use super::context::ReleasePhaseContext;
+
+/// Statuses posted for one release run
+pub(super) struct PhaseStatuses {
+    /// Released commit, `None` when statuses are off
+    sha: Option<String>,
+    /// Link shown with each status
+    target_url: Option<String>,
+    /// Phase with a pending status, failed if the release stops
+    running: Mutex<Option<ReleasePhase>>,
+}
+
+impl PhaseStatuses {
+    /// Statuses for the commit checked out in the release clone
+    pub(super) async fn new(ctx: &ReleasePhaseContext<'_>) -> Self {
+        let sha = if ctx.config.commit_status() {
+            match super::adopt::head_commit(ctx.release_clone_path).await {
+                Ok(sha) => Some(sha),
+                Err(e) => {
+                    ctx.config
+                        .warning_println(&format!("⚠️  Not posting commit statuses: {}", e))
+                        .expect("Failed to write to stdout");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        Self {
+            sha,
+            target_url: actions_run_url(),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// `phase` started
+    pub(super) async fn start(&self, ctx: &ReleasePhaseContext<'_>, phase: ReleasePhase) {
+        *self.running.lock().unwrap_or_else(|e| e.into_inner()) = Some(phase);
+        self.post(ctx, phase, CommitState::Pending, "In progress").await;
+    }
+
+    /// `phase` completed, was skipped or was already done; `description` says which
+    pub(super) async fn finish(&self, ctx: &ReleasePhaseContext<'_>, phase: ReleasePhase, description: &str) {
+        {
+            let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+            if *running == Some(phase) {
+                *running = None;
+            }
+        }
+        self.post(ctx, phase, CommitState::Success, description).await;
+    }
+
+    /// The release stopped with `error` in the running phase
+    pub(super) async fn fail(&self, ctx: &ReleasePhaseContext<'_>, error: &crate::error::ReleaseError) {
+        let phase = self.running.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(phase) = phase {
+            let state = if matches!(error, crate::error::ReleaseError::Cli(crate::error::CliError::Aborted { .. })) {
+                CommitState::Error
+            } else {
+                CommitState::Failure
+            };
+            self.post(ctx, phase, state, &error.to_string()).await;
+        }
+    }
+
+    async fn post(&self, ctx: &ReleasePhaseContext<'_>, phase: ReleasePhase, state: CommitState, description: &str) {
+        let Some(sha) = &self.sha else {
+            return;
+        };
+        let context = format!("release/{}", phase.cli_name());
+        if let Err(e) = ctx
+            .github_manager
+            .set_commit_status(sha, &context, state, description, self.target_url.as_deref())
+            .await
+        {
+            ctx.config
+                .warning_println(&format!("⚠️  Failed to post commit status {}: {}", context, e))
+                .expect("Failed to write to stdout");
+        }
+    }
+}
+
+/// URL of the GitHub Actions run, when running in one
+fn actions_run_url() -> Option<String> {
+    let server = std::env::var("GITHUB_SERVER_URL").ok()?;
+    let repository = std::env::var("GITHUB_REPOSITORY").ok()?;
+    let run_id = std::env::var("GITHUB_RUN_ID").ok()?;
+    Some(format!("{}/{}/actions/runs/{}", server, repository, run_id))
+}
@@ -0,0 +1,136 @@
+//! Microsoft Store submission of the release's MSIX packages.
+//!
+//! Runs after the artifacts are uploaded to GitHub. The packages are added
+//! to the product's pending Partner Center submission (created from the last
+//! published one if there is none), uploaded, and the submission is committed
+//! for certification unless the store configuration sets `commit = false`.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::manifest::StoreSubmission;
+use crate::store::{PartnerCenterClient, StoreConfig, stored_zip};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::context::ReleasePhaseContext;
+
+/// Delay between status checks while a commit is being started
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Add `packages` to the product's submission and commit it
+pub(super) async fn submit_to_store(
+    ctx: &ReleasePhaseContext<'_>,
+    store: &StoreConfig,
+    packages: &[PathBuf],
+) -> Result<StoreSubmission> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let operation = format!("Microsoft Store submission for {}", store.app_id);
+    ctx.timeouts
+        .run(TimedPhase::Upload, &operation, ctx.config, submit(ctx, store, packages))
+        .await
+}
+
+async fn submit(
+    ctx: &ReleasePhaseContext<'_>,
+    store: &StoreConfig,
+    packages: &[PathBuf],
+) -> Result<StoreSubmission> {
+    let client = PartnerCenterClient::connect(ctx.env_config, &store.app_id).await?;
+    ctx.config
+        .verbose_println("   ✓ Partner Center authenticated")
+        .expect("Failed to write to stdout");
+
+    let (mut submission, created) = client.pending_or_new_submission().await?;
+    let submission_id = submission_field(&submission, "id")?;
+    let upload_url = submission_field(&submission, "fileUploadUrl")?;
+    ctx.config
+        .indent(&format!(
+            "{} submission {}",
+            if created { "Created" } else { "Using pending" },
+            submission_id
+        ))
+        .expect("Failed to write to stdout");
+
+    let mut entries = Vec::with_capacity(packages.len());
+    for path in packages {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        entries.push((name, std::fs::read(path)?));
+    }
+    let names: Vec<String> = entries.iter().map(|(name, _)| name.clone()).collect();
+
+    // Packages of earlier versions stay; the Store offers each device the newest applicable one
+    let listed = submission
+        .get_mut("applicationPackages")
+        .and_then(Value::as_array_mut)
+        .ok_or_else(|| store_error(format!("submission {} has no applicationPackages", submission_id)))?;
+    listed.retain(|package| {
+        package
+            .get("fileName")
+            .and_then(Value::as_str)
+            .is_none_or(|name| !names.iter().any(|n| n == name))
+    });
+    for name in &names {
+        listed.push(json!({ "fileName": name, "fileStatus": "PendingUpload" }));
+    }
+
+    client.update_submission(&submission_id, &submission).await?;
+    client.upload_packages(&upload_url, stored_zip(&entries)).await?;
+    for name in &names {
+        ctx.config
+            .indent(&format!("✓ Uploaded {}", name))
+            .expect("Failed to write to stdout");
+    }
+
+    let status = if store.commit {
+        client.commit(&submission_id).await?;
+        loop {
+            let status = client.status(&submission_id).await?;
+            match status.status.as_str() {
+                "CommitStarted" => tokio::time::sleep(STATUS_POLL_INTERVAL).await,
+                "CommitFailed" => {
+                    return Err(store_error(format!(
+                        "commit of submission {} failed: {}",
+                        submission_id,
+                        status.errors.join("; ")
+                    )));
+                }
+                _ => break status.status,
+            }
+        }
+    } else {
+        ctx.config
+            .warning_println(&format!(
+                "⚠️  Submission {} left pending (store commit = false); commit it in Partner Center",
+                submission_id
+            ))
+            .expect("Failed to write to stdout");
+        "PendingCommit".to_string()
+    };
+
+    Ok(StoreSubmission {
+        app_id: store.app_id.clone(),
+        submission_id,
+        packages: names,
+        status,
+        submitted_at: chrono::Utc::now(),
+    })
+}
+
+fn submission_field(submission: &Value, key: &str) -> Result<String> {
+    submission
+        .get(key)
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or_else(|| store_error(format!("submission response has no {}", key)))
+}
+
+fn store_error(reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "Partner Center".to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,144 @@
+//! Release tags on GitHub under tag protection rulesets.
+//!
+//! With `--tag-via git` (the default) tags are pushed from the release clone;
+//! a refused push is reported with the rulesets that protect the tag. With
+//! `--tag-via api` the tag ref is written through the REST API, which lets a
+//! token on a ruleset's bypass list create or move a protected tag.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::git_auth::GitAuth;
+use crate::github::{GitHubReleaseManager, TagMethod, TagOperation};
+use std::path::Path;
+
+use super::context::ReleasePhaseContext;
+
+/// Point `tag` at `commit` on GitHub, moving it if it exists
+pub(super) async fn move_tag(
+    github_manager: &GitHubReleaseManager,
+    git_auth: &GitAuth,
+    repo: &Path,
+    remote_url: &str,
+    tag: &str,
+    commit: &str,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    let written = match config.tag_via() {
+        TagMethod::Api => github_manager.write_tag(tag, commit).await,
+        TagMethod::Git => git_auth
+            .git()
+            .args(["push", "--force", remote_url, &format!("refs/tags/{0}:refs/tags/{0}", tag)])
+            .current_dir(repo)
+            .run()
+            .await
+            .map(drop),
+    };
+    match written {
+        Ok(()) => Ok(()),
+        Err(e) => Err(explain_refusal(github_manager, tag, TagOperation::Move, config, e).await),
+    }
+}
+
+/// Check the release tag on GitHub before the draft release refers to it
+///
+/// A tag missing from GitHub is created when the release is published. With
+/// `--tag-via api` it is created now instead, at the commit the release clone
+/// has for it (or its HEAD); otherwise rulesets that would refuse creating it
+/// are reported up front.
+pub(super) async fn check_release_tag(ctx: &ReleasePhaseContext<'_>, tag: &str) -> Result<()> {
+    if ctx.github_manager.remote_tag(tag).await?.is_some() {
+        return Ok(());
+    }
+
+    match ctx.config.tag_via() {
+        TagMethod::Api => {
+            let commit = local_commit(ctx.release_clone_path, tag).await?;
+            if let Err(e) = ctx.github_manager.write_tag(tag, &commit).await {
+                return Err(explain_refusal(ctx.github_manager, tag, TagOperation::Create, ctx.config, e).await);
+            }
+            ctx.config
+                .success_println(&format!(
+                    "✓ Created tag {} at {} through the GitHub API",
+                    tag,
+                    &commit[..commit.len().min(12)]
+                ))
+                .expect("Failed to write to stdout");
+        }
+        TagMethod::Git => {
+            ctx.config
+                .warning_println(&format!(
+                    "⚠️  Tag {} is not on GitHub yet; it will be created from the default branch on publish",
+                    tag
+                ))
+                .expect("Failed to write to stdout");
+            // Best effort: the token may not be allowed to read rulesets
+            let rulesets = ctx
+                .github_manager
+                .tag_rulesets(tag, TagOperation::Create)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            for ruleset in rulesets.iter().filter(|ruleset| !ruleset.can_bypass) {
+                ctx.config
+                    .warning_println(&format!(
+                        "⚠️  {} restricts creating {}; publishing may be refused (create the tag with --tag-via api using a token on its bypass list)",
+                        ruleset, tag
+                    ))
+                    .expect("Failed to write to stdout");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Commit `tag` names in `repo`, or its HEAD when the tag is not there
+async fn local_commit(repo: &Path, tag: &str) -> Result<String> {
+    let rev_parse = |rev: String| async move {
+        crate::exec::Exec::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &rev])
+            .current_dir(repo)
+            .stdout()
+            .await
+            .map(|sha| sha.trim().to_string())
+    };
+    match rev_parse(format!("refs/tags/{}^{{commit}}", tag)).await {
+        Ok(commit) => Ok(commit),
+        Err(_) => rev_parse("HEAD".to_string()).await,
+    }
+}
+
+/// Add the rulesets protecting `tag` to a failed tag write
+///
+/// Returns `error` unchanged when no readable ruleset restricts `operation`.
+async fn explain_refusal(
+    github_manager: &GitHubReleaseManager,
+    tag: &str,
+    operation: TagOperation,
+    config: &RuntimeConfig,
+    error: ReleaseError,
+) -> ReleaseError {
+    let rulesets = match github_manager.tag_rulesets(tag, operation).await {
+        Ok(Some(rulesets)) if !rulesets.is_empty() => rulesets,
+        _ => return error,
+    };
+    let protected_by = rulesets
+        .iter()
+        .map(|ruleset| {
+            if ruleset.can_bypass {
+                ruleset.to_string()
+            } else {
+                format!("{}, which the token cannot bypass", ruleset)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    let hint = match config.tag_via() {
+        TagMethod::Git => "write it through the GitHub API with --tag-via api, using a token on the bypass list",
+        TagMethod::Api => "add the token's user or app to the ruleset's bypass list",
+    };
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: format!("tag {}", tag),
+        reason: format!("{}. The tag is protected by {}; {}", error, protected_by, hint),
+    })
+}
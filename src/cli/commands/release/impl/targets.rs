@@ -0,0 +1,229 @@
+//! Per-target build matrix from `[[package.metadata.release.targets]]`.
+//!
+//! Each entry (target triple × features × cargo profile) is compiled with
+//! `cargo` when the host can build it natively, otherwise with `cross` in
+//! Docker, and released as
+//!
+//! ```text
+//! <bin>-<version>-<target>[-<name>].tar.gz   # <bin>-<version>-<target>[-<name>]/{<bin>, README*, LICENSE*}
+//! ```
+//!
+//! `universal-apple-darwin` builds both macOS architectures and joins them
+//! with `lipo`. The helpers here are shared with the BSD targets.
+
+use crate::compression::ArtifactKind;
+use crate::exec::Exec;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::{BuildTarget, Builder};
+use std::path::{Path, PathBuf};
+
+use super::compress::compression_variants;
+use super::context::ReleasePhaseContext;
+
+/// Target name for a fat macOS binary
+const UNIVERSAL_DARWIN: &str = "universal-apple-darwin";
+
+/// Build every matrix entry
+///
+/// Returns `(label, artifacts)` per entry, in configuration order.
+pub async fn build_matrix_artifacts(ctx: &ReleasePhaseContext<'_>) -> Result<Vec<(String, Vec<PathBuf>)>> {
+    let mut built = Vec::new();
+
+    for build in ctx.targets {
+        let label = build.label();
+        ctx.config
+            .verbose_println(&format!("\n   Building {} (tarball)...", label))
+            .expect("Failed to write to stdout");
+
+        let binary = if build.target == UNIVERSAL_DARWIN {
+            build_universal(ctx, build).await?
+        } else {
+            let builder = build.builder.unwrap_or_else(|| default_builder(&build.target));
+            compile(ctx, &build.target, builder, Some(build)).await?
+        };
+
+        let name = format!("{}-{}-{}", ctx.binary_name, ctx.new_version, label);
+        let staging = stage(ctx, &name, &binary)?;
+        let tarball = tarball(ctx, &name, &staging).await?;
+        built.push((label, compression_variants(ctx, ArtifactKind::Targets, vec![tarball]).await?));
+    }
+
+    if !built.is_empty() {
+        ctx.config
+            .success_println(&format!("✓ Built {} matrix target(s)", built.len()))
+            .expect("Failed to write to stdout");
+    }
+
+    Ok(built)
+}
+
+/// `cargo` when the host runs the target's OS and architecture, else `cross`
+///
+/// macOS hosts build both Apple architectures natively.
+pub(super) fn default_builder(target: &str) -> Builder {
+    let os = match std::env::consts::OS {
+        "macos" => "-apple-darwin",
+        "linux" => "-linux-",
+        "windows" => "-windows-",
+        other => other,
+    };
+    let same_os = target.contains(os) || target.ends_with(&format!("-{}", std::env::consts::OS));
+    let same_arch = target.starts_with(std::env::consts::ARCH) || std::env::consts::OS == "macos";
+    if same_os && same_arch {
+        Builder::Cargo
+    } else {
+        Builder::Cross
+    }
+}
+
+/// Compile `target` with `builder` and return the binary's path
+///
+/// `options` supplies features and the cargo profile; `None` builds the
+/// default features with the `release` profile.
+pub(super) async fn compile(
+    ctx: &ReleasePhaseContext<'_>,
+    target: &str,
+    builder: Builder,
+    options: Option<&BuildTarget>,
+) -> Result<PathBuf> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let profile = options.map_or("release", |o| o.profile.as_str());
+    let mut args = vec![
+        "build".to_string(),
+        "--profile".to_string(),
+        profile.to_string(),
+        "--target".to_string(),
+        target.to_string(),
+    ];
+    if let Some(options) = options {
+        if options.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if !options.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(options.features.join(","));
+        }
+    }
+
+    let tool = builder.command();
+    let command = format!("{} {}", tool, args.join(" "));
+    if builder == Builder::Cross && which::which(tool).is_err() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command,
+            reason: "cross is required to build this target (cargo install cross; Docker must be running)".to_string(),
+        }));
+    }
+    let output = ctx
+        .timeouts
+        .run(
+            TimedPhase::Build,
+            &command,
+            ctx.config,
+            Exec::new(tool)
+                .args(&args)
+                .current_dir(ctx.release_clone_path)
+                .output_watched(ctx.config),
+        )
+        .await?;
+
+    if !output.status.success() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command,
+            reason: String::from_utf8_lossy(&output.stderr).to_string(),
+        }));
+    }
+
+    // `dev` builds land in `debug`; every other profile in a directory of its name
+    let profile_dir = if profile == "dev" { "debug" } else { profile };
+    let executable = if target.contains("-windows") {
+        format!("{}.exe", ctx.binary_name)
+    } else {
+        ctx.binary_name.to_string()
+    };
+    let binary = ctx
+        .release_clone_path
+        .join("target")
+        .join(target)
+        .join(profile_dir)
+        .join(executable);
+    if !binary.is_file() {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command,
+            reason: format!("Build succeeded but {} was not produced", binary.display()),
+        }));
+    }
+
+    Ok(binary)
+}
+
+/// Build both macOS architectures and join them with `lipo`
+async fn build_universal(ctx: &ReleasePhaseContext<'_>, build: &BuildTarget) -> Result<PathBuf> {
+    if std::env::consts::OS != "macos" {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("{} can only be built on a macOS host", UNIVERSAL_DARWIN),
+        }));
+    }
+
+    let arm = compile(ctx, "aarch64-apple-darwin", Builder::Cargo, Some(build)).await?;
+    let intel = compile(ctx, "x86_64-apple-darwin", Builder::Cargo, Some(build)).await?;
+
+    let dir = ctx.release_clone_path.join("target").join(UNIVERSAL_DARWIN).join(&build.profile);
+    std::fs::create_dir_all(&dir)?;
+    let universal = dir.join(ctx.binary_name);
+    Exec::new("lipo")
+        .arg("-create")
+        .arg("-output")
+        .arg(&universal)
+        .arg(&arm)
+        .arg(&intel)
+        .run_watched(ctx.config)
+        .await?;
+
+    Ok(universal)
+}
+
+/// Fresh `artifacts/<name>/` holding the binary and the repository's docs
+pub(super) fn stage(ctx: &ReleasePhaseContext<'_>, name: &str, binary: &Path) -> Result<PathBuf> {
+    let staging = ctx.release_clone_path.join("artifacts").join(name);
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+    let file_name = binary.file_name().unwrap_or_else(|| std::ffi::OsStr::new(ctx.binary_name));
+    std::fs::copy(binary, staging.join(file_name))?;
+    copy_docs(ctx.release_clone_path, &staging)?;
+    Ok(staging)
+}
+
+/// Pack a staging directory into `artifacts/<name>.tar.gz`
+pub(super) async fn tarball(ctx: &ReleasePhaseContext<'_>, name: &str, staging: &Path) -> Result<PathBuf> {
+    let artifacts_dir = staging.parent().unwrap_or(staging);
+    let tarball = artifacts_dir.join(format!("{}.tar.gz", name));
+    Exec::new("tar")
+        .arg("-czf")
+        .arg(&tarball)
+        .arg("-C")
+        .arg(artifacts_dir)
+        .arg(name)
+        .run_watched(ctx.config)
+        .await?;
+    ctx.config
+        .indent(&format!("✓ {}.tar.gz", name))
+        .expect("Failed to write to stdout");
+    Ok(tarball)
+}
+
+/// Copy README and LICENSE files from the repository root into the tarball
+fn copy_docs(repo: &Path, staging: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(repo)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_uppercase();
+        if (name.starts_with("README") || name.starts_with("LICENSE") || name.starts_with("COPYING"))
+            && entry.file_type()?.is_file()
+        {
+            std::fs::copy(entry.path(), staging.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
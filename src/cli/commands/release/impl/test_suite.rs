@@ -0,0 +1,146 @@
+//! Test suite gate run in the release clone before anything is released.
+//!
+//! Runs the configured command (`cargo test --workspace` by default) in the
+//! clone that is about to be released, so a failing suite stops the release
+//! before a GitHub release exists. Counts and failed test names come from the
+//! JUnit XML the command writes, if configured, otherwise from the libtest
+//! `test result:` lines in its output, and are kept for the release report.
+
+use crate::cli::TestSummary;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::metadata::TestGate;
+
+use super::context::ReleasePhaseContext;
+
+/// Failed test names shown in the error message
+const SHOWN_FAILURES: usize = 10;
+
+/// Trailing output lines shown when the suite fails
+const SHOWN_OUTPUT_LINES: usize = 20;
+
+/// Run the test suite and fail if any test fails
+pub(super) async fn run_test_suite(ctx: &ReleasePhaseContext<'_>, gate: &TestGate) -> Result<TestSummary> {
+    use crate::cli::timeouts::TimedPhase;
+
+    let command = gate.command.join(" ");
+    let (success, output) = ctx
+        .timeouts
+        .run(TimedPhase::Test, &command, ctx.config, run_command(ctx, gate))
+        .await?;
+
+    let junit = gate.junit.as_ref().map(|path| ctx.release_clone_path.join(path));
+    let mut summary = match junit.as_ref().and_then(|path| std::fs::read_to_string(path).ok()) {
+        Some(xml) => summary_from_junit(&xml, &command, gate.junit.as_deref().unwrap_or(std::path::Path::new(""))),
+        None => {
+            if let Some(path) = &junit {
+                ctx.config
+                    .warning_println(&format!("⚠️  JUnit report not found at {}; counting from the test output", path.display()))
+                    .expect("Failed to write to stdout");
+            }
+            summary_from_output(&output, &command)
+        }
+    };
+    // A failing command without parseable failures (e.g. a compile error) still fails the gate
+    if !success && summary.failed == 0 {
+        summary.failed = 1;
+        summary.failures.push(format!("`{}` exited with an error", command));
+    }
+    ctx.config.journal().set_tests(summary.clone());
+
+    if summary.failed > 0 {
+        let mut shown = summary.failures.iter().take(SHOWN_FAILURES).cloned().collect::<Vec<_>>().join(", ");
+        if summary.failures.len() > SHOWN_FAILURES {
+            shown.push_str(&format!(" and {} more", summary.failures.len() - SHOWN_FAILURES));
+        }
+        // Verbose mode already echoed everything
+        if !ctx.config.is_verbose() {
+            for line in &output[output.len().saturating_sub(SHOWN_OUTPUT_LINES)..] {
+                ctx.config.indent(line).expect("Failed to write to stdout");
+            }
+        }
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command,
+            reason: format!("{} test(s) failed: {}", summary.failed, shown),
+        }));
+    }
+
+    Ok(summary)
+}
+
+/// Run the command in the clone, echoing its output in verbose mode
+///
+/// Returns whether it succeeded and its combined output lines.
+async fn run_command(ctx: &ReleasePhaseContext<'_>, gate: &TestGate) -> Result<(bool, Vec<String>)> {
+    let mut output = Vec::new();
+    let status = crate::exec::Exec::new(&gate.command[0])
+        .args(&gate.command[1..])
+        .current_dir(ctx.release_clone_path)
+        .stream(ctx.config, |_, line| {
+            ctx.config.verbose_println(&format!("   {}", line)).expect("Failed to write to stdout");
+            output.push(line.to_string());
+        })
+        .await?;
+    Ok((status.success(), output))
+}
+
+/// Counts from libtest's `test result:` lines, failures from `---- name stdout ----`
+fn summary_from_output(output: &[String], command: &str) -> TestSummary {
+    let result = regex::Regex::new(r"test result: \w+\. (\d+) passed; (\d+) failed; (\d+) ignored").expect("valid regex");
+    let failure = regex::Regex::new(r"^---- (.+) stdout ----$").expect("valid regex");
+
+    let mut summary = TestSummary {
+        command: command.to_string(),
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failures: Vec::new(),
+        source: "test output".to_string(),
+    };
+    for line in output {
+        if let Some(caps) = result.captures(line) {
+            let count = |i: usize| caps[i].parse::<u64>().unwrap_or(0);
+            summary.passed += count(1);
+            summary.failed += count(2);
+            summary.ignored += count(3);
+        } else if let Some(caps) = failure.captures(line) {
+            summary.failures.push(caps[1].to_string());
+        }
+    }
+    summary
+}
+
+/// Counts and failures from a JUnit XML report
+fn summary_from_junit(xml: &str, command: &str, path: &std::path::Path) -> TestSummary {
+    let testcase = regex::Regex::new(r#"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)"#).expect("valid regex");
+    let attribute = |attributes: &str, name: &str| {
+        regex::Regex::new(&format!(r#"\b{}="([^"]*)""#, name))
+            .expect("valid regex")
+            .captures(attributes)
+            .map(|caps| caps[1].to_string())
+    };
+
+    let mut summary = TestSummary {
+        command: command.to_string(),
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failures: Vec::new(),
+        source: path.display().to_string(),
+    };
+    for caps in testcase.captures_iter(xml) {
+        let body = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+        if body.contains("<failure") || body.contains("<error") {
+            let name = attribute(&caps[1], "name").unwrap_or_default();
+            summary.failures.push(match attribute(&caps[1], "classname") {
+                Some(class) if !class.is_empty() => format!("{}::{}", class, name),
+                _ => name,
+            });
+            summary.failed += 1;
+        } else if body.contains("<skipped") {
+            summary.ignored += 1;
+        } else {
+            summary.passed += 1;
+        }
+    }
+    summary
+}
@@ -2,62 +2,273 @@
 //!
 //! Handles the complete release workflow by coordinating all modules
 //! in an isolated temporary clone to prevent modifications to the user's working directory.
+//! The `rebuild` command reuses the same flow with the clone checked out at an existing tag,
+//! and so does `--from-tag`, which releases a tag pushed by CI instead of main.
 
 mod r#impl;
 
-use crate::cli::{Args, RuntimeConfig};
+use crate::cli::{Args, Command, ReleaseChannel, RuntimeConfig};
 use crate::error::{CliError, ReleaseError, Result};
 use crate::EnvConfig;
-use kodegen_config::GIT_STATUS;
 
-/// Execute release command
+/// Execute release command (or `rebuild` of an existing tag)
 pub(super) async fn execute_release(
     args: &Args,
     config: &RuntimeConfig,
     env_config: &EnvConfig,
 ) -> Result<i32> {
+    let rebuild_tag = match &args.command {
+        Some(Command::Rebuild { tag, .. }) => Some(tag.as_str()),
+        _ => None,
+    };
+    let nightly = args.channel == ReleaseChannel::Nightly;
+    let from_tag = args.from_tag.as_deref();
+
     // 1. Parse and resolve repository source
+    config.group("Resolve repository").expect("Failed to write to stdout");
     config.println("📦 Resolving repository source...").expect("Failed to write to stdout");
-    let source_parsed = crate::source::RepositorySource::parse(&args.source)?;
-    let resolved = source_parsed.resolve().await?;
+    let source_parsed = crate::source::RepositorySource::parse(args.source())?;
+    let resolved = source_parsed
+        .resolve(&crate::git_auth::GitAuth::resolve(args.git_auth, env_config)?)
+        .await?;
     config.verbose_println(&format!("✓ Repository: {}", resolved.path.display())).expect("Failed to write to stdout");
 
+    // Layer a local repository's .env file under the shell/process environment,
+    // then check the environment before doing any real work. CI mode takes
+    // credentials from the process environment only.
+    let mut env_config = env_config.clone();
+    if !config.is_ci()
+        && let Some(dotenv) = resolved.dotenv_path()
+    {
+        let dotenv_count = env_config.load_dotenv(&dotenv)?;
+        if dotenv_count > 0 {
+            config.verbose_println(&format!("✓ Loaded {} variable(s) from .env", dotenv_count)).expect("Failed to write to stdout");
+        }
+    }
+    for warning in env_config.validate()? {
+        config.warning_println(&format!("⚠️  {}", warning)).expect("Failed to write to stdout");
+    }
+    env_config.require(crate::env::EnvRequirement::GitHubRelease)?;
+    // Again with the .env layer, which may hold the token
+    let git_auth = crate::git_auth::GitAuth::resolve(args.git_auth, &env_config)?;
+
+    // Profile overrides from release.toml (repository, draft, signing, prerelease)
+    let profile = match &args.profile {
+        Some(name) => {
+            let profile = crate::profile::ReleaseProfile::load(&resolved.path, name)?;
+            config.println(&format!("🎭 Profile: {}", profile.describe())).expect("Failed to write to stdout");
+            profile
+        }
+        None => crate::profile::ReleaseProfile::load_default(&resolved.path)?,
+    };
+    // Freeze windows from release.toml
+    check_freeze(
+        &resolved.path,
+        args.override_freeze.as_deref(),
+        rebuild_tag.is_some(),
+        chrono::Utc::now(),
+        config,
+    )?;
+    if nightly && (profile.repository.is_some() || profile.draft) {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: "--channel nightly only supports profiles that set sign; the nightly tag and release live in the source repository".to_string(),
+        }));
+    }
+    if !profile.sign {
+        if args.import_p12.is_some() {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "--import-p12 cannot be combined with a profile that sets sign = false".to_string(),
+            }));
+        }
+        env_config.disable_signing();
+        config.warning_println("⚠️  Signing disabled by profile: bundles will be unsigned and not notarized").expect("Failed to write to stdout");
+    }
+
+    // Signing credentials: an explicitly imported .p12, otherwise whatever the
+    // login keychain already holds (outside CI)
+    let keychain = match &args.import_p12 {
+        Some(p12) => {
+            config.println("🔑 Importing signing certificate into a temporary keychain...").expect("Failed to write to stdout");
+            let password = env_config.get("APPLE_CERTIFICATE_PASSWORD").unwrap_or_default();
+            let keychain = crate::env::TemporaryKeychain::import(p12, &password).await?;
+            config.verbose_println(&format!("✓ Signing identity: {}", keychain.signing_identity())).expect("Failed to write to stdout");
+            env_config.set_signing_identity(keychain.signing_identity());
+            Some(keychain)
+        }
+        None => {
+            if !config.is_ci() && profile.sign {
+                let keychain_count = env_config.load_keychain();
+                if keychain_count > 0 {
+                    config.verbose_println(&format!("✓ Loaded {} credential(s) from the keychain", keychain_count)).expect("Failed to write to stdout");
+                }
+            }
+            None
+        }
+    };
+    let env_config = &env_config;
+
     // 2. Extract metadata from single Cargo.toml
     let cargo_toml = resolved.path.join("Cargo.toml");
-    let manifest = crate::metadata::load_manifest(&cargo_toml)?;
+    let manifest = crate::metadata::load_manifest(&cargo_toml, config).await?;
     let metadata = manifest.metadata;
+    // Only a published stable release makes older release branches obsolete
+    let keep_release_branches = metadata
+        .retention
+        .keep_release_branches
+        .filter(|_| rebuild_tag.is_none() && !nightly && !profile.draft);
     let binary_name = manifest.binary_name;
 
     config.verbose_println(&format!("✓ Package: {}", metadata.name)).expect("Failed to write to stdout");
-    config.verbose_println(&format!("✓ Binary: {}", binary_name)).expect("Failed to write to stdout");
+    match &binary_name {
+        Some(binary) => config.verbose_println(&format!("✓ Binary: {}", binary)).expect("Failed to write to stdout"),
+        None => {
+            // Nothing to build, so nothing to snapshot or rebuild
+            if nightly || rebuild_tag.is_some() {
+                return Err(library_only_error(&metadata.name));
+            }
+            config.println(&format!("📚 {} is a library crate: skipping build, bundles and artifact uploads", metadata.name)).expect("Failed to write to stdout");
+        }
+    }
+
+    // Broken icons would otherwise only fail the bundler after the build
+    if binary_name.is_some() {
+        for warning in crate::icons::check_icons(&resolved.path)? {
+            config.warning_println(&format!("⚠️  Bundle icon {}", warning)).expect("Failed to write to stdout");
+        }
+    }
+
+    // Store credentials are checked now rather than after bundling
+    let stable_binary = binary_name.is_some() && !nightly && rebuild_tag.is_none();
+    if stable_binary && metadata.store.is_some() && !config.skip_store_submission() {
+        env_config.require(crate::env::EnvRequirement::MicrosoftStore)?;
+    }
+    if stable_binary && !config.skip_mirrors() && metadata.mirrors.iter().any(|m| m.is_s3()) {
+        env_config.require(crate::env::EnvRequirement::S3Mirror)?;
+    }
+    if stable_binary && !config.skip_mirrors() {
+        let missing: Vec<String> = metadata
+            .mirrors
+            .iter()
+            .filter_map(|m| m.token_env())
+            .filter(|name| !env_config.is_set(name))
+            .map(String::from)
+            .collect();
+        if !missing.is_empty() {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("Artifact mirror token(s) not set: {}; use --skip-mirrors to release without them", missing.join(", ")),
+            }));
+        }
+    }
+    if binary_name.is_some()
+        && let Some(env) = metadata.encryption.as_ref().and_then(|e| e.passphrase_env())
+    {
+        crate::encryption::passphrase(env_config, env)?;
+    }
+    if stable_binary && let Some(mas) = &metadata.mac_app_store && !config.skip_mac_app_store() {
+        if !profile.sign {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "The Mac App Store package must be signed; use --skip-mac-app-store with a profile that sets sign = false".to_string(),
+            }));
+        }
+        env_config.require(crate::env::EnvRequirement::MacAppStore)?;
+        let missing: Vec<String> = ["APPLE_API_KEY", "APPLE_API_ISSUER"]
+            .into_iter()
+            .filter(|name| mas.upload && !env_config.is_set(name))
+            .map(String::from)
+            .collect();
+        if !missing.is_empty() {
+            return Err(crate::error::EnvError::MissingVariables {
+                names: missing,
+                required_for: "App Store Connect upload".to_string(),
+            }
+            .into());
+        }
+    }
 
     // 3. Validation - git status check
+    config.group("Validate repository").expect("Failed to write to stdout");
     config.println("🔍 Validating repository...").expect("Failed to write to stdout");
-    let git_status = std::process::Command::new("git")
+    let git_status = crate::exec::Exec::new("git")
         .args(["status", "--porcelain"])
         .current_dir(&resolved.path)
-        .output()
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: GIT_STATUS.to_string(),
-                reason: e.to_string(),
-            })
-        })?;
+        .output_blocking()?;
     
-    if !git_status.stdout.is_empty() {
-        config.warning_println("⚠️  Working directory has uncommitted changes").expect("Failed to write to stdout");
-        config.warning_println("   This may cause issues with the release process").expect("Failed to write to stdout");
+    // Local changes don't matter when building a tag, and are the point of --in-place
+    let in_place = args.in_place && !resolved.is_temp;
+    if !git_status.stdout.is_empty() && rebuild_tag.is_none() && from_tag.is_none() {
+        if in_place {
+            config.warning_println("⚠️  Releasing uncommitted changes in place").expect("Failed to write to stdout");
+        } else {
+            config.soft_failure("⚠️  Working directory has uncommitted changes")?;
+            config.warning_println("   This may cause issues with the release process").expect("Failed to write to stdout");
+        }
     }
 
-    // 4. Create temp clone for isolated execution
-    config.println("📁 Creating temporary clone...").expect("Failed to write to stdout");
-    let temp_dir = if resolved.is_temp {
+    // Workspace roots: report patterns that would break `cargo publish`
+    // (nightlies are never published to crates.io)
+    if rebuild_tag.is_none() && !nightly && crate::workspace::WorkspaceInfo::is_workspace_root(&resolved.path)? {
+        check_publish_blockers(&resolved.path, config)?;
+    }
+
+    // Pick the GitHub repository from the source's remotes
+    let mut github_remote = super::github_remote::detect_github_repo(&resolved.path, &args.remotes).await?;
+    if let Some(url) = git_auth.rewrite_url(&github_remote.url, &github_remote.owner, &github_remote.repo) {
+        config.verbose_println("ℹ️  No SSH key available; using HTTPS with the GitHub token for git").expect("Failed to write to stdout");
+        github_remote.url = url;
+    }
+    config.verbose_println(&format!("✓ GitHub: {}/{} (remote {})", github_remote.owner, github_remote.repo, github_remote.remote)).expect("Failed to write to stdout");
+
+    // The profile's repository receives the release; the source is still
+    // cloned from the detected remote
+    let release_remote = match &profile.repository {
+        Some((owner, repo)) => {
+            config.println(&format!("✓ Releasing to {}/{} (profile)", owner, repo)).expect("Failed to write to stdout");
+            super::github_remote::GitHubRemote { owner: owner.clone(), repo: repo.clone(), ..github_remote.clone() }
+        }
+        None => github_remote.clone(),
+    };
+
+    // A local checkout must agree with the branch the release is cloned from
+    if !resolved.is_temp && rebuild_tag.is_none() && from_tag.is_none() {
+        super::remote_sync::check_remote_divergence(&resolved.path, &github_remote, &git_auth, args.allow_diverged, in_place, config).await?;
+    }
+
+    // Docs still naming the previous version would ship with the release
+    // (the previous tag is only found with the history since it); a tag
+    // release checks the files at its tag instead
+    let tag_pattern = metadata.version_scheme.tag_pattern();
+    if !nightly && rebuild_tag.is_none() && from_tag.is_none() && !metadata.version_files.is_empty() {
+        super::history::ensure_full_history(&resolved.path, &github_remote.url, &tag_pattern, &git_auth, config).await?;
+        check_version_references(&resolved.path, &metadata, config)?;
+    }
+
+    // Fail early if the clone, build and bundles will not fit on disk
+    let work_dir = (in_place || resolved.is_temp).then_some(resolved.path.as_path());
+    r#impl::check_disk_space(&resolved.path, work_dir, config, env_config)?;
+
+    // 4. Create temp clone for isolated execution (or use the working repository)
+    crate::cli::abort::check("temp clone")?;
+    if args.in_place && resolved.is_temp {
+        config.verbose_println("ℹ️  Source is already a temporary clone; --in-place has no effect").expect("Failed to write to stdout");
+    }
+    let temp_dir = if in_place {
+        prepare_in_place(&resolved.path, config)?;
+        resolved.path.clone()
+    } else if resolved.is_temp {
+        config.println("📁 Creating temporary clone...").expect("Failed to write to stdout");
         resolved.path.clone()
     } else {
-        super::temp_clone::clone_main_to_temp_for_release(&resolved.path).await?
+        config.println("📁 Creating temporary clone...").expect("Failed to write to stdout");
+        super::temp_clone::clone_main_to_temp_for_release(&github_remote.url, Some(&resolved.path), &git_auth, config).await?
     };
     let temp_dir_pathbuf = temp_dir.to_path_buf();
 
+    // Release notes list the commits since the previous tag; a clone of a
+    // shallow checkout is shallow too
+    if !nightly && rebuild_tag.is_none() {
+        super::history::ensure_full_history(&temp_dir_pathbuf, &github_remote.url, &tag_pattern, &git_auth, config).await?;
+    }
+
     // Clean up any stale tracking from crashed previous releases
     match super::temp_clone::cleanup_stale_tracking() {
         Ok(count) if count > 0 => {
@@ -65,22 +276,44 @@ pub(super) async fn execute_release(
         }
         Ok(_) => {}, // No stale releases
         Err(e) => {
-            config.verbose_println(&format!("⚠ Warning: Failed to clean stale tracking: {}", e)).expect("Failed to write to stdout");
-            // Non-fatal - continue with release
+            // Non-fatal - continue with release (fatal in CI mode)
+            config.soft_failure(&format!("⚠ Failed to clean stale tracking: {}", e))?;
         }
     }
 
     // 5. Execute release in temp
-    let result = r#impl::perform_release_single_repo(
-        &temp_dir_pathbuf,
-        metadata,
-        binary_name,
-        config,
-        env_config,
-    ).await;
+    let result = match rebuild_tag {
+        Some(tag) => rebuild_in_temp(&temp_dir_pathbuf, tag, &release_remote, &git_auth, config, env_config).await,
+        None if nightly => {
+            r#impl::perform_nightly_single_repo(
+                &temp_dir_pathbuf,
+                metadata,
+                binary_name.unwrap_or_default(),
+                &release_remote,
+                &git_auth,
+                config,
+                env_config,
+            ).await
+        }
+        None => match from_tag {
+            Some(tag) => release_tag_in_temp(&temp_dir_pathbuf, tag, &release_remote, &profile, &git_auth, config, env_config).await,
+            None => {
+                r#impl::perform_release_single_repo(
+                    &temp_dir_pathbuf,
+                    metadata,
+                    binary_name,
+                    &release_remote,
+                    &profile,
+                    config,
+                    env_config,
+                ).await
+            }
+        },
+    };
 
-    // 6. Cleanup temp directory
-    if !resolved.is_temp {
+    // 6. Cleanup temp directory (never the working repository)
+    config.group("Cleanup").expect("Failed to write to stdout");
+    if !resolved.is_temp && !in_place {
         match std::fs::remove_dir_all(&temp_dir_pathbuf) {
             Ok(()) => {
                 config.verbose_println("✅ Temp clone cleaned up").expect("Failed to write to stdout");
@@ -103,5 +336,338 @@ pub(super) async fn execute_release(
         }
     }
 
+    if let Some(keychain) = keychain {
+        match keychain.cleanup().await {
+            Ok(()) => config.verbose_println("✅ Temporary keychain removed").expect("Failed to write to stdout"),
+            Err(e) => config.warning_println(&format!("Failed to remove temporary keychain: {}", e)).expect("Failed to write to stdout"),
+        }
+    }
+
+    // Release branches of older releases, once this one is out
+    if let Some(keep) = keep_release_branches
+        && result.is_ok()
+    {
+        config.group("Release branches").expect("Failed to write to stdout");
+        // A profile's repository is not the one the local checkout belongs to
+        let local_repo = (!resolved.is_temp && profile.repository.is_none()).then_some(resolved.path.as_path());
+        let pruned = match crate::github::GitHubReleaseManager::new(
+            crate::github::GitHubReleaseConfig {
+                owner: release_remote.owner.clone(),
+                repo: release_remote.repo.clone(),
+                ..Default::default()
+            },
+            env_config,
+        ) {
+            Ok(github_manager) => r#impl::prune_release_branches(&github_manager, keep, local_repo, config).await,
+            Err(e) => Err(e),
+        };
+        match pruned {
+            Ok(0) => config.verbose_println("✓ No release branches to delete").expect("Failed to write to stdout"),
+            Ok(count) => config.success_println(&format!("✓ Deleted {} release branch(es)", count)).expect("Failed to write to stdout"),
+            Err(e) => config.warning_println(&format!("⚠️  Release branches not cleaned up: {}", e)).expect("Failed to write to stdout"),
+        }
+    }
+
+    // Only a successful run has a release (and manifest) to attach the report to
+    if config.attach_report() && result.is_ok()
+        && let Err(e) = attach_report(args.source(), config, env_config).await
+    {
+        config.warning_println(&format!("⚠️  Failed to attach the release report: {}", e)).expect("Failed to write to stdout");
+    }
+
     result
 }
+
+/// Refuse to release while a freeze window is in effect, unless overridden
+/// with a reason; rebuilding an existing tag is not a new release
+fn check_freeze(
+    repo_path: &std::path::Path,
+    override_reason: Option<&str>,
+    rebuild: bool,
+    now: chrono::DateTime<chrono::Utc>,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    let override_reason = override_reason.map(str::trim);
+    if override_reason == Some("") {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: "--override-freeze needs a reason, e.g. --override-freeze \"hotfix for #123\"".to_string(),
+        }));
+    }
+    if rebuild {
+        return Ok(());
+    }
+
+    let windows = crate::freeze::FreezeWindow::load(repo_path)?;
+    let Some((window, until)) = windows
+        .iter()
+        .find_map(|window| window.active_until(now).map(|until| (window, until)))
+    else {
+        if override_reason.is_some() {
+            config.verbose_println("ℹ️  No release freeze in effect; --override-freeze is not needed").expect("Failed to write to stdout");
+        }
+        return Ok(());
+    };
+
+    let until = until.format("%a %Y-%m-%d %H:%M UTC");
+    match override_reason {
+        Some(reason) => {
+            config.warning_println(&format!("⚠️  Release freeze {} overridden until {}: {}", window.describe(), until, reason)).expect("Failed to write to stdout");
+            config.journal().set_freeze_override(&format!("{}: {}", window.describe(), reason));
+            Ok(())
+        }
+        None => Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Release freeze in effect: {} (ends {}); use --override-freeze REASON to release anyway",
+                window.describe(),
+                until
+            ),
+        })),
+    }
+}
+
+/// Upload the run report to the release as `release-report.md` (and
+/// `release-report.html` with `--report-html`), replacing earlier reports
+async fn attach_report(subject: &str, config: &RuntimeConfig, env_config: &EnvConfig) -> Result<()> {
+    let (report, manifest) = {
+        let journal = config.journal();
+        let Some(manifest) = journal.manifest().cloned() else {
+            return Ok(());
+        };
+        (crate::report::ReleaseReport::new(&journal, subject, None), manifest)
+    };
+    let Some((owner, repo)) = manifest.repository.split_once('/') else {
+        return Ok(());
+    };
+
+    let dir = std::env::temp_dir().join(format!("kodegen-release-report-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let mut files = vec![dir.join(crate::report::REPORT_FILE_NAME)];
+    std::fs::write(&files[0], report.to_markdown())?;
+    if config.report_html_out().is_some() {
+        let html = dir.join(crate::report::REPORT_HTML_FILE_NAME);
+        std::fs::write(&html, report.to_html())?;
+        files.push(html);
+    }
+
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, env_config)?;
+    let uploaded = github_manager.replace_artifacts(manifest.release_id, &files, config).await;
+    let _ = std::fs::remove_dir_all(&dir);
+    uploaded?;
+
+    config.success_println(&format!("✓ Release report attached to {}", manifest.html_url)).expect("Failed to write to stdout");
+    Ok(())
+}
+
+/// Check out `tag` in the temp clone and rebuild its artifacts
+///
+/// Metadata is re-read at the tag, since the working tree may have moved on.
+async fn rebuild_in_temp(
+    temp_dir: &std::path::Path,
+    tag: &str,
+    github_remote: &super::github_remote::GitHubRemote,
+    git_auth: &crate::git_auth::GitAuth,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    config.println(&format!("🏷  Checking out {}...", tag)).expect("Failed to write to stdout");
+    super::temp_clone::checkout_tag(temp_dir, tag, git_auth).await?;
+
+    let manifest = crate::metadata::load_manifest(&temp_dir.join("Cargo.toml"), config).await?;
+    let binary_name = manifest
+        .binary_name
+        .ok_or_else(|| library_only_error(&manifest.metadata.name))?;
+    r#impl::perform_rebuild_single_repo(
+        temp_dir,
+        tag,
+        manifest.metadata,
+        binary_name,
+        github_remote,
+        config,
+        env_config,
+    ).await
+}
+
+/// Check out `tag` in the temp clone and release it
+///
+/// The tag must exist on the remote and name the Cargo.toml version at the
+/// tag. Metadata is re-read at the tag, since main may have moved on.
+async fn release_tag_in_temp(
+    temp_dir: &std::path::Path,
+    tag: &str,
+    github_remote: &super::github_remote::GitHubRemote,
+    profile: &crate::profile::ReleaseProfile,
+    git_auth: &crate::git_auth::GitAuth,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    config.println(&format!("🏷  Checking out {}...", tag)).expect("Failed to write to stdout");
+    super::temp_clone::checkout_tag(temp_dir, tag, git_auth).await?;
+    let commit = crate::exec::Exec::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(temp_dir)
+        .stdout()
+        .await?;
+    config.verbose_println(&format!("✓ {} is commit {}", tag, commit)).expect("Failed to write to stdout");
+
+    let manifest = crate::metadata::load_manifest(&temp_dir.join("Cargo.toml"), config).await?;
+    let metadata = manifest.metadata;
+    let expected = semver::Version::parse(&metadata.version)
+        .map(|version| metadata.version_scheme.tag_name(&version))
+        .unwrap_or_default();
+    if tag != expected {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "Tag {} does not match Cargo.toml version {} at that tag (expected tag {})",
+                tag, metadata.version, expected
+            ),
+        }));
+    }
+    if !metadata.version_files.is_empty() {
+        check_version_references(temp_dir, &metadata, config)?;
+    }
+
+    r#impl::perform_release_single_repo(
+        temp_dir,
+        metadata,
+        manifest.binary_name,
+        github_remote,
+        profile,
+        config,
+        env_config,
+    ).await
+}
+
+/// Error for a command that needs a binary on a library-only crate
+fn library_only_error(package: &str) -> ReleaseError {
+    ReleaseError::Cli(CliError::InvalidArguments {
+        reason: format!(
+            "{} is a library crate without a binary: rebuild and --channel nightly have nothing to build",
+            package
+        ),
+    })
+}
+
+/// Fail when a `version-files` entry still mentions the previous version
+fn check_version_references(
+    repo_path: &std::path::Path,
+    metadata: &crate::metadata::PackageMetadata,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    let tag = semver::Version::parse(&metadata.version)
+        .map(|version| metadata.version_scheme.tag_name(&version))
+        .unwrap_or_default();
+    let Some(previous) = crate::version_refs::previous_version(repo_path, &metadata.version_scheme, &tag) else {
+        return Ok(());
+    };
+    if previous == metadata.version {
+        return Ok(());
+    }
+
+    let stale = crate::version_refs::find_stale(repo_path, &metadata.version_files, &previous)?;
+    if stale.is_empty() {
+        config
+            .verbose_println(&format!("✓ No references to {} left in version-files", previous))
+            .expect("Failed to write to stdout");
+        return Ok(());
+    }
+
+    for reference in &stale {
+        config.error_println(&format!("{}:{}: {}", reference.file, reference.line, reference.text));
+    }
+    Err(ReleaseError::Cli(CliError::InvalidArguments {
+        reason: format!(
+            "{} line(s) in version-files still mention the previous version {}; update them to {}",
+            stale.len(),
+            previous,
+            metadata.version
+        ),
+    }))
+}
+
+/// Validate a workspace for publish blockers before the release starts, and
+/// report the publish order with the packages it skips
+fn check_publish_blockers(repo_path: &std::path::Path, config: &RuntimeConfig) -> Result<()> {
+    let workspace = std::sync::Arc::new(crate::workspace::WorkspaceInfo::analyze(repo_path)?);
+    let result = crate::workspace::WorkspaceValidator::new(workspace.clone()).validate_publish_blockers();
+
+    for warning in &result.warnings {
+        config.soft_failure(warning)?;
+    }
+
+    if !result.success {
+        for error in &result.critical_errors {
+            config.error_println(error);
+        }
+        return Err(ReleaseError::Workspace(crate::error::WorkspaceError::InvalidStructure {
+            reason: format!("{} publish blocker(s) found", result.critical_errors.len()),
+        }));
+    }
+
+    config.verbose_println(&format!("✓ Workspace: {}", result.summary())).expect("Failed to write to stdout");
+
+    let plan = crate::workspace::PublishPlan::build(&workspace, &[])?;
+    config.println("📦 Publish order:").expect("Failed to write to stdout");
+    for line in plan.report_lines() {
+        config.indent(&line).expect("Failed to write to stdout");
+    }
+    Ok(())
+}
+
+/// Warn loudly about an in-place release and record a backup point
+///
+/// The backup is a commit of the current working tree (including uncommitted
+/// changes) stored under `refs/kodegen/backup/<timestamp>`; nothing in the
+/// working tree or index is modified.
+fn prepare_in_place(repo_path: &std::path::Path, config: &RuntimeConfig) -> Result<()> {
+    config.warning_println("⚠️  --in-place: running release phases directly in the working repository").expect("Failed to write to stdout");
+    config.warning_println(&format!("   Build output, artifacts/ and release state will be written to {}", repo_path.display())).expect("Failed to write to stdout");
+
+    let git = |args: &[&str]| -> Result<String> {
+        crate::exec::Exec::new("git").args(args).current_dir(repo_path).stdout_blocking()
+    };
+
+    // `git stash create` prints nothing when the tree is clean
+    let stash = git(&["stash", "create", "kodegen release backup"])?;
+    let has_changes = !stash.is_empty();
+    let snapshot = if has_changes { stash } else { git(&["rev-parse", "HEAD"])? };
+
+    let backup_ref = format!("refs/kodegen/backup/{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ"));
+    git(&["update-ref", &backup_ref, &snapshot])?;
+    config.record_action(&format!("Recorded backup point {} before the in-place release", backup_ref));
+
+    config.warning_println(&format!("   Backup point: {} ({})", backup_ref, &snapshot[..snapshot.len().min(12)])).expect("Failed to write to stdout");
+    let restore = if has_changes {
+        format!("   Restore uncommitted changes with: git stash apply {}", backup_ref)
+    } else {
+        format!("   No uncommitted changes to restore; reset with: git reset --hard {}", backup_ref)
+    };
+    config.warning_println(&restore).expect("Failed to write to stdout");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuilds_ignore_freeze_windows() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::write(
+            repo.path().join(crate::profile::PROFILE_FILE),
+            "[[freeze]]\nfrom = \"2026-12-21\"\nuntil = \"2027-01-04\"\n",
+        )
+        .unwrap();
+        let config = RuntimeConfig::new();
+        let frozen = chrono::DateTime::parse_from_rfc3339("2026-12-24T12:00:00Z").unwrap().to_utc();
+
+        let error = check_freeze(repo.path(), None, false, frozen, &config).unwrap_err();
+        assert!(error.to_string().contains("Release freeze in effect"), "{}", error);
+        check_freeze(repo.path(), None, true, frozen, &config).unwrap();
+        check_freeze(repo.path(), None, false, frozen + chrono::Duration::days(30), &config).unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+//! Preflight check that the local checkout agrees with the remote release branch.
+//!
+//! Releases are built from the remote's `main` (or, with `--in-place`, from the
+//! local tree), while metadata is read from the local checkout. If the two have
+//! diverged, typically because the remote was force-pushed or local commits
+//! were never pushed, the release would ship something other than what was
+//! checked locally.
+
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use std::path::Path;
+
+use super::github_remote::GitHubRemote;
+use super::temp_clone::RELEASE_BRANCH;
+
+/// Fetch the remote release branch and compare it with local `HEAD`
+///
+/// - equal: passes
+/// - behind (unpulled commits): warns
+/// - ahead (unpushed commits): warns that they are not part of a cloned release
+/// - diverged: fails unless `allow_diverged` is set
+///
+/// If the fetch fails (e.g. offline), the check is skipped with a warning.
+pub(super) async fn check_remote_divergence(
+    repo_path: &Path,
+    remote: &GitHubRemote,
+    auth: &crate::git_auth::GitAuth,
+    allow_diverged: bool,
+    in_place: bool,
+    config: &RuntimeConfig,
+) -> Result<()> {
+    let tracking_ref = format!("refs/remotes/{}/{}", remote.remote, RELEASE_BRANCH);
+    let refspec = format!("+refs/heads/{}:{}", RELEASE_BRANCH, tracking_ref);
+
+    // With token auth the remote URL was rewritten to HTTPS; fetch from it
+    // rather than the remote's configured (SSH) URL
+    let source = if auth.uses_token_for_ssh() { &remote.url } else { &remote.remote };
+    if let Err(e) = git_with(auth.git(), repo_path, &["fetch", "--quiet", source, &refspec]).await {
+        config.soft_failure(&format!(
+            "⚠️  Could not fetch {}/{}; skipping divergence check: {}",
+            remote.remote, RELEASE_BRANCH, e
+        ))?;
+        return Ok(());
+    }
+
+    // "<ahead>\t<behind>" relative to the remote branch
+    let counts = git(
+        repo_path,
+        &["rev-list", "--left-right", "--count", &format!("HEAD...{}", tracking_ref)],
+    )
+    .await?;
+    let (ahead, behind) = counts
+        .split_once(char::is_whitespace)
+        .and_then(|(a, b)| Some((a.trim().parse::<u64>().ok()?, b.trim().parse::<u64>().ok()?)))
+        .ok_or_else(|| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "git rev-list --left-right --count".to_string(),
+                reason: format!("Unexpected output: {}", counts),
+            })
+        })?;
+
+    let branch = format!("{}/{}", remote.remote, RELEASE_BRANCH);
+    match (ahead, behind) {
+        (0, 0) => {
+            config
+                .verbose_println(&format!("✓ Local HEAD matches {}", branch))
+                .expect("Failed to write to stdout");
+        }
+        (0, behind) => {
+            config
+                .warning_println(&format!(
+                    "⚠️  Local HEAD is {} commit(s) behind {}; run `git pull` to release what you reviewed",
+                    behind, branch
+                ))
+                .expect("Failed to write to stdout");
+        }
+        (ahead, 0) => {
+            let note = if in_place {
+                "they will be released in place but are not on the remote"
+            } else {
+                "they will not be part of this release"
+            };
+            config
+                .warning_println(&format!(
+                    "⚠️  Local HEAD has {} unpushed commit(s) relative to {}; {}",
+                    ahead, branch, note
+                ))
+                .expect("Failed to write to stdout");
+        }
+        (ahead, behind) => {
+            let message = format!(
+                "Local HEAD and {} have diverged ({} local, {} remote commit(s)); \
+                 the remote may have been force-pushed",
+                branch, ahead, behind
+            );
+            if !allow_diverged {
+                return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: format!(
+                        "{}. Reconcile the branches or pass --allow-diverged to release anyway",
+                        message
+                    ),
+                }));
+            }
+            config
+                .warning_println(&format!("⚠️  {} (continuing: --allow-diverged)", message))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a git command in `repo_path` and return trimmed stdout
+async fn git(repo_path: &Path, args: &[&str]) -> Result<String> {
+    git_with(Exec::new("git"), repo_path, args).await
+}
+
+/// Run `git` (a prepared git command) in `repo_path` and return trimmed stdout
+async fn git_with(mut git: Exec, repo_path: &Path, args: &[&str]) -> Result<String> {
+    git.args(args).current_dir(repo_path).stdout().await
+}
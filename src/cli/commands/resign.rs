@@ -0,0 +1,532 @@
+//! `resign`: sign a release's assets again with the current credentials.
+//!
+//! For certificates that expire or get rotated after a release. The assets of
+//! the chosen platforms are downloaded as listed in the release manifest and
+//! checked against their SHA-256, then signed again:
+//!
+//! - `dmg` (macOS host): every app bundle in the disk image is re-signed with
+//!   the Developer ID identity, the image is rebuilt and signed, and it is
+//!   notarized and stapled when notarization credentials are set.
+//! - `exe` (alias `nsis`), `msi`, `msix` (Windows host): Authenticode with
+//!   `signtool`, using the certificate and timestamp settings of
+//!   `[package.metadata.bundle.windows]`.
+//!
+//! Signing settings come from the source checkout, not from the tag. Nothing
+//! on the release changes until every asset is signed; the assets are then
+//! swapped in place and the manifest is uploaded with the new sizes, hashes
+//! and notarization status, as is the checksum table in the release body.
+//! Encrypted assets cannot be re-signed.
+
+use crate::cli::{Args, RuntimeConfig};
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+use crate::github::ReleaseAssets;
+use crate::manifest::{MANIFEST_FILE_NAME, NotarizationStatus};
+use crate::EnvConfig;
+use base64::Engine;
+use std::path::{Path, PathBuf};
+
+use super::download::release_manifest;
+
+/// Re-sign the `platforms` assets of `tag` and replace them on its release
+pub(super) async fn execute_resign(
+    args: &Args,
+    tag: &str,
+    platforms: &[String],
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let mut platforms: Vec<&str> = platforms
+        .iter()
+        .map(|platform| if platform == "nsis" { "exe" } else { platform.as_str() })
+        .collect();
+    platforms.sort_unstable();
+    platforms.dedup();
+    for platform in &platforms {
+        let (os, host) = if *platform == "dmg" { ("macos", "macOS") } else { ("windows", "Windows") };
+        if std::env::consts::OS != os {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("Re-signing {} assets needs a {} host", platform, host),
+            }));
+        }
+    }
+
+    let git_auth = crate::git_auth::GitAuth::resolve(args.git_auth, env_config)?;
+    let resolved = crate::source::RepositorySource::parse(args.source())?
+        .resolve(&git_auth)
+        .await?;
+
+    let mut env_config = env_config.clone();
+    if !config.is_ci()
+        && let Some(dotenv) = resolved.dotenv_path()
+    {
+        env_config.load_dotenv(&dotenv)?;
+    }
+    env_config.require(crate::env::EnvRequirement::GitHubRelease)?;
+
+    let work = std::env::temp_dir().join(format!("kodegen-resign-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work)?;
+    let result = resign_with_credentials(args, tag, &platforms, &resolved.path, &work, &mut env_config, config).await;
+    let _ = std::fs::remove_dir_all(&work);
+    result
+}
+
+async fn resign_with_credentials(
+    args: &Args,
+    tag: &str,
+    platforms: &[&str],
+    repo: &Path,
+    work: &Path,
+    env_config: &mut EnvConfig,
+    config: &RuntimeConfig,
+) -> Result<i32> {
+    let cargo_toml = repo.join("Cargo.toml");
+    let metadata = crate::metadata::load_manifest(&cargo_toml, config).await?.metadata;
+    let bundle = bundle_settings(&cargo_toml)?;
+
+    let mut keychain = None;
+    let mac = if platforms.contains(&"dmg") {
+        let (identity, imported) = signing_identity(args, env_config, work, config).await?;
+        keychain = imported;
+        Some(MacSigning::new(identity, &bundle, repo, env_config))
+    } else {
+        None
+    };
+    let windows = if platforms.iter().any(|platform| *platform != "dmg") {
+        Some(WindowsSigning::new(&bundle)?)
+    } else {
+        None
+    };
+
+    let signer = Signer {
+        mac,
+        windows,
+        env_config,
+        work,
+    };
+    let result = resign_release(args, tag, platforms, &metadata, &signer, repo, config).await;
+
+    if let Some(keychain) = keychain
+        && let Err(e) = keychain.cleanup().await
+    {
+        config
+            .warning_println(&format!("⚠️  Failed to remove the temporary keychain: {}", e))
+            .expect("Failed to write to stdout");
+    }
+    result
+}
+
+async fn resign_release(
+    args: &Args,
+    tag: &str,
+    platforms: &[&str],
+    metadata: &crate::metadata::PackageMetadata,
+    signer: &Signer<'_>,
+    repo: &Path,
+    config: &RuntimeConfig,
+) -> Result<i32> {
+    let github_remote = super::github_remote::detect_github_repo(repo, &args.remotes).await?;
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_remote.owner.clone(),
+        repo: github_remote.repo.clone(),
+        asset_labels: metadata.asset_labels.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, signer.env_config)?;
+
+    let repository = format!("{}/{}", github_remote.owner, github_remote.repo);
+    let (release_id, mut manifest) = release_manifest(&github_manager, &repository, tag).await?;
+
+    let artifacts: Vec<_> = manifest
+        .artifacts
+        .iter()
+        .filter(|artifact| platforms.contains(&artifact.platform.as_str()))
+        .cloned()
+        .collect();
+    if artifacts.is_empty() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("The manifest of {} lists no {} assets", tag, platforms.join("/")),
+        }));
+    }
+    let encrypted: Vec<&str> = artifacts
+        .iter()
+        .filter(|artifact| artifact.encryption.is_some())
+        .map(|artifact| artifact.name.as_str())
+        .collect();
+    if !encrypted.is_empty() {
+        return Err(ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Encrypted assets cannot be re-signed: {}", encrypted.join(", ")),
+        }));
+    }
+
+    // Sign everything first, so a signing failure leaves the release untouched
+    config
+        .println(&format!("🔏 Re-signing {} asset(s) of {}", artifacts.len(), tag))
+        .expect("Failed to write to stdout");
+    let assets = signer.work.join("assets");
+    std::fs::create_dir_all(&assets)?;
+    let mut signed = Vec::new();
+    for artifact in &artifacts {
+        crate::cli::abort::check("resign")?;
+        let content = github_manager
+            .download_verified(release_id, &artifact.name, &artifact.sha256)
+            .await?;
+        let path = assets.join(&artifact.name);
+        std::fs::write(&path, &content)?;
+
+        let notarization = signer.sign(&artifact.platform, &path, config).await?;
+        config
+            .indent(&format!("✓ {}", artifact.name))
+            .expect("Failed to write to stdout");
+        signed.push((artifact.platform.clone(), path, notarization));
+    }
+
+    config
+        .println("☁️  Replacing release assets...")
+        .expect("Failed to write to stdout");
+    let paths: Vec<PathBuf> = signed.iter().map(|(_, path, _)| path.clone()).collect();
+    github_manager
+        .replace_artifacts(release_id, &paths, config)
+        .await?;
+
+    for (platform, path, notarization) in &signed {
+        manifest.add_artifacts(platform, std::slice::from_ref(path), *notarization)?;
+    }
+    let manifest_path = signer.work.join(MANIFEST_FILE_NAME);
+    manifest.write(&manifest_path)?;
+    github_manager
+        .replace_artifacts(release_id, std::slice::from_ref(&manifest_path), config)
+        .await?;
+    if let Err(e) = github_manager.update_checksum_table(release_id, &manifest.artifacts).await {
+        config
+            .warning_println(&format!("⚠️  Failed to update the checksum table in the release body: {}", e))
+            .expect("Failed to write to stdout");
+    }
+    if let Some(path) = config.manifest_out() {
+        manifest.write(path)?;
+        config
+            .println(&format!("📄 Release manifest written to {}", path.display()))
+            .expect("Failed to write to stdout");
+    }
+
+    config
+        .success_println(&format!("✓ Re-signed {} asset(s) of {}", signed.len(), tag))
+        .expect("Failed to write to stdout");
+    config.result_println(&manifest.html_url);
+    Ok(0)
+}
+
+/// Signing settings for the chosen platforms
+struct Signer<'a> {
+    mac: Option<MacSigning>,
+    windows: Option<WindowsSigning>,
+    env_config: &'a EnvConfig,
+    /// Scratch directory, removed afterwards
+    work: &'a Path,
+}
+
+impl Signer<'_> {
+    /// Sign `path` in place; returns the asset's notarization status
+    async fn sign(&self, platform: &str, path: &Path, config: &RuntimeConfig) -> Result<NotarizationStatus> {
+        match (platform, &self.mac, &self.windows) {
+            ("dmg", Some(mac), _) => mac.sign_dmg(path, self.work, self.env_config, config).await,
+            (_, _, Some(windows)) => {
+                windows.sign(path, config).await?;
+                Ok(NotarizationStatus::NotApplicable)
+            }
+            _ => Err(resign_error("resign", format!("No signing settings for {}", platform))),
+        }
+    }
+}
+
+/// Developer ID signing of disk images
+struct MacSigning {
+    identity: String,
+    entitlements: Option<PathBuf>,
+    hardened_runtime: bool,
+    /// Notarize after signing (notarization credentials are set)
+    notarize: bool,
+}
+
+impl MacSigning {
+    fn new(identity: String, bundle: &toml::Value, repo: &Path, env_config: &EnvConfig) -> Self {
+        let macos = bundle.get("macos");
+        let setting = |key: &str| macos.and_then(|macos| macos.get(key));
+        Self {
+            identity,
+            entitlements: setting("entitlements")
+                .and_then(|v| v.as_str())
+                .map(|path| repo.join(path)),
+            hardened_runtime: setting("hardened_runtime").and_then(|v| v.as_bool()).unwrap_or(true),
+            notarize: env_config
+                .require(crate::env::EnvRequirement::MacosNotarization)
+                .is_ok(),
+        }
+    }
+
+    /// Re-sign the apps inside `dmg`, rebuild and sign the image, then notarize it
+    async fn sign_dmg(
+        &self,
+        dmg: &Path,
+        work: &Path,
+        env_config: &EnvConfig,
+        config: &RuntimeConfig,
+    ) -> Result<NotarizationStatus> {
+        let writable = work.join("resign-rw.dmg");
+        let mount = work.join("resign-mount");
+        let _ = std::fs::remove_file(&writable);
+        std::fs::create_dir_all(&mount)?;
+
+        Exec::new("hdiutil")
+            .arg("convert")
+            .arg(dmg)
+            .args(["-format", "UDRW", "-o"])
+            .arg(&writable)
+            .run_watched(config)
+            .await?;
+        Exec::new("hdiutil")
+            .arg("attach")
+            .arg(&writable)
+            .args(["-nobrowse", "-noautoopen", "-mountpoint"])
+            .arg(&mount)
+            .run_watched(config)
+            .await?;
+        let signed = self.sign_apps(&mount, config).await;
+        Exec::new("hdiutil")
+            .arg("detach")
+            .arg(&mount)
+            .arg("-force")
+            .run_watched(config)
+            .await?;
+        signed?;
+
+        std::fs::remove_file(dmg)?;
+        Exec::new("hdiutil")
+            .arg("convert")
+            .arg(&writable)
+            .args(["-format", "UDZO", "-o"])
+            .arg(dmg)
+            .run_watched(config)
+            .await?;
+        std::fs::remove_file(&writable)?;
+        Exec::new("codesign")
+            .args(["--force", "--timestamp", "--sign", &self.identity])
+            .arg(dmg)
+            .run_watched(config)
+            .await?;
+
+        if !self.notarize {
+            config
+                .warning_println(&format!(
+                    "⚠️  {} is signed but not notarized: no notarization credentials are set",
+                    dmg.display()
+                ))
+                .expect("Failed to write to stdout");
+            return Ok(NotarizationStatus::NotNotarized);
+        }
+        notarize(dmg, work, env_config, config).await?;
+        Exec::new("xcrun")
+            .args(["stapler", "staple"])
+            .arg(dmg)
+            .run_watched(config)
+            .await?;
+        Ok(NotarizationStatus::Notarized)
+    }
+
+    /// `codesign` every app bundle at the top of the mounted image
+    async fn sign_apps(&self, mount: &Path, config: &RuntimeConfig) -> Result<()> {
+        let mut apps = Vec::new();
+        for entry in std::fs::read_dir(mount)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "app") && !path.is_symlink() {
+                apps.push(path);
+            }
+        }
+        if apps.is_empty() {
+            return Err(resign_error("codesign", format!("No app bundle in {}", mount.display())));
+        }
+
+        for app in apps {
+            let mut codesign = Exec::new("codesign");
+            codesign.args(["--force", "--deep", "--timestamp", "--sign", &self.identity]);
+            if self.hardened_runtime {
+                codesign.args(["--options", "runtime"]);
+            }
+            if let Some(entitlements) = &self.entitlements {
+                codesign.arg("--entitlements").arg(entitlements);
+            }
+            codesign.arg(&app).run_watched(config).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Submit `dmg` with `notarytool` and wait for Apple's verdict
+async fn notarize(dmg: &Path, work: &Path, env_config: &EnvConfig, config: &RuntimeConfig) -> Result<()> {
+    config
+        .println(&format!("📨 Notarizing {}...", dmg.display()))
+        .expect("Failed to write to stdout");
+    let mut notarytool = Exec::new("xcrun");
+    notarytool
+        .args(["notarytool", "submit"])
+        .arg(dmg)
+        .args(["--wait", "--output-format", "json"]);
+
+    if let Some(key_id) = env_config.get("APPLE_API_KEY") {
+        let issuer = env_config.get("APPLE_API_ISSUER").unwrap_or_default();
+        let key = match env_config.get("APPLE_API_KEY_PATH") {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let content = env_config.get("APPLE_API_KEY_CONTENT").ok_or_else(|| {
+                    resign_error(
+                        "notarytool",
+                        "APPLE_API_KEY needs APPLE_API_KEY_PATH or APPLE_API_KEY_CONTENT".to_string(),
+                    )
+                })?;
+                let key = base64::engine::general_purpose::STANDARD
+                    .decode(content.trim())
+                    .map_err(|e| {
+                        resign_error("notarytool", format!("APPLE_API_KEY_CONTENT is not valid base64: {}", e))
+                    })?;
+                let path = work.join(format!("AuthKey_{}.p8", key_id));
+                std::fs::write(&path, key)?;
+                path
+            }
+        };
+        notarytool
+            .arg("--key")
+            .arg(key)
+            .args(["--key-id", &key_id, "--issuer", &issuer]);
+    } else {
+        let apple_id = env_config.get("APPLE_ID").unwrap_or_default();
+        let password = env_config.get("APPLE_PASSWORD").unwrap_or_default();
+        notarytool.args(["--apple-id", &apple_id, "--password"]).secret_arg(password);
+        if let Some(team_id) = env_config.get("APPLE_TEAM_ID") {
+            notarytool.args(["--team-id", &team_id]);
+        }
+    }
+
+    let output = notarytool.run_watched(config).await?;
+    let verdict: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let status = verdict.get("status").and_then(|s| s.as_str()).unwrap_or("unknown");
+    if status != "Accepted" {
+        let id = verdict.get("id").and_then(|s| s.as_str()).unwrap_or("?");
+        return Err(resign_error(
+            "notarytool",
+            format!(
+                "Notarization of {} ended as {} (xcrun notarytool log {} for details)",
+                dmg.display(),
+                status,
+                id
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Authenticode signing of Windows installers and packages
+struct WindowsSigning {
+    thumbprint: String,
+    digest_algorithm: String,
+    timestamp_url: Option<String>,
+    /// RFC 3161 timestamp server (`/tr`) instead of Authenticode (`/t`)
+    tsp: bool,
+}
+
+impl WindowsSigning {
+    fn new(bundle: &toml::Value) -> Result<Self> {
+        let windows = bundle.get("windows");
+        let setting = |key: &str| windows.and_then(|windows| windows.get(key));
+        let thumbprint = setting("certificate_thumbprint")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: "Re-signing Windows assets needs certificate_thumbprint in [package.metadata.bundle.windows]"
+                        .to_string(),
+                })
+            })?;
+        Ok(Self {
+            thumbprint: thumbprint.to_string(),
+            digest_algorithm: setting("digest_algorithm")
+                .and_then(|v| v.as_str())
+                .unwrap_or("sha256")
+                .to_string(),
+            timestamp_url: setting("timestamp_url").and_then(|v| v.as_str()).map(str::to_string),
+            tsp: setting("tsp").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+    }
+
+    /// Replace the signature of `path` with `signtool sign`
+    async fn sign(&self, path: &Path, config: &RuntimeConfig) -> Result<()> {
+        let mut signtool = Exec::new("signtool");
+        signtool.args(["sign", "/sha1", &self.thumbprint, "/fd", &self.digest_algorithm]);
+        if let Some(url) = &self.timestamp_url {
+            if self.tsp {
+                signtool.args(["/tr", url, "/td", &self.digest_algorithm]);
+            } else {
+                signtool.args(["/t", url]);
+            }
+        }
+        signtool.arg(path).run_watched(config).await?;
+        Ok(())
+    }
+}
+
+/// Developer ID identity to sign with, and the keychain it was imported into
+///
+/// `--import-p12` wins, then `APPLE_SIGNING_IDENTITY` (from the environment or,
+/// outside CI, the login keychain), then `APPLE_CERTIFICATE`.
+async fn signing_identity(
+    args: &Args,
+    env_config: &mut EnvConfig,
+    work: &Path,
+    config: &RuntimeConfig,
+) -> Result<(String, Option<crate::env::TemporaryKeychain>)> {
+    let import = |p12: PathBuf, password: String| async move {
+        config
+            .println("🔑 Importing signing certificate into a temporary keychain...")
+            .expect("Failed to write to stdout");
+        let keychain = crate::env::TemporaryKeychain::import(&p12, &password).await?;
+        Ok::<_, ReleaseError>((keychain.signing_identity().to_string(), Some(keychain)))
+    };
+
+    let password = env_config.get("APPLE_CERTIFICATE_PASSWORD").unwrap_or_default();
+    if let Some(p12) = &args.import_p12 {
+        return import(p12.clone(), password).await;
+    }
+    if !config.is_ci() {
+        env_config.load_keychain();
+    }
+    env_config.require(crate::env::EnvRequirement::MacosSigning)?;
+    if let Some(identity) = env_config.get("APPLE_SIGNING_IDENTITY") {
+        return Ok((identity, None));
+    }
+
+    let certificate = env_config.get("APPLE_CERTIFICATE").unwrap_or_default();
+    let p12 = work.join("certificate.p12");
+    std::fs::write(
+        &p12,
+        base64::engine::general_purpose::STANDARD
+            .decode(certificate.trim())
+            .map_err(|e| resign_error("security import", format!("APPLE_CERTIFICATE is not valid base64: {}", e)))?,
+    )?;
+    import(p12, password).await
+}
+
+/// `[package.metadata.bundle]` of the source checkout (empty when unset)
+fn bundle_settings(cargo_toml: &Path) -> Result<toml::Value> {
+    let manifest: toml::Value = toml::from_str(&std::fs::read_to_string(cargo_toml)?)?;
+    Ok(manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("bundle"))
+        .cloned()
+        .unwrap_or_else(|| toml::Value::Table(Default::default())))
+}
+
+fn resign_error(command: &str, reason: String) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: command.to_string(),
+        reason,
+    })
+}
@@ -0,0 +1,199 @@
+//! `stats`: download counts of a repository's recent releases.
+//!
+//! Reads the download count GitHub keeps for every release asset. Assets are
+//! grouped into platforms by their default label ("Debian/Ubuntu Package
+//! (x86_64)"), and the platform table has one column per release, oldest
+//! first, to show how adoption moves between releases. Drafts are left out.
+
+use crate::cli::{Args, RuntimeConfig};
+use crate::error::Result;
+use crate::github::ReleaseListing;
+use crate::EnvConfig;
+use serde::Serialize;
+
+/// Download statistics of recent releases
+#[derive(Debug, Serialize)]
+struct DownloadStats {
+    /// GitHub repository as `owner/repo`
+    repository: String,
+    /// Releases, oldest first
+    releases: Vec<ReleaseDownloads>,
+    /// Downloads per platform, one count per release in `releases` order
+    platforms: Vec<PlatformDownloads>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReleaseDownloads {
+    tag: String,
+    prerelease: bool,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    downloads: u64,
+    /// Assets, most downloaded first
+    assets: Vec<AssetDownloads>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssetDownloads {
+    name: String,
+    platform: String,
+    size: u64,
+    downloads: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct PlatformDownloads {
+    platform: String,
+    downloads: Vec<u64>,
+}
+
+/// Print download statistics of the `count` most recent published releases
+pub(super) async fn execute_stats(
+    args: &Args,
+    count: usize,
+    json: bool,
+    config: &RuntimeConfig,
+    env_config: &EnvConfig,
+) -> Result<i32> {
+    let git_auth = crate::git_auth::GitAuth::resolve(args.git_auth, env_config)?;
+    let resolved = crate::source::RepositorySource::parse(args.source())?
+        .resolve(&git_auth)
+        .await?;
+
+    let mut env_config = env_config.clone();
+    if !config.is_ci()
+        && let Some(dotenv) = resolved.dotenv_path()
+    {
+        env_config.load_dotenv(&dotenv)?;
+    }
+    env_config.require(crate::env::EnvRequirement::GitHubRelease)?;
+
+    let github_remote = super::github_remote::detect_github_repo(&resolved.path, &args.remotes).await?;
+    let github_config = crate::github::GitHubReleaseConfig {
+        owner: github_remote.owner.clone(),
+        repo: github_remote.repo.clone(),
+        ..Default::default()
+    };
+    let github_manager = crate::github::GitHubReleaseManager::new(github_config, &env_config)?;
+
+    let mut releases: Vec<ReleaseListing> = github_manager
+        .list_releases()
+        .await?
+        .into_iter()
+        .filter(|release| !release.draft)
+        .collect();
+    releases.sort_by_key(|release| std::cmp::Reverse(release.published_at));
+    releases.truncate(count);
+    releases.reverse();
+
+    let stats = collect(&format!("{}/{}", github_remote.owner, github_remote.repo), releases);
+
+    if json {
+        config
+            .println(&serde_json::to_string_pretty(&stats)?)
+            .expect("Failed to write to stdout");
+    } else {
+        print_stats(&stats, config);
+    }
+    Ok(0)
+}
+
+fn collect(repository: &str, releases: Vec<ReleaseListing>) -> DownloadStats {
+    let releases: Vec<ReleaseDownloads> = releases
+        .into_iter()
+        .map(|release| {
+            let mut assets: Vec<AssetDownloads> = release
+                .assets
+                .into_iter()
+                .map(|asset| AssetDownloads {
+                    platform: crate::github::default_label(&asset.name),
+                    name: asset.name,
+                    size: asset.size,
+                    downloads: asset.download_count,
+                })
+                .collect();
+            assets.sort_by(|a, b| b.downloads.cmp(&a.downloads).then_with(|| a.name.cmp(&b.name)));
+            ReleaseDownloads {
+                tag: release.tag_name,
+                prerelease: release.prerelease,
+                published_at: release.published_at,
+                downloads: assets.iter().map(|asset| asset.downloads).sum(),
+                assets,
+            }
+        })
+        .collect();
+
+    let mut platforms: Vec<PlatformDownloads> = Vec::new();
+    for (index, release) in releases.iter().enumerate() {
+        for asset in &release.assets {
+            let position = match platforms.iter().position(|p| p.platform == asset.platform) {
+                Some(position) => position,
+                None => {
+                    platforms.push(PlatformDownloads {
+                        platform: asset.platform.clone(),
+                        downloads: vec![0; releases.len()],
+                    });
+                    platforms.len() - 1
+                }
+            };
+            platforms[position].downloads[index] += asset.downloads;
+        }
+    }
+    // Most downloaded platform first, by total
+    platforms.sort_by(|a, b| {
+        let total = |p: &PlatformDownloads| p.downloads.iter().sum::<u64>();
+        total(b).cmp(&total(a)).then_with(|| a.platform.cmp(&b.platform))
+    });
+
+    DownloadStats {
+        repository: repository.to_string(),
+        releases,
+        platforms,
+    }
+}
+
+fn print_stats(stats: &DownloadStats, config: &RuntimeConfig) {
+    let output = config.output();
+    output
+        .section(&format!(
+            "Downloads of {} (last {} release(s))",
+            stats.repository,
+            stats.releases.len()
+        ))
+        .expect("Failed to write to stdout");
+    if stats.releases.is_empty() {
+        config.println("No published releases").expect("Failed to write to stdout");
+        return;
+    }
+
+    for release in stats.releases.iter().rev() {
+        let published = release
+            .published_at
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let kind = if release.prerelease { " prerelease" } else { "" };
+        config
+            .println(&format!(
+                "{} ({}{}): {} download(s)",
+                release.tag, published, kind, release.downloads
+            ))
+            .expect("Failed to write to stdout");
+        for asset in &release.assets {
+            config
+                .indent(&format!("{:>8}  {}", asset.downloads, asset.name))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    output.section("Per platform").expect("Failed to write to stdout");
+    let width = stats.platforms.iter().map(|p| p.platform.len()).max().unwrap_or(0).max(8);
+    let header: Vec<String> = stats.releases.iter().map(|r| format!("{:>12}", r.tag)).collect();
+    config
+        .println(&format!("{:width$}  {}", "Platform", header.join(""), width = width))
+        .expect("Failed to write to stdout");
+    for platform in &stats.platforms {
+        let counts: Vec<String> = platform.downloads.iter().map(|n| format!("{:>12}", n)).collect();
+        config
+            .println(&format!("{:width$}  {}", platform.platform, counts.join(""), width = width))
+            .expect("Failed to write to stdout");
+    }
+}
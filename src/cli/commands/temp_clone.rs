@@ -3,39 +3,24 @@
 use crate::error::{CliError, ReleaseError, Result};
 use kodegen_config::KodegenConfig;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use sysinfo::{Pid, System};
 
-/// Get origin URL from current workspace for cloning
-pub(super) async fn get_origin_url_for_clone(workspace_path: &std::path::Path) -> Result<String> {
-    let output = tokio::process::Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .current_dir(workspace_path)
-        .output()
-        .await
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "git remote get-url origin".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
-
-    if !output.status.success() {
-        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-            command: "git remote get-url origin".to_string(),
-            reason: String::from_utf8_lossy(&output.stderr).to_string(),
-        }));
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
+/// Branch that releases are cloned from
+pub(super) const RELEASE_BRANCH: &str = "main";
 
 /// Clone main branch to temporary directory for isolated release execution
+///
+/// `remote_url` is the URL of the remote selected for the release; it becomes
+/// `origin` in the clone. With `local_repo`, the clone borrows that checkout's
+/// objects (see [`clone_from_local`]) and only fetches what the remote has
+/// beyond them; if that fails the remote is cloned in full.
 pub(super) async fn clone_main_to_temp_for_release(
-    workspace_path: &std::path::Path,
+    remote_url: &str,
+    local_repo: Option<&Path>,
+    auth: &crate::git_auth::GitAuth,
+    config: &crate::cli::RuntimeConfig,
 ) -> Result<PathBuf> {
-    let remote_url = get_origin_url_for_clone(workspace_path).await?;
-
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|e| {
@@ -48,47 +33,163 @@ pub(super) async fn clone_main_to_temp_for_release(
 
     let temp_dir = std::env::temp_dir().join(format!("kodegen-release-{}", timestamp));
 
-    // Clone using git command
-    let output = tokio::process::Command::new("git")
-        .args([
-            "clone",
-            "--branch",
-            "main",
-            "--single-branch",
-            &remote_url,
-            temp_dir.to_str().unwrap(),
-        ])
-        .output()
-        .await
-        .map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "git clone".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
-
-    if !output.status.success() {
-        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-            command: "git clone".to_string(),
-            reason: String::from_utf8_lossy(&output.stderr).to_string(),
-        }));
+    if let Some(local_repo) = local_repo {
+        match clone_from_local(local_repo, remote_url, &temp_dir, auth).await {
+            Ok(hard_linked) => {
+                config
+                    .verbose_println(if hard_linked {
+                        "   Cloned from the local checkout (objects hard-linked)"
+                    } else {
+                        "   Cloned from the local checkout (objects copied: temp dir is on another filesystem)"
+                    })
+                    .expect("Failed to write to stdout");
+                save_active_temp_path(&temp_dir)?;
+                return Ok(temp_dir);
+            }
+            Err(e) => {
+                config
+                    .verbose_println(&format!("   Local clone failed ({}); cloning from the remote", e))
+                    .expect("Failed to write to stdout");
+                let _ = std::fs::remove_dir_all(&temp_dir);
+            }
+        }
     }
 
+    // Clone using git command
+    auth.git()
+        .args(["clone", "--branch", RELEASE_BRANCH, "--single-branch", remote_url])
+        .arg(&temp_dir)
+        .run()
+        .await?;
+
     save_active_temp_path(&temp_dir)?;
 
     Ok(temp_dir)
 }
 
+/// Clone `main` of `remote_url` using the objects of a local checkout
+///
+/// The local repository is cloned first: on the same filesystem its object
+/// files are hard-linked (they are immutable, so the clone stays isolated),
+/// otherwise they are copied, which is still far cheaper than a download.
+/// `origin` is then pointed at the remote and `main` fetched from it, so the
+/// clone has the remote's `main` and the tags in its history, exactly like a
+/// network clone; local branches and unpushed tags are not carried over.
+///
+/// Returns whether the objects were hard-linked.
+async fn clone_from_local(
+    local_repo: &Path,
+    remote_url: &str,
+    temp_dir: &Path,
+    auth: &crate::git_auth::GitAuth,
+) -> Result<bool> {
+    let hard_link = same_filesystem(local_repo, temp_dir.parent().unwrap_or(temp_dir));
+    let source = local_repo.to_string_lossy();
+    let target = temp_dir.to_string_lossy();
+    let link_mode = if hard_link { "--local" } else { "--no-hardlinks" };
+    run_git(
+        auth,
+        None,
+        &["clone", "--no-checkout", "--no-tags", "--single-branch", link_mode, &source, &target],
+    )
+    .await?;
+
+    let local_branch = run_git(auth, Some(temp_dir), &["symbolic-ref", "--short", "HEAD"]).await?;
+    let refspec = format!("+refs/heads/{0}:refs/remotes/origin/{0}", RELEASE_BRANCH);
+    let tracking = format!("origin/{}", RELEASE_BRANCH);
+    let steps: [&[&str]; 5] = [
+        // --no-tags is remembered for origin; tags should follow main as in a normal clone
+        &["config", "--unset", "remote.origin.tagOpt"],
+        &["remote", "set-url", "origin", remote_url],
+        &["config", "remote.origin.fetch", &refspec],
+        &["fetch", "origin"],
+        &["checkout", "-B", RELEASE_BRANCH, &tracking],
+    ];
+    for args in steps {
+        run_git(auth, Some(temp_dir), args).await?;
+    }
+
+    // Drop the branch cloned from the checkout and its remote-tracking ref
+    if local_branch != RELEASE_BRANCH {
+        run_git(auth, Some(temp_dir), &["branch", "-D", &local_branch]).await?;
+        let stale = format!("refs/remotes/origin/{}", local_branch);
+        run_git(auth, Some(temp_dir), &["update-ref", "-d", &stale]).await?;
+    }
+
+    Ok(hard_link)
+}
+
+/// Whether two paths are on the same filesystem (hard links are possible)
+#[cfg(unix)]
+fn same_filesystem(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_filesystem(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// Run git with `auth` (in `dir`, if given) and return its trimmed stdout
+async fn run_git(auth: &crate::git_auth::GitAuth, dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = auth.git();
+    command.args(args);
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    command.stdout().await
+}
+
+/// Check out an existing tag in a release clone (detached HEAD)
+///
+/// Fetches the tag explicitly since single-branch clones may not include it.
+pub(super) async fn checkout_tag(repo_path: &Path, tag: &str, auth: &crate::git_auth::GitAuth) -> Result<()> {
+    let tag_ref = format!("refs/tags/{tag}:refs/tags/{tag}");
+    let steps: [&[&str]; 2] = [
+        &["fetch", "--no-tags", "origin", &tag_ref],
+        &["checkout", "--detach", tag],
+    ];
+
+    for args in steps {
+        auth.git().args(args).current_dir(repo_path).run().await?;
+    }
+
+    Ok(())
+}
+
 /// Metadata for tracking an active release process
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ReleaseTracking {
-    pid: u32,
-    temp_path: PathBuf,
-    started_at: String,
+pub(super) struct ReleaseTracking {
+    pub(super) pid: u32,
+    pub(super) temp_path: PathBuf,
+    pub(super) started_at: String,
     project: String,
     version: String,
 }
 
+/// Releases recorded in the tracking directory, whether or not they are still running
+pub(super) fn tracked_releases() -> Vec<ReleaseTracking> {
+    let Ok(config_dir) = KodegenConfig::state_dir().map(|dir| dir.join("active_releases")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&config_dir) else {
+        return Vec::new();
+    };
+
+    let mut releases: Vec<ReleaseTracking> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().and_then(|s| s.to_str()) == Some("json"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    releases.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    releases
+}
+
 /// Save active temp release path with metadata for current process
 pub(super) fn save_active_temp_path(temp_dir: &std::path::Path) -> Result<()> {
     let config_dir = KodegenConfig::state_dir()
@@ -0,0 +1,197 @@
+//! Record of a run for the release report.
+//!
+//! Every log group started with `RuntimeConfig::group` is a phase whose
+//! duration runs until the next group starts. Warnings, cleanup actions
+//! (deleted releases and assets), test, lint, advisory and policy check outcomes,
+//! an overridden release freeze and the final release manifest are collected alongside, so the report can be
+//! written however the run ends.
+
+use crate::manifest::ReleaseManifest;
+use std::time::{Duration, Instant};
+
+/// Duration of one phase of the run
+#[derive(Debug, Clone)]
+pub struct PhaseTiming {
+    /// Log group title
+    pub name: String,
+    /// Time until the next phase started (or until now for the last one)
+    pub duration: Duration,
+}
+
+/// Outcome of the test suite run before the release
+#[derive(Debug, Clone)]
+pub struct TestSummary {
+    /// Command that was run
+    pub command: String,
+    pub passed: u64,
+    pub failed: u64,
+    pub ignored: u64,
+    /// Names of the failed tests, where the output lists them
+    pub failures: Vec<String>,
+    /// Where the counts come from (the JUnit file, or the test output)
+    pub source: String,
+}
+
+/// Outcome of one lint gate for one crate
+#[derive(Debug, Clone)]
+pub struct LintResult {
+    /// Crate name
+    pub package: String,
+    /// `fmt` or `clippy`
+    pub gate: &'static str,
+    pub passed: bool,
+    /// A failure does not stop the release
+    pub advisory: bool,
+    /// Summary of the findings, e.g. `3 file(s) need formatting`
+    pub detail: String,
+}
+
+/// Vulnerable dependency found by the RustSec advisory check
+#[derive(Debug, Clone)]
+pub struct AdvisoryFinding {
+    /// Advisory ID, e.g. `RUSTSEC-2023-0071`
+    pub id: String,
+    /// Affected crate and the locked version
+    pub package: String,
+    pub version: String,
+    pub title: String,
+    /// Version requirements that fix the advisory
+    pub patched: Vec<String>,
+    /// `blocked`, `warned` or `ignored`
+    pub status: &'static str,
+    /// Why an advisory was ignored, or that its ignore entry expired
+    pub note: String,
+}
+
+/// Violation found by the cargo-deny policy check
+#[derive(Debug, Clone)]
+pub struct PolicyFinding {
+    /// cargo-deny diagnostic code, e.g. `rejected` or `banned`
+    pub code: String,
+    /// Offending crate and its locked version
+    pub package: String,
+    pub version: String,
+    pub message: String,
+    /// `blocked` or `warned`
+    pub status: &'static str,
+}
+
+/// Phases, warnings and actions of a run
+#[derive(Debug, Default)]
+pub struct RunJournal {
+    finished: Vec<PhaseTiming>,
+    current: Option<(String, Instant)>,
+    warnings: Vec<String>,
+    actions: Vec<String>,
+    manifest: Option<ReleaseManifest>,
+    tests: Option<TestSummary>,
+    lints: Vec<LintResult>,
+    advisories: Vec<AdvisoryFinding>,
+    policy: Vec<PolicyFinding>,
+    freeze_override: Option<String>,
+}
+
+impl RunJournal {
+    /// End the running phase and start `name`
+    pub fn begin_phase(&mut self, name: &str) {
+        if let Some((previous, started)) = self.current.take() {
+            self.finished.push(PhaseTiming {
+                name: previous,
+                duration: started.elapsed(),
+            });
+        }
+        self.current = Some((name.to_string(), Instant::now()));
+    }
+
+    /// Record a warning shown to the user
+    pub fn warn(&mut self, message: &str) {
+        self.warnings.push(message.trim().to_string());
+    }
+
+    /// Record a destructive cleanup action (a deleted release or asset)
+    pub fn action(&mut self, description: &str) {
+        self.actions.push(description.to_string());
+    }
+
+    /// Keep the manifest of what the release uploaded
+    pub fn set_manifest(&mut self, manifest: &ReleaseManifest) {
+        self.manifest = Some(manifest.clone());
+    }
+
+    /// Keep the outcome of the test suite
+    pub fn set_tests(&mut self, tests: TestSummary) {
+        self.tests = Some(tests);
+    }
+
+    /// Record the outcome of a lint gate
+    pub fn add_lint(&mut self, result: LintResult) {
+        self.lints.push(result);
+    }
+
+    /// Record a finding of the advisory check
+    pub fn add_advisory(&mut self, finding: AdvisoryFinding) {
+        self.advisories.push(finding);
+    }
+
+    /// Record a violation found by the policy check
+    pub fn add_policy_finding(&mut self, finding: PolicyFinding) {
+        self.policy.push(finding);
+    }
+
+    /// Record that the run released during a freeze window, and why
+    pub fn set_freeze_override(&mut self, description: &str) {
+        self.freeze_override = Some(description.to_string());
+    }
+
+    /// All phases so far, the running one timed until now
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        let mut phases = self.finished.clone();
+        if let Some((name, started)) = &self.current {
+            phases.push(PhaseTiming {
+                name: name.clone(),
+                duration: started.elapsed(),
+            });
+        }
+        phases
+    }
+
+    /// Warnings in the order they were shown
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Cleanup actions in the order they were taken
+    pub fn actions(&self) -> &[String] {
+        &self.actions
+    }
+
+    /// Outcome of the test suite, if it ran
+    pub fn tests(&self) -> Option<&TestSummary> {
+        self.tests.as_ref()
+    }
+
+    /// Lint gate outcomes in the order they ran
+    pub fn lints(&self) -> &[LintResult] {
+        &self.lints
+    }
+
+    /// Findings of the advisory check
+    pub fn advisories(&self) -> &[AdvisoryFinding] {
+        &self.advisories
+    }
+
+    /// Violations found by the policy check
+    pub fn policy_findings(&self) -> &[PolicyFinding] {
+        &self.policy
+    }
+
+    /// Freeze window the run overrode, with the reason given
+    pub fn freeze_override(&self) -> Option<&str> {
+        self.freeze_override.as_deref()
+    }
+
+    /// Manifest of the release, once its artifacts were uploaded
+    pub fn manifest(&self) -> Option<&ReleaseManifest> {
+        self.manifest.as_ref()
+    }
+}
@@ -3,21 +3,44 @@
 //! This module provides a comprehensive CLI for release management operations,
 //! with proper argument parsing, command execution, and user feedback.
 
+pub mod abort;
 mod args;
 pub mod commands;
+mod journal;
 mod output;
+mod phase_selection;
 mod retry_config;
+pub mod timeouts;
+pub mod watchdog;
 
-pub use args::{Args, RuntimeConfig};
+pub use args::{Args, BundleCommand, Command, ConfigCommand, NotesSource, ReleaseChannel, RuntimeConfig};
 pub use commands::execute_command;
+pub use journal::{AdvisoryFinding, LintResult, PolicyFinding, RunJournal, TestSummary};
 pub use output::OutputManager;
 
 use crate::error::Result;
 use crate::EnvConfig;
 
 /// Main CLI entry point
-pub async fn run(env_config: EnvConfig) -> Result<i32> {
+///
+/// Shell rc sourcing happens here, after argument parsing, so that `--ci`
+/// can refuse it and require credentials from the process environment.
+pub async fn run() -> Result<i32> {
     let args = Args::parse_args();
+    // Releases stop gracefully on Ctrl-C / SIGTERM; bundle inspection just exits
+    if !matches!(args.command, Some(Command::Bundle { .. })) {
+        abort::install();
+    }
+    // Bundle inspection only reads local files and needs no credentials
+    let mut env_config = if args.ci || matches!(args.command, Some(Command::Bundle { .. })) {
+        EnvConfig::default()
+    } else {
+        crate::env::load_shell_env()
+    };
+    // Tokens stored with `login`; CI passes its tokens in the environment
+    if !args.ci && !matches!(args.command, Some(Command::Bundle { .. } | Command::Login { .. } | Command::Logout { .. })) {
+        env_config.load_keyring();
+    }
     execute_command(args, env_config).await
 }
 
@@ -35,6 +58,6 @@ pub fn validate_args(args: &Args) -> std::result::Result<(), String> {
 
 /// Create runtime configuration from arguments
 #[allow(dead_code)] // Public API - preserved for external consumers
-pub fn create_runtime_config(_args: &Args) -> RuntimeConfig {
-    RuntimeConfig::new()
+pub fn create_runtime_config(args: &Args) -> RuntimeConfig {
+    RuntimeConfig::from_args(args)
 }
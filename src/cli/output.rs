@@ -5,6 +5,8 @@
 #![allow(dead_code)] // Public API - methods may be used by external consumers
 
 use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
 /// Output manager for consistent colored terminal output
@@ -13,6 +15,10 @@ pub struct OutputManager {
     bufwtr: BufferWriter,
     verbose: bool,
     quiet: bool,
+    /// Emit GitHub Actions `::group::` workflow commands for sections
+    ci_groups: bool,
+    /// Whether a group is currently open (shared between clones)
+    group_open: Arc<AtomicBool>,
 }
 
 impl Clone for OutputManager {
@@ -21,6 +27,8 @@ impl Clone for OutputManager {
             bufwtr: BufferWriter::stdout(ColorChoice::Auto),
             verbose: self.verbose,
             quiet: self.quiet,
+            ci_groups: self.ci_groups,
+            group_open: Arc::clone(&self.group_open),
         }
     }
 }
@@ -32,9 +40,17 @@ impl OutputManager {
             bufwtr: BufferWriter::stdout(ColorChoice::Auto),
             verbose,
             quiet,
+            ci_groups: false,
+            group_open: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Emit GitHub Actions log groups instead of plain section headers
+    pub fn with_ci_groups(mut self, enabled: bool) -> Self {
+        self.ci_groups = enabled;
+        self
+    }
+
     /// Print an info message (normal output)
     pub fn info(&self, message: &str) -> std::io::Result<()> {
         if self.quiet {
@@ -142,6 +158,36 @@ impl OutputManager {
         self.bufwtr.print(&buffer)
     }
 
+    /// Start a log group
+    ///
+    /// In CI mode this emits `::group::` (closing any open group first, since
+    /// GitHub Actions does not nest groups); otherwise it prints a section header.
+    pub fn group(&self, title: &str) -> std::io::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+        if !self.ci_groups {
+            return self.section(title);
+        }
+
+        self.end_group()?;
+        let mut buffer = self.bufwtr.buffer();
+        let _ = writeln!(&mut buffer, "::group::{}", title);
+        self.group_open.store(true, Ordering::SeqCst);
+        self.bufwtr.print(&buffer)
+    }
+
+    /// End the current log group (no-op outside CI mode or without an open group)
+    pub fn end_group(&self) -> std::io::Result<()> {
+        if !self.ci_groups || !self.group_open.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let mut buffer = self.bufwtr.buffer();
+        let _ = writeln!(&mut buffer, "::endgroup::");
+        self.bufwtr.print(&buffer)
+    }
+
     /// Print indented text (for sub-items)
     pub fn indent(&self, message: &str) -> std::io::Result<()> {
         if self.quiet {
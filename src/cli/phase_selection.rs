@@ -0,0 +1,122 @@
+//! `--skip` / `--only` selection of release phases.
+//!
+//! Phases are the selectable [`ReleasePhase`]s: `github-release`, `build`,
+//! `bundles`, `upload` and `github-publish`. Skipping `github-release` reuses
+//! the release that already exists for the tag. Combinations that cannot
+//! work in a fresh release clone are refused up front.
+
+use crate::state::ReleasePhase;
+
+/// Release phases selected with `--skip` / `--only`
+#[derive(Debug, Clone, Default)]
+pub struct PhaseSelection {
+    skipped: Vec<ReleasePhase>,
+}
+
+impl PhaseSelection {
+    /// Resolve `--skip` and `--only` and check phase dependencies
+    ///
+    /// `--skip` also skips the phases depending on a skipped one; `--only`
+    /// refuses a phase whose dependency is not selected.
+    pub fn from_flags(skip: &[ReleasePhase], only: &[ReleasePhase]) -> Result<Self, String> {
+        if !skip.is_empty() && !only.is_empty() {
+            return Err("--skip and --only cannot be combined".to_string());
+        }
+
+        let mut skipped: Vec<ReleasePhase> = ReleasePhase::SELECTABLE
+            .into_iter()
+            .filter(|phase| skip.contains(phase) || (!only.is_empty() && !only.contains(phase)))
+            .collect();
+
+        // DEPENDENCIES is in execution order, so one pass resolves chains
+        for (phase, needs) in DEPENDENCIES {
+            if skipped.contains(&needs) && !skipped.contains(&phase) {
+                if !only.is_empty() {
+                    return Err(format!(
+                        "the {} phase needs the {} phase ({})",
+                        phase.cli_name(),
+                        needs.cli_name(),
+                        dependency_reason(phase)
+                    ));
+                }
+                skipped.push(phase);
+            }
+        }
+
+        if ReleasePhase::SELECTABLE.iter().all(|phase| skipped.contains(phase)) {
+            return Err("--skip leaves no phase to run".to_string());
+        }
+
+        Ok(Self { skipped })
+    }
+
+    /// Whether `phase` runs
+    pub fn runs(&self, phase: ReleasePhase) -> bool {
+        !self.skipped.contains(&phase)
+    }
+
+    /// The selection for a library crate, which has nothing to build or bundle
+    ///
+    /// The upload phase still uploads the release manifest.
+    pub fn without_binaries(&self) -> Self {
+        self.without(&[ReleasePhase::Building, ReleasePhase::Bundling])
+    }
+
+    /// The selection with `phases` left out, e.g. because an earlier run completed them
+    pub fn without(&self, phases: &[ReleasePhase]) -> Self {
+        let mut selection = self.clone();
+        for phase in phases {
+            if selection.runs(*phase) {
+                selection.skipped.push(*phase);
+            }
+        }
+        selection
+    }
+
+    /// Whether every phase runs
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    /// Selected phases, e.g. `build, bundles (skipping github-release, upload, github-publish)`
+    pub fn describe(&self) -> String {
+        let names = |phases: Vec<ReleasePhase>| {
+            phases.into_iter().map(ReleasePhase::cli_name).collect::<Vec<_>>().join(", ")
+        };
+        let running = ReleasePhase::SELECTABLE.into_iter().filter(|p| self.runs(*p)).collect();
+        let skipped = ReleasePhase::SELECTABLE.into_iter().filter(|p| !self.runs(*p)).collect();
+        format!("{} (skipping {})", names(running), names(skipped))
+    }
+}
+
+/// `(phase, phase it needs)`, in execution order
+const DEPENDENCIES: [(ReleasePhase, ReleasePhase); 2] = [
+    (ReleasePhase::Bundling, ReleasePhase::Building),
+    (ReleasePhase::Uploading, ReleasePhase::Bundling),
+];
+
+fn dependency_reason(phase: ReleasePhase) -> &'static str {
+    match phase {
+        // The shell integration archive is generated from the built binary
+        ReleasePhase::Bundling => "bundling uses the built binaries",
+        // A fresh release clone has no artifacts from an earlier run
+        _ => "there are no artifacts to upload otherwise",
+    }
+}
+
+/// Parse a phase name for `--skip` / `--only`
+pub fn parse_release_phase(value: &str) -> Result<ReleasePhase, String> {
+    if value == "crates-publish" {
+        return Err("crates-publish is not a phase of this tool: crates are published by `just publish` \
+                    before the release runs (use --skip-publish-verification to skip waiting for them on crates.io)"
+            .to_string());
+    }
+
+    ReleasePhase::SELECTABLE
+        .into_iter()
+        .find(|phase| phase.cli_name() == value)
+        .ok_or_else(|| {
+            let names: Vec<&str> = ReleasePhase::SELECTABLE.iter().map(|p| p.cli_name()).collect();
+            format!("unknown phase '{}' (expected {})", value, names.join(", "))
+        })
+}
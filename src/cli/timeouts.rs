@@ -0,0 +1,252 @@
+//! Per-phase timeouts and the overall release deadline.
+//!
+//! A hung notarization inside the bundler or a wedged Docker build would
+//! otherwise stall a release for hours. Each long-running phase gets its own
+//! limit, and an optional `--deadline` bounds the whole run; whichever is
+//! reached first aborts the phase with a recoverable error.
+//!
+//! Limits are resolved from, in increasing precedence: built-in defaults,
+//! `KODEGEN_BUILD_TIMEOUT` (build only), `[package.metadata.release.timeouts]`
+//! and `--timeout PHASE=DURATION`.
+
+use super::retry_config::CargoTimeoutConfig;
+use super::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use std::time::Duration;
+
+/// Release phase with its own timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimedPhase {
+    /// `cargo build --release` (per target)
+    Build,
+    /// One platform bundle, including signing and notarization
+    Bundle,
+    /// Uploading one artifact, including retries
+    Upload,
+    /// GitHub API calls (draft creation, publishing)
+    Github,
+    /// Waiting for published crates to appear in the crates.io index
+    Registry,
+    /// The test suite run before the release (`--run-tests`)
+    Test,
+}
+
+impl TimedPhase {
+    /// Name used on the command line and in Cargo.toml
+    pub fn name(self) -> &'static str {
+        match self {
+            TimedPhase::Build => "build",
+            TimedPhase::Bundle => "bundle",
+            TimedPhase::Upload => "upload",
+            TimedPhase::Github => "github",
+            TimedPhase::Registry => "registry",
+            TimedPhase::Test => "test",
+        }
+    }
+
+    /// Parse a phase name
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            TimedPhase::Build,
+            TimedPhase::Bundle,
+            TimedPhase::Upload,
+            TimedPhase::Github,
+            TimedPhase::Registry,
+            TimedPhase::Test,
+        ]
+        .into_iter()
+        .find(|phase| phase.name() == name)
+    }
+}
+
+/// Resolved timeout for every timed phase
+#[derive(Debug, Clone)]
+pub struct PhaseTimeouts {
+    build: Duration,
+    bundle: Duration,
+    upload: Duration,
+    github: Duration,
+    registry: Duration,
+    test: Duration,
+}
+
+impl Default for PhaseTimeouts {
+    fn default() -> Self {
+        Self {
+            build: Duration::from_secs(600),   // 10 minutes per target
+            bundle: Duration::from_secs(3600), // notarization can be slow
+            upload: Duration::from_secs(1800),
+            github: Duration::from_secs(300),
+            registry: Duration::from_secs(900), // index updates usually take seconds
+            test: Duration::from_secs(1800),
+        }
+    }
+}
+
+impl PhaseTimeouts {
+    /// Resolve timeouts from the environment, Cargo.toml and the command line
+    pub fn resolve(
+        env_config: &crate::EnvConfig,
+        configured: &[(TimedPhase, Duration)],
+        overrides: &[(TimedPhase, Duration)],
+    ) -> Self {
+        let mut timeouts = Self {
+            build: Duration::from_secs(CargoTimeoutConfig::from_env(env_config).build_timeout_secs),
+            ..Self::default()
+        };
+
+        for (phase, duration) in configured.iter().chain(overrides) {
+            *timeouts.slot(*phase) = *duration;
+        }
+        timeouts
+    }
+
+    /// Timeout for a phase
+    pub fn get(&self, phase: TimedPhase) -> Duration {
+        match phase {
+            TimedPhase::Build => self.build,
+            TimedPhase::Bundle => self.bundle,
+            TimedPhase::Upload => self.upload,
+            TimedPhase::Github => self.github,
+            TimedPhase::Registry => self.registry,
+            TimedPhase::Test => self.test,
+        }
+    }
+
+    fn slot(&mut self, phase: TimedPhase) -> &mut Duration {
+        match phase {
+            TimedPhase::Build => &mut self.build,
+            TimedPhase::Bundle => &mut self.bundle,
+            TimedPhase::Upload => &mut self.upload,
+            TimedPhase::Github => &mut self.github,
+            TimedPhase::Registry => &mut self.registry,
+            TimedPhase::Test => &mut self.test,
+        }
+    }
+
+    /// Run `operation` bounded by the phase timeout and the release deadline
+    ///
+    /// The future is dropped on timeout, or when it has not finished within the
+    /// grace period after an abort; child processes it spawned must use
+    /// `kill_on_drop` so they do not outlive the release.
+    pub async fn run<T, F>(
+        &self,
+        phase: TimedPhase,
+        operation: &str,
+        config: &RuntimeConfig,
+        future: F,
+    ) -> Result<T>
+    where
+        F: std::future::Future<Output = Result<T>>,
+    {
+        let phase_limit = self.get(phase);
+        let (limit, reason) = match config.deadline_remaining() {
+            Some(remaining) if remaining < phase_limit => (
+                remaining,
+                "release deadline reached (--deadline)".to_string(),
+            ),
+            _ => (
+                phase_limit,
+                format!(
+                    "{} phase exceeded its {} timeout",
+                    phase.name(),
+                    format_duration(phase_limit)
+                ),
+            ),
+        };
+
+        let timed_out = || {
+            ReleaseError::Cli(CliError::TimedOut {
+                operation: operation.to_string(),
+                reason: reason.clone(),
+            })
+        };
+
+        if limit.is_zero() {
+            return Err(timed_out());
+        }
+        // No new work once an abort was requested
+        super::abort::check(operation)?;
+
+        tokio::pin!(future);
+        let result = tokio::select! {
+            result = tokio::time::timeout(limit, &mut future) => result.unwrap_or_else(|_| Err(timed_out())),
+            _ = super::abort::cancelled() => {
+                // Let the operation finish if it can, so its result is kept
+                tokio::time::timeout(super::abort::GRACE_PERIOD, &mut future)
+                    .await
+                    .unwrap_or_else(|_| Err(super::abort::error(operation)))
+            }
+        };
+        result.map_err(|e| super::abort::or_aborted(operation, e))
+    }
+}
+
+/// Parse a duration like `45m`, `1h30m`, `90s` or a plain number of seconds
+pub fn parse_duration(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>()
+        && secs > 0
+    {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("invalid duration '{}': unknown unit '{}'", value, c)),
+        };
+        let amount: u64 = digits
+            .parse()
+            .map_err(|_| format!("invalid duration '{}': expected a number before '{}'", value, c))?;
+        total = total.saturating_add(amount.saturating_mul(unit));
+        digits.clear();
+    }
+
+    if !digits.is_empty() || total == 0 {
+        return Err(format!(
+            "invalid duration '{}': use e.g. 45m, 1h30m or 90s",
+            value
+        ));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// Parse a `PHASE=DURATION` command line override
+pub fn parse_phase_timeout(value: &str) -> std::result::Result<(TimedPhase, Duration), String> {
+    let (phase, duration) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected PHASE=DURATION, got '{}'", value))?;
+    let phase = TimedPhase::from_name(phase.trim()).ok_or_else(|| {
+        format!(
+            "unknown phase '{}' (expected build, bundle, upload, github, registry or test)",
+            phase
+        )
+    })?;
+    Ok((phase, parse_duration(duration)?))
+}
+
+/// Format a duration compactly, e.g. `1h30m`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    let mut out = String::new();
+    if h > 0 {
+        out.push_str(&format!("{}h", h));
+    }
+    if m > 0 {
+        out.push_str(&format!("{}m", m));
+    }
+    if s > 0 || out.is_empty() {
+        out.push_str(&format!("{}s", s));
+    }
+    out
+}
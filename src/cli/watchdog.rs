@@ -0,0 +1,284 @@
+//! Watchdog for subprocesses that stop producing output.
+//!
+//! `cargo build`, `hdiutil` and `makensis` occasionally hang without a word,
+//! and the phase timeout only notices hours later. Long-running commands run
+//! through [`crate::exec::Exec::output_watched`] or
+//! [`crate::exec::Exec::stream`], which watch their stdout and stderr: after
+//! `--stall-timeout` (default 10 minutes) without a line, the watchdog reports
+//! the stall with the command's last output (and its process tree
+//! with `--verbose`), then acts on `--on-stall`:
+//!
+//! - `warn` (default): keep waiting, and warn again if it stays silent;
+//! - `kill`: kill the command and fail with a recoverable timeout;
+//! - `retry`: kill it and run it once more, then fail if it stalls again.
+//!
+//! The stall timeout is separate from the phase timeouts in
+//! [`super::timeouts`], which still bound the total run time.
+
+use super::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+use std::collections::VecDeque;
+use std::process::{ExitStatus, Output, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// Stall timeout without `--stall-timeout`
+pub const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Trailing output lines shown with a stall warning
+const SHOWN_OUTPUT_LINES: usize = 20;
+
+/// What to do with a command that stopped producing output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum StallAction {
+    /// Warn and keep waiting
+    #[default]
+    Warn,
+    /// Kill the command and fail
+    Kill,
+    /// Kill the command and run it once more
+    Retry,
+}
+
+/// Stream a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Run `command` to completion and collect its output, like `Command::output`
+pub(crate) async fn output(
+    config: &RuntimeConfig,
+    label: &str,
+    command: &mut Command,
+    input: Option<&[u8]>,
+    spawn_error: impl Fn(std::io::Error) -> ReleaseError,
+) -> Result<Output> {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let status = stream(
+        config,
+        label,
+        command,
+        input,
+        |stream, line| {
+            let buffer = match stream {
+                Stream::Stdout => &mut stdout,
+                Stream::Stderr => &mut stderr,
+            };
+            buffer.extend_from_slice(line.as_bytes());
+            buffer.push(b'\n');
+        },
+        spawn_error,
+    )
+    .await?;
+    Ok(Output { status, stdout, stderr })
+}
+
+/// Run `command` to completion, passing each output line to `on_line`
+///
+/// `input` is written to a piped stdin, which is then closed. With
+/// `--on-stall retry`, `on_line` also sees the output of the killed run.
+pub(crate) async fn stream(
+    config: &RuntimeConfig,
+    label: &str,
+    command: &mut Command,
+    input: Option<&[u8]>,
+    mut on_line: impl FnMut(Stream, &str),
+    spawn_error: impl Fn(std::io::Error) -> ReleaseError,
+) -> Result<ExitStatus> {
+    let stall_timeout = config.stall_timeout();
+    let action = config.on_stall();
+    let attempts = if action == StallAction::Retry { 2 } else { 1 };
+
+    command.stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+    for attempt in 1..=attempts {
+        let mut child = command.spawn().map_err(&spawn_error)?;
+        if let Some(input) = input
+            && let Some(mut pipe) = child.stdin.take()
+        {
+            // Written alongside the output so a large input cannot deadlock;
+            // dropping the pipe closes stdin. A command exiting without reading
+            // it all fails on its own, so the write error is not reported.
+            let input = input.to_vec();
+            tokio::spawn(async move {
+                let _ = pipe.write_all(&input).await;
+            });
+        }
+        let (sender, mut lines) = mpsc::unbounded_channel();
+        if let Some(stdout) = child.stdout.take() {
+            forward(stdout, Stream::Stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward(stderr, Stream::Stderr, sender);
+        }
+
+        let mut tail = VecDeque::with_capacity(SHOWN_OUTPUT_LINES);
+        let mut silent_since = tokio::time::Instant::now();
+        let mut stalled = false;
+        loop {
+            tokio::select! {
+                line = lines.recv() => {
+                    let Some((stream, line)) = line else {
+                        break;
+                    };
+                    on_line(stream, &line);
+                    if tail.len() == SHOWN_OUTPUT_LINES {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                    silent_since = tokio::time::Instant::now();
+                }
+                _ = tokio::time::sleep_until(silent_since + stall_timeout) => {
+                    report_stall(config, label, stall_timeout, &tail, child.id());
+                    if action == StallAction::Warn {
+                        // Warn again after another silent period
+                        silent_since = tokio::time::Instant::now();
+                        continue;
+                    }
+                    let _ = child.kill().await;
+                    stalled = true;
+                    break;
+                }
+            }
+        }
+
+        if !stalled {
+            return child.wait().await.map_err(&spawn_error);
+        }
+        if attempt < attempts {
+            config
+                .warning_println(&format!("⚠️  Killed {}; running it again", label))
+                .expect("Failed to write to stdout");
+        }
+    }
+
+    Err(ReleaseError::Cli(CliError::TimedOut {
+        operation: label.to_string(),
+        reason: format!(
+            "no output for {} (--stall-timeout), killed with --on-stall {}",
+            super::timeouts::format_duration(stall_timeout),
+            if action == StallAction::Retry { "retry" } else { "kill" }
+        ),
+    }))
+}
+
+/// Send the lines of `reader` to `sender` until it closes
+///
+/// Lines are decoded lossily: a tool printing invalid UTF-8 must not stop
+/// the forwarding and look stalled.
+fn forward(
+    reader: impl AsyncRead + Unpin + Send + 'static,
+    stream: Stream,
+    sender: mpsc::UnboundedSender<(Stream, String)>,
+) {
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        while let Ok(read) = reader.read_until(b'\n', &mut buffer).await {
+            if read == 0 {
+                break;
+            }
+            let line = buffer.strip_suffix(b"\n").unwrap_or(&buffer);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if sender.send((stream, String::from_utf8_lossy(line).into_owned())).is_err() {
+                break;
+            }
+            buffer.clear();
+        }
+    });
+}
+
+/// Warn about a silent command, with its last output and process tree
+fn report_stall(config: &RuntimeConfig, label: &str, silent_for: Duration, tail: &VecDeque<String>, pid: Option<u32>) {
+    config
+        .warning_println(&format!(
+            "⚠️  {} has printed nothing for {}",
+            label,
+            super::timeouts::format_duration(silent_for)
+        ))
+        .expect("Failed to write to stdout");
+    if tail.is_empty() {
+        config.indent("(no output yet)").expect("Failed to write to stdout");
+    } else {
+        config.indent("Last output:").expect("Failed to write to stdout");
+        for line in tail {
+            config.indent(&format!("  {}", line)).expect("Failed to write to stdout");
+        }
+    }
+
+    if config.is_verbose()
+        && let Some(tree) = pid.and_then(process_tree)
+    {
+        config.indent("Process tree:").expect("Failed to write to stdout");
+        for line in tree {
+            config.indent(&format!("  {}", line)).expect("Failed to write to stdout");
+        }
+    }
+}
+
+/// `ps` lines of `pid` and its descendants (Unix only)
+fn process_tree(pid: u32) -> Option<Vec<String>> {
+    if !cfg!(unix) {
+        return None;
+    }
+    let output = crate::exec::Exec::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,etime=,args="])
+        .stdout_blocking()
+        .ok()?;
+
+    let processes: Vec<(u32, u32, String)> = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            Some((pid, ppid, line.trim().to_string()))
+        })
+        .collect();
+
+    let mut tree = Vec::new();
+    let mut parents = vec![(pid, 0)];
+    while let Some((parent, depth)) = parents.pop() {
+        if let Some((_, _, line)) = processes.iter().find(|(pid, _, _)| *pid == parent) {
+            tree.push(format!("{}{}", "  ".repeat(depth), line));
+        }
+        parents.extend(
+            processes
+                .iter()
+                .filter(|(_, ppid, _)| *ppid == parent)
+                .map(|(pid, _, _)| (*pid, depth + 1)),
+        );
+    }
+    (!tree.is_empty()).then_some(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn forward_keeps_reading_past_invalid_utf8() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        forward(&b"one\r\nt\xffo\nthree"[..], Stream::Stdout, sender);
+
+        let mut lines = Vec::new();
+        while let Some((_, line)) = receiver.recv().await {
+            lines.push(line);
+        }
+        assert_eq!(lines, ["one", "t\u{fffd}o", "three"]);
+    }
+
+    #[tokio::test]
+    async fn watched_commands_read_their_input() {
+        let output = crate::exec::Exec::new("cat")
+            .input("piped\n")
+            .output_watched(&RuntimeConfig::new())
+            .await
+            .unwrap();
+        assert_eq!(output.stdout, b"piped\n");
+    }
+}
@@ -0,0 +1,227 @@
+//! Compression of tarball and raw-file artifacts.
+//!
+//! Configured in `[package.metadata.release.compression]`, with optional
+//! overrides per artifact kind:
+//!
+//! ```toml
+//! [package.metadata.release.compression]
+//! gzip-level = 9     # level of every .tar.gz (1-9; default: as tar writes them)
+//! zstd-level = 19    # also release a .zst variant at this level (1-22)
+//!
+//! [package.metadata.release.compression.debug-symbols]
+//! zstd = false       # no zstd variant for this kind
+//! ```
+//!
+//! Kinds are `targets` (build matrix tarballs), `bsd`, `bundlers` (custom
+//! bundler output) and `debug-symbols`. A `.tar.gz` gets a `.tar.zst` with the
+//! same contents; any other file that is not already compressed (e.g. a bare
+//! binary from a custom bundler) gets `<name>.zst`. Packages, zip files and
+//! other compressed formats are released as they are.
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Artifacts with their own compression settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// Build matrix tarballs (`[[package.metadata.release.targets]]`)
+    Targets,
+    /// BSD tarballs and packages
+    Bsd,
+    /// Output of custom bundlers
+    Bundlers,
+    /// Split debug symbol archives
+    DebugSymbols,
+}
+
+impl ArtifactKind {
+    const ALL: [Self; 4] = [Self::Targets, Self::Bsd, Self::Bundlers, Self::DebugSymbols];
+
+    /// Key of the kind's override table
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Targets => "targets",
+            Self::Bsd => "bsd",
+            Self::Bundlers => "bundlers",
+            Self::DebugSymbols => "debug-symbols",
+        }
+    }
+}
+
+/// Compression of one kind of artifact
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionSettings {
+    /// Level `.tar.gz` artifacts are recompressed at (`None` keeps tar's default)
+    pub gzip_level: Option<u32>,
+    /// Level of the zstd variant (`None`: no variant)
+    pub zstd_level: Option<i32>,
+}
+
+impl CompressionSettings {
+    /// Whether the artifacts are left as built
+    pub fn is_noop(&self) -> bool {
+        self.gzip_level.is_none() && self.zstd_level.is_none()
+    }
+}
+
+/// `[package.metadata.release.compression]`
+#[derive(Debug, Clone, Default)]
+pub struct CompressionConfig {
+    default: CompressionSettings,
+    overrides: Vec<(ArtifactKind, CompressionSettings)>,
+}
+
+impl CompressionConfig {
+    /// Parse `[package.metadata.release.compression]` (defaults when absent)
+    pub fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("compression"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let invalid = |section: &str, reason: &str| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.compression{}] {}", section, reason),
+            })
+        };
+        let table = table.as_table().ok_or_else(|| invalid("", "must be a table"))?;
+
+        let default = parse_settings(table, CompressionSettings::default(), "")?;
+        let mut overrides = Vec::new();
+        for (key, value) in table {
+            if matches!(key.as_str(), "gzip-level" | "zstd-level" | "zstd") {
+                continue;
+            }
+            let kind = ArtifactKind::ALL.into_iter().find(|kind| kind.key() == key).ok_or_else(|| {
+                invalid(
+                    "",
+                    &format!(
+                        "unknown key '{}' (expected gzip-level, zstd-level, zstd or an artifact kind: targets, bsd, bundlers, debug-symbols)",
+                        key
+                    ),
+                )
+            })?;
+            let section = format!(".{}", key);
+            let kind_table = value.as_table().ok_or_else(|| invalid(&section, "must be a table"))?;
+            overrides.push((kind, parse_settings(kind_table, default, &section)?));
+        }
+
+        Ok(Self { default, overrides })
+    }
+
+    /// Settings for artifacts of `kind`
+    pub fn settings(&self, kind: ArtifactKind) -> CompressionSettings {
+        self.overrides
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map_or(self.default, |(_, settings)| *settings)
+    }
+}
+
+/// Settings of one table, starting from `inherited`
+fn parse_settings(
+    table: &toml::map::Map<String, toml::Value>,
+    inherited: CompressionSettings,
+    section: &str,
+) -> Result<CompressionSettings> {
+    let invalid = |reason: String| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("[package.metadata.release.compression{}] {}", section, reason),
+        })
+    };
+    let level = |key: &str, range: std::ops::RangeInclusive<i64>| -> Result<Option<i64>> {
+        match table.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_integer()
+                .filter(|level| range.contains(level))
+                .map(Some)
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "{} must be an integer from {} to {}",
+                        key,
+                        range.start(),
+                        range.end()
+                    ))
+                }),
+        }
+    };
+
+    let mut settings = inherited;
+    if let Some(level) = level("gzip-level", 1..=9)? {
+        settings.gzip_level = Some(level as u32);
+    }
+    if let Some(level) = level("zstd-level", 1..=22)? {
+        settings.zstd_level = Some(level as i32);
+    }
+    match table.get("zstd") {
+        None => {}
+        Some(toml::Value::Boolean(false)) => settings.zstd_level = None,
+        Some(toml::Value::Boolean(true)) if settings.zstd_level.is_none() => {
+            return Err(invalid("zstd = true needs a zstd-level".to_string()));
+        }
+        Some(toml::Value::Boolean(true)) => {}
+        Some(_) => return Err(invalid("zstd must be true or false".to_string())),
+    }
+    Ok(settings)
+}
+
+/// Apply `settings` to `path`: recompress a `.tar.gz` in place and write its
+/// zstd variant next to it
+///
+/// Returns the variants written. Blocking; large artifacts take a while at
+/// high zstd levels.
+pub fn compress_variants(path: &Path, settings: CompressionSettings) -> Result<Vec<PathBuf>> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let lower = name.to_ascii_lowercase();
+
+    let mut variants = Vec::new();
+    if lower.ends_with(".tar.gz") {
+        let stem = &name[..name.len() - ".tar.gz".len()];
+        if let Some(level) = settings.gzip_level {
+            let recompressed = path.with_file_name(format!("{}.recompress", name));
+            let mut encoder = flate2::write::GzEncoder::new(
+                BufWriter::new(File::create(&recompressed)?),
+                flate2::Compression::new(level),
+            );
+            std::io::copy(&mut flate2::read::GzDecoder::new(BufReader::new(File::open(path)?)), &mut encoder)?;
+            encoder.finish()?.flush()?;
+            std::fs::rename(&recompressed, path)?;
+        }
+        if let Some(level) = settings.zstd_level {
+            let variant = path.with_file_name(format!("{}.tar.zst", stem));
+            let mut decoder = flate2::read::GzDecoder::new(BufReader::new(File::open(path)?));
+            write_zstd(&mut decoder, &variant, level)?;
+            variants.push(variant);
+        }
+    } else if let Some(level) = settings.zstd_level.filter(|_| !is_compressed(&lower)) {
+        let variant = path.with_file_name(format!("{}.zst", name));
+        write_zstd(&mut BufReader::new(File::open(path)?), &variant, level)?;
+        variants.push(variant);
+    }
+    Ok(variants)
+}
+
+fn write_zstd(input: &mut impl std::io::Read, output: &Path, level: i32) -> Result<()> {
+    let mut encoder = zstd::stream::write::Encoder::new(BufWriter::new(File::create(output)?), level)?;
+    std::io::copy(input, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+/// Formats that are compressed already (a zstd variant would not be smaller)
+fn is_compressed(lower_name: &str) -> bool {
+    const EXTENSIONS: &[&str] = &[
+        ".gz", ".tgz", ".zst", ".xz", ".txz", ".bz2", ".zip", ".7z", ".deb", ".rpm", ".dmg", ".pkg", ".msi",
+        ".msix", ".appimage", ".jar", ".apk", ".snap", ".flatpak",
+    ];
+    EXTENSIONS.iter().any(|ext| lower_name.ends_with(ext))
+}
@@ -0,0 +1,213 @@
+//! Artifact encryption for private distribution.
+//!
+//! Configured in `[package.metadata.release.encryption]`, with either age
+//! recipients or a symmetric passphrase:
+//!
+//! ```toml
+//! [package.metadata.release.encryption]
+//! recipients = ["age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p"]
+//! # or
+//! passphrase-env = "RELEASE_PASSPHRASE"
+//! ```
+//!
+//! Artifacts are encrypted after bundling and before upload, so the GitHub
+//! release and the mirrors only receive ciphertext. Recipients use the `age`
+//! CLI and produce `<artifact>.age`. A passphrase uses `gpg --symmetric`
+//! (AES-256) and produces `<artifact>.gpg`, because `age` only reads
+//! passphrases from a terminal. The release manifest records which assets
+//! are encrypted, with the name and SHA-256 of the plaintext.
+
+use crate::error::{CliError, ReleaseError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::exec::Exec;
+
+/// How an asset was encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionMethod {
+    /// `age` to the configured recipients
+    Age,
+    /// `gpg --symmetric` with a passphrase
+    Gpg,
+}
+
+impl EncryptionMethod {
+    /// Extension appended to the encrypted file
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Age => "age",
+            Self::Gpg => "gpg",
+        }
+    }
+}
+
+/// `[package.metadata.release.encryption]`
+#[derive(Debug, Clone)]
+pub enum EncryptionConfig {
+    /// age public keys (`age1...`) or SSH public keys
+    Recipients(Vec<String>),
+    /// Environment variable holding the passphrase
+    Passphrase { env: String },
+}
+
+impl EncryptionConfig {
+    /// Parse `[package.metadata.release.encryption]`, if present
+    pub fn from_package(package: &toml::Value) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("encryption"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.encryption] {}", reason),
+            })
+        };
+
+        let recipients = match table.get("recipients") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_array()
+                    .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect::<Option<Vec<_>>>())
+                    .filter(|recipients| !recipients.is_empty())
+                    .ok_or_else(|| invalid("recipients must be a non-empty array of strings"))?,
+            ),
+        };
+        let passphrase_env = match table.get("passphrase-env") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .ok_or_else(|| invalid("passphrase-env must be a string"))?
+                    .to_string(),
+            ),
+        };
+
+        match (recipients, passphrase_env) {
+            (Some(recipients), None) => Ok(Some(Self::Recipients(recipients))),
+            (None, Some(env)) => Ok(Some(Self::Passphrase { env })),
+            (Some(_), Some(_)) => Err(invalid("sets both recipients and passphrase-env; use one")),
+            (None, None) => Err(invalid("needs recipients or passphrase-env")),
+        }
+    }
+
+    /// Method used for this configuration
+    pub fn method(&self) -> EncryptionMethod {
+        match self {
+            Self::Recipients(_) => EncryptionMethod::Age,
+            Self::Passphrase { .. } => EncryptionMethod::Gpg,
+        }
+    }
+
+    /// Environment variable that must hold the passphrase, if any
+    pub fn passphrase_env(&self) -> Option<&str> {
+        match self {
+            Self::Recipients(_) => None,
+            Self::Passphrase { env } => Some(env),
+        }
+    }
+
+    /// Encrypt `path` next to itself and return the encrypted file
+    pub async fn encrypt(&self, path: &Path, env_config: &crate::EnvConfig) -> Result<PathBuf> {
+        let output = encrypted_path(path, self.method());
+        let input = path.to_string_lossy().to_string();
+        let target = output.to_string_lossy().to_string();
+
+        match self {
+            Self::Recipients(recipients) => {
+                let mut args = Vec::new();
+                for recipient in recipients {
+                    args.push("-r".to_string());
+                    args.push(recipient.clone());
+                }
+                args.extend(["-o".to_string(), target, input]);
+                run("age", &args, None).await?;
+            }
+            Self::Passphrase { env } => {
+                let passphrase = passphrase(env_config, env)?;
+                let mut args = gpg_batch_args();
+                args.extend(
+                    ["--symmetric", "--cipher-algo", "AES256", "-o", &target, &input]
+                        .map(String::from),
+                );
+                run("gpg", &args, Some(&passphrase)).await?;
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// Decrypt `input` into `output`
+///
+/// age files need an `identity` file; gpg files need the passphrase.
+pub async fn decrypt(
+    method: EncryptionMethod,
+    input: &Path,
+    output: &Path,
+    identity: Option<&Path>,
+    passphrase: Option<&str>,
+) -> Result<()> {
+    let input = input.to_string_lossy().to_string();
+    let target = output.to_string_lossy().to_string();
+
+    match method {
+        EncryptionMethod::Age => {
+            let identity = identity.ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: "Decrypting age assets needs --identity <FILE>".to_string(),
+                })
+            })?;
+            let args = ["-d", "-i", &identity.to_string_lossy(), "-o", &target, &input].map(String::from);
+            run("age", &args, None).await
+        }
+        EncryptionMethod::Gpg => {
+            let passphrase = passphrase.ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: "Decrypting gpg assets needs the passphrase".to_string(),
+                })
+            })?;
+            let mut args = gpg_batch_args();
+            args.extend(["--decrypt", "-o", &target, &input].map(String::from));
+            run("gpg", &args, Some(passphrase)).await
+        }
+    }
+}
+
+/// Passphrase from the variable named in `passphrase-env`
+pub fn passphrase(env_config: &crate::EnvConfig, env: &str) -> Result<String> {
+    env_config.get(env).filter(|value| !value.is_empty()).ok_or_else(|| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Encryption passphrase not set: {}", env),
+        })
+    })
+}
+
+/// `<path>.age` / `<path>.gpg`
+pub fn encrypted_path(path: &Path, method: EncryptionMethod) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(method.extension());
+    PathBuf::from(name)
+}
+
+/// Non-interactive gpg reading the passphrase from stdin
+fn gpg_batch_args() -> Vec<String> {
+    ["--batch", "--yes", "--quiet", "--pinentry-mode", "loopback", "--passphrase-fd", "0"]
+        .map(String::from)
+        .to_vec()
+}
+
+async fn run(program: &str, args: &[String], stdin: Option<&str>) -> Result<()> {
+    let mut command = Exec::new(program);
+    command.args(args);
+    if let Some(input) = stdin {
+        command.input(format!("{}\n", input));
+    }
+    command.run().await?;
+    Ok(())
+}
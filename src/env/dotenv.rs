@@ -0,0 +1,135 @@
+//! Minimal `.env` file parser for repository-local configuration.
+//!
+//! Supports `KEY=VALUE` lines, an optional `export ` prefix, `#` comments,
+//! and single- or double-quoted values. Double-quoted values understand the
+//! `\n`, `\t`, `\"` and `\\` escapes; nothing is interpolated.
+
+use crate::error::{EnvError, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Parse a `.env` file, returning an empty map if it does not exist
+pub fn load_dotenv(path: &Path) -> Result<HashMap<String, String>> {
+    if !path.is_file() {
+        return Ok(HashMap::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    parse_dotenv(&content).map_err(|(line, reason)| {
+        EnvError::InvalidDotenv {
+            path: path.to_path_buf(),
+            line,
+            reason,
+        }
+        .into()
+    })
+}
+
+/// Parse `.env` content; errors carry the 1-based line number
+fn parse_dotenv(content: &str) -> std::result::Result<HashMap<String, String>, (usize, String)> {
+    let mut vars = HashMap::new();
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw_line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err((line_number, "expected KEY=VALUE".to_string()));
+        };
+
+        let key = key.trim();
+        if key.is_empty()
+            || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            || key.starts_with(|c: char| c.is_ascii_digit())
+        {
+            return Err((line_number, format!("invalid variable name '{}'", key)));
+        }
+
+        let value = parse_value(value.trim()).map_err(|reason| (line_number, reason))?;
+        vars.insert(key.to_string(), value);
+    }
+
+    Ok(vars)
+}
+
+/// Parse the value part of a line, handling quotes and trailing comments
+fn parse_value(value: &str) -> std::result::Result<String, String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest
+            .find('\'')
+            .ok_or_else(|| "unterminated single quote".to_string())?;
+        return Ok(rest[..end].to_string());
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut result = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Ok(result),
+                '\\' => match chars.next() {
+                    Some('n') => result.push('\n'),
+                    Some('t') => result.push('\t'),
+                    Some(other) => result.push(other),
+                    None => break,
+                },
+                other => result.push(other),
+            }
+        }
+        return Err("unterminated double quote".to_string());
+    }
+
+    // Unquoted: strip inline comments introduced by " #"
+    let value = match value.find(" #") {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> HashMap<String, String> {
+        parse_dotenv(content).unwrap()
+    }
+
+    #[test]
+    fn parses_quoted_and_unquoted_values() {
+        let vars = parse(concat!(
+            "PLAIN=value # comment\n",
+            "SINGLE='kept # as \\n is'\n",
+            "DOUBLE=\"line\\none\\t\\\"quoted\\\"\"\n",
+            "EMPTY=\n",
+            "HASH=a#b\n",
+        ));
+        assert_eq!(vars["PLAIN"], "value");
+        assert_eq!(vars["SINGLE"], "kept # as \\n is");
+        assert_eq!(vars["DOUBLE"], "line\none\t\"quoted\"");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars["HASH"], "a#b");
+    }
+
+    #[test]
+    fn skips_comments_and_the_export_prefix() {
+        let vars = parse("# header\n\n  # indented\nexport   TOKEN=abc\n  KEY = spaced \n");
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars["TOKEN"], "abc");
+        assert_eq!(vars["KEY"], "spaced");
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        assert_eq!(parse_dotenv("A=1\nnot a pair\n").unwrap_err().0, 2);
+        assert_eq!(parse_dotenv("1ST=x").unwrap_err().0, 1);
+        assert!(parse_dotenv("A=\"open").unwrap_err().1.contains("unterminated"));
+        assert!(parse_dotenv("A='open").unwrap_err().1.contains("unterminated"));
+    }
+}
@@ -0,0 +1,144 @@
+//! macOS keychain as a source of signing credentials.
+//!
+//! Lets releases sign and notarize with credentials already stored in the
+//! user's keychains instead of exporting `APPLE_CERTIFICATE` and friends from
+//! the shell rc file:
+//!
+//! - `APPLE_SIGNING_IDENTITY` / `APPLE_TEAM_ID` from the first valid
+//!   "Developer ID Application" identity (`security find-identity`)
+//! - `APPLE_PASSWORD` from a generic password item whose account is `APPLE_ID`
+//!
+//! A `.p12` can also be imported into a temporary keychain that only exists
+//! for the duration of the release ([`TemporaryKeychain`]).
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use crate::exec::Exec;
+
+/// Keychain item service holding the notarization app-specific password
+/// (override with `KODEGEN_KEYCHAIN_SERVICE`)
+pub const DEFAULT_PASSWORD_SERVICE: &str = "kodegen_bundler_release";
+
+/// Look up signing and notarization credentials in the user's keychains
+///
+/// Returns nothing on non-macOS hosts or when `security` is unavailable.
+pub(super) fn lookup(apple_id: Option<&str>, service: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    if !cfg!(target_os = "macos") {
+        return vars;
+    }
+
+    if let Some(identity) = find_signing_identity() {
+        if let Some(team_id) = team_id(&identity) {
+            vars.insert("APPLE_TEAM_ID".to_string(), team_id);
+        }
+        vars.insert("APPLE_SIGNING_IDENTITY".to_string(), identity);
+    }
+
+    if let Some(apple_id) = apple_id
+        && let Some(password) =
+            security(&["find-generic-password", "-s", service, "-a", apple_id, "-w"])
+    {
+        let password = password.trim();
+        if !password.is_empty() {
+            vars.insert("APPLE_PASSWORD".to_string(), password.to_string());
+        }
+    }
+
+    vars
+}
+
+/// Run `security` and capture stdout, or `None` on any failure
+fn security(args: &[&str]) -> Option<String> {
+    Exec::new("security")
+        .args(args)
+        .run_blocking()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// First valid "Developer ID Application" identity in the keychain search list
+fn find_signing_identity() -> Option<String> {
+    let output = security(&["find-identity", "-v", "-p", "codesigning"])?;
+    output.lines().find_map(|line| {
+        let start = line.find("\"Developer ID Application:")? + 1;
+        let end = line[start..].find('"')?;
+        Some(line[start..start + end].to_string())
+    })
+}
+
+/// Team ID from an identity like `Developer ID Application: Name (TEAMID)`
+fn team_id(identity: &str) -> Option<String> {
+    let start = identity.rfind('(')? + 1;
+    let end = identity.rfind(')')?;
+    (end > start).then(|| identity[start..end].to_string())
+}
+
+/// Certificate imported into a temporary keychain for the duration of a release
+///
+/// The keychain is added to the user's search list so `codesign` can find the
+/// identity. Call [`TemporaryKeychain::cleanup`] when the release finishes;
+/// dropping it deletes the keychain on a best-effort basis.
+pub struct TemporaryKeychain {
+    #[cfg(target_os = "macos")]
+    inner: kodegen_bundler_sign::macos::TempKeychain,
+    #[cfg(not(target_os = "macos"))]
+    never: std::convert::Infallible,
+}
+
+impl TemporaryKeychain {
+    /// Import a `.p12` certificate into a new temporary keychain
+    pub async fn import(p12: &Path, password: &str) -> Result<Self> {
+        #[cfg(target_os = "macos")]
+        {
+            let inner = kodegen_bundler_sign::macos::TempKeychain::from_certificate_file(p12, password)
+                .await
+                .map_err(|e| {
+                    ReleaseError::Cli(CliError::ExecutionFailed {
+                        command: format!("security import {}", p12.display()),
+                        reason: e.to_string(),
+                    })
+                })?;
+            Ok(Self { inner })
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = password;
+            Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!(
+                    "Cannot import {}: temporary keychains require a macOS host",
+                    p12.display()
+                ),
+            }))
+        }
+    }
+
+    /// Signing identity found in the imported certificate
+    pub fn signing_identity(&self) -> &str {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.signing_identity()
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        match self.never {}
+    }
+
+    /// Remove the keychain from the search list and delete it
+    pub async fn cleanup(self) -> Result<()> {
+        #[cfg(target_os = "macos")]
+        {
+            self.inner.cleanup().await.map_err(|e| {
+                ReleaseError::Cli(CliError::ExecutionFailed {
+                    command: "security delete-keychain".to_string(),
+                    reason: e.to_string(),
+                })
+            })
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        match self.never {}
+    }
+}
@@ -0,0 +1,82 @@
+//! GitHub and crates.io tokens stored in the OS keyring.
+//!
+//! `login` stores a token under the keychain service (`kodegen_bundler_release`,
+//! or `KODEGEN_KEYCHAIN_SERVICE`) with the variable name as the account, so it
+//! no longer has to be exported from a shell rc file:
+//!
+//! - macOS: a generic password in the login keychain (`security`)
+//! - Linux and BSD: the Secret Service, e.g. GNOME Keyring or KWallet (`secret-tool`)
+//!
+//! Stored tokens are the lowest-precedence layer of [`super::EnvConfig`];
+//! anything set in the environment wins. Windows Credential Manager has no
+//! command-line tool that reads secrets back, so Windows is not supported.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::exec::Exec;
+
+/// Variables that can be stored in the keyring
+pub const TOKEN_VARS: [&str; 2] = ["GH_TOKEN", "CARGO_REGISTRY_TOKEN"];
+
+/// Stored value of `name`, or `None` when absent or the keyring is unavailable
+pub(super) fn get(service: &str, name: &str) -> Option<String> {
+    let command = if cfg!(target_os = "macos") {
+        let mut command = Exec::new("security");
+        command.args(["find-generic-password", "-s", service, "-a", name, "-w"]);
+        command
+    } else if cfg!(unix) {
+        let mut command = Exec::new("secret-tool");
+        command.args(["lookup", "service", service, "account", name]);
+        command
+    } else {
+        return None;
+    };
+
+    let value = command.stdout_blocking().ok()?;
+    (!value.is_empty()).then_some(value)
+}
+
+/// Store `value` as `name`, replacing an earlier value
+pub fn store(service: &str, name: &str, value: &str) -> Result<()> {
+    if cfg!(target_os = "macos") {
+        Exec::new("security")
+            .args(["add-generic-password", "-U", "-s", service, "-a", name, "-w"])
+            .secret_arg(value)
+            .run_blocking()?;
+    } else if cfg!(unix) {
+        let label = format!("{} {}", service, name);
+        Exec::new("secret-tool")
+            .args(["store", "--label", &label, "service", service, "account", name])
+            .input(value)
+            .run_blocking()?;
+    } else {
+        return Err(unsupported());
+    }
+    Ok(())
+}
+
+/// Delete the stored `name`; returns whether there was one
+pub fn delete(service: &str, name: &str) -> Result<bool> {
+    if get(service, name).is_none() {
+        return Ok(false);
+    }
+    if cfg!(target_os = "macos") {
+        Exec::new("security")
+            .args(["delete-generic-password", "-s", service, "-a", name])
+            .run_blocking()?;
+    } else if cfg!(unix) {
+        Exec::new("secret-tool")
+            .args(["clear", "service", service, "account", name])
+            .run_blocking()?;
+    } else {
+        return Err(unsupported());
+    }
+    Ok(true)
+}
+
+fn unsupported() -> ReleaseError {
+    ReleaseError::Cli(CliError::InvalidArguments {
+        reason: "Storing tokens in the OS keyring is not supported on this platform; \
+                 set GH_TOKEN / CARGO_REGISTRY_TOKEN in the environment"
+            .to_string(),
+    })
+}
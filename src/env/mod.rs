@@ -0,0 +1,258 @@
+//! Environment configuration for release operations.
+//!
+//! Holds variables captured from the user's shell rc file ([`sourcing`]), the
+//! repository's `.env` file ([`dotenv`]), the macOS keychain ([`keychain`])
+//! and tokens stored in the OS keyring ([`keyring`]), and validates them, together with the process environment, against the
+//! typed schema in [`schema`].
+
+mod dotenv;
+mod keychain;
+pub mod keyring;
+pub mod schema;
+mod sourcing;
+
+pub use keychain::TemporaryKeychain;
+pub use schema::EnvRequirement;
+pub use sourcing::load_shell_env;
+
+use crate::error::{EnvError, Result};
+use std::collections::HashMap;
+
+/// Environment configuration that holds variables from the user's shell rc
+/// file, the repository's `.env` file and the macOS keychain, with fallback to
+/// actual environment variables.
+///
+/// Lookup precedence: shell rc > process environment > `.env` > keychain.
+///
+/// This struct eliminates the need for unsafe `std::env::set_var()` calls
+/// by storing parsed values and providing safe access methods.
+#[derive(Clone, Debug, Default)]
+pub struct EnvConfig {
+    /// Variables captured after sourcing the shell rc file
+    shell_vars: HashMap<String, String>,
+    /// Variables parsed from the repository's .env file
+    dotenv_vars: HashMap<String, String>,
+    /// Signing credentials found in the macOS keychain, and tokens from the OS keyring
+    keychain_vars: HashMap<String, String>,
+    /// Bundle unsigned: no signing credentials reach the bundler
+    signing_disabled: bool,
+}
+
+impl EnvConfig {
+    /// Create new EnvConfig from captured shell variables
+    pub(crate) fn new(shell_vars: HashMap<String, String>) -> Self {
+        Self {
+            shell_vars,
+            dotenv_vars: HashMap::new(),
+            keychain_vars: HashMap::new(),
+            signing_disabled: false,
+        }
+    }
+
+    /// Layer variables from a `.env` file underneath the shell and process environment
+    ///
+    /// A missing file is not an error. Returns the number of variables loaded.
+    pub fn load_dotenv(&mut self, path: &std::path::Path) -> Result<usize> {
+        self.dotenv_vars = dotenv::load_dotenv(path)?;
+        Ok(self.dotenv_vars.len())
+    }
+
+    /// Fill in signing credentials from the macOS keychain
+    ///
+    /// Only variables not already set in another layer are used. Returns the
+    /// number of variables found; always zero on other platforms.
+    pub fn load_keychain(&mut self) -> usize {
+        let found = keychain::lookup(self.get("APPLE_ID").as_deref(), &self.keychain_service());
+
+        for (key, value) in found {
+            if !self.is_set(&key) {
+                self.keychain_vars.insert(key, value);
+            }
+        }
+        self.keychain_vars.len()
+    }
+
+    /// Fill in tokens stored with `login` from the OS keyring
+    ///
+    /// Only tokens not already set in another layer are looked up. Returns the
+    /// number of tokens found.
+    pub fn load_keyring(&mut self) -> usize {
+        let service = self.keychain_service();
+        let mut found = 0;
+        for name in keyring::TOKEN_VARS {
+            if !self.is_set(name)
+                && let Some(value) = keyring::get(&service, name)
+            {
+                self.keychain_vars.insert(name.to_string(), value);
+                found += 1;
+            }
+        }
+        found
+    }
+
+    /// Keychain / keyring service holding this tool's secrets
+    pub fn keychain_service(&self) -> String {
+        self.get("KODEGEN_KEYCHAIN_SERVICE")
+            .unwrap_or_else(|| keychain::DEFAULT_PASSWORD_SERVICE.to_string())
+    }
+
+    /// Use the identity from a temporary keychain for signing
+    ///
+    /// An explicitly set `APPLE_SIGNING_IDENTITY` still takes precedence.
+    pub fn set_signing_identity(&mut self, identity: &str) {
+        self.keychain_vars
+            .insert("APPLE_SIGNING_IDENTITY".to_string(), identity.to_string());
+    }
+
+    /// Signing and notarization variables to forward to the bundler process
+    ///
+    /// Empty once signing is disabled.
+    pub fn signing_vars(&self) -> Vec<(&'static str, String)> {
+        if self.signing_disabled {
+            return Vec::new();
+        }
+        schema::specs_for(EnvRequirement::MacosSigning)
+            .chain(schema::specs_for(EnvRequirement::MacosNotarization))
+            .filter_map(|spec| self.get(spec.name).map(|value| (spec.name, value)))
+            .collect()
+    }
+
+    /// Variables of `requirement` that are set, for forwarding to a child process
+    pub fn vars_for(&self, requirement: EnvRequirement) -> Vec<(&'static str, String)> {
+        schema::specs_for(requirement)
+            .filter_map(|spec| self.get(spec.name).map(|value| (spec.name, value)))
+            .collect()
+    }
+
+    /// Stop signing and notarization credentials from reaching the bundler
+    pub fn disable_signing(&mut self) {
+        self.signing_disabled = true;
+    }
+
+    /// Whether bundles are produced unsigned
+    pub fn signing_disabled(&self) -> bool {
+        self.signing_disabled
+    }
+
+    /// Variables to remove from the bundler's inherited environment
+    ///
+    /// With signing disabled, credentials exported in the process environment
+    /// would otherwise still be inherited by the bundler.
+    pub fn masked_signing_vars(&self) -> Vec<&'static str> {
+        if !self.signing_disabled {
+            return Vec::new();
+        }
+        schema::specs_for(EnvRequirement::MacosSigning)
+            .chain(schema::specs_for(EnvRequirement::MacosNotarization))
+            .map(|spec| spec.name)
+            .collect()
+    }
+
+    /// Get environment variable value, checking shell rc vars first,
+    /// then the actual environment, then the repository's .env file.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.shell_vars
+            .get(key)
+            .cloned()
+            .or_else(|| std::env::var(key).ok())
+            .or_else(|| self.dotenv_vars.get(key).cloned())
+            .or_else(|| self.keychain_vars.get(key).cloned())
+    }
+
+    /// Check if an environment variable is set (in any layer)
+    pub fn is_set(&self, key: &str) -> bool {
+        self.shell_vars.contains_key(key)
+            || std::env::var(key).is_ok()
+            || self.dotenv_vars.contains_key(key)
+            || self.keychain_vars.contains_key(key)
+    }
+
+    /// Validate the value of every schema variable that is currently set
+    ///
+    /// Unset variables are ignored; use [`EnvConfig::require`] to check that a
+    /// capability has everything it needs. Returns warnings for values that
+    /// are used clamped to their maximum.
+    pub fn validate(&self) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+        for spec in schema::ENV_SCHEMA {
+            if let Some(value) = self.get(spec.name) {
+                spec.validate(&value).map_err(|reason| EnvError::InvalidValue {
+                    name: spec.name.to_string(),
+                    reason,
+                })?;
+                warnings.extend(spec.clamp_warning(&value));
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Ensure all variables needed by `requirement` are present and valid
+    pub fn require(&self, requirement: EnvRequirement) -> Result<()> {
+        let mut missing: Vec<String> = Vec::new();
+        let mut reported: Vec<&str> = Vec::new();
+
+        for spec in schema::specs_for(requirement) {
+            match self.get(spec.name) {
+                Some(value) => {
+                    spec.validate(&value).map_err(|reason| EnvError::InvalidValue {
+                        name: spec.name.to_string(),
+                        reason,
+                    })?;
+                }
+                None if spec.required => {
+                    let satisfied = spec.alternatives.iter().any(|alt| self.is_set(alt));
+                    let already_reported =
+                        spec.alternatives.iter().any(|alt| reported.contains(alt));
+
+                    if !satisfied && !already_reported {
+                        reported.push(spec.name);
+                        if spec.alternatives.is_empty() {
+                            missing.push(spec.name.to_string());
+                        } else {
+                            missing.push(format!(
+                                "{} (or {})",
+                                spec.name,
+                                spec.alternatives.join(", ")
+                            ));
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(EnvError::MissingVariables {
+                names: missing,
+                required_for: requirement.to_string(),
+            }
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_variables_take_precedence_over_dotenv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(&path, "KODEGEN_TEST_BOTH=dotenv\nKODEGEN_TEST_DOTENV=dotenv\n").unwrap();
+
+        let mut env_config = EnvConfig::new(HashMap::from([(
+            "KODEGEN_TEST_BOTH".to_string(),
+            "shell".to_string(),
+        )]));
+        assert_eq!(env_config.load_dotenv(&path).unwrap(), 2);
+        assert_eq!(env_config.get("KODEGEN_TEST_BOTH").as_deref(), Some("shell"));
+        assert_eq!(env_config.get("KODEGEN_TEST_DOTENV").as_deref(), Some("dotenv"));
+
+        // A missing file clears the layer rather than failing
+        assert_eq!(env_config.load_dotenv(&dir.path().join("missing")).unwrap(), 0);
+        assert!(!env_config.is_set("KODEGEN_TEST_DOTENV"));
+    }
+}
@@ -0,0 +1,470 @@
+//! Typed schema of the environment variables this tool understands.
+//!
+//! Every variable read anywhere in the release pipeline is declared here with
+//! its purpose, the capability that needs it, and how its value is validated.
+//! `EnvConfig` uses the schema to reject missing or malformed values before any
+//! work starts, instead of failing halfway through a release.
+
+use base64::Engine;
+
+/// Capability that depends on environment configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvRequirement {
+    /// Creating GitHub releases and uploading assets
+    GitHubRelease,
+    /// Signing macOS bundles with a Developer ID certificate
+    MacosSigning,
+    /// Notarizing macOS bundles with App Store Connect
+    MacosNotarization,
+    /// Signing the Mac App Store package
+    MacAppStore,
+    /// Submitting MSIX packages to the Microsoft Store
+    MicrosoftStore,
+    /// Uploading artifacts to S3 mirrors
+    S3Mirror,
+    /// Optional tuning knobs (retries, timeouts, startup behaviour)
+    Tuning,
+}
+
+impl std::fmt::Display for EnvRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvRequirement::GitHubRelease => write!(f, "GitHub releases"),
+            EnvRequirement::MacosSigning => write!(f, "macOS code signing"),
+            EnvRequirement::MacosNotarization => write!(f, "macOS notarization"),
+            EnvRequirement::MacAppStore => write!(f, "Mac App Store signing"),
+            EnvRequirement::MicrosoftStore => write!(f, "Microsoft Store submission"),
+            EnvRequirement::S3Mirror => write!(f, "S3 mirror uploads"),
+            EnvRequirement::Tuning => write!(f, "tuning"),
+        }
+    }
+}
+
+/// Shape of a variable's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvValueKind {
+    /// Presence is what matters, any value is accepted
+    Flag,
+    /// Non-empty free-form text
+    Text,
+    /// Path to an existing file
+    File,
+    /// Base64-encoded binary content
+    Base64,
+    /// Unsigned integer; readers clamp values above `max` to it
+    Integer { max: u64 },
+}
+
+/// Declaration of a single environment variable
+#[derive(Debug, Clone, Copy)]
+pub struct EnvVarSpec {
+    /// Variable name
+    pub name: &'static str,
+    /// Human-readable purpose
+    pub description: &'static str,
+    /// Capability that needs this variable
+    pub required_for: EnvRequirement,
+    /// Whether the capability cannot work without it
+    pub required: bool,
+    /// Other variables that satisfy the requirement instead of this one
+    pub alternatives: &'static [&'static str],
+    /// How the value is validated
+    pub kind: EnvValueKind,
+}
+
+impl EnvVarSpec {
+    /// Validate a value against this variable's declared kind
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self.kind {
+            EnvValueKind::Flag => Ok(()),
+            EnvValueKind::Text => {
+                if value.trim().is_empty() {
+                    Err("value is empty".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            EnvValueKind::File => {
+                if std::path::Path::new(value).is_file() {
+                    Ok(())
+                } else {
+                    Err(format!("file not found: {}", value))
+                }
+            }
+            EnvValueKind::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(value.trim())
+                .map(|_| ())
+                .map_err(|e| format!("not valid base64: {}", e)),
+            EnvValueKind::Integer { .. } => match value.trim().parse::<u64>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err(format!("'{}' is not a non-negative integer", value)),
+            },
+        }
+    }
+
+    /// Why a valid value is not used as given: an integer above its maximum
+    /// is clamped
+    pub fn clamp_warning(&self, value: &str) -> Option<String> {
+        let EnvValueKind::Integer { max } = self.kind else {
+            return None;
+        };
+        value
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|v| *v > max)
+            .map(|v| format!("{}={} exceeds the maximum of {}; using {}", self.name, v, max, max))
+    }
+}
+
+/// All environment variables read by the release pipeline
+pub static ENV_SCHEMA: &[EnvVarSpec] = &[
+    // GitHub
+    EnvVarSpec {
+        name: "GH_TOKEN",
+        description: "GitHub token used to create releases and upload assets",
+        required_for: EnvRequirement::GitHubRelease,
+        required: true,
+        alternatives: &["GITHUB_TOKEN"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "GITHUB_TOKEN",
+        description: "Fallback GitHub token when GH_TOKEN is not set",
+        required_for: EnvRequirement::GitHubRelease,
+        required: true,
+        alternatives: &["GH_TOKEN"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "GITHUB_API_URL",
+        description: "GitHub REST API root, for GitHub Enterprise Server or a mock API",
+        required_for: EnvRequirement::GitHubRelease,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // macOS signing
+    EnvVarSpec {
+        name: "APPLE_CERTIFICATE",
+        description: "Base64-encoded Developer ID .p12 certificate",
+        required_for: EnvRequirement::MacosSigning,
+        required: true,
+        alternatives: &["APPLE_SIGNING_IDENTITY"],
+        kind: EnvValueKind::Base64,
+    },
+    EnvVarSpec {
+        name: "APPLE_CERTIFICATE_PASSWORD",
+        description: "Password protecting APPLE_CERTIFICATE (or the .p12 given to --import-p12)",
+        required_for: EnvRequirement::MacosSigning,
+        required: true,
+        alternatives: &["APPLE_SIGNING_IDENTITY"],
+        kind: EnvValueKind::Flag,
+    },
+    EnvVarSpec {
+        name: "APPLE_SIGNING_IDENTITY",
+        description: "Keychain signing identity, e.g. \"Developer ID Application: Name (TEAMID)\"",
+        required_for: EnvRequirement::MacosSigning,
+        required: true,
+        alternatives: &["APPLE_CERTIFICATE"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "APPLE_TEAM_ID",
+        description: "Apple Developer team identifier",
+        required_for: EnvRequirement::MacosSigning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // macOS notarization
+    EnvVarSpec {
+        name: "APPLE_API_KEY",
+        description: "App Store Connect API key ID",
+        required_for: EnvRequirement::MacosNotarization,
+        required: true,
+        alternatives: &["APPLE_ID"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "APPLE_API_ISSUER",
+        description: "App Store Connect API issuer ID",
+        required_for: EnvRequirement::MacosNotarization,
+        required: true,
+        alternatives: &["APPLE_ID"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "APPLE_API_KEY_PATH",
+        description: "Path to the App Store Connect .p8 private key",
+        required_for: EnvRequirement::MacosNotarization,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::File,
+    },
+    EnvVarSpec {
+        name: "APPLE_API_KEY_CONTENT",
+        description: "Base64-encoded App Store Connect .p8 private key",
+        required_for: EnvRequirement::MacosNotarization,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Base64,
+    },
+    EnvVarSpec {
+        name: "APPLE_ID",
+        description: "Apple ID for notarization with an app-specific password",
+        required_for: EnvRequirement::MacosNotarization,
+        required: true,
+        alternatives: &["APPLE_API_KEY"],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "APPLE_PASSWORD",
+        description: "App-specific password for APPLE_ID",
+        required_for: EnvRequirement::MacosNotarization,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_KEYCHAIN_SERVICE",
+        description: "Keychain item service holding the app-specific password for APPLE_ID",
+        required_for: EnvRequirement::MacosNotarization,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // Mac App Store
+    EnvVarSpec {
+        name: "MAS_APPLICATION_IDENTITY",
+        description: "Identity signing the App Store app, e.g. \"3rd Party Mac Developer Application: Name (TEAMID)\"",
+        required_for: EnvRequirement::MacAppStore,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "MAS_INSTALLER_IDENTITY",
+        description: "Identity signing the App Store pkg, e.g. \"3rd Party Mac Developer Installer: Name (TEAMID)\"",
+        required_for: EnvRequirement::MacAppStore,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // Microsoft Store
+    EnvVarSpec {
+        name: "PARTNER_CENTER_TENANT_ID",
+        description: "Azure AD tenant of the Partner Center account",
+        required_for: EnvRequirement::MicrosoftStore,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "PARTNER_CENTER_CLIENT_ID",
+        description: "Client ID of the Azure AD application with Partner Center access",
+        required_for: EnvRequirement::MicrosoftStore,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "PARTNER_CENTER_CLIENT_SECRET",
+        description: "Client secret (key) of the Azure AD application",
+        required_for: EnvRequirement::MicrosoftStore,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // S3 mirrors
+    EnvVarSpec {
+        name: "AWS_ACCESS_KEY_ID",
+        description: "Access key ID used to sign uploads to S3 mirrors",
+        required_for: EnvRequirement::S3Mirror,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "AWS_SECRET_ACCESS_KEY",
+        description: "Secret access key used to sign uploads to S3 mirrors",
+        required_for: EnvRequirement::S3Mirror,
+        required: true,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "AWS_SESSION_TOKEN",
+        description: "Session token of temporary AWS credentials",
+        required_for: EnvRequirement::S3Mirror,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    // Tuning
+    EnvVarSpec {
+        name: "KODEGEN_SKIP_SHELL_RC",
+        description: "Skip sourcing the shell rc/profile file at startup",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Flag,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_SKIP_ZSHRC",
+        description: "Legacy alias for KODEGEN_SKIP_SHELL_RC",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Flag,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_REMOTES",
+        description: "Git remotes to release from, in preference order (same as --remote)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_GIT_AUTH",
+        description: "Git authentication: auto, ssh or token (same as --git-auth)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_PRERELEASE",
+        description: "Which releases are marked as a prerelease (same as --prerelease)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_TAG_VIA",
+        description: "How tags are written to GitHub: git or api (same as --tag-via)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_COMMIT_STATUS",
+        description: "Post a commit status per release phase (same as --commit-status)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Flag,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_SKIP_DISK_CHECK",
+        description: "Skip the disk-space preflight before cloning and building",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Flag,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_RETRY_GIT",
+        description: "Retry count for git operations",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 10 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_RETRY_GITHUB",
+        description: "Retry count for GitHub API calls",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 20 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_RETRY_UPLOADS",
+        description: "Retry count for asset uploads",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 20 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_RETRY_PUBLISH",
+        description: "Retry count for release publishing",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 10 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_RETRY_CLEANUP",
+        description: "Retry count for cleanup operations",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 10 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_BUILD_TIMEOUT",
+        description: "Timeout in seconds for cargo build",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 3600 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_DEADLINE",
+        description: "Overall release deadline (same as --deadline, e.g. 45m)",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+    EnvVarSpec {
+        name: "KODEGEN_UPDATE_TIMEOUT",
+        description: "Timeout in seconds for cargo update",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Integer { max: 1800 },
+    },
+    EnvVarSpec {
+        name: "KODEGEN_REGISTRY_INDEX",
+        description: "Sparse index checked for published crates instead of crates.io",
+        required_for: EnvRequirement::Tuning,
+        required: false,
+        alternatives: &[],
+        kind: EnvValueKind::Text,
+    },
+];
+
+/// Look up the schema entry for a variable
+pub fn lookup(name: &str) -> Option<&'static EnvVarSpec> {
+    ENV_SCHEMA.iter().find(|spec| spec.name == name)
+}
+
+/// Iterate over the variables needed by a capability
+pub fn specs_for(requirement: EnvRequirement) -> impl Iterator<Item = &'static EnvVarSpec> {
+    ENV_SCHEMA
+        .iter()
+        .filter(move |spec| spec.required_for == requirement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lookup;
+
+    #[test]
+    fn integers_above_max_are_clamped_not_rejected() {
+        let spec = lookup("KODEGEN_RETRY_GIT").unwrap();
+        assert!(spec.validate("50").is_ok());
+        assert_eq!(
+            spec.clamp_warning("50").as_deref(),
+            Some("KODEGEN_RETRY_GIT=50 exceeds the maximum of 10; using 10")
+        );
+        assert!(spec.clamp_warning("3").is_none());
+        assert!(spec.validate("-1").is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Shell rc/profile sourcing.
+//!
+//! Detects the user's login shell from `$SHELL`, sources the matching rc or
+//! profile file in a child process and captures the resulting environment.
+//! Nothing is written back into this process with `std::env::set_var()`.
+
+use super::EnvConfig;
+use crate::error::{CliError, ReleaseError};
+use crate::exec::Exec;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Maximum time a shell rc file may take to source
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Variables that disable shell rc sourcing when set
+const SKIP_VARS: [&str; 2] = ["KODEGEN_SKIP_SHELL_RC", "KODEGEN_SKIP_ZSHRC"];
+
+/// Shell families with distinct rc syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellKind {
+    Zsh,
+    Bash,
+    Fish,
+    /// Any other POSIX-compatible shell (`sh`, `dash`, `ksh`, ...)
+    Posix,
+}
+
+impl ShellKind {
+    /// Detect the user's shell from the `$SHELL` path
+    pub fn detect() -> Self {
+        std::env::var("SHELL")
+            .ok()
+            .as_deref()
+            .map(Self::from_shell_path)
+            .unwrap_or(ShellKind::Posix)
+    }
+
+    /// Classify a shell executable path such as `/usr/bin/fish`
+    pub fn from_shell_path(path: &str) -> Self {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        match name {
+            "zsh" => ShellKind::Zsh,
+            "bash" => ShellKind::Bash,
+            "fish" => ShellKind::Fish,
+            _ => ShellKind::Posix,
+        }
+    }
+
+    /// Executable used to source the rc file
+    fn program(self) -> &'static str {
+        match self {
+            ShellKind::Zsh => "zsh",
+            ShellKind::Bash => "bash",
+            ShellKind::Fish => "fish",
+            ShellKind::Posix => "sh",
+        }
+    }
+
+    /// Candidate rc files in order of preference; the first existing one is sourced
+    fn rc_candidates(self, home: &Path) -> Vec<PathBuf> {
+        match self {
+            ShellKind::Zsh => vec![home.join(".zshrc"), home.join(".zprofile")],
+            ShellKind::Bash => vec![
+                home.join(".bashrc"),
+                home.join(".bash_profile"),
+                home.join(".profile"),
+            ],
+            ShellKind::Fish => {
+                let config_home = std::env::var_os("XDG_CONFIG_HOME")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| home.join(".config"));
+                vec![config_home.join("fish").join("config.fish")]
+            }
+            ShellKind::Posix => vec![home.join(".profile")],
+        }
+    }
+
+    /// Script that sources `rc_file` and prints the environment as `KEY\0VALUE\0` pairs
+    fn capture_script(self, rc_file: &Path) -> String {
+        let rc = shell_quote(&rc_file.display().to_string());
+        match self {
+            ShellKind::Fish => format!(
+                r#"source {rc}; and env | while read -l line; set -l kv (string split -m 1 = -- $line); printf '%s\0%s\0' $kv[1] "$kv[2]"; end"#
+            ),
+            ShellKind::Posix => format!(
+                r#". {rc} && env | while IFS='=' read -r key value; do printf '%s\0%s\0' "$key" "$value"; done"#
+            ),
+            ShellKind::Zsh | ShellKind::Bash => format!(
+                r#"source {rc} && env | while IFS='=' read -r key value; do printf '%s\0%s\0' "$key" "$value"; done"#
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for ShellKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program())
+    }
+}
+
+/// Quote a path for use inside a single shell word
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Check whether rc sourcing has been disabled through the environment
+pub fn sourcing_disabled() -> bool {
+    SKIP_VARS.iter().any(|var| std::env::var_os(var).is_some())
+}
+
+/// Source the user's shell rc file and return the captured environment.
+///
+/// Falls back to an empty configuration (process environment only) when
+/// sourcing is disabled, no home directory or rc file exists, or the shell
+/// is not installed. Exits the process with troubleshooting hints when the rc
+/// file writes to stderr or does not finish within [`SOURCE_TIMEOUT`].
+pub fn load_shell_env() -> EnvConfig {
+    // Allow skipping rc sourcing if problematic
+    // Useful for: CI environments, debugging, or when the rc file has issues
+    if sourcing_disabled() {
+        return EnvConfig::default();
+    }
+
+    // Windows has no login-shell rc convention; use the process environment
+    if cfg!(windows) {
+        return EnvConfig::default();
+    }
+
+    let Some(home) = dirs::home_dir() else {
+        return EnvConfig::default();
+    };
+
+    let shell = ShellKind::detect();
+    let Some(rc_file) = shell
+        .rc_candidates(&home)
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+    else {
+        return EnvConfig::default();
+    };
+
+    // Tokens stay in the environment: the rc file may build on them
+    let output = Exec::new(shell.program())
+        .arg("-c")
+        .arg(shell.capture_script(&rc_file))
+        .timeout(SOURCE_TIMEOUT)
+        .with_credentials()
+        .output_blocking();
+    // Check for a timeout and for warnings/errors from rc sourcing
+    let problem = match &output {
+        Err(ReleaseError::Cli(CliError::TimedOut { .. })) => {
+            Some(format!("Sourcing did not finish within {}s", SOURCE_TIMEOUT.as_secs()))
+        }
+        // Shell not installed
+        Err(_) => return EnvConfig::default(),
+        Ok(output) if !output.stderr.is_empty() => Some(String::from_utf8_lossy(&output.stderr).into_owned()),
+        Ok(_) => None,
+    };
+    if let Some(problem) = problem {
+        eprintln!("\n❌ Error: Failed to source {} with {}:", rc_file.display(), shell);
+        eprintln!("{}", problem);
+        eprintln!("\n💡 Troubleshooting:");
+        eprintln!("   1. Fix errors in {} (it must run non-interactively)", rc_file.display());
+        eprintln!("   2. OR skip shell rc sourcing: export KODEGEN_SKIP_SHELL_RC=1");
+        eprintln!("   3. OR set env vars directly or in the repository's .env file\n");
+        std::process::exit(1);
+    }
+    let Ok(output) = output else {
+        return EnvConfig::default();
+    };
+
+    EnvConfig::new(parse_null_separated(&output.stdout))
+}
+
+/// Parse null-separated key-value pairs
+///
+/// Format: KEY1\0VALUE1\0KEY2\0VALUE2\0...
+fn parse_null_separated(data: &[u8]) -> HashMap<String, String> {
+    let env_data = String::from_utf8_lossy(data);
+    let mut parts = env_data.split('\0');
+    let mut vars = HashMap::new();
+
+    while let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+        if !key.is_empty() {
+            vars.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    vars
+}
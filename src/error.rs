@@ -1,7 +1,33 @@
 //! Error types for release operations.
+//!
+//! Every [`ReleaseError`] maps to a stable, machine-readable code and a
+//! category, so automation can branch on the cause of a failure without
+//! parsing messages. Codes never change meaning once published; new causes get
+//! new codes.
+//!
+//! | Category | Exit code | Codes |
+//! |----------|-----------|-------|
+//! | `internal` | 1 | `E-INT-001` unexpected error |
+//! | `validation` | 2 | `E-CLI-001` invalid arguments, `E-CLI-002` missing argument, `E-CLI-003` conflicting arguments, `E-CLI-004` command failed, `E-WS-001`..`E-WS-005` workspace problems, `E-PARSE-001` JSON, `E-PARSE-002` TOML |
+//! | `environment` | 3 | `E-ENV-001` missing variables, `E-ENV-002` invalid value, `E-ENV-003` invalid `.env` file |
+//! | `auth` | 4 | `E-GH-001` GitHub rejected the credentials, `E-GH-002` permission denied or rate limited, `E-AUTH-001` a command was refused credentials |
+//! | `network` | 5 | `E-NET-001` connection failure, `E-GH-005` GitHub unreachable |
+//! | `conflict` | 6 | `E-GH-004` already exists on GitHub, `E-CONFLICT-001` a command reported a conflict, `E-STATE-004` concurrent state modification |
+//! | `timeout` | 7 | `E-TIME-001` phase timeout or deadline |
+//! | `git` | 8 | `E-GIT-001` git command failed |
+//! | `github` | 9 | `E-GH-003` not found, `E-GH-006` other GitHub API failure |
+//! | `state` | 10 | `E-STATE-001` corrupted, `E-STATE-002` not found, `E-STATE-003` version mismatch, `E-STATE-005` save failed, `E-STATE-006` load failed |
+//! | `io` | 11 | `E-IO-001` file system error |
+//! | `partial` | 12 | `E-PARTIAL-001` the release was published, but a later step failed |
+//! | `aborted` | 130 | `E-ABORT-001` stopped by Ctrl-C or SIGTERM |
+//!
+//! GitHub and command failures carry free-form messages from the API or the
+//! failing tool. Their auth, network and conflict causes are recognized from
+//! HTTP statuses and well-known phrases in those messages.
 
 #![allow(dead_code)]
 
+use serde::Serialize;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -23,6 +49,10 @@ pub enum ReleaseError {
     #[error("CLI error: {0}")]
     Cli(#[from] CliError),
 
+    /// Environment configuration errors
+    #[error("Environment error: {0}")]
+    Env(#[from] EnvError),
+
     /// IO errors
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -144,6 +174,53 @@ pub enum CliError {
         command: String,
         reason: String,
     },
+
+    /// Phase timeout or release deadline exceeded
+    #[error("Timed out: {operation} - {reason}")]
+    TimedOut {
+        operation: String,
+        reason: String,
+    },
+
+    /// Stopped by SIGINT / SIGTERM
+    #[error("Aborted by {signal} during {operation}")]
+    Aborted {
+        operation: String,
+        signal: String,
+    },
+
+    /// The release is public, but a step after publishing failed
+    #[error("Release {url} was published, but {reason}")]
+    PartiallyPublished {
+        url: String,
+        reason: String,
+    },
+}
+
+/// Environment configuration errors
+#[derive(Error, Debug)]
+pub enum EnvError {
+    /// Variables needed by a capability are not set
+    #[error("Missing environment variable(s) for {required_for}: {}", names.join(", "))]
+    MissingVariables {
+        names: Vec<String>,
+        required_for: String,
+    },
+
+    /// Variable is set but its value is malformed
+    #[error("Invalid value for environment variable {name}: {reason}")]
+    InvalidValue {
+        name: String,
+        reason: String,
+    },
+
+    /// Repository .env file could not be parsed
+    #[error("Invalid .env file {path}:{line}: {reason}")]
+    InvalidDotenv {
+        path: PathBuf,
+        line: usize,
+        reason: String,
+    },
 }
 
 impl ReleaseError {
@@ -154,6 +231,38 @@ impl ReleaseError {
                 "Navigate to a directory containing a Cargo workspace".to_string(),
                 "Ensure you have a Cargo.toml file with [workspace] section".to_string(),
             ],
+            ReleaseError::Env(EnvError::MissingVariables { names, .. }) => {
+                let mut suggestions: Vec<String> = names
+                    .iter()
+                    .map(|name| {
+                        let var = name.split_whitespace().next().unwrap_or(name);
+                        match crate::env::schema::lookup(var) {
+                            Some(spec) => format!("export {}=<value>  # {}", var, spec.description),
+                            None => format!("export {}=<value>", var),
+                        }
+                    })
+                    .collect();
+                suggestions.push(
+                    "Add the exports to your shell rc file, the repository's .env file, or the CI environment"
+                        .to_string(),
+                );
+                suggestions
+            }
+            ReleaseError::Env(EnvError::InvalidValue { name, .. }) => vec![
+                format!("Fix or unset {} and re-run the release", name),
+            ],
+            ReleaseError::Cli(CliError::Aborted { .. }) => vec![
+                "Re-run with --skip github-release to rebuild into the draft release, if one was created".to_string(),
+                "Re-run with --only github-publish if every artifact was uploaded".to_string(),
+                "Re-run the release as before to start over (the interrupted draft is replaced)".to_string(),
+            ],
+            ReleaseError::Cli(CliError::PartiallyPublished { .. }) => vec![
+                "The release is live; finish the failed step by hand instead of re-running the release".to_string(),
+            ],
+            ReleaseError::Cli(CliError::TimedOut { .. }) => vec![
+                "Raise the phase limit with --timeout PHASE=DURATION or [package.metadata.release.timeouts]".to_string(),
+                "Extend or drop --deadline".to_string(),
+            ],
             _ => vec!["Check the error message above for specific details".to_string()],
         }
     }
@@ -162,7 +271,211 @@ impl ReleaseError {
     pub fn is_recoverable(&self) -> bool {
         !matches!(
             self,
-            ReleaseError::Workspace(WorkspaceError::RootNotFound)
+            ReleaseError::Workspace(WorkspaceError::RootNotFound) | ReleaseError::Env(_)
         )
     }
+
+    /// Stable machine-readable error code (e.g. `E-GIT-001`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            ReleaseError::Workspace(e) => match e {
+                WorkspaceError::RootNotFound => "E-WS-001",
+                WorkspaceError::InvalidStructure { .. } => "E-WS-002",
+                WorkspaceError::PackageNotFound { .. } => "E-WS-003",
+                WorkspaceError::MissingCargoToml { .. } => "E-WS-004",
+                WorkspaceError::InvalidPackage { .. } => "E-WS-005",
+            },
+            ReleaseError::State(e) => match e {
+                StateError::Corrupted { .. } => "E-STATE-001",
+                StateError::NotFound => "E-STATE-002",
+                StateError::VersionMismatch { .. } => "E-STATE-003",
+                StateError::ConcurrentModification { .. } => "E-STATE-004",
+                StateError::SaveFailed { .. } => "E-STATE-005",
+                StateError::LoadFailed { .. } => "E-STATE-006",
+            },
+            ReleaseError::Cli(e) => match e {
+                CliError::InvalidArguments { .. } => "E-CLI-001",
+                CliError::MissingArgument { .. } => "E-CLI-002",
+                CliError::ConflictingArguments { .. } => "E-CLI-003",
+                CliError::ExecutionFailed { command, reason } => {
+                    match classify_message(reason) {
+                        Some(ErrorCategory::Auth) => "E-AUTH-001",
+                        Some(ErrorCategory::Network) => "E-NET-001",
+                        Some(ErrorCategory::Conflict) => "E-CONFLICT-001",
+                        _ if command.starts_with("git ") || command == "git" => "E-GIT-001",
+                        _ => "E-CLI-004",
+                    }
+                }
+                CliError::TimedOut { .. } => "E-TIME-001",
+                CliError::Aborted { .. } => "E-ABORT-001",
+                CliError::PartiallyPublished { .. } => "E-PARTIAL-001",
+            },
+            ReleaseError::Env(e) => match e {
+                EnvError::MissingVariables { .. } => "E-ENV-001",
+                EnvError::InvalidValue { .. } => "E-ENV-002",
+                EnvError::InvalidDotenv { .. } => "E-ENV-003",
+            },
+            ReleaseError::Io(_) => "E-IO-001",
+            ReleaseError::Json(_) => "E-PARSE-001",
+            ReleaseError::Toml(_) => "E-PARSE-002",
+            ReleaseError::GitHub(message) => {
+                let lower = message.to_lowercase();
+                if lower.contains("401 unauthorized") || lower.contains("bad credentials") {
+                    "E-GH-001"
+                } else if lower.contains("403 forbidden") || lower.contains("rate limit") {
+                    "E-GH-002"
+                } else if lower.contains("404 not found") || lower.contains("not found") {
+                    "E-GH-003"
+                } else if classify_message(message) == Some(ErrorCategory::Conflict) {
+                    "E-GH-004"
+                } else if classify_message(message) == Some(ErrorCategory::Network) {
+                    "E-GH-005"
+                } else {
+                    "E-GH-006"
+                }
+            }
+            ReleaseError::Anyhow(e) => match classify_message(&e.to_string()) {
+                Some(ErrorCategory::Network) => "E-NET-001",
+                _ => "E-INT-001",
+            },
+        }
+    }
+
+    /// Category of this error, derived from its code
+    pub fn category(&self) -> ErrorCategory {
+        match self.code() {
+            "E-GH-001" | "E-GH-002" | "E-AUTH-001" => ErrorCategory::Auth,
+            "E-NET-001" | "E-GH-005" => ErrorCategory::Network,
+            "E-GH-004" | "E-STATE-004" | "E-CONFLICT-001" => ErrorCategory::Conflict,
+            "E-TIME-001" => ErrorCategory::Timeout,
+            "E-ABORT-001" => ErrorCategory::Aborted,
+            "E-PARTIAL-001" => ErrorCategory::Partial,
+            "E-GIT-001" => ErrorCategory::Git,
+            "E-IO-001" => ErrorCategory::Io,
+            "E-INT-001" => ErrorCategory::Internal,
+            code if code.starts_with("E-GH-") => ErrorCategory::GitHub,
+            code if code.starts_with("E-ENV-") => ErrorCategory::Environment,
+            code if code.starts_with("E-STATE-") => ErrorCategory::State,
+            _ => ErrorCategory::Validation,
+        }
+    }
+
+    /// Process exit code for this error (see the module docs)
+    pub fn exit_code(&self) -> i32 {
+        self.category().exit_code()
+    }
+
+    /// Machine-readable summary for `--error-format json`
+    pub fn report(&self) -> ErrorReport {
+        ErrorReport {
+            code: self.code(),
+            category: self.category(),
+            exit_code: self.exit_code(),
+            message: self.to_string(),
+            recoverable: self.is_recoverable(),
+            suggestions: self.recovery_suggestions(),
+        }
+    }
+}
+
+/// Broad cause of a failure, for automation to branch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    /// Unexpected failure
+    Internal,
+    /// Bad arguments, configuration or workspace layout
+    Validation,
+    /// Missing or malformed environment variables
+    Environment,
+    /// Credentials rejected or insufficient
+    Auth,
+    /// Connection failures
+    Network,
+    /// Something already exists or was changed concurrently
+    Conflict,
+    /// Phase timeout or release deadline
+    Timeout,
+    /// A git command failed
+    Git,
+    /// A GitHub API call failed for another reason
+    #[serde(rename = "github")]
+    GitHub,
+    /// Release state file problems
+    State,
+    /// File system errors
+    Io,
+    /// Published, but a step after publishing failed
+    Partial,
+    /// Stopped by Ctrl-C or SIGTERM
+    Aborted,
+}
+
+impl ErrorCategory {
+    /// Process exit code for failures in this category
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::Internal => 1,
+            ErrorCategory::Validation => 2,
+            ErrorCategory::Environment => 3,
+            ErrorCategory::Auth => 4,
+            ErrorCategory::Network => 5,
+            ErrorCategory::Conflict => 6,
+            ErrorCategory::Timeout => 7,
+            ErrorCategory::Git => 8,
+            ErrorCategory::GitHub => 9,
+            ErrorCategory::State => 10,
+            ErrorCategory::Io => 11,
+            ErrorCategory::Partial => 12,
+            // Conventional exit code of a process stopped by SIGINT
+            ErrorCategory::Aborted => 130,
+        }
+    }
+}
+
+/// JSON error object printed with `--error-format json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub code: &'static str,
+    pub category: ErrorCategory,
+    pub exit_code: i32,
+    pub message: String,
+    pub recoverable: bool,
+    pub suggestions: Vec<String>,
+}
+
+/// Recognize auth, network and conflict causes in a free-form message
+fn classify_message(message: &str) -> Option<ErrorCategory> {
+    let lower = message.to_lowercase();
+    const AUTH: &[&str] = &[
+        "401 unauthorized",
+        "bad credentials",
+        "authentication failed",
+        "permission denied (publickey)",
+    ];
+    const NETWORK: &[&str] = &[
+        "error sending request",
+        "connection refused",
+        "connection reset",
+        "could not resolve host",
+        "dns error",
+        "network is unreachable",
+        "operation timed out",
+    ];
+    const CONFLICT: &[&str] = &[
+        "already_exists",
+        "already exists",
+        "409 conflict",
+        "422 unprocessable entity",
+    ];
+
+    if AUTH.iter().any(|p| lower.contains(p)) {
+        Some(ErrorCategory::Auth)
+    } else if NETWORK.iter().any(|p| lower.contains(p)) {
+        Some(ErrorCategory::Network)
+    } else if CONFLICT.iter().any(|p| lower.contains(p)) {
+        Some(ErrorCategory::Conflict)
+    } else {
+        None
+    }
 }
@@ -0,0 +1,276 @@
+//! Release freeze windows from `release.toml`.
+//!
+//! `release` refuses to start while a window is in effect:
+//!
+//! ```toml
+//! # release.toml (repository root)
+//! [[freeze]]
+//! from = "Fri 16:00"          # every week, UTC
+//! until = "Mon 08:00"
+//! reason = "No releases over the weekend"
+//!
+//! [[freeze]]
+//! from = "2026-12-21"         # once: dates, "YYYY-MM-DD HH:MM" (UTC)
+//! until = "2027-01-04"        # or RFC 3339 times; a date includes the whole day
+//! reason = "Holiday freeze"
+//! ```
+//!
+//! `from` and `until` are both weekly (`Ddd HH:MM`), both daily (`HH:MM`) or
+//! both dates. A recurring window may wrap around the end of the week or day.
+//! `--override-freeze REASON` releases anyway and puts the reason in the
+//! release report.
+
+use crate::error::{CliError, ReleaseError, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday};
+use std::path::Path;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+const MINUTES_PER_WEEK: i64 = 7 * MINUTES_PER_DAY;
+
+/// When a window applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Schedule {
+    /// Minutes since Monday 00:00 UTC
+    Weekly { from: i64, until: i64 },
+    /// Minutes since 00:00 UTC
+    Daily { from: i64, until: i64 },
+    /// One stretch of time, `until` exclusive
+    Once { from: DateTime<Utc>, until: DateTime<Utc> },
+}
+
+/// A `[[freeze]]` entry
+#[derive(Debug, Clone)]
+pub struct FreezeWindow {
+    schedule: Schedule,
+    /// `from` and `until` as written
+    span: String,
+    pub reason: Option<String>,
+}
+
+impl FreezeWindow {
+    /// Freeze windows in `release.toml` in `repo_path` (none without the file)
+    pub fn load(repo_path: &Path) -> Result<Vec<Self>> {
+        let path = repo_path.join(crate::profile::PROFILE_FILE);
+        let invalid = |reason: String| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("{}: {}", path.display(), reason),
+            })
+        };
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(invalid(e.to_string())),
+        };
+        let document: toml::Value =
+            toml::from_str(&content).map_err(|e| invalid(format!("invalid TOML: {}", e)))?;
+        let Some(entries) = document.get("freeze") else {
+            return Ok(Vec::new());
+        };
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| invalid("freeze must be an array of [[freeze]] tables".to_string()))?;
+
+        entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let section = format!("[[freeze]] #{}", index + 1);
+                let table = entry
+                    .as_table()
+                    .ok_or_else(|| invalid(format!("{} must be a table", section)))?;
+                if let Some(key) = table.keys().find(|key| !matches!(key.as_str(), "from" | "until" | "reason")) {
+                    return Err(invalid(format!(
+                        "{} has unknown key `{}` (expected from, until, reason)",
+                        section, key
+                    )));
+                }
+                let text = |key: &str| {
+                    table
+                        .get(key)
+                        .and_then(|value| value.as_str())
+                        .ok_or_else(|| invalid(format!("{} {} must be a string", section, key)))
+                };
+                let reason = match table.get("reason") {
+                    None => None,
+                    Some(_) => Some(text("reason")?.to_string()),
+                };
+                Self::parse(text("from")?, text("until")?, reason)
+                    .map_err(|e| invalid(format!("{} {}", section, e)))
+            })
+            .collect()
+    }
+
+    fn parse(from: &str, until: &str, reason: Option<String>) -> std::result::Result<Self, String> {
+        let schedule = match (parse_bound(from, false)?, parse_bound(until, true)?) {
+            (Bound::Weekly(from), Bound::Weekly(until)) if from != until => Schedule::Weekly { from, until },
+            (Bound::Daily(from), Bound::Daily(until)) if from != until => Schedule::Daily { from, until },
+            (Bound::At(from), Bound::At(until)) if from < until => Schedule::Once { from, until },
+            (Bound::At(_), Bound::At(_)) => return Err("until must be later than from".to_string()),
+            (Bound::Weekly(_), Bound::Weekly(_)) | (Bound::Daily(_), Bound::Daily(_)) => {
+                return Err("from and until must differ".to_string());
+            }
+            _ => {
+                return Err("from and until must both be weekly (\"Fri 16:00\"), daily (\"16:00\") or dates".to_string());
+            }
+        };
+        Ok(Self {
+            schedule,
+            span: format!("{}–{}", from, until),
+            reason,
+        })
+    }
+
+    /// End of the window if `now` falls inside it
+    pub fn active_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let recurring = |from: i64, until: i64, minute: i64, period: i64| {
+            let inside = if from < until {
+                (from..until).contains(&minute)
+            } else {
+                minute >= from || minute < until
+            };
+            inside.then(|| {
+                let start_of_minute = now.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(now);
+                start_of_minute + Duration::minutes((until - minute).rem_euclid(period))
+            })
+        };
+        let minute_of_day = i64::from(now.hour() * 60 + now.minute());
+        match self.schedule {
+            Schedule::Weekly { from, until } => {
+                let minute = i64::from(now.weekday().num_days_from_monday()) * MINUTES_PER_DAY + minute_of_day;
+                recurring(from, until, minute, MINUTES_PER_WEEK)
+            }
+            Schedule::Daily { from, until } => recurring(from, until, minute_of_day, MINUTES_PER_DAY),
+            Schedule::Once { from, until } => (from..until).contains(&now).then_some(until),
+        }
+    }
+
+    /// e.g. `Fri 16:00–Mon 08:00 UTC (No releases over the weekend)`
+    pub fn describe(&self) -> String {
+        let zone = match self.schedule {
+            Schedule::Once { .. } => "",
+            _ => " UTC",
+        };
+        match &self.reason {
+            Some(reason) => format!("{}{} ({})", self.span, zone, reason),
+            None => format!("{}{}", self.span, zone),
+        }
+    }
+}
+
+/// One side of a window
+enum Bound {
+    Weekly(i64),
+    Daily(i64),
+    At(DateTime<Utc>),
+}
+
+/// Parse `Fri 16:00`, `16:00`, `2026-12-21`, `2026-12-21 10:00` (UTC) or an
+/// RFC 3339 time; a date as the `until` bound means the end of that day
+fn parse_bound(text: &str, is_end: bool) -> std::result::Result<Bound, String> {
+    let minutes = |time: &str| {
+        NaiveTime::parse_from_str(time, "%H:%M")
+            .map(|t| i64::from(t.hour() * 60 + t.minute()))
+            .map_err(|_| format!("`{}` is not a time (HH:MM)", time))
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        let date = if is_end { date.succ_opt().unwrap_or(date) } else { date };
+        return Ok(Bound::At(date.and_time(NaiveTime::MIN).and_utc()));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(Bound::At(time.with_timezone(&Utc)));
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M") {
+        return Ok(Bound::At(time.and_utc()));
+    }
+    if let Some((day, time)) = text.split_once(' ') {
+        if NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok() {
+            return Err(format!(
+                "`{}` is not a date and time (\"YYYY-MM-DD HH:MM\" in UTC, or RFC 3339)",
+                text
+            ));
+        }
+        let day: Weekday = day
+            .parse()
+            .map_err(|_| format!("`{}` does not start with a weekday (Mon..Sun)", text))?;
+        return Ok(Bound::Weekly(
+            i64::from(day.num_days_from_monday()) * MINUTES_PER_DAY + minutes(time.trim())?,
+        ));
+    }
+    if text.contains(':') && !text.contains('T') {
+        return minutes(text).map(Bound::Daily);
+    }
+    Err(format!(
+        "`{}` is not a weekly time (\"Fri 16:00\"), a daily time (\"16:00\"), a date or an RFC 3339 time",
+        text
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(from: &str, until: &str) -> FreezeWindow {
+        FreezeWindow::parse(from, until, None).unwrap()
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn weekly_windows_wrap_around_the_week() {
+        // 2026-10-16 is a Friday
+        let weekend = window("Fri 16:00", "Mon 08:00");
+        assert_eq!(weekend.active_until(at("2026-10-16T15:59:59Z")), None);
+        assert_eq!(weekend.active_until(at("2026-10-16T16:00:00Z")), Some(at("2026-10-19T08:00:00Z")));
+        assert_eq!(weekend.active_until(at("2026-10-18T12:30:45Z")), Some(at("2026-10-19T08:00:00Z")));
+        assert_eq!(weekend.active_until(at("2026-10-19T07:59:00Z")), Some(at("2026-10-19T08:00:00Z")));
+        assert_eq!(weekend.active_until(at("2026-10-19T08:00:00Z")), None);
+    }
+
+    #[test]
+    fn weekly_windows_cover_a_range_of_days() {
+        let midweek = window("Tue 09:00", "Thu 17:00");
+        assert_eq!(midweek.active_until(at("2026-10-19T12:00:00Z")), None);
+        assert_eq!(midweek.active_until(at("2026-10-21T03:00:00Z")), Some(at("2026-10-22T17:00:00Z")));
+        assert_eq!(midweek.active_until(at("2026-10-22T17:00:00Z")), None);
+    }
+
+    #[test]
+    fn daily_windows_wrap_around_midnight() {
+        let night = window("22:00", "06:00");
+        assert_eq!(night.active_until(at("2026-10-16T21:59:00Z")), None);
+        assert_eq!(night.active_until(at("2026-10-16T23:00:00Z")), Some(at("2026-10-17T06:00:00Z")));
+        assert_eq!(night.active_until(at("2026-10-17T05:59:00Z")), Some(at("2026-10-17T06:00:00Z")));
+        assert_eq!(night.active_until(at("2026-10-17T06:00:00Z")), None);
+    }
+
+    #[test]
+    fn date_windows_include_the_whole_last_day() {
+        let holiday = window("2026-12-21", "2027-01-04");
+        assert_eq!(holiday.active_until(at("2026-12-20T23:59:59Z")), None);
+        assert_eq!(holiday.active_until(at("2026-12-21T00:00:00Z")), Some(at("2027-01-05T00:00:00Z")));
+        assert_eq!(holiday.active_until(at("2027-01-04T23:59:59Z")), Some(at("2027-01-05T00:00:00Z")));
+        assert_eq!(holiday.active_until(at("2027-01-05T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn dates_with_a_time_are_utc() {
+        let maintenance = window("2026-12-21 10:00", "2026-12-21T12:00:00+01:00");
+        assert_eq!(maintenance.active_until(at("2026-12-21T09:59:00Z")), None);
+        assert_eq!(maintenance.active_until(at("2026-12-21T10:00:00Z")), Some(at("2026-12-21T11:00:00Z")));
+
+        let error = FreezeWindow::parse("2026-12-21 25:00", "2026-12-22", None).unwrap_err();
+        assert!(error.contains("not a date and time"), "{}", error);
+    }
+
+    #[test]
+    fn bounds_must_be_of_the_same_kind() {
+        assert!(FreezeWindow::parse("Fri 16:00", "08:00", None).is_err());
+        assert!(FreezeWindow::parse("16:00", "16:00", None).is_err());
+        assert!(FreezeWindow::parse("2027-01-04", "2026-12-21", None).is_err());
+        assert!(FreezeWindow::parse("Fry 16:00", "Mon 08:00", None).unwrap_err().contains("weekday"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Authentication of git operations against GitHub.
+//!
+//! Locally, remotes are used as configured, which usually means SSH with the
+//! user's keys. CI runners often have a token but no SSH key, so there SSH
+//! remotes are rewritten to HTTPS and authenticated with `GH_TOKEN` (or
+//! `GITHUB_TOKEN`). The same command then works in both places.
+//!
+//! The token is handed to git as an `http.extraHeader` through `GIT_CONFIG_*`
+//! environment variables, never as part of a URL or argument, so it does not
+//! show up in logs, error messages, process listings or `.git/config`.
+
+use crate::error::{CliError, ReleaseError, Result};
+use base64::Engine;
+
+/// How git authenticates to GitHub
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GitAuthMode {
+    /// SSH when a key or agent is available, else HTTPS with the token
+    #[default]
+    Auto,
+    /// Use remotes as configured
+    Ssh,
+    /// Always HTTPS with the token
+    Token,
+}
+
+/// Resolved git authentication for one run
+#[derive(Clone, Default)]
+pub struct GitAuth {
+    /// Token sent to github.com over HTTPS
+    token: Option<String>,
+    /// Rewrite SSH remotes to HTTPS
+    rewrite_ssh: bool,
+}
+
+// The token must never end up in debug output
+impl std::fmt::Debug for GitAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitAuth")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("rewrite_ssh", &self.rewrite_ssh)
+            .finish()
+    }
+}
+
+impl GitAuth {
+    /// Pick the strategy for `mode` from the credentials at hand
+    pub fn resolve(mode: GitAuthMode, env_config: &crate::EnvConfig) -> Result<Self> {
+        let token = env_config
+            .get("GH_TOKEN")
+            .or_else(|| env_config.get("GITHUB_TOKEN"))
+            .filter(|token| !token.trim().is_empty());
+
+        Ok(match mode {
+            GitAuthMode::Ssh => Self::default(),
+            GitAuthMode::Token => Self {
+                token: Some(token.ok_or_else(|| {
+                    ReleaseError::Cli(CliError::InvalidArguments {
+                        reason: "--git-auth token needs GH_TOKEN or GITHUB_TOKEN".to_string(),
+                    })
+                })?),
+                rewrite_ssh: true,
+            },
+            GitAuthMode::Auto => Self {
+                rewrite_ssh: token.is_some() && !ssh_available(),
+                token,
+            },
+        })
+    }
+
+    /// Whether SSH remotes are rewritten to HTTPS with the token
+    pub fn uses_token_for_ssh(&self) -> bool {
+        self.rewrite_ssh
+    }
+
+    /// HTTPS URL to use instead of the SSH remote `url` of `owner/repo`
+    ///
+    /// Returns `None` when `url` is used as it is.
+    pub fn rewrite_url(&self, url: &str, owner: &str, repo: &str) -> Option<String> {
+        let is_ssh = url.starts_with("ssh://") || (!url.contains("://") && url.contains(':'));
+        (self.rewrite_ssh && is_ssh).then(|| format!("https://github.com/{}/{}.git", owner, repo))
+    }
+
+    /// A `git` command that authenticates to github.com over HTTPS with the token
+    ///
+    /// Keeps the tokens in the environment for credential helpers such as
+    /// `gh auth git-credential`.
+    pub fn git(&self) -> crate::exec::Exec {
+        let mut command = crate::exec::Exec::new("git");
+        command.with_credentials();
+        if let Some(token) = &self.token {
+            // Fail instead of prompting when the token is rejected
+            command.env("GIT_TERMINAL_PROMPT", "0");
+            // Append to any GIT_CONFIG_* entries the environment already has
+            let index = std::env::var("GIT_CONFIG_COUNT")
+                .ok()
+                .and_then(|count| count.parse::<usize>().ok())
+                .unwrap_or(0);
+            let credentials =
+                base64::engine::general_purpose::STANDARD.encode(format!("x-access-token:{}", token));
+            // An empty value first drops a header persisted by the CI checkout,
+            // which would otherwise be sent as a second Authorization header
+            let headers = [String::new(), format!("AUTHORIZATION: basic {}", credentials)];
+            for (offset, value) in headers.into_iter().enumerate() {
+                command
+                    .env(format!("GIT_CONFIG_KEY_{}", index + offset), "http.https://github.com/.extraheader")
+                    .env(format!("GIT_CONFIG_VALUE_{}", index + offset), value);
+            }
+            command.env("GIT_CONFIG_COUNT", (index + 2).to_string());
+        }
+        command
+    }
+}
+
+/// Whether SSH can authenticate: an agent is running or a default key exists
+fn ssh_available() -> bool {
+    if std::env::var_os("SSH_AUTH_SOCK").is_some_and(|sock| !sock.is_empty()) {
+        return true;
+    }
+    let Some(ssh_dir) = dirs::home_dir().map(|home| home.join(".ssh")) else {
+        return false;
+    };
+    ["id_ed25519", "id_ecdsa", "id_rsa", "id_ed25519_sk", "id_ecdsa_sk"]
+        .iter()
+        .any(|key| ssh_dir.join(key).is_file())
+}
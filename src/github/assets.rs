@@ -0,0 +1,792 @@
+//! Release asset upload, listing, deletion and download.
+//!
+//! [`ReleaseAssets`] is the one seam between the release flow and GitHub's
+//! asset endpoints: listing, uploading, renaming, deleting and downloading
+//! assets of a release, drafts included. Everything else (checksum-verified
+//! downloads, staged replacement, pruning) is built on those five calls, so
+//! the commands that fetch or swap assets (`download`, `diff`, `resign`,
+//! `rebuild`) work against any implementation, not only the GitHub API.
+//!
+//! [`GitHubReleaseManager`] implements it over the REST API, and builds the
+//! artifact uploads of a release on top.
+
+use super::labels::{asset_label, content_type};
+use super::release_manager::{ExistingAssetPolicy, GitHubReleaseManager, NotesOverflow};
+use crate::error::{CliError, ReleaseError, Result};
+use reqwest::Method;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Asset name prefix of bundler logs attached to draft releases
+const BUNDLE_LOG_PREFIX: &str = "bundle-log-";
+
+/// An asset of a release
+#[derive(Debug, Clone)]
+pub struct ReleaseAsset {
+    pub id: u64,
+    pub name: String,
+    /// Lowercase hex SHA-256 reported by GitHub, if available
+    pub sha256: Option<String>,
+    /// API URL serving the content (works for drafts too)
+    pub url: String,
+}
+
+/// Asset returned by the upload endpoint
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct UploadedAsset {
+    pub id: u64,
+    pub size: i64,
+    pub browser_download_url: String,
+}
+
+/// Asset operations on a release
+///
+/// Implementors provide the five API calls; the rest has default implementations.
+pub trait ReleaseAssets {
+    /// Assets of a release (drafts included)
+    async fn list_assets(&self, release_id: u64) -> Result<Vec<ReleaseAsset>>;
+
+    /// Upload `content` as asset `name`, with an explicit content type and label
+    async fn upload_asset(
+        &self,
+        release_id: u64,
+        name: &str,
+        content_type: &str,
+        label: Option<&str>,
+        content: Vec<u8>,
+    ) -> Result<UploadedAsset>;
+
+    /// Give an asset a new name and label
+    async fn rename_asset(&self, asset_id: u64, name: &str, label: &str) -> Result<()>;
+
+    /// Delete one asset
+    async fn delete_asset(&self, asset_id: u64) -> Result<()>;
+
+    /// Content of an asset
+    async fn download_asset(&self, asset: &ReleaseAsset) -> Result<Vec<u8>>;
+
+    /// The asset named `name` on a release
+    async fn find_asset(&self, release_id: u64, name: &str) -> Result<Option<ReleaseAsset>> {
+        Ok(self
+            .list_assets(release_id)
+            .await?
+            .into_iter()
+            .find(|asset| asset.name == name))
+    }
+
+    /// Content of the asset named `name`, `None` if the release has no such asset
+    async fn download_named(&self, release_id: u64, name: &str) -> Result<Option<Vec<u8>>> {
+        match self.find_asset(release_id, name).await? {
+            Some(asset) => self.download_asset(&asset).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Content of the asset named `name`, checked against its expected SHA-256
+    ///
+    /// A missing asset is an error: the caller knows it should be there.
+    async fn download_verified(&self, release_id: u64, name: &str, sha256: &str) -> Result<Vec<u8>> {
+        let content = self.download_named(release_id, name).await?.ok_or_else(|| {
+            ReleaseError::Cli(CliError::ExecutionFailed {
+                command: "download".to_string(),
+                reason: format!("{} is in the manifest but not on the release", name),
+            })
+        })?;
+        verify_sha256(name, &content, sha256)?;
+        Ok(content)
+    }
+
+    /// Swap an asset in, keeping the previous one until the new upload succeeds
+    ///
+    /// The content is uploaded under a staging name first; only after the
+    /// upload succeeds is the old asset deleted and the new one renamed into
+    /// place. A failed upload therefore never leaves the release without the
+    /// previous asset. If the rename fails, the error names the staging asset,
+    /// and the next call renames it into place before replacing it.
+    ///
+    /// Returns the uploaded size in bytes.
+    async fn replace_asset(
+        &self,
+        release_id: u64,
+        filename: &str,
+        label: &str,
+        content: Vec<u8>,
+        old_id: Option<u64>,
+    ) -> Result<i64> {
+        let staging_name = staging_name(filename);
+        let mut old_id = old_id;
+
+        // Step 0: finish or discard an interrupted replacement
+        if let Some(leftover) = self.find_asset(release_id, &staging_name).await? {
+            if self.find_asset(release_id, filename).await?.is_none() {
+                // The old asset is gone but its replacement was never renamed
+                self.rename_asset(leftover.id, filename, label).await?;
+                old_id = Some(leftover.id);
+            } else {
+                self.delete_asset(leftover.id).await?;
+            }
+        }
+
+        // Step 1: upload under the staging name. The content type follows the
+        // real filename, since renaming keeps it.
+        let staged = self
+            .upload_asset(release_id, &staging_name, content_type(filename), None, content)
+            .await?;
+
+        // Step 2: remove the previous asset
+        if let Some(old_id) = old_id {
+            self.delete_asset(old_id).await?;
+        }
+
+        // Step 3: rename the staged asset into place
+        self.rename_asset(staged.id, filename, label).await.map_err(|e| {
+            ReleaseError::GitHub(format!(
+                "{} was deleted, but its replacement is still named {} ({}). \
+                 Run the command again to rename it into place.",
+                filename, staging_name, e
+            ))
+        })?;
+        Ok(staged.size)
+    }
+
+    /// Delete every asset of a release for which `keep` is false
+    ///
+    /// Returns the names of the deleted assets.
+    async fn prune_assets(&self, release_id: u64, keep: impl Fn(&str) -> bool) -> Result<Vec<String>> {
+        let mut deleted = Vec::new();
+        for asset in self.list_assets(release_id).await? {
+            if !keep(&asset.name) {
+                self.delete_asset(asset.id).await?;
+                deleted.push(asset.name);
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+/// Name a replacement is uploaded under before it is renamed into place
+fn staging_name(filename: &str) -> String {
+    format!("{}.rebuild-staging", filename)
+}
+
+/// Check downloaded `content` against the SHA-256 a manifest lists for it
+pub fn verify_sha256(name: &str, content: &[u8], expected: &str) -> Result<()> {
+    let actual = format!("{:x}", Sha256::digest(content));
+    if actual != expected {
+        return Err(ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "download".to_string(),
+            reason: format!("{} has SHA-256 {}, but the manifest lists {}", name, actual, expected),
+        }));
+    }
+    Ok(())
+}
+
+/// An asset as the REST API returns it
+#[derive(serde::Deserialize)]
+struct ApiAsset {
+    id: u64,
+    name: String,
+    digest: Option<String>,
+    url: String,
+}
+
+impl ReleaseAssets for GitHubReleaseManager {
+    async fn list_assets(&self, release_id: u64) -> Result<Vec<ReleaseAsset>> {
+        let mut assets = Vec::new();
+        for page in 1.. {
+            let response = self
+                .repo_request(
+                    Method::GET,
+                    &format!("releases/{}/assets?per_page=100&page={}", release_id, page),
+                )
+                .send()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Listing release assets failed: {}", e)))?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(ReleaseError::GitHub(format!("Listing release assets failed: {}", status)));
+            }
+            let page_assets: Vec<ApiAsset> = response
+                .json()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Listing release assets failed: {}", e)))?;
+            let count = page_assets.len();
+            assets.extend(page_assets);
+            if count < 100 {
+                break;
+            }
+        }
+
+        Ok(assets
+            .into_iter()
+            .map(|asset| ReleaseAsset {
+                id: asset.id,
+                name: asset.name,
+                sha256: asset
+                    .digest
+                    .as_deref()
+                    .and_then(|d| d.strip_prefix("sha256:"))
+                    .map(str::to_lowercase),
+                url: asset.url,
+            })
+            .collect())
+    }
+
+    /// octocrab always sends `application/octet-stream`, so the upload request
+    /// is made directly against the release's upload URL
+    async fn upload_asset(
+        &self,
+        release_id: u64,
+        name: &str,
+        content_type: &str,
+        label: Option<&str>,
+        content: Vec<u8>,
+    ) -> Result<UploadedAsset> {
+        #[derive(serde::Deserialize)]
+        struct Release {
+            upload_url: String,
+        }
+        let release: Release = self
+            .repo_request(Method::GET, &format!("releases/{}", release_id))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| ReleaseError::GitHub(format!("Upload of {} failed: {}", name, e)))?
+            .json()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Upload of {} failed: {}", name, e)))?;
+        let upload_url = release.upload_url.replace("{?name,label}", "");
+
+        let mut query = vec![("name", name)];
+        if let Some(label) = label {
+            query.push(("label", label));
+        }
+
+        let response = self
+            .request(Method::POST, &upload_url)
+            .query(&query)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(content)
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Upload of {} failed: {}", name, e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ReleaseError::GitHub(format!(
+                "Upload of {} failed: {} {}",
+                name, status, body
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Invalid upload response for {}: {}", name, e)))
+    }
+
+    async fn rename_asset(&self, asset_id: u64, name: &str, label: &str) -> Result<()> {
+        let response = self
+            .repo_request(Method::PATCH, &format!("releases/assets/{}", asset_id))
+            .json(&serde_json::json!({ "name": name, "label": label }))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Renaming asset {} failed: {}", name, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ReleaseError::GitHub(format!("Renaming asset {} failed: {}", name, status)));
+        }
+        Ok(())
+    }
+
+    async fn delete_asset(&self, asset_id: u64) -> Result<()> {
+        let response = self
+            .repo_request(Method::DELETE, &format!("releases/assets/{}", asset_id))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Deleting asset {} failed: {}", asset_id, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ReleaseError::GitHub(format!("Deleting asset {} failed: {}", asset_id, status)));
+        }
+        Ok(())
+    }
+
+    /// Draft assets have no public download URL; the API serves them
+    async fn download_asset(&self, asset: &ReleaseAsset) -> Result<Vec<u8>> {
+        let response = self
+            .request(Method::GET, &asset.url)
+            .header(reqwest::header::ACCEPT, "application/octet-stream")
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Download of {} failed: {}", asset.name, e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ReleaseError::GitHub(format!("Download of {} failed: {}", asset.name, status)));
+        }
+        let content = response
+            .bytes()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Download of {} failed: {}", asset.name, e)))?;
+        Ok(content.to_vec())
+    }
+}
+
+impl GitHubReleaseManager {
+    /// Attach the full release notes to a release, unless already attached
+    pub async fn upload_full_notes(&self, release_id: u64, overflow: &NotesOverflow) -> Result<()> {
+        if self.find_asset(release_id, &overflow.asset_name).await?.is_some() {
+            return Ok(());
+        }
+        self.upload_asset(
+            release_id,
+            &overflow.asset_name,
+            content_type(&overflow.asset_name),
+            Some("Full changelog"),
+            overflow.notes.clone().into_bytes(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Attach a failed bundler's log to a draft release
+    ///
+    /// Returns the asset name (`bundle-log-<file name>`).
+    pub async fn upload_bundle_log(&self, release_id: u64, path: &Path) -> Result<String> {
+        let name = format!(
+            "{}{}",
+            BUNDLE_LOG_PREFIX,
+            path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+        );
+        let content = std::fs::read(path)?;
+        self.upload_asset(release_id, &name, content_type(&name), Some("Bundler log"), content)
+            .await?;
+        Ok(name)
+    }
+
+    /// Delete the bundler logs that failed runs attached to a release
+    ///
+    /// Returns the names of the deleted assets.
+    pub async fn delete_bundle_logs(&self, release_id: u64) -> Result<Vec<String>> {
+        self.prune_assets(release_id, |name| !name.starts_with(BUNDLE_LOG_PREFIX))
+            .await
+    }
+
+    /// Replace release assets with freshly built artifacts
+    ///
+    /// Every artifact is swapped in via [`ReleaseAssets::replace_asset`],
+    /// regardless of the configured [`ExistingAssetPolicy`].
+    ///
+    /// Returns the number of assets replaced or added.
+    pub async fn replace_artifacts(
+        &self,
+        release_id: u64,
+        artifact_paths: &[PathBuf],
+        runtime_config: &crate::cli::RuntimeConfig,
+    ) -> Result<usize> {
+        let existing_assets = self.assets_by_name(release_id).await?;
+        let mut replaced = 0;
+
+        for artifact_path in artifact_paths {
+            let Some((filename, content)) = read_artifact(artifact_path, runtime_config)? else {
+                continue;
+            };
+
+            let old_id = existing_assets.get(&filename).map(|a| a.id);
+            let label = asset_label(&filename, &self.config().asset_labels);
+            let size = self.replace_asset(release_id, &filename, &label, content, old_id).await?;
+
+            let verb = if old_id.is_some() { "Replaced" } else { "Added" };
+            runtime_config.indent(&format!("✓ {}: {} ({} bytes)", verb, filename, size)).expect("Failed to write to stdout");
+            replaced += 1;
+        }
+
+        Ok(replaced)
+    }
+
+    /// Upload signed artifacts to release
+    ///
+    /// Reads artifact files and uploads them as release assets. Artifacts whose
+    /// name already exists on the release are handled per the configured
+    /// [`ExistingAssetPolicy`]; identical content (same SHA-256) is always skipped.
+    /// Returns list of download URLs for the uploaded assets.
+    pub async fn upload_artifacts(
+        &self,
+        release_id: u64,
+        artifact_paths: &[PathBuf],
+        version: &semver::Version,
+        runtime_config: &crate::cli::RuntimeConfig,
+    ) -> Result<Vec<String>> {
+        let mut uploaded_urls = Vec::new();
+
+        // Query existing assets ONCE before upload loop
+        runtime_config.verbose_println("   Checking for existing assets...").expect("Failed to write to stdout");
+        let existing_assets = self.assets_by_name(release_id).await?;
+
+        if !existing_assets.is_empty() {
+            runtime_config.verbose_println(&format!(
+                "   Found {} existing asset(s)",
+                existing_assets.len()
+            )).expect("Failed to write to stdout");
+        }
+
+        for artifact_path in artifact_paths {
+            let Some((filename, content)) = read_artifact(artifact_path, runtime_config)? else {
+                continue;
+            };
+            let label = asset_label(&filename, &self.config().asset_labels);
+
+            if let Some(existing) = existing_assets.get(&filename) {
+                let local_sha256 = format!("{:x}", Sha256::digest(&content));
+
+                // IDEMPOTENCY: identical content is never re-uploaded
+                if existing.sha256.as_deref() == Some(local_sha256.as_str()) {
+                    runtime_config.indent(&format!("✓ Skipping {} (already uploaded, checksum matches)", filename)).expect("Failed to write to stdout");
+                    continue;
+                }
+
+                match self.config().on_existing_asset {
+                    ExistingAssetPolicy::Fail => {
+                        return Err(ReleaseError::GitHub(format!(
+                            "Asset {} already exists on release {} (--on-existing-asset fail)",
+                            filename,
+                            self.tag_name(version)
+                        )));
+                    }
+                    ExistingAssetPolicy::Skip => {
+                        match &existing.sha256 {
+                            Some(_) => runtime_config.soft_failure(&format!(
+                                "⚠️  Skipping {}: existing asset differs from local build (use --on-existing-asset replace)",
+                                filename
+                            ))?,
+                            None => runtime_config.indent(&format!(
+                                "✓ Skipping {} (already uploaded, no checksum to compare)",
+                                filename
+                            )).expect("Failed to write to stdout"),
+                        }
+                        continue;
+                    }
+                    ExistingAssetPolicy::Replace => {
+                        let size = self
+                            .replace_asset(release_id, &filename, &label, content, Some(existing.id))
+                            .await?;
+                        runtime_config.indent(&format!("✓ Replaced: {} ({} bytes)", filename, size)).expect("Failed to write to stdout");
+                        continue;
+                    }
+                }
+            }
+
+            // An interrupted replacement left only the staging asset
+            if existing_assets.contains_key(&staging_name(&filename)) {
+                let size = self.replace_asset(release_id, &filename, &label, content, None).await?;
+                runtime_config.indent(&format!("✓ Restored: {} ({} bytes)", filename, size)).expect("Failed to write to stdout");
+                continue;
+            }
+
+            // Existing assets are handled above
+            let asset = self
+                .upload_asset(release_id, &filename, content_type(&filename), Some(&label), content)
+                .await?;
+
+            uploaded_urls.push(asset.browser_download_url);
+
+            runtime_config.indent(&format!("✓ Uploaded: {} ({} bytes)", filename, asset.size)).expect("Failed to write to stdout");
+        }
+
+        Ok(uploaded_urls)
+    }
+
+    /// Assets already uploaded to a release, keyed by filename
+    async fn assets_by_name(&self, release_id: u64) -> Result<HashMap<String, ReleaseAsset>> {
+        Ok(self
+            .list_assets(release_id)
+            .await?
+            .into_iter()
+            .map(|asset| (asset.name.clone(), asset))
+            .collect())
+    }
+}
+
+/// Read an artifact file for upload
+///
+/// Returns `None` (after warning) for paths that aren't regular files.
+fn read_artifact(
+    artifact_path: &Path,
+    runtime_config: &crate::cli::RuntimeConfig,
+) -> Result<Option<(String, Vec<u8>)>> {
+    // Safety check: should be filtered at call site, but double-check
+    if !artifact_path.is_file() {
+        runtime_config.warning_println(&format!(
+            "⚠️  Skipping non-file artifact: {}",
+            artifact_path.display()
+        )).expect("Failed to write to stdout");
+        return Ok(None);
+    }
+
+    // Extract filename for the asset
+    let filename = artifact_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("Invalid artifact filename: {:?}", artifact_path),
+            })
+        })?
+        .to_string();
+
+    // Read file content
+    let content = std::fs::read(artifact_path).map_err(|e| {
+        ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "read_artifact".to_string(),
+            reason: e.to_string(),
+        })
+    })?;
+
+    Ok(Some((filename, content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// In-memory [`ReleaseAssets`] holding the assets of any number of releases
+    #[derive(Default)]
+    struct MemoryAssets {
+        /// `(release_id, asset, content)`, in upload order
+        assets: Mutex<Vec<(u64, ReleaseAsset, Vec<u8>)>>,
+        next_id: Mutex<u64>,
+        /// Fail every rename, like a network error after the upload
+        fail_renames: std::sync::atomic::AtomicBool,
+    }
+
+    impl MemoryAssets {
+        fn with(release_id: u64, files: &[(&str, &[u8])]) -> Self {
+            let store = Self::default();
+            for (name, content) in files {
+                store.insert(release_id, name, content.to_vec());
+            }
+            store
+        }
+
+        fn insert(&self, release_id: u64, name: &str, content: Vec<u8>) -> u64 {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            let asset = ReleaseAsset {
+                id: *next_id,
+                name: name.to_string(),
+                sha256: Some(format!("{:x}", Sha256::digest(&content))),
+                url: format!("memory://{}", next_id),
+            };
+            self.assets.lock().unwrap().push((release_id, asset, content));
+            *next_id
+        }
+
+        /// `(name, content)` of a release's assets, in upload order
+        fn contents(&self, release_id: u64) -> Vec<(String, Vec<u8>)> {
+            self.assets
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(release, _, _)| *release == release_id)
+                .map(|(_, asset, content)| (asset.name.clone(), content.clone()))
+                .collect()
+        }
+    }
+
+    fn missing(what: String) -> ReleaseError {
+        ReleaseError::GitHub(format!("{} not found", what))
+    }
+
+    impl ReleaseAssets for MemoryAssets {
+        async fn list_assets(&self, release_id: u64) -> Result<Vec<ReleaseAsset>> {
+            Ok(self
+                .assets
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(release, _, _)| *release == release_id)
+                .map(|(_, asset, _)| asset.clone())
+                .collect())
+        }
+
+        async fn upload_asset(
+            &self,
+            release_id: u64,
+            name: &str,
+            _content_type: &str,
+            _label: Option<&str>,
+            content: Vec<u8>,
+        ) -> Result<UploadedAsset> {
+            if self.find_asset(release_id, name).await?.is_some() {
+                return Err(ReleaseError::GitHub(format!("{} already exists", name)));
+            }
+            let size = content.len() as i64;
+            let id = self.insert(release_id, name, content);
+            Ok(UploadedAsset {
+                id,
+                size,
+                browser_download_url: format!("memory://{}", name),
+            })
+        }
+
+        async fn rename_asset(&self, asset_id: u64, name: &str, _label: &str) -> Result<()> {
+            if self.fail_renames.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(ReleaseError::GitHub("connection reset".to_string()));
+            }
+            let mut assets = self.assets.lock().unwrap();
+            let (_, asset, _) = assets
+                .iter_mut()
+                .find(|(_, asset, _)| asset.id == asset_id)
+                .ok_or_else(|| missing(format!("asset {}", asset_id)))?;
+            asset.name = name.to_string();
+            Ok(())
+        }
+
+        async fn delete_asset(&self, asset_id: u64) -> Result<()> {
+            let mut assets = self.assets.lock().unwrap();
+            let before = assets.len();
+            assets.retain(|(_, asset, _)| asset.id != asset_id);
+            if assets.len() == before {
+                return Err(missing(format!("asset {}", asset_id)));
+            }
+            Ok(())
+        }
+
+        async fn download_asset(&self, asset: &ReleaseAsset) -> Result<Vec<u8>> {
+            self.assets
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(_, stored, _)| stored.id == asset.id)
+                .map(|(_, _, content)| content.clone())
+                .ok_or_else(|| missing(asset.name.clone()))
+        }
+    }
+
+    fn sha256(content: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    #[tokio::test]
+    async fn replace_asset_swaps_content_under_the_same_name() {
+        let store = MemoryAssets::with(1, &[("tool.deb", b"old"), ("tool.rpm", b"rpm")]);
+        let old = store.find_asset(1, "tool.deb").await.unwrap().unwrap();
+
+        let size = store
+            .replace_asset(1, "tool.deb", "Debian", b"new!".to_vec(), Some(old.id))
+            .await
+            .unwrap();
+
+        assert_eq!(size, 4);
+        assert_eq!(
+            store.contents(1),
+            [("tool.rpm".to_string(), b"rpm".to_vec()), ("tool.deb".to_string(), b"new!".to_vec())]
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_asset_drops_a_leftover_staging_asset() {
+        let store = MemoryAssets::with(1, &[("tool.deb", b"old"), ("tool.deb.rebuild-staging", b"stale")]);
+        let old = store.find_asset(1, "tool.deb").await.unwrap().unwrap();
+
+        store
+            .replace_asset(1, "tool.deb", "Debian", b"new".to_vec(), Some(old.id))
+            .await
+            .unwrap();
+
+        assert_eq!(store.contents(1), [("tool.deb".to_string(), b"new".to_vec())]);
+    }
+
+    #[tokio::test]
+    async fn replace_asset_recovers_from_a_failed_rename() {
+        let store = MemoryAssets::with(1, &[("tool.deb", b"old")]);
+        let old = store.find_asset(1, "tool.deb").await.unwrap().unwrap();
+
+        store.fail_renames.store(true, std::sync::atomic::Ordering::SeqCst);
+        let error = store
+            .replace_asset(1, "tool.deb", "Debian", b"new".to_vec(), Some(old.id))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("tool.deb.rebuild-staging"), "{}", error);
+        assert_eq!(store.contents(1), [("tool.deb.rebuild-staging".to_string(), b"new".to_vec())]);
+
+        // The retry no longer sees tool.deb, so it passes no old asset
+        store.fail_renames.store(false, std::sync::atomic::Ordering::SeqCst);
+        store
+            .replace_asset(1, "tool.deb", "Debian", b"newer".to_vec(), None)
+            .await
+            .unwrap();
+        assert_eq!(store.contents(1), [("tool.deb".to_string(), b"newer".to_vec())]);
+    }
+
+    #[tokio::test]
+    async fn prune_assets_deletes_only_rejected_names() {
+        let store = MemoryAssets::with(1, &[("tool.deb", b"a"), ("bundle-log-deb.log", b"b"), ("tool.dmg", b"c")]);
+        store.insert(2, "bundle-log-dmg.log", b"other release".to_vec());
+
+        let deleted = store
+            .prune_assets(1, |name| !name.starts_with(BUNDLE_LOG_PREFIX))
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, ["bundle-log-deb.log"]);
+        assert_eq!(store.contents(1).len(), 2);
+        assert_eq!(store.contents(2).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn download_verified_checks_presence_and_checksum() {
+        let store = MemoryAssets::with(1, &[("tool.deb", b"content")]);
+
+        let content = store.download_verified(1, "tool.deb", &sha256(b"content")).await.unwrap();
+        assert_eq!(content, b"content");
+
+        let mismatch = store.download_verified(1, "tool.deb", &sha256(b"other")).await.unwrap_err();
+        assert!(mismatch.to_string().contains("but the manifest lists"), "{}", mismatch);
+
+        let absent = store.download_verified(1, "tool.rpm", &sha256(b"content")).await.unwrap_err();
+        assert!(absent.to_string().contains("not on the release"), "{}", absent);
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[tokio::test]
+    async fn list_assets_reads_every_page() {
+        use kodegen_bundler_release::test_harness::{MockGitHub, TEST_TOKEN};
+
+        let mock = MockGitHub::start("acme", "widget").unwrap();
+        let release_id = mock.add_release("v1.0.0", false);
+        for index in 0..250 {
+            mock.add_asset(release_id, &format!("asset-{:03}.bin", index), b"x").unwrap();
+        }
+
+        let env_config = crate::EnvConfig::new(HashMap::from([
+            ("GITHUB_API_URL".to_string(), mock.url()),
+            ("GH_TOKEN".to_string(), TEST_TOKEN.to_string()),
+        ]));
+        let manager = GitHubReleaseManager::new(
+            super::super::GitHubReleaseConfig {
+                owner: "acme".to_string(),
+                repo: "widget".to_string(),
+                ..Default::default()
+            },
+            &env_config,
+        )
+        .unwrap();
+
+        let names: Vec<String> = manager
+            .list_assets(release_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|asset| asset.name)
+            .collect();
+        assert_eq!(names.len(), 250);
+        assert_eq!(names.first().map(String::as_str), Some("asset-000.bin"));
+        assert_eq!(names.last().map(String::as_str), Some("asset-249.bin"));
+    }
+}
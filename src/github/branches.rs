@@ -0,0 +1,63 @@
+//! Branches on GitHub, for deleting release branches after a release.
+
+use super::release_manager::GitHubReleaseManager;
+use super::tags::json;
+use crate::error::{ReleaseError, Result};
+use reqwest::Method;
+
+impl GitHubReleaseManager {
+    /// Names of the repository's branches
+    pub async fn list_branches(&self) -> Result<Vec<String>> {
+        #[derive(serde::Deserialize)]
+        struct Branch {
+            name: String,
+        }
+
+        let mut names = Vec::new();
+        for page in 1.. {
+            let response = self
+                .repo_request(Method::GET, &format!("branches?per_page=100&page={}", page))
+                .send()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Listing branches failed: {}", e)))?;
+            let branches: Vec<Branch> = json(response, "Listing branches").await?;
+            let count = branches.len();
+            names.extend(branches.into_iter().map(|branch| branch.name));
+            if count < 100 {
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    /// Number of commits on `branch` that `tag` does not contain
+    pub async fn commits_beyond_tag(&self, branch: &str, tag: &str) -> Result<u64> {
+        #[derive(serde::Deserialize)]
+        struct Comparison {
+            ahead_by: u64,
+        }
+
+        // A release branch shares its tag's name, so both refs are qualified
+        let response = self
+            .repo_request(Method::GET, &format!("compare/refs/tags/{}...refs/heads/{}", tag, branch))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Comparing {} with {} failed: {}", branch, tag, e)))?;
+        let comparison: Comparison = json(response, &format!("Comparing {} with {}", branch, tag)).await?;
+        Ok(comparison.ahead_by)
+    }
+
+    /// Delete `branch` on GitHub
+    pub async fn delete_branch(&self, branch: &str) -> Result<()> {
+        let response = self
+            .repo_request(Method::DELETE, &format!("git/refs/heads/{}", branch))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Deleting branch {} failed: {}", branch, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ReleaseError::GitHub(format!("Deleting branch {} failed: {}", branch, status)));
+        }
+        Ok(())
+    }
+}
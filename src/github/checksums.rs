@@ -0,0 +1,106 @@
+//! Checksum table in the release body.
+//!
+//! After the release manifest is uploaded, the release body gets a table of
+//! the manifest's artifacts with their label, size and SHA-256. The table sits
+//! between two HTML comments and is replaced on every run, so re-runs,
+//! rebuilds and re-signed assets keep it in sync with the assets.
+
+use super::labels::asset_label;
+use super::release_manager::{GitHubReleaseManager, RELEASE_BODY_LIMIT};
+use super::tags::json;
+use crate::error::{ReleaseError, Result};
+use crate::manifest::ManifestArtifact;
+use crate::util::format_bytes;
+use reqwest::Method;
+
+const TABLE_START: &str = "<!-- kodegen:checksums -->";
+const TABLE_END: &str = "<!-- /kodegen:checksums -->";
+
+impl GitHubReleaseManager {
+    /// Write the checksum table for `artifacts` into the body of a release
+    ///
+    /// Without artifacts, an existing table is removed. Returns whether the
+    /// body changed.
+    pub async fn update_checksum_table(&self, release_id: u64, artifacts: &[ManifestArtifact]) -> Result<bool> {
+        #[derive(serde::Deserialize)]
+        struct Release {
+            body: Option<String>,
+        }
+
+        let route = format!("releases/{}", release_id);
+        let response = self
+            .repo_request(Method::GET, &route)
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Reading release {} failed: {}", release_id, e)))?;
+        let release: Release = json(response, &format!("Reading release {}", release_id)).await?;
+        let body = release.body.unwrap_or_default();
+
+        let table = (!artifacts.is_empty()).then(|| checksum_table(artifacts, &self.config().asset_labels));
+        let updated = with_checksum_table(&body, table.as_deref());
+        if updated == body.trim_end() {
+            return Ok(false);
+        }
+        if updated.chars().count() > RELEASE_BODY_LIMIT {
+            return Err(ReleaseError::GitHub(format!(
+                "The release body with the checksum table would exceed GitHub's limit of {} characters",
+                RELEASE_BODY_LIMIT
+            )));
+        }
+
+        let response = self
+            .repo_request(Method::PATCH, &route)
+            .json(&serde_json::json!({ "body": updated }))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Updating release {} failed: {}", release_id, e)))?;
+        let _: serde_json::Value = json(response, &format!("Updating release {}", release_id)).await?;
+        Ok(true)
+    }
+}
+
+/// Markdown table of the artifacts, between the table markers
+fn checksum_table(artifacts: &[ManifestArtifact], labels: &[super::AssetLabelRule]) -> String {
+    let mut sorted: Vec<&ManifestArtifact> = artifacts.iter().collect();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut lines = vec![
+        TABLE_START.to_string(),
+        "### Checksums".to_string(),
+        String::new(),
+        "| Asset | Platform | Size | SHA-256 |".to_string(),
+        "|-------|----------|-----:|---------|".to_string(),
+    ];
+    lines.extend(sorted.into_iter().map(|artifact| {
+        format!(
+            "| [{}]({}) | {} | {} | `{}` |",
+            artifact.name,
+            artifact.download_url,
+            asset_label(&artifact.name, labels).replace('|', "\\|"),
+            format_bytes(artifact.size),
+            artifact.sha256
+        )
+    }));
+    lines.push(TABLE_END.to_string());
+    lines.join("\n")
+}
+
+/// `body` with its checksum table replaced by `table`, appended when it has
+/// none, or removed when `table` is `None`
+fn with_checksum_table(body: &str, table: Option<&str>) -> String {
+    let existing = body.find(TABLE_START).and_then(|start| {
+        body[start..]
+            .find(TABLE_END)
+            .map(|end| (start, start + end + TABLE_END.len()))
+    });
+    let (before, after) = match existing {
+        Some((start, end)) => (body[..start].trim_end(), body[end..].trim_start()),
+        None => (body.trim_end(), ""),
+    };
+
+    [before, table.unwrap_or_default(), after]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -0,0 +1,195 @@
+//! Content types and display labels for release assets.
+//!
+//! GitHub shows an asset's label in the release download list instead of its
+//! filename, so labels are what users actually read. Defaults are derived from
+//! the package format and architecture ("macOS Installer (Apple Silicon)");
+//! projects can override them per filename pattern:
+//!
+//! ```toml
+//! [[package.metadata.release.asset-labels]]
+//! pattern = "*.dmg"
+//! label = "MyApp for macOS ({arch})"
+//! ```
+//!
+//! Rules are tried in order and the first matching pattern wins. Patterns
+//! support `*` and `?`; labels may use `{arch}` and `{filename}`.
+
+use crate::error::{CliError, ReleaseError, Result};
+
+/// A configured label for assets matching `pattern`
+#[derive(Debug, Clone)]
+pub struct AssetLabelRule {
+    /// Filename pattern with `*` / `?` wildcards
+    pub pattern: String,
+    /// Label template (`{arch}`, `{filename}`)
+    pub label: String,
+}
+
+impl AssetLabelRule {
+    /// Parse `[[package.metadata.release.asset-labels]]`
+    pub(crate) fn from_package(package: &toml::Value) -> Result<Vec<Self>> {
+        let Some(value) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("asset-labels"))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let invalid = || {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "[[package.metadata.release.asset-labels]] entries need string `pattern` and `label`"
+                    .to_string(),
+            })
+        };
+
+        value
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|entry| {
+                let field = |key: &str| entry.get(key).and_then(|v| v.as_str()).map(String::from);
+                Ok(Self {
+                    pattern: field("pattern").ok_or_else(invalid)?,
+                    label: field("label").ok_or_else(invalid)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// MIME type for an asset, from its extension
+pub fn content_type(filename: &str) -> &'static str {
+    let lower = filename.to_ascii_lowercase();
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        return "application/gzip";
+    }
+    if lower.ends_with(".zst") {
+        return "application/zstd";
+    }
+
+    match lower.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("deb") => "application/vnd.debian.binary-package",
+        Some("rpm") => "application/x-rpm",
+        Some("dmg") => "application/x-apple-diskimage",
+        Some("appimage") => "application/vnd.appimage",
+        Some("exe") => "application/vnd.microsoft.portable-executable",
+        Some("msi") => "application/x-msi",
+        Some("msix") => "application/msix",
+        Some("zip") => "application/zip",
+        Some("gz") => "application/gzip",
+        Some("xz") => "application/x-xz",
+        Some("json") => "application/json",
+        Some("sha256") | Some("txt") | Some("log") => "text/plain",
+        Some("md") => "text/markdown",
+        Some("sig") | Some("asc") => "application/pgp-signature",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Display label for an asset: the first matching configured rule, else a default
+pub fn asset_label(filename: &str, rules: &[AssetLabelRule]) -> String {
+    if let Some(rule) = rules.iter().find(|rule| wildcard_match(&rule.pattern, filename)) {
+        return rule
+            .label
+            .replace("{arch}", arch_name(filename).unwrap_or("all architectures"))
+            .replace("{filename}", filename);
+    }
+
+    default_label(filename)
+}
+
+/// Default label from the package format and architecture
+///
+/// Also names the platform of an asset, e.g. in download statistics.
+pub(crate) fn default_label(filename: &str) -> String {
+    let lower = filename.to_ascii_lowercase();
+    let kind = if lower.ends_with(".dmg") {
+        "macOS Installer"
+    } else if lower.ends_with(".deb") {
+        "Debian/Ubuntu Package"
+    } else if lower.ends_with(".rpm") {
+        "Fedora/RHEL Package"
+    } else if lower.ends_with(".appimage") {
+        "Linux AppImage"
+    } else if lower.ends_with(".msi") {
+        "Windows Installer (MSI)"
+    } else if lower.ends_with(".msix") {
+        "Windows App Package (MSIX)"
+    } else if lower.ends_with(".exe") {
+        "Windows Installer"
+    } else if lower.ends_with(".pkg") && lower.contains("freebsd") {
+        "FreeBSD Package"
+    } else if let Some(os) = binary_os(&lower).filter(|_| lower.ends_with(".tar.gz")) {
+        return match arch_name(filename) {
+            Some(arch) => format!("{} Binary ({})", os, arch),
+            None => format!("{} Binary", os),
+        };
+    } else if let Some(os) = binary_os(&lower).filter(|_| lower.ends_with(".tar.zst")) {
+        return match arch_name(filename) {
+            Some(arch) => format!("{} Binary ({}, zstd)", os, arch),
+            None => format!("{} Binary (zstd)", os),
+        };
+    } else if lower.contains("shell-integration") {
+        return "Shell Completions and Man Pages".to_string();
+    } else if lower == crate::manifest::MANIFEST_FILE_NAME {
+        return "Release Manifest".to_string();
+    } else {
+        return filename.to_string();
+    };
+
+    match arch_name(filename) {
+        Some(arch) => format!("{} ({})", kind, arch),
+        None => kind.to_string(),
+    }
+}
+
+/// Operating system of a binary tarball, from the target in its (lowercased) filename
+fn binary_os(lower: &str) -> Option<&'static str> {
+    if lower.contains("freebsd") {
+        Some("FreeBSD")
+    } else if lower.contains("netbsd") {
+        Some("NetBSD")
+    } else if lower.contains("openbsd") {
+        Some("OpenBSD")
+    } else if lower.contains("-linux-musl") {
+        Some("Linux (static musl)")
+    } else if lower.contains("-linux-") {
+        Some("Linux")
+    } else if lower.contains("-apple-darwin") {
+        Some("macOS")
+    } else if lower.contains("-windows-") {
+        Some("Windows")
+    } else {
+        None
+    }
+}
+
+/// Human-readable architecture from the filename, if it names one
+fn arch_name(filename: &str) -> Option<&'static str> {
+    let lower = filename.to_ascii_lowercase();
+    let is_macos = lower.ends_with(".dmg") || lower.contains("apple-darwin");
+    if lower.contains("universal") {
+        Some("Universal")
+    } else if lower.contains("aarch64") || lower.contains("arm64") {
+        Some(if is_macos { "Apple Silicon" } else { "ARM64" })
+    } else if lower.contains("x86_64") || lower.contains("amd64") || lower.contains("x64") {
+        Some(if is_macos { "Intel" } else { "x86_64" })
+    } else {
+        None
+    }
+}
+
+/// Match `text` against a pattern with `*` and `?` wildcards
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    fn go(p: &[u8], t: &[u8]) -> bool {
+        match (p.first(), t.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => go(&p[1..], t) || (!t.is_empty() && go(p, &t[1..])),
+            (Some(b'?'), Some(_)) => go(&p[1..], &t[1..]),
+            (Some(a), Some(b)) if a == b => go(&p[1..], &t[1..]),
+            _ => false,
+        }
+    }
+    go(pattern.as_bytes(), text.as_bytes())
+}
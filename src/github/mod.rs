@@ -1,5 +1,22 @@
 //! GitHub integration for release operations
 
+mod assets;
+mod branches;
+mod checksums;
+mod labels;
 mod release_manager;
+mod retention;
+mod statuses;
+mod tags;
 
-pub use release_manager::{GitHubReleaseConfig, GitHubReleaseManager, GitHubReleaseResult};
+pub use assets::{ReleaseAssets, verify_sha256};
+pub use labels::AssetLabelRule;
+pub(crate) use labels::{content_type, default_label, wildcard_match};
+
+pub use release_manager::{
+    ExistingAssetPolicy, GitHubReleaseConfig, GitHubReleaseManager, GitHubReleaseResult, PrereleasePolicy,
+    ReleaseListing,
+};
+pub use retention::{RetentionPolicy, apply_retention};
+pub use statuses::CommitState;
+pub use tags::{TagMethod, TagOperation};
@@ -1,14 +1,14 @@
 //! GitHub Release management for coordinating release operations
 
 use crate::error::{CliError, ReleaseError, Result};
-use bytes::Bytes;
+use crate::version::VersionScheme;
+use super::labels::AssetLabelRule;
 use kodegen_tools_github::{GitHubClient, GitHubReleaseOptions};
 use semver::Version;
-use std::path::PathBuf;
 use std::sync::OnceLock;
 
 /// Configuration for GitHub releases
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct GitHubReleaseConfig {
     /// Repository owner
     pub owner: String,
@@ -16,27 +16,67 @@ pub struct GitHubReleaseConfig {
     pub repo: String,
     /// Whether to create draft releases
     pub draft: bool,
-    /// Whether to mark as pre-release for pre-1.0 versions
-    pub prerelease_for_zero_versions: bool,
+    /// Which versions are marked as a prerelease
+    pub prerelease: PrereleasePolicy,
     /// Custom release notes
     pub notes: Option<String>,
     /// GitHub token (from environment or config)
     pub token: Option<String>,
+    /// What to do when an asset with the same name already exists
+    pub on_existing_asset: ExistingAssetPolicy,
+    /// Version scheme, which determines tag names
+    pub version_scheme: VersionScheme,
+    /// Display label overrides for uploaded assets
+    pub asset_labels: Vec<AssetLabelRule>,
+}
+
+/// Policy for uploading an artifact whose name already exists on the release
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExistingAssetPolicy {
+    /// Abort the upload
+    Fail,
+    /// Keep the existing asset (warns if its checksum differs)
+    #[default]
+    Skip,
+    /// Replace the existing asset with the local build
+    Replace,
+}
+
+/// Which versions a GitHub release is marked as a prerelease for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrereleasePolicy {
+    /// 0.x versions and versions with a pre-release part (`-rc.1`)
+    #[default]
+    ZeroMajor,
+    /// Only versions with a pre-release part, so a stable 0.x is a full release
+    PreTag,
+    /// Every release
+    Always,
+    /// No release
+    Never,
 }
 
-impl Default for GitHubReleaseConfig {
-    fn default() -> Self {
-        Self {
-            owner: String::new(),
-            repo: String::new(),
-            draft: false,
-            prerelease_for_zero_versions: true,
-            notes: None,
-            token: None,
+impl PrereleasePolicy {
+    /// Whether a release of `version` is a prerelease
+    pub fn is_prerelease(self, version: &Version) -> bool {
+        match self {
+            Self::ZeroMajor => version.major == 0 || !version.pre.is_empty(),
+            Self::PreTag => !version.pre.is_empty(),
+            Self::Always => true,
+            Self::Never => false,
         }
     }
 }
 
+impl std::str::FromStr for PrereleasePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        <Self as clap::ValueEnum>::from_str(s, false)
+            .map_err(|_| format!("unknown prerelease policy `{}` (expected zero-major, pre-tag, always, never)", s))
+    }
+}
+
 /// Result of GitHub release operation
 #[derive(Debug, Clone)]
 pub struct GitHubReleaseResult {
@@ -50,14 +90,72 @@ pub struct GitHubReleaseResult {
     pub prerelease: bool,
 }
 
+/// A release as listed by [`GitHubReleaseManager::list_releases`]
+#[derive(Debug, Clone)]
+pub struct ReleaseListing {
+    /// Release ID
+    pub release_id: u64,
+    /// Tag the release belongs to
+    pub tag_name: String,
+    /// Whether this is a draft
+    pub draft: bool,
+    /// Whether this is a prerelease
+    pub prerelease: bool,
+    /// When the release (or draft) was created
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the release was published (`None` for drafts)
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// IDs of the uploaded assets
+    pub asset_ids: Vec<u64>,
+    /// Uploaded assets with their download counts
+    pub assets: Vec<ListedAsset>,
+}
+
+/// An asset of a [`ReleaseListing`]
+#[derive(Debug, Clone)]
+pub struct ListedAsset {
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// How often GitHub served the asset
+    pub download_count: u64,
+}
+
+/// Longest release body GitHub accepts, in characters
+pub(super) const RELEASE_BODY_LIMIT: usize = 125_000;
+
+/// Release body characters kept free for the checksum table added after upload
+const CHECKSUM_TABLE_RESERVE: usize = 20_000;
+
+/// Release notes too long for the release body
+///
+/// The body keeps as much of the notes as fits, followed by a link to the
+/// complete notes, which are uploaded as a release asset.
+#[derive(Debug, Clone)]
+pub struct NotesOverflow {
+    /// Asset name, `CHANGELOG-v<version>.md`
+    pub asset_name: String,
+    /// The complete notes
+    pub notes: String,
+}
+
 /// GitHub release manager
 pub struct GitHubReleaseManager {
     /// GitHub client
     client: GitHubClient,
     /// Configuration
     config: GitHubReleaseConfig,
+    /// Token for requests that bypass octocrab (asset uploads, plain REST calls)
+    token: String,
+    /// HTTP client for asset uploads and plain REST calls
+    http: reqwest::Client,
+    /// REST API root, without a trailing slash
+    api_url: String,
 }
 
+/// REST API root without `GITHUB_API_URL`
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
 /// One-time initialization guard for rustls crypto provider
 /// 
 /// Ensures install_default() is called exactly once per process, even when
@@ -88,14 +186,113 @@ impl GitHubReleaseManager {
                 reason: "GitHub token not provided. Set GH_TOKEN or GITHUB_TOKEN environment variable or use --github-token".to_string(),
             }))?;
 
-        let client = GitHubClient::with_token(token).map_err(|e| {
-            ReleaseError::Cli(CliError::ExecutionFailed {
-                command: "github_client_init".to_string(),
-                reason: e.to_string(),
-            })
-        })?;
+        // GitHub Enterprise Server (and Actions there) sets GITHUB_API_URL
+        let api_url = env_config
+            .get("GITHUB_API_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+
+        let client = GitHubClient::builder()
+            .personal_token(token.clone())
+            .base_uri(api_url.clone())
+            .build()
+            .map_err(|e| {
+                ReleaseError::Cli(CliError::ExecutionFailed {
+                    command: "github_client_init".to_string(),
+                    reason: e.to_string(),
+                })
+            })?;
+
+        let http = reqwest::Client::builder()
+            .user_agent("kodegen_bundler_release")
+            .build()
+            .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            config,
+            token,
+            http,
+            api_url,
+        })
+    }
 
-        Ok(Self { client, config })
+    /// REST request to `route` under `/repos/{owner}/{repo}/`
+    pub(super) fn repo_request(&self, method: reqwest::Method, route: &str) -> reqwest::RequestBuilder {
+        let url = format!(
+            "{}/repos/{}/{}/{}",
+            self.api_url, self.config.owner, self.config.repo, route
+        );
+        self.request(method, &url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+    }
+
+    /// Authenticated request to an absolute URL from an API response
+    /// (upload and asset URLs), without an `Accept` header
+    pub(super) fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.http.request(method, url).bearer_auth(&self.token)
+    }
+
+    /// Configuration the manager was created with
+    pub(super) fn config(&self) -> &GitHubReleaseConfig {
+        &self.config
+    }
+
+    /// Tag name for a release of `version` under the configured version scheme
+    pub fn tag_name(&self, version: &Version) -> String {
+        self.config.version_scheme.tag_name(version)
+    }
+
+    /// Append a section to the configured release notes
+    pub fn append_notes(&mut self, section: String) {
+        self.config.notes = Some(match self.config.notes.take() {
+            Some(notes) => format!("{}\n\n{}", notes, section),
+            None => section,
+        });
+    }
+
+    /// Release notes that do not fit in the release body of `version`
+    pub fn notes_overflow(&self, version: &Version, tag_name: &str) -> Option<NotesOverflow> {
+        self.release_body(version, tag_name, None).1
+    }
+
+    /// Body of the release of `version`: the given notes, else the configured
+    /// ones, truncated at a line break with a link to the full notes when they
+    /// would not leave room for the checksum table within GitHub's limit
+    fn release_body(
+        &self,
+        version: &Version,
+        tag_name: &str,
+        release_notes: Option<String>,
+    ) -> (String, Option<NotesOverflow>) {
+        let notes = release_notes
+            .or_else(|| self.config.notes.clone())
+            .unwrap_or_else(|| format!("Release version {}", version));
+        let limit = RELEASE_BODY_LIMIT - CHECKSUM_TABLE_RESERVE;
+        if notes.chars().count() <= limit {
+            return (notes, None);
+        }
+
+        let asset_name = format!("CHANGELOG-v{}.md", version);
+        let footer = format!(
+            "\n\n---\n\n*These notes were shortened to fit on the release page. \
+             The full changelog is attached: [{name}](https://github.com/{}/{}/releases/download/{}/{name})*",
+            self.config.owner,
+            self.config.repo,
+            tag_name,
+            name = asset_name,
+        );
+        let budget = limit - footer.chars().count();
+        let cut = notes.char_indices().nth(budget).map_or(notes.len(), |(i, _)| i);
+        // Prefer ending on a whole line, unless that throws away most of the budget
+        let cut = match notes[..cut].rfind('\n') {
+            Some(line_end) if line_end > cut / 2 => line_end,
+            _ => cut,
+        };
+        let body = format!("{}{}", notes[..cut].trim_end(), footer);
+
+        (body, Some(NotesOverflow { asset_name, notes }))
     }
 
     /// Create a GitHub release from an existing tag
@@ -108,23 +305,16 @@ impl GitHubReleaseManager {
         tag_name: &str,
         release_notes: Option<String>,
     ) -> Result<GitHubReleaseResult> {
-        // Determine if this should be a prerelease
-        let is_prerelease = if self.config.prerelease_for_zero_versions {
-            version.major == 0 || !version.pre.is_empty()
-        } else {
-            !version.pre.is_empty()
-        };
+        let is_prerelease = self.config.prerelease.is_prerelease(version);
 
-        // Use provided release notes or custom notes from config
-        let body = release_notes
-            .or_else(|| self.config.notes.clone())
-            .or_else(|| Some(format!("Release version {}", version)));
+        // Use provided release notes or custom notes from config, shortened to fit
+        let (body, _) = self.release_body(version, tag_name, release_notes);
 
         let options = GitHubReleaseOptions {
             tag_name: tag_name.to_string(),
             target_commitish: None, // Tag already exists, no commit needed
             name: Some(format!("Release {}", version)),
-            body,
+            body: Some(body),
             draft: true, // Always create as draft, publish later
             prerelease: is_prerelease,
         };
@@ -159,25 +349,18 @@ impl GitHubReleaseManager {
         commit_sha: &str,
         release_notes: Option<String>,
     ) -> Result<GitHubReleaseResult> {
-        let tag_name = format!("v{}", version);
+        let tag_name = self.tag_name(version);
 
-        // Determine if this should be a prerelease
-        let is_prerelease = if self.config.prerelease_for_zero_versions {
-            version.major == 0 || !version.pre.is_empty()
-        } else {
-            !version.pre.is_empty()
-        };
+        let is_prerelease = self.config.prerelease.is_prerelease(version);
 
-        // Use provided release notes or custom notes from config
-        let body = release_notes
-            .or_else(|| self.config.notes.clone())
-            .or_else(|| Some(format!("Release version {}", version)));
+        // Use provided release notes or custom notes from config, shortened to fit
+        let (body, _) = self.release_body(version, &tag_name, release_notes);
 
         let options = GitHubReleaseOptions {
             tag_name: tag_name.clone(),
             target_commitish: Some(commit_sha.to_string()),
             name: Some(format!("Release {}", version)),
-            body,
+            body: Some(body),
             draft: self.config.draft,
             prerelease: is_prerelease,
         };
@@ -205,7 +388,6 @@ impl GitHubReleaseManager {
     }
 
     /// Delete a release (for rollback)
-    #[allow(dead_code)]
     pub async fn delete_release(&self, release_id: u64) -> Result<()> {
         kodegen_tools_github::delete_release(
             self.client.inner().clone(),
@@ -224,7 +406,7 @@ impl GitHubReleaseManager {
 
     /// Check if a release already exists for this version
     ///
-    /// Uses the GitHub API to check if a release exists with tag v{version}.
+    /// Uses the GitHub API to check if a release exists with the version's tag.
     ///
     /// # Returns
     /// - `Ok(true)` - Release exists
@@ -232,7 +414,7 @@ impl GitHubReleaseManager {
     /// - `Err(_)` - Network or authentication error
     #[allow(dead_code)]
     pub async fn release_exists(&self, version: &Version) -> Result<bool> {
-        let tag_name = format!("v{}", version);
+        let tag_name = self.tag_name(version);
         
         match kodegen_tools_github::get_release_by_tag(
             self.client.inner().clone(),
@@ -248,38 +430,193 @@ impl GitHubReleaseManager {
         }
     }
 
-    /// Clean up existing GitHub release for this version
+    /// Releases whose tag is `tag_name`, drafts included
+    ///
+    /// The tag lookup endpoint does not return drafts, so this scans the most
+    /// recent 100 releases (GitHub lists newest first, drafts included).
+    pub async fn releases_for_tag(&self, tag_name: &str) -> Result<Vec<GitHubReleaseResult>> {
+        let page = self
+            .client
+            .inner()
+            .repos(&self.config.owner, &self.config.repo)
+            .releases()
+            .list()
+            .per_page(100)
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
+
+        Ok(page
+            .items
+            .into_iter()
+            .filter(|release| release.tag_name == tag_name)
+            .map(|release| GitHubReleaseResult {
+                release_id: release.id.0,
+                html_url: release.html_url.to_string(),
+                draft: release.draft,
+                prerelease: release.prerelease,
+            })
+            .collect())
+    }
+
+    /// Every release of the repository, drafts included, newest first
+    pub async fn list_releases(&self) -> Result<Vec<ReleaseListing>> {
+        let mut listings = Vec::new();
+
+        for page_number in 1u32.. {
+            let page = self
+                .client
+                .inner()
+                .repos(&self.config.owner, &self.config.repo)
+                .releases()
+                .list()
+                .per_page(100)
+                .page(page_number)
+                .send()
+                .await
+                .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
+
+            let count = page.items.len();
+            listings.extend(page.items.into_iter().map(|release| ReleaseListing {
+                release_id: release.id.0,
+                tag_name: release.tag_name,
+                draft: release.draft,
+                prerelease: release.prerelease,
+                created_at: release.created_at,
+                published_at: release.published_at,
+                asset_ids: release.assets.iter().map(|asset| asset.id.0).collect(),
+                assets: release
+                    .assets
+                    .iter()
+                    .map(|asset| ListedAsset {
+                        name: asset.name.clone(),
+                        size: asset.size.max(0) as u64,
+                        download_count: asset.download_count.max(0) as u64,
+                    })
+                    .collect(),
+            }));
+            if count < 100 {
+                break;
+            }
+        }
+
+        Ok(listings)
+    }
+
+    /// Merged pull requests whose merge commit is one of `range.commits`
+    ///
+    /// Pages through closed pull requests, most recently updated first, until
+    /// they were last updated before the previous tag.
+    pub async fn merged_pull_requests(
+        &self,
+        range: &crate::release_notes::ChangeRange,
+    ) -> Result<Vec<crate::release_notes::PullRequestNote>> {
+        /// The fields of a pull request used for release notes
+        #[derive(serde::Deserialize)]
+        struct PullRequest {
+            number: u64,
+            title: String,
+            html_url: String,
+            user: Option<User>,
+            #[serde(default)]
+            labels: Vec<Label>,
+            updated_at: Option<chrono::DateTime<chrono::Utc>>,
+            merged_at: Option<chrono::DateTime<chrono::Utc>>,
+            merge_commit_sha: Option<String>,
+        }
+        #[derive(serde::Deserialize)]
+        struct User {
+            login: String,
+        }
+        #[derive(serde::Deserialize)]
+        struct Label {
+            name: String,
+        }
+
+        let mut notes = Vec::new();
+        for page_number in 1u32.. {
+            let route = format!(
+                "pulls?state=closed&sort=updated&direction=desc&per_page=100&page={}",
+                page_number
+            );
+            let response = self
+                .repo_request(reqwest::Method::GET, &route)
+                .send()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Listing pull requests failed: {}", e)))?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(ReleaseError::GitHub(format!("Listing pull requests failed: {}", status)));
+            }
+            let page: Vec<PullRequest> = response
+                .json()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Listing pull requests failed: {}", e)))?;
+
+            let count = page.len();
+            let mut past_range = false;
+            for pr in page {
+                if let (Some(since), Some(updated)) = (range.since, pr.updated_at)
+                    && updated < since
+                {
+                    past_range = true;
+                    break;
+                }
+                let merged = pr.merged_at.is_some()
+                    && pr.merge_commit_sha.as_ref().is_some_and(|sha| range.commits.contains(sha));
+                if !merged {
+                    continue;
+                }
+                notes.push(crate::release_notes::PullRequestNote {
+                    number: pr.number,
+                    title: pr.title,
+                    url: pr.html_url,
+                    author: pr.user.map(|user| user.login),
+                    labels: pr.labels.into_iter().map(|label| label.name).collect(),
+                });
+            }
+            if past_range || count < 100 {
+                break;
+            }
+        }
+
+        Ok(notes)
+    }
+
+    /// Clean up existing GitHub releases for this version
     ///
-    /// Finds and deletes the GitHub release with tag v{version} if it exists.
-    /// Safe to call even if release doesn't exist - will silently succeed.
+    /// Deletes every release with the version's tag. Only drafts are deleted
+    /// automatically: a published release is refused unless `allow_published`
+    /// is set, in which case the caller is responsible for confirmation and
+    /// auditing. Safe to call when no release exists.
     ///
     /// # Returns
-    /// - `Ok(())` - Release deleted or didn't exist
-    /// - `Err(_)` - Network or authentication error
-    #[allow(dead_code)]
-    pub async fn cleanup_existing_release(&self, version: &Version) -> Result<()> {
-        let tag_name = format!("v{}", version);
-        
-        // Get release by tag to find the release_id
-        match kodegen_tools_github::get_release_by_tag(
-            self.client.inner().clone(),
-            &self.config.owner,
-            &self.config.repo,
-            &tag_name,
-        )
-        .await
+    /// - `Ok(deleted)` - Releases that were deleted (empty if none existed)
+    /// - `Err(_)` - A published release exists and `allow_published` is false,
+    ///   or a network/authentication error
+    pub async fn cleanup_existing_release(
+        &self,
+        version: &Version,
+        allow_published: bool,
+    ) -> Result<Vec<GitHubReleaseResult>> {
+        let tag_name = self.tag_name(version);
+        let existing = self.releases_for_tag(&tag_name).await?;
+
+        if !allow_published
+            && let Some(published) = existing.iter().find(|release| !release.draft)
         {
-            Ok(Some(release)) => {
-                // Release exists - delete it
-                self.delete_release(release.id.0).await?;
-                Ok(())
-            }
-            Ok(None) => {
-                // Release doesn't exist - nothing to cleanup
-                Ok(())
-            }
-            Err(e) => Err(ReleaseError::GitHub(e.to_string())),
+            return Err(ReleaseError::GitHub(format!(
+                "Release {} is already published ({}); refusing to delete it. \
+                 Use --force-recreate-release {} to delete and recreate it",
+                tag_name, published.html_url, tag_name
+            )));
         }
+
+        for release in &existing {
+            self.delete_release(release.release_id).await?;
+        }
+
+        Ok(existing)
     }
 
     /// Publish a draft release (remove draft status)
@@ -337,173 +674,40 @@ impl GitHubReleaseManager {
         }
     }
 
-    /// Get list of assets already uploaded to a release
+    /// Find an existing release by its tag name
     ///
-    /// Returns a HashSet of asset filenames for fast lookup.
-    ///
-    /// Uses octocrab::models::repos::Release which includes:
-    /// - `assets: Vec<octocrab::models::repos::Asset>` - List of uploaded assets
-    /// - Each Asset has `name: String` field for filename comparison
-    pub async fn get_release_asset_names(
-        &self,
-        version: &semver::Version,
-    ) -> Result<std::collections::HashSet<String>> {
-        use kodegen_tools_github::get_release_by_tag;
-
-        let tag_name = format!("v{}", version);
-
-        let release = get_release_by_tag(
+    /// Only published releases are visible through the tag lookup; drafts
+    /// return `Ok(None)`.
+    pub async fn find_release_by_tag(&self, tag_name: &str) -> Result<Option<GitHubReleaseResult>> {
+        let release = kodegen_tools_github::get_release_by_tag(
             self.client.inner().clone(),
             &self.config.owner,
             &self.config.repo,
-            &tag_name,
+            tag_name,
         )
         .await
         .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
 
-        // If release doesn't exist, return empty set
-        let release = match release {
-            Some(r) => r,
-            None => return Ok(std::collections::HashSet::new()),
-        };
-
-        // Extract asset names from octocrab Release.assets Vec
-        let asset_names: std::collections::HashSet<String> = release
-            .assets
-            .iter()
-            .map(|asset| asset.name.clone())
-            .collect();
-
-        Ok(asset_names)
+        Ok(release.map(|r| GitHubReleaseResult {
+            release_id: r.id.0,
+            html_url: r.html_url.to_string(),
+            draft: r.draft,
+            prerelease: r.prerelease,
+        }))
     }
 
-    /// Upload signed artifacts to release
-    ///
-    /// Reads artifact files and uploads them as release assets.
-    /// Returns list of download URLs for the uploaded assets.
-    pub async fn upload_artifacts(
-        &self,
-        release_id: u64,
-        artifact_paths: &[PathBuf],
-        version: &semver::Version,
-        runtime_config: &crate::cli::RuntimeConfig,
-    ) -> Result<Vec<String>> {
-        let mut uploaded_urls = Vec::new();
-
-        // Query existing assets ONCE before upload loop
-        runtime_config.verbose_println("   Checking for existing assets...").expect("Failed to write to stdout");
-        let existing_assets = self.get_release_asset_names(version).await?;
-
-        if !existing_assets.is_empty() {
-            runtime_config.verbose_println(&format!(
-                "   Found {} existing asset(s)",
-                existing_assets.len()
-            )).expect("Failed to write to stdout");
-        }
-
-        for artifact_path in artifact_paths {
-            // Safety check: should be filtered at call site, but double-check
-            if !artifact_path.is_file() {
-                runtime_config.warning_println(&format!(
-                    "⚠️  Skipping non-file artifact: {}",
-                    artifact_path.display()
-                )).expect("Failed to write to stdout");
-                continue;
-            }
-
-            // Extract filename for the asset
-            let filename = artifact_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or_else(|| {
-                    ReleaseError::Cli(CliError::InvalidArguments {
-                        reason: format!("Invalid artifact filename: {:?}", artifact_path),
-                    })
-                })?;
-
-            // IDEMPOTENCY: Skip if already uploaded
-            if existing_assets.contains(filename) {
-                runtime_config.indent(&format!("✓ Skipping {} (already uploaded)", filename)).expect("Failed to write to stdout");
-                continue;
-            }
-
-            // Read file content
-            let content = std::fs::read(artifact_path).map_err(|e| {
-                ReleaseError::Cli(CliError::ExecutionFailed {
-                    command: "read_artifact".to_string(),
-                    reason: e.to_string(),
-                })
-            })?;
-
-            // Create upload options
-            let upload_options = kodegen_tools_github::UploadAssetOptions {
-                release_id,
-                asset_name: filename.to_string(),
-                label: Some(create_artifact_label(filename)),
-                content: Bytes::from(content),
-                replace_existing: false, // Safer default - fails if asset exists
-            };
-
-            // Upload via GitHub client
-            let asset = self
-                .client
-                .upload_release_asset(&self.config.owner, &self.config.repo, upload_options)
-                .await
-                .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
-
-            // Extract download URL from asset
-            uploaded_urls.push(asset.browser_download_url.to_string());
-
-            runtime_config.indent(&format!("✓ Uploaded: {} ({} bytes)", filename, asset.size)).expect("Failed to write to stdout");
-        }
-
-        Ok(uploaded_urls)
-    }
-}
-
-/// Detect MIME type for bundle artifacts
-///
-/// Note: octocrab automatically detects content types from file extensions,
-/// but we provide this for future extensibility and explicit documentation.
-#[allow(dead_code)]
-fn detect_bundle_content_type(path: &std::path::Path) -> &'static str {
-    match path.extension().and_then(|e| e.to_str()) {
-        Some("deb") => "application/vnd.debian.binary-package",
-        Some("rpm") => "application/x-rpm",
-        Some("exe") => "application/x-msdownload",
-        Some("dmg") => "application/x-apple-diskimage",
-        Some("AppImage") => "application/x-executable",
-        Some("zip") => "application/zip",
-        Some("tar") | Some("gz") | Some("tgz") => "application/gzip",
-        _ => "application/octet-stream",
+    /// Rename a release and replace its body (used for the rolling nightly release)
+    pub async fn update_release_details(&self, release_id: u64, name: &str, body: &str) -> Result<()> {
+        self.client
+            .inner()
+            .repos(&self.config.owner, &self.config.repo)
+            .releases()
+            .update(release_id)
+            .name(name)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(e.to_string()))?;
+        Ok(())
     }
 }
-
-/// Create descriptive label for artifact based on filename
-fn create_artifact_label(filename: &str) -> String {
-    // Extract architecture
-    let arch = if filename.contains("aarch64") || filename.contains("arm64") {
-        "ARM64"
-    } else if filename.contains("x86_64") || filename.contains("amd64") {
-        "x86_64"
-    } else {
-        "multi-arch"
-    };
-
-    // Extract platform
-    let platform = if filename.contains("deb") {
-        "Debian/Ubuntu"
-    } else if filename.contains("rpm") {
-        "RedHat/Fedora"
-    } else if filename.contains("dmg") {
-        "macOS"
-    } else if filename.contains(".exe") {
-        "Windows"
-    } else if filename.contains("AppImage") {
-        "Linux AppImage"
-    } else {
-        "Binary"
-    };
-
-    format!("kodegen {} - {}", platform, arch)
-}
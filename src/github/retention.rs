@@ -0,0 +1,399 @@
+//! Retention policy for old GitHub releases and their assets.
+//!
+//! Runs after a successful release when `[package.metadata.release.retention]`
+//! is configured:
+//!
+//! ```toml
+//! [package.metadata.release.retention]
+//! draft-max-age-days = 14     # delete drafts created more than 14 days ago
+//! keep-prereleases = 5        # delete the assets of all but the 5 newest prereleases
+//! nightly-tags = "nightly-*"  # prereleases whose tag matches are nightlies...
+//! keep-nightlies = 1          # ...and all but the newest are deleted (default 1)
+//! keep-release-branches = 2   # delete the branches of all but the 2 newest releases
+//! ```
+//!
+//! Each rule is off unless its key is set. Pruned prereleases keep their
+//! release page and notes; only the uploaded assets go. Deleted releases keep
+//! their git tags. The release just created is never touched, and published
+//! stable releases are never deleted.
+//!
+//! `keep-release-branches` is applied by the release command itself, since
+//! release branches are also deleted from the local checkout.
+
+use super::assets::ReleaseAssets;
+use super::release_manager::{GitHubReleaseManager, ReleaseListing};
+use super::wildcard_match;
+use crate::cli::RuntimeConfig;
+use crate::error::{CliError, ReleaseError, Result};
+
+/// Retention rules from `[package.metadata.release.retention]`
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Delete drafts older than this many days
+    pub draft_max_age_days: Option<u32>,
+    /// Keep assets of only this many of the newest prereleases
+    pub keep_prereleases: Option<usize>,
+    /// Tag pattern (`*` / `?`) identifying nightly releases
+    pub nightly_tags: Option<String>,
+    /// Number of newest nightly releases to keep
+    pub keep_nightlies: usize,
+    /// Keep the release branches of only this many of the newest releases
+    pub keep_release_branches: Option<usize>,
+}
+
+/// What retention does to one release
+#[derive(Debug, Clone)]
+enum RetentionAction {
+    /// Delete a draft older than the maximum age
+    DeleteDraft { age_days: i64 },
+    /// Delete the assets of a prerelease beyond the newest `keep-prereleases`
+    PruneAssets,
+    /// Delete a nightly release superseded by newer ones
+    DeleteNightly,
+}
+
+/// Counts of what a retention run removed
+#[derive(Debug, Clone, Default)]
+pub struct RetentionSummary {
+    /// Releases deleted (old drafts and superseded nightlies)
+    pub releases_deleted: usize,
+    /// Assets deleted from old prereleases
+    pub assets_deleted: usize,
+    /// Deletions that failed (reported as warnings)
+    pub failures: usize,
+}
+
+impl RetentionPolicy {
+    /// Parse `[package.metadata.release.retention]`
+    pub(crate) fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("retention"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.retention] {}", reason),
+            })
+        };
+        let count = |key: &str, min: i64| -> Result<Option<i64>> {
+            match table.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_integer()
+                    .filter(|n| *n >= min)
+                    .map(Some)
+                    .ok_or_else(|| invalid(&format!("{} must be an integer >= {}", key, min))),
+            }
+        };
+
+        let nightly_tags = match table.get("nightly-tags") {
+            None => None,
+            Some(value) => Some(
+                value
+                    .as_str()
+                    .filter(|pattern| !pattern.is_empty())
+                    .ok_or_else(|| invalid("nightly-tags must be a tag pattern such as \"nightly-*\""))?
+                    .to_string(),
+            ),
+        };
+        let keep_nightlies = count("keep-nightlies", 1)?;
+        if keep_nightlies.is_some() && nightly_tags.is_none() {
+            return Err(invalid("keep-nightlies requires nightly-tags"));
+        }
+
+        Ok(Self {
+            // At least a day, so drafts of a release running concurrently survive
+            draft_max_age_days: count("draft-max-age-days", 1)?.map(|n| n as u32),
+            keep_prereleases: count("keep-prereleases", 0)?.map(|n| n as usize),
+            nightly_tags,
+            keep_nightlies: keep_nightlies.unwrap_or(1) as usize,
+            keep_release_branches: count("keep-release-branches", 0)?.map(|n| n as usize),
+        })
+    }
+
+    /// Whether any rule for releases and their assets is configured
+    pub fn is_enabled(&self) -> bool {
+        self.draft_max_age_days.is_some() || self.keep_prereleases.is_some() || self.nightly_tags.is_some()
+    }
+
+    /// A prerelease whose tag matches `nightly-tags`; a stable release never
+    /// counts as a nightly, however broad the pattern
+    fn is_nightly(&self, release: &ReleaseListing) -> bool {
+        release.prerelease
+            && self
+                .nightly_tags
+                .as_deref()
+                .is_some_and(|pattern| wildcard_match(pattern, &release.tag_name))
+    }
+
+    /// Decide what to do with each release (newest first, as GitHub lists them)
+    fn plan<'a>(
+        &self,
+        releases: &'a [ReleaseListing],
+        now: chrono::DateTime<chrono::Utc>,
+        current_release_id: u64,
+    ) -> Vec<(&'a ReleaseListing, RetentionAction)> {
+        let mut actions = Vec::new();
+
+        // Newest first by publication (drafts by creation)
+        let mut published: Vec<&ReleaseListing> = releases.iter().filter(|r| !r.draft).collect();
+        published.sort_by_key(|r| std::cmp::Reverse(r.published_at.or(r.created_at)));
+
+        if let Some(max_age) = self.draft_max_age_days {
+            for release in releases.iter().filter(|r| r.draft && r.release_id != current_release_id) {
+                let Some(created) = release.created_at else {
+                    continue;
+                };
+                let age_days = (now - created).num_days();
+                if age_days > i64::from(max_age) {
+                    actions.push((release, RetentionAction::DeleteDraft { age_days }));
+                }
+            }
+        }
+
+        if self.nightly_tags.is_some() {
+            let superseded = published
+                .iter()
+                .filter(|r| self.is_nightly(r))
+                .skip(self.keep_nightlies)
+                .filter(|r| r.release_id != current_release_id);
+            for release in superseded {
+                actions.push((*release, RetentionAction::DeleteNightly));
+            }
+        }
+
+        if let Some(keep) = self.keep_prereleases {
+            let old = published
+                .iter()
+                .filter(|r| r.prerelease && !self.is_nightly(r))
+                .skip(keep)
+                .filter(|r| r.release_id != current_release_id && !r.asset_ids.is_empty());
+            for release in old {
+                actions.push((*release, RetentionAction::PruneAssets));
+            }
+        }
+
+        actions
+    }
+}
+
+/// Apply `policy` to the repository's releases
+///
+/// Individual deletions that fail are reported as warnings and counted; only
+/// listing the releases is fatal.
+pub async fn apply_retention(
+    manager: &GitHubReleaseManager,
+    policy: &RetentionPolicy,
+    current_release_id: u64,
+    config: &RuntimeConfig,
+) -> Result<RetentionSummary> {
+    let releases = manager.list_releases().await?;
+    let actions = policy.plan(&releases, chrono::Utc::now(), current_release_id);
+    let mut summary = RetentionSummary::default();
+
+    if actions.is_empty() {
+        config
+            .verbose_println("✓ Retention: nothing to remove")
+            .expect("Failed to write to stdout");
+        return Ok(summary);
+    }
+
+    for (release, action) in actions {
+        let (description, outcome) = match action {
+            RetentionAction::DeleteDraft { age_days } => (
+                format!("draft {} ({} days old)", release.tag_name, age_days),
+                manager.delete_release(release.release_id).await.map(|()| {
+                    summary.releases_deleted += 1;
+                }),
+            ),
+            RetentionAction::DeleteNightly => (
+                format!("superseded nightly {}", release.tag_name),
+                manager.delete_release(release.release_id).await.map(|()| {
+                    summary.releases_deleted += 1;
+                }),
+            ),
+            RetentionAction::PruneAssets => {
+                let mut outcome = Ok(());
+                for asset_id in &release.asset_ids {
+                    match manager.delete_asset(*asset_id).await {
+                        Ok(()) => summary.assets_deleted += 1,
+                        Err(e) => outcome = Err(e),
+                    }
+                }
+                (
+                    format!("{} asset(s) of prerelease {}", release.asset_ids.len(), release.tag_name),
+                    outcome,
+                )
+            }
+        };
+
+        match outcome {
+            Ok(()) => {
+                config.record_action(&format!("Retention: deleted {}", description));
+                config
+                    .indent(&format!("🗑  Deleted {}", description))
+                    .expect("Failed to write to stdout");
+            }
+            Err(e) => {
+                summary.failures += 1;
+                config
+                    .warning_println(&format!("⚠️  Failed to delete {}: {}", description, e))
+                    .expect("Failed to write to stdout");
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn now() -> DateTime<Utc> {
+        "2026-06-15T12:00:00Z".parse().unwrap()
+    }
+
+    /// A release with one asset, published (or created, for a draft) `days_ago`
+    fn release(id: u64, tag: &str, draft: bool, prerelease: bool, days_ago: i64) -> ReleaseListing {
+        let at = now() - Duration::days(days_ago);
+        ReleaseListing {
+            release_id: id,
+            tag_name: tag.to_string(),
+            draft,
+            prerelease,
+            created_at: Some(at),
+            published_at: (!draft).then_some(at),
+            asset_ids: vec![id * 100],
+            assets: Vec::new(),
+        }
+    }
+
+    /// `(tag, action)` pairs of a plan, for comparison
+    fn planned(policy: &RetentionPolicy, releases: &[ReleaseListing], current: u64) -> Vec<(String, String)> {
+        policy
+            .plan(releases, now(), current)
+            .into_iter()
+            .map(|(release, action)| {
+                let action = match action {
+                    RetentionAction::DeleteDraft { .. } => "delete-draft",
+                    RetentionAction::PruneAssets => "prune-assets",
+                    RetentionAction::DeleteNightly => "delete-nightly",
+                };
+                (release.tag_name.clone(), action.to_string())
+            })
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(tag, action)| (tag.to_string(), action.to_string())).collect()
+    }
+
+    #[test]
+    fn deletes_drafts_older_than_max_age() {
+        let policy = RetentionPolicy {
+            draft_max_age_days: Some(14),
+            ..Default::default()
+        };
+        let releases = [
+            release(1, "v1.0.0", true, false, 30),
+            release(2, "v1.1.0", true, false, 14),
+            release(3, "v0.9.0", false, false, 60),
+        ];
+        assert_eq!(planned(&policy, &releases, 0), pairs(&[("v1.0.0", "delete-draft")]));
+    }
+
+    #[test]
+    fn keeps_newest_prereleases_assets() {
+        let policy = RetentionPolicy {
+            keep_prereleases: Some(1),
+            ..Default::default()
+        };
+        let releases = [
+            release(1, "v2.0.0-rc.2", false, true, 1),
+            release(2, "v2.0.0-rc.1", false, true, 5),
+            release(3, "v2.0.0-beta.1", false, true, 10),
+            release(4, "v1.0.0", false, false, 20),
+        ];
+        assert_eq!(
+            planned(&policy, &releases, 0),
+            pairs(&[("v2.0.0-rc.1", "prune-assets"), ("v2.0.0-beta.1", "prune-assets")])
+        );
+    }
+
+    #[test]
+    fn keeps_newest_nightlies() {
+        let policy = RetentionPolicy {
+            nightly_tags: Some("nightly-*".to_string()),
+            keep_nightlies: 2,
+            ..Default::default()
+        };
+        let releases = [
+            release(1, "nightly-20260614", false, true, 1),
+            release(2, "nightly-20260613", false, true, 2),
+            release(3, "nightly-20260612", false, true, 3),
+            release(4, "v1.0.0", false, false, 4),
+        ];
+        assert_eq!(
+            planned(&policy, &releases, 0),
+            pairs(&[("nightly-20260612", "delete-nightly")])
+        );
+    }
+
+    #[test]
+    fn broad_nightly_pattern_spares_stable_releases() {
+        let policy = RetentionPolicy {
+            nightly_tags: Some("*".to_string()),
+            keep_nightlies: 1,
+            ..Default::default()
+        };
+        let releases = [
+            release(1, "v1.2.0-nightly.2", false, true, 1),
+            release(2, "v1.2.0-nightly.1", false, true, 2),
+            release(3, "v1.1.0", false, false, 3),
+            release(4, "v1.0.0", false, false, 4),
+        ];
+        assert_eq!(
+            planned(&policy, &releases, 0),
+            pairs(&[("v1.2.0-nightly.1", "delete-nightly")])
+        );
+    }
+
+    #[test]
+    fn never_touches_the_current_release() {
+        let policy = RetentionPolicy {
+            draft_max_age_days: Some(1),
+            keep_prereleases: Some(0),
+            nightly_tags: Some("nightly-*".to_string()),
+            keep_nightlies: 1,
+            ..Default::default()
+        };
+        let releases = [
+            release(1, "nightly-20260614", false, true, 1),
+            release(2, "nightly-20260613", false, true, 2),
+            release(3, "v2.0.0-rc.1", false, true, 3),
+            release(4, "v2.0.0", true, false, 30),
+        ];
+        assert_eq!(
+            planned(&policy, &releases, 0),
+            pairs(&[
+                ("v2.0.0", "delete-draft"),
+                ("nightly-20260613", "delete-nightly"),
+                ("v2.0.0-rc.1", "prune-assets"),
+            ])
+        );
+        for current in [2, 3, 4] {
+            let tag = &releases.iter().find(|r| r.release_id == current).unwrap().tag_name;
+            assert!(
+                !planned(&policy, &releases, current).iter().any(|(planned_tag, _)| planned_tag == tag),
+                "release {} was planned for removal",
+                tag
+            );
+        }
+    }
+}
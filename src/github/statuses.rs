@@ -0,0 +1,73 @@
+//! Commit statuses on the released commit.
+//!
+//! With `--commit-status` each release phase posts a status under its own
+//! context (`release/build`, `release/upload`, ...), so the progress of a
+//! release shows on the commit in the GitHub UI, and branch protection or
+//! other automation can wait for `release/github-publish`.
+
+use super::release_manager::GitHubReleaseManager;
+use super::tags::json;
+use crate::error::{ReleaseError, Result};
+use reqwest::Method;
+
+/// State of a commit status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitState {
+    Pending,
+    Success,
+    Failure,
+    Error,
+}
+
+impl CommitState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// GitHub rejects longer descriptions
+const MAX_DESCRIPTION: usize = 140;
+
+impl GitHubReleaseManager {
+    /// Set the status `context` on commit `sha`
+    ///
+    /// `target_url` is linked from the status (e.g. the CI run).
+    pub async fn set_commit_status(
+        &self,
+        sha: &str,
+        context: &str,
+        state: CommitState,
+        description: &str,
+        target_url: Option<&str>,
+    ) -> Result<()> {
+        let description: String = if description.chars().count() > MAX_DESCRIPTION {
+            let mut short: String = description.chars().take(MAX_DESCRIPTION - 1).collect();
+            short.push('…');
+            short
+        } else {
+            description.to_string()
+        };
+        let mut body = serde_json::json!({
+            "state": state.as_str(),
+            "context": context,
+            "description": description,
+        });
+        if let Some(url) = target_url {
+            body["target_url"] = serde_json::Value::String(url.to_string());
+        }
+
+        let response = self
+            .repo_request(Method::POST, &format!("statuses/{}", sha))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Setting status {} failed: {}", context, e)))?;
+        json::<serde_json::Value>(response, &format!("Setting status {}", context)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+//! Tag protection rulesets, and tags written through the REST API.
+//!
+//! A repository (or its organization) can protect tags with rulesets whose
+//! target is `tag`: creating, moving or deleting a matching tag is refused
+//! unless the token's actor is on the ruleset's bypass list. A refused
+//! `git push` only reports "push declined due to repository rule
+//! violations", so the release looks the rulesets up to say which one, and
+//! with `--tag-via api` it writes the tag ref through the API instead, where
+//! the bypass list applies to GitHub App and fine-grained tokens as well.
+//!
+//! The legacy tag protection rules (`/tags/protection`) were retired in
+//! favour of rulesets and are not checked.
+
+use super::release_manager::GitHubReleaseManager;
+use super::wildcard_match;
+use crate::error::{ReleaseError, Result};
+use reqwest::{Method, StatusCode};
+
+/// How the release creates and moves tags on GitHub
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TagMethod {
+    /// `git push` from the release clone
+    #[default]
+    Git,
+    /// The git refs REST API, which honours ruleset bypass lists
+    Api,
+}
+
+/// What is about to happen to a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagOperation {
+    /// The tag does not exist yet
+    Create,
+    /// The tag exists and is moved to another commit (nightly)
+    Move,
+}
+
+impl TagOperation {
+    /// Rule types that restrict this operation
+    fn rules(self) -> &'static [&'static str] {
+        match self {
+            Self::Create => &["creation", "required_signatures"],
+            Self::Move => &["update", "non_fast_forward", "required_signatures"],
+        }
+    }
+}
+
+/// An active tag ruleset that restricts an operation on a tag
+#[derive(Debug, Clone)]
+pub struct TagRuleset {
+    pub name: String,
+    /// The restricting rule types, e.g. `creation`
+    pub rules: Vec<String>,
+    /// Whether the token's actor is on the bypass list
+    pub can_bypass: bool,
+}
+
+impl std::fmt::Display for TagRuleset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ruleset '{}' ({})", self.name, self.rules.join(", "))
+    }
+}
+
+/// A ruleset as returned by `GET /repos/{owner}/{repo}/rulesets/{id}`
+#[derive(serde::Deserialize)]
+struct Ruleset {
+    name: String,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    enforcement: String,
+    #[serde(default)]
+    conditions: Option<Conditions>,
+    #[serde(default)]
+    rules: Vec<Rule>,
+    #[serde(default)]
+    current_user_can_bypass: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Conditions {
+    ref_name: Option<RefName>,
+}
+
+#[derive(serde::Deserialize)]
+struct RefName {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Rule {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RulesetSummary {
+    id: u64,
+}
+
+#[derive(serde::Deserialize)]
+struct GitRef {
+    object: GitObject,
+}
+
+#[derive(serde::Deserialize)]
+struct GitObject {
+    sha: String,
+}
+
+impl GitHubReleaseManager {
+    /// Active rulesets that restrict `operation` on `tag`
+    ///
+    /// `None` when the rulesets cannot be read: the token lacks access, or
+    /// the server predates rulesets.
+    pub async fn tag_rulesets(&self, tag: &str, operation: TagOperation) -> Result<Option<Vec<TagRuleset>>> {
+        let response = self
+            .repo_request(Method::GET, "rulesets?targets=tag&includes_parents=true&per_page=100")
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Listing rulesets failed: {}", e)))?;
+        if matches!(response.status(), StatusCode::FORBIDDEN | StatusCode::NOT_FOUND) {
+            return Ok(None);
+        }
+        let summaries: Vec<RulesetSummary> = json(response, "Listing rulesets").await?;
+
+        let full_ref = format!("refs/tags/{}", tag);
+        let mut restricting = Vec::new();
+        for summary in summaries {
+            let response = self
+                .repo_request(Method::GET, &format!("rulesets/{}", summary.id))
+                .send()
+                .await
+                .map_err(|e| ReleaseError::GitHub(format!("Reading ruleset {} failed: {}", summary.id, e)))?;
+            if matches!(response.status(), StatusCode::FORBIDDEN | StatusCode::NOT_FOUND) {
+                return Ok(None);
+            }
+            let ruleset: Ruleset = json(response, "Reading a ruleset").await?;
+            if ruleset.enforcement != "active" || ruleset.target.as_deref().is_some_and(|t| t != "tag") {
+                continue;
+            }
+            let Some(ref_name) = ruleset.conditions.as_ref().and_then(|c| c.ref_name.as_ref()) else {
+                continue;
+            };
+            let matches = |patterns: &[String]| patterns.iter().any(|pattern| ref_matches(pattern, &full_ref));
+            if !matches(&ref_name.include) || matches(&ref_name.exclude) {
+                continue;
+            }
+
+            let rules: Vec<String> = ruleset
+                .rules
+                .iter()
+                .filter(|rule| operation.rules().contains(&rule.kind.as_str()))
+                .map(|rule| rule.kind.clone())
+                .collect();
+            if rules.is_empty() {
+                continue;
+            }
+            restricting.push(TagRuleset {
+                name: ruleset.name,
+                rules,
+                can_bypass: matches!(ruleset.current_user_can_bypass.as_deref(), Some("always" | "exempt")),
+            });
+        }
+        Ok(Some(restricting))
+    }
+
+    /// Object the remote `tag` points to, `None` when GitHub has no such tag
+    pub async fn remote_tag(&self, tag: &str) -> Result<Option<String>> {
+        let response = self
+            .repo_request(Method::GET, &format!("git/ref/tags/{}", tag))
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Looking up tag {} failed: {}", tag, e)))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let git_ref: GitRef = json(response, &format!("Looking up tag {}", tag)).await?;
+        Ok(Some(git_ref.object.sha))
+    }
+
+    /// Point `tag` at commit `sha` through the API, creating or force-moving it
+    pub async fn write_tag(&self, tag: &str, sha: &str) -> Result<()> {
+        let request = if self.remote_tag(tag).await?.is_some() {
+            self.repo_request(Method::PATCH, &format!("git/refs/tags/{}", tag))
+                .json(&serde_json::json!({ "sha": sha, "force": true }))
+        } else {
+            self.repo_request(Method::POST, "git/refs")
+                .json(&serde_json::json!({ "ref": format!("refs/tags/{}", tag), "sha": sha }))
+        };
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ReleaseError::GitHub(format!("Writing tag {} failed: {}", tag, e)))?;
+        json::<serde_json::Value>(response, &format!("Writing tag {}", tag)).await?;
+        Ok(())
+    }
+}
+
+/// Whether a ruleset `ref_name` pattern matches `full_ref`
+///
+/// Patterns are full ref globs (`refs/tags/v*`) or `~ALL`.
+fn ref_matches(pattern: &str, full_ref: &str) -> bool {
+    if pattern == "~ALL" {
+        return true;
+    }
+    if pattern.starts_with("refs/") {
+        wildcard_match(pattern, full_ref)
+    } else {
+        wildcard_match(&format!("refs/tags/{}", pattern), full_ref)
+    }
+}
+
+/// Decode a successful response, or fail with GitHub's message
+pub(super) async fn json<T: serde::de::DeserializeOwned>(response: reqwest::Response, action: &str) -> Result<T> {
+    let status = response.status();
+    if !status.is_success() {
+        let message = response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body.get("message").and_then(|m| m.as_str()).map(String::from))
+            .unwrap_or_default();
+        return Err(ReleaseError::GitHub(format!("{} failed: {} {}", action, status, message).trim_end().to_string()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| ReleaseError::GitHub(format!("{} failed: {}", action, e)))
+}
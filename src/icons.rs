@@ -0,0 +1,152 @@
+//! Upfront checks of the bundle icons listed in `[package.metadata.bundle] icon`.
+//!
+//! The bundler only reads icons when it packages a platform, and a missing or
+//! broken icon then fails deep inside a platform tool (`iconutil`, WiX, the
+//! AppImage tooling) after the build. The listed paths and patterns are
+//! checked before anything is built instead:
+//!
+//! - every entry must match at least one file (`*` / `?` in the file name);
+//! - PNGs must be valid, and should be square and have an alpha channel;
+//! - `.icns` and `.ico` files must carry their format's header.
+//!
+//! Missing and malformed files stop the release; shape and transparency
+//! problems only warn, since platform tools accept them with worse results.
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::path::{Path, PathBuf};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Check the icons of the package in `package_dir`
+///
+/// Returns warnings; fails on entries without files and on malformed icons.
+pub fn check_icons(package_dir: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(package_dir.join("Cargo.toml"))?;
+    let manifest: toml::Value = toml::from_str(&content)?;
+    let Some(entries) = manifest
+        .get("package")
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("bundle"))
+        .and_then(|b| b.get("icon"))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let invalid = |reason: String| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("[package.metadata.bundle] icon: {}", reason),
+        })
+    };
+    let entries: Vec<&str> = entries
+        .as_array()
+        .and_then(|arr| arr.iter().map(|v| v.as_str()).collect())
+        .ok_or_else(|| invalid("must be an array of paths".to_string()))?;
+
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+    for entry in entries {
+        let files = matching_files(package_dir, entry);
+        if files.is_empty() {
+            problems.push(format!("{} matches no file", entry));
+            continue;
+        }
+        for file in files {
+            let display = file.strip_prefix(package_dir).unwrap_or(&file).display().to_string();
+            match check_icon(&file) {
+                Ok(notes) => warnings.extend(notes.into_iter().map(|note| format!("{}: {}", display, note))),
+                Err(problem) => problems.push(format!("{}: {}", display, problem)),
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(invalid(problems.join("; ")));
+    }
+    Ok(warnings)
+}
+
+/// Files matching an icon entry, sorted
+pub(crate) fn matching_files(package_dir: &Path, entry: &str) -> Vec<PathBuf> {
+    let path = package_dir.join(entry);
+    let Some(pattern) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    if !pattern.contains(['*', '?']) {
+        return if path.is_file() { vec![path] } else { Vec::new() };
+    }
+
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|file| {
+            file.is_file()
+                && file
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| crate::github::wildcard_match(pattern, name))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Check one icon file; returns warnings, or the reason it is unusable
+fn check_icon(path: &Path) -> std::result::Result<Vec<String>, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "png" => check_png(&bytes),
+        "icns" if bytes.starts_with(b"icns") => Ok(Vec::new()),
+        "icns" => Err("not an ICNS file".to_string()),
+        "ico" if bytes.starts_with(&[0, 0, 1, 0]) => Ok(Vec::new()),
+        "ico" => Err("not an ICO file".to_string()),
+        other => Ok(vec![format!("unrecognized icon type .{}", other)]),
+    }
+}
+
+fn check_png(bytes: &[u8]) -> std::result::Result<Vec<String>, String> {
+    if !bytes.starts_with(PNG_SIGNATURE) || bytes.len() < 33 || &bytes[12..16] != b"IHDR" {
+        return Err("not a valid PNG".to_string());
+    }
+    let width = u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+    let height = u32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]);
+    let color_type = bytes[25];
+
+    let mut warnings = Vec::new();
+    if width != height {
+        warnings.push(format!("{}x{} is not square", width, height));
+    }
+    // Grayscale+alpha and RGBA carry alpha; other types only through a tRNS chunk
+    if !matches!(color_type, 4 | 6) && !has_chunk(bytes, b"tRNS") {
+        warnings.push(format!("{}x{} has no alpha channel", width, height));
+    }
+    Ok(warnings)
+}
+
+/// Whether a PNG has a chunk of `kind` before its image data
+fn has_chunk(bytes: &[u8], kind: &[u8; 4]) -> bool {
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 8 <= bytes.len() {
+        let length = u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let chunk = &bytes[offset + 4..offset + 8];
+        if chunk == kind {
+            return true;
+        }
+        if chunk == b"IDAT" {
+            return false;
+        }
+        offset += 12 + length;
+    }
+    false
+}
@@ -0,0 +1,144 @@
+//! AppImages: an ELF runtime followed by a squashfs image.
+//!
+//! Type 2 AppImages mark themselves with `AI\x02` in the ELF identification
+//! padding. Update information and the embedded signature live in the
+//! `.upd_info` and `.sha256_sig` ELF sections; the squashfs starts right after
+//! the section header table.
+
+use super::{BundleFormat, Inspection, bytes, le_u16, le_u32, le_u64, malformed};
+use crate::error::Result;
+
+pub(super) fn inspect(data: &[u8]) -> Result<Inspection> {
+    let mut inspection = Inspection::default();
+
+    let kind = data.get(10).copied().unwrap_or(0);
+    inspection.field("AppImage type", kind.to_string());
+
+    let elf = Elf::parse(data)?;
+    inspection.field(
+        "Architecture",
+        match elf.machine {
+            0x3e => "x86_64".to_string(),
+            0xb7 => "aarch64".to_string(),
+            0x03 => "i386".to_string(),
+            0x28 => "armhf".to_string(),
+            other => format!("ELF machine {:#x}", other),
+        },
+    );
+
+    if let Some(update) = elf.section(data, ".upd_info") {
+        inspection.field("Update information", nul_trimmed(update));
+    }
+
+    if let Some(signature) = elf.section(data, ".sha256_sig")
+        && signature.iter().any(|&b| b != 0)
+    {
+        inspection.signed = Some("OpenPGP (.sha256_sig)".to_string());
+    }
+
+    let squashfs = elf.section_table_end();
+    if bytes(data, squashfs, 4) == Some(b"hsqs") {
+        inspection.field("Payload offset", squashfs.to_string());
+        inspection.field(
+            "Payload compression",
+            match le_u16(data, squashfs + 20).unwrap_or(0) {
+                1 => "gzip",
+                2 => "lzma",
+                3 => "lzo",
+                4 => "xz",
+                5 => "lz4",
+                6 => "zstd",
+                _ => "unknown",
+            },
+        );
+        inspection.field(
+            "Payload inodes",
+            le_u32(data, squashfs + 4).unwrap_or(0).to_string(),
+        );
+    } else {
+        inspection
+            .notes
+            .push("No squashfs payload found after the ELF section headers".to_string());
+    }
+
+    inspection
+        .notes
+        .push("File listing requires extracting the squashfs payload (--appimage-extract)".to_string());
+
+    Ok(inspection)
+}
+
+/// The parts of an ELF header needed to find sections
+struct Elf {
+    is_64: bool,
+    machine: u16,
+    shoff: u64,
+    shentsize: u16,
+    shnum: u16,
+    shstrndx: u16,
+}
+
+impl Elf {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let truncated = || malformed(BundleFormat::AppImage, "ELF header is truncated");
+        if data.get(5) != Some(&1) {
+            return Err(malformed(BundleFormat::AppImage, "big-endian ELF is not supported"));
+        }
+        let is_64 = data.get(4) == Some(&2);
+        let (shoff, rest) = if is_64 {
+            (le_u64(data, 0x28).ok_or_else(truncated)?, 0x3a)
+        } else {
+            (u64::from(le_u32(data, 0x20).ok_or_else(truncated)?), 0x2e)
+        };
+        Ok(Self {
+            is_64,
+            machine: le_u16(data, 0x12).ok_or_else(truncated)?,
+            shoff,
+            shentsize: le_u16(data, rest).ok_or_else(truncated)?,
+            shnum: le_u16(data, rest + 2).ok_or_else(truncated)?,
+            shstrndx: le_u16(data, rest + 4).ok_or_else(truncated)?,
+        })
+    }
+
+    fn section_table_end(&self) -> usize {
+        self.shoff as usize + self.shentsize as usize * self.shnum as usize
+    }
+
+    /// `(name offset, file offset, size)` of section `index`
+    fn section_header(&self, data: &[u8], index: u16) -> Option<(usize, usize, usize)> {
+        let header = self.shoff as usize + index as usize * self.shentsize as usize;
+        let name = le_u32(data, header)? as usize;
+        if self.is_64 {
+            Some((
+                name,
+                le_u64(data, header + 0x18)? as usize,
+                le_u64(data, header + 0x20)? as usize,
+            ))
+        } else {
+            Some((
+                name,
+                le_u32(data, header + 0x10)? as usize,
+                le_u32(data, header + 0x14)? as usize,
+            ))
+        }
+    }
+
+    /// Contents of the section called `name`
+    fn section<'a>(&self, data: &'a [u8], name: &str) -> Option<&'a [u8]> {
+        let (_, strtab, strtab_len) = self.section_header(data, self.shstrndx)?;
+        let names = bytes(data, strtab, strtab_len)?;
+        (0..self.shnum).find_map(|index| {
+            let (name_offset, offset, size) = self.section_header(data, index)?;
+            let section_name = names.get(name_offset..)?.split(|&b| b == 0).next()?;
+            (section_name == name.as_bytes())
+                .then(|| bytes(data, offset, size))
+                .flatten()
+        })
+    }
+}
+
+fn nul_trimmed(data: &[u8]) -> String {
+    String::from_utf8_lossy(data.split(|&b| b == 0).next().unwrap_or_default())
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,158 @@
+//! Debian packages: an `ar` archive of `debian-binary`, `control.tar.*` and `data.tar.*`.
+
+use super::{BundleFormat, FileEntry, Inspection, malformed};
+use crate::error::Result;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+const AR_HEADER_LEN: usize = 60;
+
+pub(super) fn inspect(data: &[u8]) -> Result<Inspection> {
+    let members = ar_members(data)?;
+    let mut inspection = Inspection::default();
+
+    let member = |prefix: &str| members.iter().find(|(name, _)| name.starts_with(prefix));
+
+    if let Some((_, content)) = member("debian-binary") {
+        inspection.field("Format version", String::from_utf8_lossy(content).trim());
+    }
+
+    let (control_name, control_tar) =
+        member("control.tar").ok_or_else(|| malformed(BundleFormat::Deb, "no control archive"))?;
+    let control = read_tar_file(control_name, control_tar, "control")?
+        .ok_or_else(|| malformed(BundleFormat::Deb, "control archive has no control file"))?;
+
+    for (key, value) in parse_control(&control) {
+        match key.as_str() {
+            "Version" => inspection.version = Some(value.clone()),
+            "Description" => {
+                // Only the synopsis line; the long description can be pages long
+                let synopsis = value.lines().next().unwrap_or_default().to_string();
+                inspection.field(&key, synopsis);
+                continue;
+            }
+            _ => {}
+        }
+        inspection.field(&key, value);
+    }
+
+    if let Some((data_name, data_tar)) = member("data.tar") {
+        inspection.files = Some(list_tar(data_name, data_tar)?);
+    } else {
+        inspection.notes.push("No data archive found".to_string());
+    }
+
+    // debsigs stores signatures as extra ar members
+    let signatures: Vec<&str> = members
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| name.starts_with("_gpg"))
+        .collect();
+    if !signatures.is_empty() {
+        inspection.signed = Some(format!("debsigs ({})", signatures.join(", ")));
+    }
+
+    Ok(inspection)
+}
+
+/// `(name, content)` of every ar member
+fn ar_members(data: &[u8]) -> Result<Vec<(String, &[u8])>> {
+    let mut members = Vec::new();
+    let mut offset = AR_MAGIC.len();
+
+    while offset + AR_HEADER_LEN <= data.len() {
+        let header = &data[offset..offset + AR_HEADER_LEN];
+        if &header[58..60] != b"`\n" {
+            return Err(malformed(BundleFormat::Deb, format!("bad ar header at offset {}", offset)));
+        }
+        let name = String::from_utf8_lossy(&header[..16])
+            .trim_end()
+            .trim_end_matches('/')
+            .to_string();
+        let size: usize = String::from_utf8_lossy(&header[48..58])
+            .trim()
+            .parse()
+            .map_err(|_| malformed(BundleFormat::Deb, format!("bad size for member {}", name)))?;
+
+        let start = offset + AR_HEADER_LEN;
+        let content = data
+            .get(start..start + size)
+            .ok_or_else(|| malformed(BundleFormat::Deb, format!("member {} is truncated", name)))?;
+        members.push((name, content));
+
+        // Members are 2-byte aligned
+        offset = start + size + (size % 2);
+    }
+
+    Ok(members)
+}
+
+/// Decompress a tar member according to its name's extension
+fn decompress<'a>(name: &str, content: &'a [u8]) -> Result<Box<dyn Read + 'a>> {
+    Ok(match name.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("gz") => Box::new(flate2::read::GzDecoder::new(content)),
+        Some("xz") => Box::new(lzma_rust2::XzReader::new(content, true)),
+        Some("zst") => Box::new(zstd::stream::read::Decoder::new(content)?),
+        Some("tar") => Box::new(content),
+        _ => {
+            return Err(malformed(
+                BundleFormat::Deb,
+                format!("unsupported compression for {}", name),
+            ));
+        }
+    })
+}
+
+/// Contents of `file` (matched with or without a leading `./`) in a tar member
+fn read_tar_file(name: &str, content: &[u8], file: &str) -> Result<Option<String>> {
+    let mut archive = tar::Archive::new(decompress(name, content)?);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().trim_start_matches("./").to_string();
+        if path == file {
+            let mut text = String::new();
+            entry.read_to_string(&mut text)?;
+            return Ok(Some(text));
+        }
+    }
+    Ok(None)
+}
+
+/// Every non-directory entry of a tar member
+fn list_tar(name: &str, content: &[u8]) -> Result<Vec<FileEntry>> {
+    let mut archive = tar::Archive::new(decompress(name, content)?);
+    let mut files = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().trim_start_matches('.').to_string();
+        let size = entry.header().size()?;
+        let mut content = Vec::with_capacity(size as usize);
+        entry.read_to_end(&mut content)?;
+        files.push(FileEntry {
+            path,
+            size,
+            digest: Some(format!("sha256:{:x}", Sha256::digest(&content))),
+        });
+    }
+    Ok(files)
+}
+
+/// `Key: value` fields of a control file, with continuation lines joined
+fn parse_control(control: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in control.lines() {
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some((_, value)) = fields.last_mut() {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line.split_once(':') {
+            fields.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    fields
+}
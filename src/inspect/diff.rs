@@ -0,0 +1,171 @@
+//! File-level comparison of two inspected bundles.
+//!
+//! Files are matched by path, with each bundle's version replaced by a
+//! placeholder first, so `/usr/share/doc/app-1.2.0/` and
+//! `/usr/share/doc/app-1.3.0/` count as the same directory. A matched file has
+//! changed when its size differs, or when both bundles record a digest of the
+//! same kind and the digests differ.
+
+use super::{BundleReport, FileEntry};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A file present in both bundles with different contents
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedFile {
+    /// Path in the newer bundle
+    pub path: String,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+/// A metadata field that differs between the bundles
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataChange {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Differences between an older and a newer bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleDiff {
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub old_size: u64,
+    pub new_size: u64,
+    /// Metadata fields that differ, other than the version
+    pub metadata: Vec<MetadataChange>,
+    /// Files only in the newer bundle; `None` when the format cannot list files
+    pub added: Option<Vec<FileEntry>>,
+    /// Files only in the older bundle
+    pub removed: Option<Vec<FileEntry>>,
+    pub changed: Option<Vec<ChangedFile>>,
+    /// Files identical in both
+    pub unchanged: usize,
+    pub notes: Vec<String>,
+}
+
+impl BundleDiff {
+    /// Whether anything but the version differs
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty()
+            && self.added.as_ref().is_none_or(Vec::is_empty)
+            && self.removed.as_ref().is_none_or(Vec::is_empty)
+            && self.changed.as_ref().is_none_or(Vec::is_empty)
+    }
+}
+
+/// Compare `old` with `new`
+pub fn diff(old: &BundleReport, new: &BundleReport) -> BundleDiff {
+    let mut diff = BundleDiff {
+        old_version: old.version.clone(),
+        new_version: new.version.clone(),
+        old_size: old.size,
+        new_size: new.size,
+        metadata: metadata_changes(old, new),
+        added: None,
+        removed: None,
+        changed: None,
+        unchanged: 0,
+        notes: Vec::new(),
+    };
+
+    let (Some(old_files), Some(new_files)) = (&old.files, &new.files) else {
+        diff.notes.push(format!(
+            "{} files cannot be listed offline; only the bundles' sizes and metadata are compared",
+            new.format
+        ));
+        return diff;
+    };
+
+    let old_files = by_key(old_files, old.version.as_deref());
+    let mut new_files = by_key(new_files, new.version.as_deref());
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    for (key, before) in old_files {
+        match new_files.remove(&key) {
+            None => removed.push(before.clone()),
+            Some(after) if differs(before, after) => changed.push(ChangedFile {
+                path: after.path.clone(),
+                old_size: before.size,
+                new_size: after.size,
+            }),
+            Some(_) => diff.unchanged += 1,
+        }
+    }
+    added.extend(new_files.into_values().cloned());
+
+    if !old.files.iter().flatten().chain(new.files.iter().flatten()).all(|f| f.digest.is_some()) {
+        diff.notes.push("Some files have no recorded digest; they are compared by size only".to_string());
+    }
+    diff.added = Some(added);
+    diff.removed = Some(removed);
+    diff.changed = Some(changed);
+    diff
+}
+
+/// Files keyed by path with `version` replaced by a placeholder
+fn by_key<'a>(files: &'a [FileEntry], version: Option<&str>) -> BTreeMap<String, &'a FileEntry> {
+    files
+        .iter()
+        .map(|file| {
+            let key = match version {
+                Some(version) if !version.is_empty() => file.path.replace(version, "{version}"),
+                _ => file.path.clone(),
+            };
+            (key, file)
+        })
+        .collect()
+}
+
+fn differs(old: &FileEntry, new: &FileEntry) -> bool {
+    if old.size != new.size {
+        return true;
+    }
+    match (&old.digest, &new.digest) {
+        (Some(a), Some(b)) if a.split(':').next() == b.split(':').next() => a != b,
+        _ => false,
+    }
+}
+
+/// Metadata fields that differ, ignoring those that only carry the version
+fn metadata_changes(old: &BundleReport, new: &BundleReport) -> Vec<MetadataChange> {
+    let normalize = |value: &str, version: Option<&str>| match version {
+        Some(version) if !version.is_empty() => value.replace(version, "{version}"),
+        _ => value.to_string(),
+    };
+
+    let mut keys: Vec<&str> = old.metadata.iter().map(|(key, _)| key.as_str()).collect();
+    for (key, _) in &new.metadata {
+        if !keys.contains(&key.as_str()) {
+            keys.push(key);
+        }
+    }
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let lookup = |report: &BundleReport| {
+                report
+                    .metadata
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, value)| value.clone())
+            };
+            let (before, after) = (lookup(old), lookup(new));
+            let same = match (&before, &after) {
+                (Some(a), Some(b)) => {
+                    normalize(a, old.version.as_deref()) == normalize(b, new.version.as_deref())
+                }
+                (None, None) => true,
+                _ => false,
+            };
+            (!same).then(|| MetadataChange {
+                key: key.to_string(),
+                old: before,
+                new: after,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,59 @@
+//! macOS disk images: UDIF, identified by the 512-byte `koly` trailer.
+//!
+//! The filesystem inside (HFS+/APFS, usually compressed) is not mounted, so
+//! files are not listed; partition names come from the embedded plist.
+
+use super::{BundleFormat, Inspection, be_u32, be_u64, bytes, malformed};
+use crate::error::Result;
+
+const TRAILER_LEN: usize = 512;
+
+pub(super) fn inspect(data: &[u8]) -> Result<Inspection> {
+    let start = data
+        .len()
+        .checked_sub(TRAILER_LEN)
+        .filter(|&start| bytes(data, start, 4) == Some(b"koly"))
+        .ok_or_else(|| malformed(BundleFormat::Dmg, "no koly trailer"))?;
+    let trailer = &data[start..];
+    let field = |offset| be_u64(trailer, offset).unwrap_or(0);
+
+    let mut inspection = Inspection::default();
+    inspection.field("UDIF version", be_u32(trailer, 4).unwrap_or(0).to_string());
+    inspection.field("Data fork size", field(32).to_string());
+    inspection.field("Sectors", field(492).to_string());
+
+    let plist_offset = field(216) as usize;
+    let plist_len = field(224) as usize;
+    if let Some(plist) = bytes(data, plist_offset, plist_len) {
+        let partitions = partition_names(&String::from_utf8_lossy(plist));
+        if !partitions.is_empty() {
+            inspection.field("Partitions", partitions.join(", "));
+        }
+    }
+
+    let signature_len = field(304);
+    if signature_len > 0 {
+        inspection.signed = Some(format!(
+            "Apple code signature ({} bytes; notarization ticket not checked)",
+            signature_len
+        ));
+    }
+
+    inspection
+        .notes
+        .push("File listing requires mounting the image (hdiutil attach)".to_string());
+
+    Ok(inspection)
+}
+
+/// `CFName` of each block map entry in the resource plist
+fn partition_names(plist: &str) -> Vec<String> {
+    let Ok(re) = regex::Regex::new(r"<key>CFName</key>\s*<string>([^<]*)</string>") else {
+        return Vec::new();
+    };
+    re.captures_iter(plist)
+        .filter_map(|cap| cap.get(1))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
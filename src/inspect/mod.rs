@@ -0,0 +1,250 @@
+//! Offline inspection of bundles produced by the release tool.
+//!
+//! Reads `.deb`, `.rpm`, `.dmg`, `.AppImage`, `.exe`, `.msi` and `.msix` files directly,
+//! without `dpkg`, `rpm`, `hdiutil` or `signtool`, and reports:
+//!
+//! - package metadata (name, version, architecture, ...)
+//! - the file listing, where the format allows it without unpacking a filesystem
+//! - the embedded version
+//! - whether a signature is present
+//! - size and SHA-256
+//!
+//! Signature status only reports whether a signature is *present*; it is not
+//! verified against a trust store.
+
+#![allow(dead_code)] // Public API - items may be used by external consumers
+
+mod appimage;
+mod deb;
+mod diff;
+mod dmg;
+mod rpm;
+mod windows;
+
+pub use diff::{BundleDiff, diff};
+
+use crate::error::{CliError, ReleaseError, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Bundle formats understood by [`inspect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    Deb,
+    Rpm,
+    Dmg,
+    AppImage,
+    Exe,
+    Msi,
+    Msix,
+}
+
+impl std::fmt::Display for BundleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleFormat::Deb => write!(f, "Debian package"),
+            BundleFormat::Rpm => write!(f, "RPM package"),
+            BundleFormat::Dmg => write!(f, "macOS disk image"),
+            BundleFormat::AppImage => write!(f, "AppImage"),
+            BundleFormat::Exe => write!(f, "Windows executable"),
+            BundleFormat::Msi => write!(f, "Windows Installer package"),
+            BundleFormat::Msix => write!(f, "MSIX package"),
+        }
+    }
+}
+
+/// Whether the bundle carries a signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "lowercase")]
+pub enum SignatureStatus {
+    /// A signature is present (kind of signature as detail)
+    Signed(String),
+    /// No signature found
+    Unsigned,
+}
+
+/// One file inside the bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub size: u64,
+    /// Content digest as `algorithm:hex`, where the format records or allows one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+}
+
+/// Everything learned about a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleReport {
+    pub path: PathBuf,
+    pub format: BundleFormat,
+    /// File size in bytes
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the file
+    pub sha256: String,
+    /// Embedded version, or the version in the filename if none is embedded
+    pub version: Option<String>,
+    /// Format-specific metadata, in display order
+    #[serde(serialize_with = "serialize_fields")]
+    pub metadata: Vec<(String, String)>,
+    pub signature: SignatureStatus,
+    /// Contained files; `None` when the format cannot be listed offline
+    pub files: Option<Vec<FileEntry>>,
+    /// Caveats about what could not be determined
+    pub notes: Vec<String>,
+}
+
+/// Serialize ordered key/value pairs as a JSON object
+fn serialize_fields<S: serde::Serializer>(
+    fields: &[(String, String)],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_map(fields.iter().map(|(key, value)| (key, value)))
+}
+
+/// Partial report filled in by a format parser
+#[derive(Debug, Default)]
+struct Inspection {
+    version: Option<String>,
+    metadata: Vec<(String, String)>,
+    signed: Option<String>,
+    files: Option<Vec<FileEntry>>,
+    notes: Vec<String>,
+}
+
+impl Inspection {
+    fn field(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        if !value.is_empty() {
+            self.metadata.push((key.to_string(), value));
+        }
+    }
+}
+
+/// Inspect the bundle at `path`
+pub fn inspect(path: &Path) -> Result<BundleReport> {
+    let data = std::fs::read(path).map_err(|e| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!("Cannot read {}: {}", path.display(), e),
+        })
+    })?;
+
+    let format = detect_format(path, &data).ok_or_else(|| {
+        ReleaseError::Cli(CliError::InvalidArguments {
+            reason: format!(
+                "{} is not a .deb, .rpm, .dmg, .AppImage, .exe, .msi or .msix bundle",
+                path.display()
+            ),
+        })
+    })?;
+
+    let mut inspection = match format {
+        BundleFormat::Deb => deb::inspect(&data)?,
+        BundleFormat::Rpm => rpm::inspect(&data)?,
+        BundleFormat::Dmg => dmg::inspect(&data)?,
+        BundleFormat::AppImage => appimage::inspect(&data)?,
+        BundleFormat::Exe => windows::inspect_exe(&data)?,
+        BundleFormat::Msi => windows::inspect_msi(&data)?,
+        BundleFormat::Msix => windows::inspect_msix(&data)?,
+    };
+
+    if inspection.version.is_none()
+        && let Some(version) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(version_from_filename)
+    {
+        inspection.notes.push("Version taken from the filename".to_string());
+        inspection.version = Some(version);
+    }
+
+    Ok(BundleReport {
+        path: path.to_path_buf(),
+        format,
+        size: data.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&data)),
+        version: inspection.version,
+        metadata: inspection.metadata,
+        signature: match inspection.signed {
+            Some(kind) => SignatureStatus::Signed(kind),
+            None => SignatureStatus::Unsigned,
+        },
+        files: inspection.files,
+        notes: inspection.notes,
+    })
+}
+
+/// Identify the format from magic bytes, falling back to the extension
+fn detect_format(path: &Path, data: &[u8]) -> Option<BundleFormat> {
+    if data.starts_with(b"!<arch>\n") {
+        return Some(BundleFormat::Deb);
+    }
+    if data.starts_with(&[0xed, 0xab, 0xee, 0xdb]) {
+        return Some(BundleFormat::Rpm);
+    }
+    if data.starts_with(b"\x7fELF") && data.get(8..10) == Some(b"AI") {
+        return Some(BundleFormat::AppImage);
+    }
+    if data.starts_with(b"MZ") {
+        return Some(BundleFormat::Exe);
+    }
+    if data.starts_with(&[0xd0, 0xcf, 0x11, 0xe0, 0xa1, 0xb1, 0x1a, 0xe1]) {
+        return Some(BundleFormat::Msi);
+    }
+    // MSIX is a ZIP archive with the package manifest at its root
+    if data.starts_with(b"PK\x03\x04") && windows::is_msix(data) {
+        return Some(BundleFormat::Msix);
+    }
+    if data.len() >= 512 && &data[data.len() - 512..data.len() - 508] == b"koly" {
+        return Some(BundleFormat::Dmg);
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "dmg" => Some(BundleFormat::Dmg),
+        _ => None,
+    }
+}
+
+/// First `X.Y.Z[-pre]` in a filename
+fn version_from_filename(filename: &str) -> Option<String> {
+    let re = regex::Regex::new(r"(\d+\.\d+\.\d+(?:-[0-9A-Za-z.]+)?)").ok()?;
+    re.captures(filename)
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().trim_end_matches('.').to_string())
+}
+
+/// Error for a bundle whose structure does not match its format
+fn malformed(format: BundleFormat, reason: impl std::fmt::Display) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "bundle inspect".to_string(),
+        reason: format!("Malformed {}: {}", format, reason),
+    })
+}
+
+/// Bounds-checked slice
+fn bytes(data: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    data.get(offset..offset.checked_add(len)?)
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    bytes(data, offset, 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn be_u64(data: &[u8], offset: usize) -> Option<u64> {
+    bytes(data, offset, 8).map(|b| u64::from_be_bytes(b.try_into().expect("8 bytes")))
+}
+
+fn le_u16(data: &[u8], offset: usize) -> Option<u16> {
+    bytes(data, offset, 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn le_u32(data: &[u8], offset: usize) -> Option<u32> {
+    bytes(data, offset, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn le_u64(data: &[u8], offset: usize) -> Option<u64> {
+    bytes(data, offset, 8).map(|b| u64::from_le_bytes(b.try_into().expect("8 bytes")))
+}
@@ -0,0 +1,237 @@
+//! RPM packages: a 96-byte lead, a signature header, then the main header and payload.
+
+use super::{BundleFormat, FileEntry, Inspection, be_u32, be_u64, bytes, malformed};
+use crate::error::Result;
+use std::collections::HashMap;
+
+const LEAD_LEN: usize = 96;
+const HEADER_MAGIC: [u8; 3] = [0x8e, 0xad, 0xe8];
+
+// Signature header tags
+const SIGTAG_DSA: u32 = 267;
+const SIGTAG_RSA: u32 = 268;
+const SIGTAG_SHA256: u32 = 273;
+const SIGTAG_PGP: u32 = 1002;
+const SIGTAG_GPG: u32 = 1005;
+
+// Main header tags
+const TAG_NAME: u32 = 1000;
+const TAG_VERSION: u32 = 1001;
+const TAG_RELEASE: u32 = 1002;
+const TAG_EPOCH: u32 = 1003;
+const TAG_SUMMARY: u32 = 1004;
+const TAG_VENDOR: u32 = 1011;
+const TAG_LICENSE: u32 = 1014;
+const TAG_PACKAGER: u32 = 1015;
+const TAG_URL: u32 = 1020;
+const TAG_OS: u32 = 1021;
+const TAG_ARCH: u32 = 1022;
+const TAG_FILESIZES: u32 = 1028;
+const TAG_FILEDIGESTS: u32 = 1035;
+const TAG_DIRINDEXES: u32 = 1116;
+const TAG_BASENAMES: u32 = 1117;
+const TAG_DIRNAMES: u32 = 1118;
+const TAG_PAYLOADCOMPRESSOR: u32 = 1125;
+const TAG_LONGFILESIZES: u32 = 5008;
+const TAG_FILEDIGESTALGO: u32 = 5011;
+
+// Index entry data types
+const TYPE_INT32: u32 = 4;
+const TYPE_INT64: u32 = 5;
+const TYPE_STRING: u32 = 6;
+const TYPE_STRING_ARRAY: u32 = 8;
+const TYPE_I18NSTRING: u32 = 9;
+
+pub(super) fn inspect(data: &[u8]) -> Result<Inspection> {
+    let mut inspection = Inspection::default();
+
+    let (signature, sig_len) = Header::parse(data, LEAD_LEN)?;
+    // The signature header is padded to an 8-byte boundary
+    let main_offset = LEAD_LEN + sig_len.div_ceil(8) * 8;
+    let (header, _) = Header::parse(data, main_offset)?;
+
+    let name = header.string(TAG_NAME).unwrap_or_default();
+    let version = header.string(TAG_VERSION).unwrap_or_default();
+    let release = header.string(TAG_RELEASE).unwrap_or_default();
+
+    inspection.field("Name", name);
+    inspection.field("Version", version.clone());
+    inspection.field("Release", release.clone());
+    if let Some(epoch) = header.int32s(TAG_EPOCH).and_then(|e| e.first().copied()) {
+        inspection.field("Epoch", epoch.to_string());
+    }
+    for (key, tag) in [
+        ("Architecture", TAG_ARCH),
+        ("OS", TAG_OS),
+        ("Summary", TAG_SUMMARY),
+        ("License", TAG_LICENSE),
+        ("Vendor", TAG_VENDOR),
+        ("Packager", TAG_PACKAGER),
+        ("URL", TAG_URL),
+        ("Payload compressor", TAG_PAYLOADCOMPRESSOR),
+    ] {
+        if let Some(value) = header.string(tag) {
+            inspection.field(key, value);
+        }
+    }
+    if let Some(digest) = signature.string(SIGTAG_SHA256) {
+        inspection.field("Header SHA-256", digest);
+    }
+
+    if !version.is_empty() {
+        inspection.version = Some(if release.is_empty() {
+            version
+        } else {
+            format!("{}-{}", version, release)
+        });
+    }
+
+    let signed: Vec<&str> = [
+        (SIGTAG_RSA, "RSA"),
+        (SIGTAG_DSA, "DSA"),
+        (SIGTAG_PGP, "PGP"),
+        (SIGTAG_GPG, "GPG"),
+    ]
+    .into_iter()
+    .filter(|(tag, _)| signature.entries.contains_key(tag))
+    .map(|(_, kind)| kind)
+    .collect();
+    if !signed.is_empty() {
+        inspection.signed = Some(format!("OpenPGP ({})", signed.join(", ")));
+    }
+
+    inspection.files = Some(file_list(&header));
+
+    Ok(inspection)
+}
+
+/// Reconstruct paths from DIRNAMES[DIRINDEXES[i]] + BASENAMES[i]
+fn file_list(header: &Header) -> Vec<FileEntry> {
+    let basenames = header.strings(TAG_BASENAMES).unwrap_or_default();
+    let dirnames = header.strings(TAG_DIRNAMES).unwrap_or_default();
+    let dirindexes = header.int32s(TAG_DIRINDEXES).unwrap_or_default();
+    let sizes: Vec<u64> = header
+        .int64s(TAG_LONGFILESIZES)
+        .or_else(|| {
+            header
+                .int32s(TAG_FILESIZES)
+                .map(|sizes| sizes.into_iter().map(u64::from).collect())
+        })
+        .unwrap_or_default();
+    // Digests of regular files; MD5 unless FILEDIGESTALGO says otherwise
+    let digests = header.strings(TAG_FILEDIGESTS).unwrap_or_default();
+    let algorithm = match header.int32s(TAG_FILEDIGESTALGO).and_then(|algo| algo.first().copied()) {
+        None | Some(1) => "md5",
+        Some(2) => "sha1",
+        Some(8) => "sha256",
+        Some(9) => "sha384",
+        Some(10) => "sha512",
+        Some(_) => "digest",
+    };
+
+    basenames
+        .iter()
+        .enumerate()
+        .map(|(i, base)| {
+            let dir = dirindexes
+                .get(i)
+                .and_then(|&idx| dirnames.get(idx as usize))
+                .map(String::as_str)
+                .unwrap_or_default();
+            FileEntry {
+                path: format!("{}{}", dir, base),
+                size: sizes.get(i).copied().unwrap_or(0),
+                digest: digests
+                    .get(i)
+                    .filter(|digest| !digest.is_empty())
+                    .map(|digest| format!("{}:{}", algorithm, digest)),
+            }
+        })
+        .collect()
+}
+
+/// One RPM header structure: index entries keyed by tag, plus the data store
+struct Header<'a> {
+    /// tag -> (type, offset into store, count)
+    entries: HashMap<u32, (u32, usize, usize)>,
+    store: &'a [u8],
+}
+
+impl<'a> Header<'a> {
+    /// Parse the header at `offset`; returns it with its total length in bytes
+    fn parse(data: &'a [u8], offset: usize) -> Result<(Self, usize)> {
+        let truncated = || malformed(BundleFormat::Rpm, "header is truncated");
+
+        if bytes(data, offset, 3) != Some(&HEADER_MAGIC[..]) {
+            return Err(malformed(
+                BundleFormat::Rpm,
+                format!("no header magic at offset {}", offset),
+            ));
+        }
+        let count = be_u32(data, offset + 8).ok_or_else(truncated)? as usize;
+        let store_len = be_u32(data, offset + 12).ok_or_else(truncated)? as usize;
+
+        let index_start = offset + 16;
+        let store_start = index_start + count * 16;
+        let store = bytes(data, store_start, store_len).ok_or_else(truncated)?;
+
+        let mut entries = HashMap::with_capacity(count);
+        for i in 0..count {
+            let entry = index_start + i * 16;
+            let tag = be_u32(data, entry).ok_or_else(truncated)?;
+            let kind = be_u32(data, entry + 4).ok_or_else(truncated)?;
+            let offset = be_u32(data, entry + 8).ok_or_else(truncated)? as usize;
+            let count = be_u32(data, entry + 12).ok_or_else(truncated)? as usize;
+            entries.insert(tag, (kind, offset, count));
+        }
+
+        Ok((Self { entries, store }, 16 + count * 16 + store_len))
+    }
+
+    fn string(&self, tag: u32) -> Option<String> {
+        match self.entries.get(&tag)? {
+            (TYPE_STRING | TYPE_I18NSTRING | TYPE_STRING_ARRAY, offset, _) => {
+                self.strings_at(*offset, 1).into_iter().next()
+            }
+            _ => None,
+        }
+    }
+
+    fn strings(&self, tag: u32) -> Option<Vec<String>> {
+        match self.entries.get(&tag)? {
+            (TYPE_STRING_ARRAY | TYPE_I18NSTRING, offset, count) => {
+                Some(self.strings_at(*offset, *count))
+            }
+            _ => None,
+        }
+    }
+
+    fn int32s(&self, tag: u32) -> Option<Vec<u32>> {
+        match self.entries.get(&tag)? {
+            (TYPE_INT32, offset, count) => (0..*count)
+                .map(|i| be_u32(self.store, offset + i * 4))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn int64s(&self, tag: u32) -> Option<Vec<u64>> {
+        match self.entries.get(&tag)? {
+            (TYPE_INT64, offset, count) => (0..*count)
+                .map(|i| be_u64(self.store, offset + i * 8))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// `count` consecutive NUL-terminated strings starting at `offset`
+    fn strings_at(&self, offset: usize, count: usize) -> Vec<String> {
+        self.store
+            .get(offset..)
+            .unwrap_or_default()
+            .split(|&b| b == 0)
+            .take(count)
+            .map(|s| String::from_utf8_lossy(s).into_owned())
+            .collect()
+    }
+}
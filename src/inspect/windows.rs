@@ -0,0 +1,273 @@
+//! Windows installers: PE executables (NSIS, Inno Setup), MSI and MSIX packages.
+
+use super::{BundleFormat, FileEntry, Inspection, bytes, le_u16, le_u32, malformed};
+use crate::error::Result;
+use std::io::Read;
+
+/// Index of the certificate table in the PE data directories
+const SECURITY_DIRECTORY: usize = 4;
+
+pub(super) fn inspect_exe(data: &[u8]) -> Result<Inspection> {
+    let truncated = || malformed(BundleFormat::Exe, "PE header is truncated");
+    let mut inspection = Inspection::default();
+
+    let pe = le_u32(data, 0x3c).ok_or_else(truncated)? as usize;
+    if data.get(pe..pe + 4) != Some(b"PE\0\0") {
+        return Err(malformed(BundleFormat::Exe, "no PE signature"));
+    }
+
+    inspection.field(
+        "Architecture",
+        match le_u16(data, pe + 4).ok_or_else(truncated)? {
+            0x8664 => "x86_64".to_string(),
+            0xaa64 => "aarch64".to_string(),
+            0x014c => "i386".to_string(),
+            other => format!("PE machine {:#x}", other),
+        },
+    );
+
+    let optional = pe + 24;
+    let directories = match le_u16(data, optional).ok_or_else(truncated)? {
+        0x20b => optional + 112,
+        _ => optional + 96,
+    };
+    let security = directories + SECURITY_DIRECTORY * 8;
+    let certificate_len = le_u32(data, security + 4).unwrap_or(0);
+    if certificate_len > 0 {
+        inspection.signed = Some(format!("Authenticode ({} bytes)", certificate_len));
+    }
+
+    for (label, key) in [
+        ("Product", "ProductName"),
+        ("Description", "FileDescription"),
+        ("Company", "CompanyName"),
+        ("Product version", "ProductVersion"),
+        ("File version", "FileVersion"),
+    ] {
+        if let Some(value) = version_string(data, key) {
+            inspection.field(label, value);
+        }
+    }
+    inspection.version = version_string(data, "ProductVersion")
+        .or_else(|| version_string(data, "FileVersion"));
+
+    if let Some(installer) = installer_kind(data) {
+        inspection.field("Installer", installer);
+    }
+
+    inspection
+        .notes
+        .push("File listing requires running or unpacking the installer".to_string());
+
+    Ok(inspection)
+}
+
+pub(super) fn inspect_msi(data: &[u8]) -> Result<Inspection> {
+    let mut inspection = Inspection::default();
+
+    // The signature is a stream named "\x05DigitalSignature" in the compound file
+    if find(data, &utf16("\u{5}DigitalSignature")).is_some() {
+        inspection.signed = Some("Authenticode (DigitalSignature stream)".to_string());
+    }
+
+    inspection
+        .notes
+        .push("MSI database tables are not parsed; use msiinfo or Orca for details".to_string());
+
+    Ok(inspection)
+}
+
+/// Manifest at the root of every MSIX/AppX package
+const MSIX_MANIFEST: &str = "AppxManifest.xml";
+
+/// Whether a ZIP archive is an MSIX package
+pub(super) fn is_msix(data: &[u8]) -> bool {
+    zip_entries(data).is_some_and(|entries| entries.iter().any(|entry| entry.name == MSIX_MANIFEST))
+}
+
+pub(super) fn inspect_msix(data: &[u8]) -> Result<Inspection> {
+    let mut inspection = Inspection::default();
+    let entries = zip_entries(data)
+        .ok_or_else(|| malformed(BundleFormat::Msix, "ZIP central directory not found"))?;
+
+    let manifest = entries
+        .iter()
+        .find(|entry| entry.name == MSIX_MANIFEST)
+        .ok_or_else(|| malformed(BundleFormat::Msix, "no AppxManifest.xml"))?;
+    let manifest = String::from_utf8_lossy(&zip_read(data, manifest)?).into_owned();
+
+    if let Some(identity) = xml_element(&manifest, "Identity") {
+        inspection.field("Name", xml_attribute(identity, "Name").unwrap_or_default());
+        inspection.field("Publisher", xml_attribute(identity, "Publisher").unwrap_or_default());
+        inspection.field(
+            "Architecture",
+            xml_attribute(identity, "ProcessorArchitecture").unwrap_or_default(),
+        );
+        inspection.version = xml_attribute(identity, "Version").map(str::to_string);
+    }
+    if let Some(name) = xml_text(&manifest, "DisplayName") {
+        inspection.field("Display name", name);
+    }
+
+    // makeappx/signtool add the package signature as a separate entry
+    if entries.iter().any(|entry| entry.name == "AppxSignature.p7x") {
+        inspection.signed = Some("Authenticode (AppxSignature.p7x)".to_string());
+    }
+
+    inspection.files = Some(
+        entries
+            .into_iter()
+            .filter(|entry| !entry.name.ends_with('/'))
+            .map(|entry| FileEntry {
+                path: entry.name,
+                size: entry.size,
+                digest: Some(format!("crc32:{:08x}", entry.crc32)),
+            })
+            .collect(),
+    );
+
+    Ok(inspection)
+}
+
+/// Entry of a ZIP central directory
+struct ZipEntry {
+    name: String,
+    method: u16,
+    crc32: u32,
+    compressed_size: u64,
+    size: u64,
+    local_header: usize,
+}
+
+/// Entries of a ZIP archive, from its central directory
+///
+/// `None` when the end-of-central-directory record is missing. ZIP64
+/// archives (over 4 GiB) are not supported.
+fn zip_entries(data: &[u8]) -> Option<Vec<ZipEntry>> {
+    // The end record is at least 22 bytes, followed by a comment of up to 64 KiB
+    let search_from = data.len().saturating_sub(22 + u16::MAX as usize);
+    let end = (search_from..=data.len().checked_sub(22)?)
+        .rev()
+        .find(|&offset| data[offset..].starts_with(b"PK\x05\x06"))?;
+
+    let count = le_u16(data, end + 10)? as usize;
+    let mut offset = le_u32(data, end + 16)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if bytes(data, offset, 4)? != b"PK\x01\x02" {
+            return None;
+        }
+        let name_len = le_u16(data, offset + 28)? as usize;
+        let extra_len = le_u16(data, offset + 30)? as usize;
+        let comment_len = le_u16(data, offset + 32)? as usize;
+        entries.push(ZipEntry {
+            name: String::from_utf8_lossy(bytes(data, offset + 46, name_len)?).into_owned(),
+            method: le_u16(data, offset + 10)?,
+            crc32: le_u32(data, offset + 16)?,
+            compressed_size: le_u32(data, offset + 20)? as u64,
+            size: le_u32(data, offset + 24)? as u64,
+            local_header: le_u32(data, offset + 42)? as usize,
+        });
+        offset += 46 + name_len + extra_len + comment_len;
+    }
+    Some(entries)
+}
+
+/// Contents of a stored or deflated ZIP entry
+fn zip_read(data: &[u8], entry: &ZipEntry) -> Result<Vec<u8>> {
+    let truncated = || malformed(BundleFormat::Msix, format!("{} is truncated", entry.name));
+    let header = entry.local_header;
+    if bytes(data, header, 4) != Some(b"PK\x03\x04") {
+        return Err(malformed(BundleFormat::Msix, format!("bad local header for {}", entry.name)));
+    }
+    let name_len = le_u16(data, header + 26).ok_or_else(truncated)? as usize;
+    let extra_len = le_u16(data, header + 28).ok_or_else(truncated)? as usize;
+    let compressed = bytes(data, header + 30 + name_len + extra_len, entry.compressed_size as usize)
+        .ok_or_else(truncated)?;
+
+    match entry.method {
+        0 => Ok(compressed.to_vec()),
+        8 => {
+            let mut contents = Vec::with_capacity(entry.size as usize);
+            flate2::read::DeflateDecoder::new(compressed)
+                .read_to_end(&mut contents)
+                .map_err(|e| malformed(BundleFormat::Msix, format!("{}: {}", entry.name, e)))?;
+            Ok(contents)
+        }
+        method => Err(malformed(
+            BundleFormat::Msix,
+            format!("{} uses unsupported compression method {}", entry.name, method),
+        )),
+    }
+}
+
+/// Attributes of the first `<tag ...>` element
+fn xml_element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{} ", tag))? + tag.len() + 2;
+    let end = xml[start..].find('>')? + start;
+    Some(&xml[start..end])
+}
+
+/// Value of `name="..."` within an element's attributes
+fn xml_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{}=\"", name);
+    let start = attributes
+        .match_indices(&pattern)
+        // Skip longer attribute names ending in `name` (e.g. `ResourceId` vs `Id`)
+        .find(|(index, _)| *index == 0 || attributes.as_bytes()[index - 1].is_ascii_whitespace())?
+        .0
+        + pattern.len();
+    let end = attributes[start..].find('"')? + start;
+    Some(&attributes[start..end])
+}
+
+/// Text of the first `<tag>text</tag>` element
+fn xml_text(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    let text = xml[start..end].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Value of a `VS_VERSIONINFO` string such as `ProductVersion`
+///
+/// Each entry is the UTF-16 key, NUL, 32-bit alignment padding, then the
+/// UTF-16 value, so the value starts at the first non-zero code unit.
+fn version_string(data: &[u8], key: &str) -> Option<String> {
+    let mut needle = utf16(key);
+    needle.extend_from_slice(&[0, 0]);
+    let start = find(data, &needle)? + needle.len();
+
+    let units: Vec<u16> = data
+        .get(start..)?
+        .as_chunks::<2>()
+        .0
+        .iter()
+        .map(|&unit| u16::from_le_bytes(unit))
+        .skip_while(|&unit| unit == 0)
+        .take_while(|&unit| unit != 0)
+        .take(256)
+        .collect();
+
+    let value = String::from_utf16_lossy(&units).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Installer framework, from its marker strings
+fn installer_kind(data: &[u8]) -> Option<&'static str> {
+    if find(data, b"Nullsoft Install System").is_some() || find(data, b"NullsoftInst").is_some() {
+        Some("NSIS")
+    } else if find(data, b"Inno Setup").is_some() {
+        Some("Inno Setup")
+    } else {
+        None
+    }
+}
+
+fn utf16(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
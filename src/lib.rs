@@ -0,0 +1,10 @@
+//! Library side of `kodegen_bundler_release`.
+//!
+//! The release tool itself is the `kodegen_bundler_release` binary. The
+//! library only carries utilities for testing releases end to end, behind the
+//! `test-harness` feature (see [`test_harness`]).
+
+#[cfg(feature = "test-harness")]
+mod sparse_index;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
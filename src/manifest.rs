@@ -0,0 +1,304 @@
+//! Machine-readable record of what a release produced.
+//!
+//! The manifest lists every uploaded artifact with its platform, size,
+//! SHA-256 and download URL. It is uploaded to the GitHub release as
+//! `release-manifest.json` (and optionally written locally with
+//! `--manifest-out`), so downstream tooling can verify or download a release
+//! without scraping asset names.
+
+use crate::error::{CliError, ReleaseError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Asset name of the manifest on the GitHub release
+pub const MANIFEST_FILE_NAME: &str = "release-manifest.json";
+
+/// Current version of the manifest format
+pub const MANIFEST_FORMAT_VERSION: u32 = 1;
+
+/// Everything a release produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    /// Version of the manifest format
+    pub format_version: u32,
+    /// Package name
+    pub package: String,
+    /// Released version (from Cargo.toml)
+    pub version: semver::Version,
+    /// Git tag of the release
+    pub tag: String,
+    /// GitHub repository as `owner/repo`
+    pub repository: String,
+    /// GitHub release ID
+    pub release_id: u64,
+    /// GitHub release page
+    pub html_url: String,
+    /// Whether the release is marked as a prerelease
+    pub prerelease: bool,
+    /// Commit the release was built from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// When the manifest was written
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+    /// Uploaded artifacts, in upload order
+    pub artifacts: Vec<ManifestArtifact>,
+    /// Crates confirmed visible in the crates.io index
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crates_io: Vec<PublishedCrate>,
+    /// Mac App Store package (not a release asset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac_app_store: Option<AppStorePackage>,
+    /// Microsoft Store submission that received the MSIX packages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub microsoft_store: Option<StoreSubmission>,
+    /// Artifact mirrors and what each of them received
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<MirrorUpload>,
+}
+
+/// One uploaded release asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestArtifact {
+    /// Asset filename
+    pub name: String,
+    /// Package format or artifact kind (`deb`, `dmg`, `shell-integration`, ...)
+    pub platform: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the file
+    pub sha256: String,
+    /// Public download URL
+    pub download_url: String,
+    /// macOS notarization status
+    pub notarization: NotarizationStatus,
+    /// Set when the asset is encrypted; size and hash are of the ciphertext
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<ArtifactEncryption>,
+}
+
+/// How an asset was encrypted, and what it decrypts to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactEncryption {
+    pub method: crate::encryption::EncryptionMethod,
+    /// Filename after decryption
+    pub plaintext_name: String,
+    /// Lowercase hex SHA-256 of the decrypted file
+    pub plaintext_sha256: String,
+}
+
+/// A crate version confirmed visible on crates.io
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedCrate {
+    /// Crate name
+    pub name: String,
+    /// Published version
+    pub version: String,
+    /// When the version was first seen in the crates.io index
+    pub verified_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Mac App Store pkg built for the release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppStorePackage {
+    /// Package filename
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the file
+    pub sha256: String,
+    /// When App Store Connect accepted the upload (`None` when upload is off)
+    pub uploaded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// MSIX packages added to a Microsoft Store submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreSubmission {
+    /// Store ID of the product
+    pub app_id: String,
+    /// Partner Center submission ID
+    pub submission_id: String,
+    /// Uploaded package filenames
+    pub packages: Vec<String>,
+    /// Submission status after the upload (`PendingCommit` when not committed)
+    pub status: String,
+    /// When the packages were uploaded
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Artifacts uploaded to one mirror
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorUpload {
+    /// Mirror name from the configuration
+    pub name: String,
+    /// Where the artifacts went (`s3://bucket/prefix/` or the URL template)
+    pub destination: String,
+    /// `complete`, `partial` or `failed`
+    pub status: String,
+    /// Artifacts the mirror accepted
+    pub artifacts: Vec<MirrorArtifact>,
+    /// Artifacts the mirror rejected, with the error
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<MirrorFailure>,
+}
+
+/// An artifact on a mirror
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorArtifact {
+    /// Asset filename
+    pub name: String,
+    /// URL of the uploaded copy
+    pub url: String,
+}
+
+/// An artifact a mirror did not receive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorFailure {
+    /// Asset filename
+    pub name: String,
+    /// Last upload error
+    pub error: String,
+}
+
+/// Notarization status of an artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotarizationStatus {
+    /// Not a macOS bundle
+    NotApplicable,
+    /// Notarization credentials were passed to the bundler, which notarizes
+    /// and staples the bundle (bundling fails if notarization fails)
+    Notarized,
+    /// macOS bundle built without notarization credentials
+    NotNotarized,
+}
+
+impl ReleaseManifest {
+    /// Start an empty manifest for a release
+    pub fn new(
+        package: &str,
+        version: &semver::Version,
+        tag: &str,
+        repository: &str,
+        release: &crate::github::GitHubReleaseResult,
+    ) -> Self {
+        Self {
+            format_version: MANIFEST_FORMAT_VERSION,
+            package: package.to_string(),
+            version: version.clone(),
+            tag: tag.to_string(),
+            repository: repository.to_string(),
+            release_id: release.release_id,
+            html_url: release.html_url.clone(),
+            prerelease: release.prerelease,
+            commit: None,
+            generated_at: chrono::Utc::now(),
+            artifacts: Vec::new(),
+            crates_io: Vec::new(),
+            mac_app_store: None,
+            microsoft_store: None,
+            mirrors: Vec::new(),
+        }
+    }
+
+    /// Record artifacts uploaded for `platform`
+    ///
+    /// Hashes each file; an artifact already in the manifest is replaced.
+    pub fn add_artifacts(
+        &mut self,
+        platform: &str,
+        paths: &[PathBuf],
+        notarization: NotarizationStatus,
+    ) -> Result<()> {
+        for path in paths {
+            let content = std::fs::read(path)?;
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| {
+                    ReleaseError::Cli(CliError::ExecutionFailed {
+                        command: "release_manifest".to_string(),
+                        reason: format!("Invalid filename in path: {}", path.display()),
+                    })
+                })?
+                .to_string();
+
+            let artifact = ManifestArtifact {
+                download_url: format!(
+                    "https://github.com/{}/releases/download/{}/{}",
+                    self.repository, self.tag, name
+                ),
+                name,
+                platform: platform.to_string(),
+                size: content.len() as u64,
+                sha256: format!("{:x}", Sha256::digest(&content)),
+                notarization,
+                encryption: None,
+            };
+
+            self.artifacts.retain(|a| a.name != artifact.name);
+            self.artifacts.push(artifact);
+        }
+        Ok(())
+    }
+
+    /// Replace the record of `plaintext` with its encrypted copy `encrypted`
+    pub fn record_encryption(
+        &mut self,
+        plaintext: &Path,
+        encrypted: &Path,
+        method: crate::encryption::EncryptionMethod,
+    ) -> Result<()> {
+        let file_name = |path: &Path| path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let plaintext_name = file_name(plaintext);
+        let name = file_name(encrypted);
+        let Some(artifact) = self.artifacts.iter_mut().find(|a| a.name == plaintext_name) else {
+            return Ok(());
+        };
+
+        let content = std::fs::read(encrypted)?;
+        artifact.encryption = Some(ArtifactEncryption {
+            method,
+            plaintext_name,
+            plaintext_sha256: std::mem::take(&mut artifact.sha256),
+        });
+        artifact.download_url = format!(
+            "https://github.com/{}/releases/download/{}/{}",
+            self.repository, self.tag, name
+        );
+        artifact.name = name;
+        artifact.size = content.len() as u64;
+        artifact.sha256 = format!("{:x}", Sha256::digest(&content));
+        Ok(())
+    }
+
+    /// Write the manifest as pretty-printed JSON
+    pub fn write(&mut self, path: &Path) -> Result<()> {
+        self.generated_at = chrono::Utc::now();
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+}
+
+/// Notarization status for artifacts of `platform`
+///
+/// Only macOS bundles are notarized; whether they were depends on the
+/// notarization credentials available to the bundler (none when signing is
+/// disabled by the release profile).
+pub fn notarization_for(platform: &str, env_config: &crate::EnvConfig) -> NotarizationStatus {
+    match platform {
+        "dmg" | "app" => {
+            if !env_config.signing_disabled()
+                && env_config
+                    .require(crate::env::EnvRequirement::MacosNotarization)
+                    .is_ok()
+            {
+                NotarizationStatus::Notarized
+            } else {
+                NotarizationStatus::NotNotarized
+            }
+        }
+        _ => NotarizationStatus::NotApplicable,
+    }
+}
@@ -1,26 +1,1231 @@
 //! Metadata and binary discovery from Cargo.toml
+//!
+//! Package name, version and binary targets come from `cargo metadata`, which
+//! applies workspace inheritance, target auto-discovery and `[[bin]]` renames
+//! the way cargo itself does. When cargo cannot be run (or fails, e.g. on an
+//! offline machine with an unusual manifest), they are read from Cargo.toml
+//! directly. The release tool's own `[package.metadata.release]` settings are
+//! always read from Cargo.toml.
 
+use crate::cli::RuntimeConfig;
+use crate::cli::timeouts::{TimedPhase, parse_duration};
 use crate::error::{ReleaseError, Result};
-use std::path::Path;
+use crate::github::{AssetLabelRule, RetentionPolicy};
+use crate::release_notes::ReleaseNotesConfig;
+use crate::version::VersionScheme;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Package metadata extracted from Cargo.toml
 #[derive(Debug, Clone)]
 pub struct PackageMetadata {
     pub name: String,
     pub version: String,
+    /// `required-features` of the released binary, enabled when building it
+    pub binary_features: Vec<String>,
+    /// Shell completion / man page generation commands
+    pub shell_integration: ShellIntegration,
+    /// Release split debug symbols per target (`debug-symbols = true`)
+    pub debug_symbols: bool,
+    /// Phase timeouts from `[package.metadata.release.timeouts]`
+    pub timeouts: Vec<(TimedPhase, Duration)>,
+    /// Localized release notes templates
+    pub release_notes: Option<ReleaseNotesConfig>,
+    /// Version scheme and tag format
+    pub version_scheme: VersionScheme,
+    /// Files checked for references to the previous version
+    pub version_files: Vec<String>,
+    /// Display label overrides for release assets
+    pub asset_labels: Vec<AssetLabelRule>,
+    /// BSD targets built alongside the platform bundles
+    pub bsd: BsdTargets,
+    /// Cleanup of old drafts, prerelease assets and nightlies after a release
+    pub retention: RetentionPolicy,
+    /// Per-target builds (target triple × features × profile)
+    pub targets: Vec<BuildTarget>,
+    /// Custom package formats built by configured commands
+    pub bundlers: Vec<CustomBundler>,
+    /// Microsoft Store product receiving the MSIX packages
+    pub store: Option<crate::store::StoreConfig>,
+    /// Extra destinations that receive the artifacts next to the GitHub release
+    pub mirrors: Vec<crate::mirror::MirrorConfig>,
+    /// Encryption of the artifacts before upload
+    pub encryption: Option<crate::encryption::EncryptionConfig>,
+    /// Compression levels and zstd variants of the tarball artifacts
+    pub compression: crate::compression::CompressionConfig,
+    /// Mac App Store package, built and uploaded separately from the Developer ID bundles
+    pub mac_app_store: Option<MacAppStore>,
+    /// Test suite run in the release clone before anything is released
+    pub tests: TestGate,
+    /// `cargo fmt` / `cargo clippy` gates run in the release clone
+    pub lints: LintGates,
+    /// RustSec advisory check (`None` unless configured)
+    pub audit: Option<AuditGate>,
+    /// cargo-deny license and ban checks (`None` unless configured)
+    pub deny: Option<DenyGate>,
+}
+
+/// Commands that make the released binary print its own shell integration files
+///
+/// Read from `[package.metadata.release.shell-integration]`:
+///
+/// ```toml
+/// [package.metadata.release.shell-integration]
+/// completions = ["completions", "{shell}"]  # run once per bash/zsh/fish
+/// man = ["man"]                             # prints a roff man page
+/// ```
+///
+/// Each entry is the argument list passed to the built binary; `{shell}` is
+/// replaced with the shell name. Unset entries are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct ShellIntegration {
+    pub completions: Option<Vec<String>>,
+    pub man: Option<Vec<String>>,
+}
+
+impl ShellIntegration {
+    /// Whether anything is configured
+    pub fn is_enabled(&self) -> bool {
+        self.completions.is_some() || self.man.is_some()
+    }
+
+    fn from_package(package: &toml::Value) -> Result<Self> {
+        let table = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("shell-integration"));
+
+        let Some(table) = table else {
+            return Ok(Self::default());
+        };
+
+        let args = |key: &str| -> Result<Option<Vec<String>>> {
+            let Some(value) = table.get(key) else {
+                return Ok(None);
+            };
+            value
+                .as_array()
+                .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+                .map(Some)
+                .ok_or_else(|| {
+                    ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                        reason: format!(
+                            "[package.metadata.release.shell-integration] {} must be an array of strings",
+                            key
+                        ),
+                    })
+                })
+        };
+
+        Ok(Self {
+            completions: args("completions")?,
+            man: args("man")?,
+        })
+    }
+}
+
+/// Read `[package.metadata.release] debug-symbols`
+fn debug_symbols_from_package(package: &toml::Value) -> Result<bool> {
+    let Some(value) = package
+        .get("metadata")
+        .and_then(|m| m.get("release"))
+        .and_then(|r| r.get("debug-symbols"))
+    else {
+        return Ok(false);
+    };
+    value.as_bool().ok_or_else(|| {
+        ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+            reason: "[package.metadata.release] debug-symbols must be true or false".to_string(),
+        })
+    })
+}
+
+/// BSD release targets, read from `[package.metadata.release.bsd]`:
+///
+/// ```toml
+/// [package.metadata.release.bsd]
+/// targets = ["x86_64-unknown-freebsd", "x86_64-unknown-netbsd"]
+/// pkg = true   # also build a FreeBSD package for FreeBSD targets
+/// ```
+///
+/// Each target is built with `cross` (or natively on a matching BSD host) and
+/// released as a tarball. Package fields come from `[package]`.
+#[derive(Debug, Clone, Default)]
+pub struct BsdTargets {
+    /// Rust target triples (`*-freebsd`, `*-netbsd`, `*-openbsd`)
+    pub targets: Vec<String>,
+    /// Generate a FreeBSD `pkg` manifest (and `.pkg`, where `pkg` is available)
+    pub pkg: bool,
+    /// `[package] description`
+    pub description: Option<String>,
+    /// `[package] license`
+    pub license: Option<String>,
+    /// `[package] homepage`, else `repository`
+    pub homepage: Option<String>,
+    /// First `[package] authors` entry
+    pub maintainer: Option<String>,
+}
+
+impl BsdTargets {
+    fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("bsd"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.bsd] {}", reason),
+            })
+        };
+
+        let targets: Vec<String> = match table.get("targets") {
+            None => Vec::new(),
+            Some(value) => value
+                .as_array()
+                .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+                .ok_or_else(|| invalid("targets must be an array of strings"))?,
+        };
+        if let Some(target) = targets
+            .iter()
+            .find(|t| !["-freebsd", "-netbsd", "-openbsd"].iter().any(|os| t.ends_with(os)))
+        {
+            return Err(invalid(&format!("{} is not a FreeBSD, NetBSD or OpenBSD target", target)));
+        }
+
+        let pkg = match table.get("pkg") {
+            None => false,
+            Some(value) => value.as_bool().ok_or_else(|| invalid("pkg must be true or false"))?,
+        };
+
+        let field = |key: &str| package.get(key).and_then(|v| v.as_str()).map(String::from);
+        Ok(Self {
+            targets,
+            pkg,
+            description: field("description"),
+            license: field("license"),
+            homepage: field("homepage").or_else(|| field("repository")),
+            maintainer: package
+                .get("authors")
+                .and_then(|a| a.as_array())
+                .and_then(|a| a.first())
+                .and_then(|a| a.as_str())
+                .map(String::from),
+        })
+    }
+}
+
+/// Mac App Store package, read from `[package.metadata.release.mac-app-store]`:
+///
+/// ```toml
+/// [package.metadata.release.mac-app-store]
+/// entitlements = "macos/sandbox.entitlements"           # must enable the App Sandbox
+/// provisioning-profile = "macos/embedded.provisionprofile"
+/// upload = false   # only build the pkg (default: upload it to App Store Connect)
+/// ```
+///
+/// Paths are relative to the package directory. The pkg is signed with the
+/// App Store identities instead of Developer ID, is not notarized, and is
+/// never attached to the GitHub release.
+#[derive(Debug, Clone)]
+pub struct MacAppStore {
+    /// Entitlements plist the app is signed with
+    pub entitlements: std::path::PathBuf,
+    /// Provisioning profile embedded in the app bundle
+    pub provisioning_profile: std::path::PathBuf,
+    /// Upload the pkg to App Store Connect
+    pub upload: bool,
+}
+
+impl MacAppStore {
+    fn from_package(package: &toml::Value, package_dir: &Path) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("mac-app-store"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.mac-app-store] {}", reason),
+            })
+        };
+        let file = |key: &str| -> Result<std::path::PathBuf> {
+            let path = table
+                .get(key)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| invalid(&format!("{} must be a path", key)))?;
+            if !package_dir.join(path).is_file() {
+                return Err(invalid(&format!("{} {} does not exist", key, path)));
+            }
+            Ok(path.into())
+        };
+
+        let entitlements = file("entitlements")?;
+        let plist = std::fs::read_to_string(package_dir.join(&entitlements))?;
+        let sandbox = regex::Regex::new(r"<key>\s*com\.apple\.security\.app-sandbox\s*</key>\s*<true\s*/>")
+            .expect("valid regex");
+        if !sandbox.is_match(&plist) {
+            return Err(invalid(&format!(
+                "{} does not enable com.apple.security.app-sandbox, which the App Store requires",
+                entitlements.display()
+            )));
+        }
+
+        let provisioning_profile = file("provisioning-profile")?;
+        let upload = match table.get("upload") {
+            None => true,
+            Some(value) => value.as_bool().ok_or_else(|| invalid("upload must be true or false"))?,
+        };
+
+        Ok(Some(Self {
+            entitlements,
+            provisioning_profile,
+            upload,
+        }))
+    }
+}
+
+/// Test suite gating the release, read from `[package.metadata.release.tests]`:
+///
+/// ```toml
+/// [package.metadata.release.tests]
+/// run = true   # run without --run-tests (default: only with --run-tests)
+/// command = ["cargo", "nextest", "run", "--workspace", "--profile", "ci"]
+/// junit = "target/nextest/ci/junit.xml"   # JUnit XML written by the command
+/// ```
+///
+/// The command runs in the release clone and defaults to `cargo test --workspace`.
+#[derive(Debug, Clone)]
+pub struct TestGate {
+    /// Run the tests unless `--no-run-tests` is given
+    pub run: bool,
+    /// Program and arguments
+    pub command: Vec<String>,
+    /// JUnit XML report written by the command, relative to the clone
+    pub junit: Option<std::path::PathBuf>,
+}
+
+impl Default for TestGate {
+    fn default() -> Self {
+        Self {
+            run: false,
+            command: ["cargo", "test", "--workspace"].map(String::from).to_vec(),
+            junit: None,
+        }
+    }
+}
+
+impl TestGate {
+    fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("tests"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.tests] {}", reason),
+            })
+        };
+
+        let mut gate = Self::default();
+        if let Some(value) = table.get("run") {
+            gate.run = value.as_bool().ok_or_else(|| invalid("run must be true or false"))?;
+        }
+        if let Some(value) = table.get("command") {
+            gate.command = value
+                .as_array()
+                .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect::<Option<Vec<_>>>())
+                .filter(|command| !command.is_empty())
+                .ok_or_else(|| invalid("command must be a non-empty array of strings"))?;
+        }
+        if let Some(value) = table.get("junit") {
+            gate.junit = Some(value.as_str().ok_or_else(|| invalid("junit must be a path"))?.into());
+        }
+        Ok(gate)
+    }
+}
+
+/// Lint gates, read from `[package.metadata.release.lints]`:
+///
+/// ```toml
+/// [package.metadata.release.lints]
+/// fmt = true                                   # cargo fmt --check
+/// clippy = true                                # cargo clippy --all-targets -- -D warnings
+/// clippy-args = ["-W", "clippy::pedantic"]     # appended after -D warnings
+/// advisory = ["fmt"]                           # report these without stopping the release
+/// ```
+///
+/// Both gates are off unless enabled.
+#[derive(Debug, Clone, Default)]
+pub struct LintGates {
+    /// Check formatting with `cargo fmt --check`
+    pub fmt: bool,
+    /// Lint with `cargo clippy -- -D warnings`
+    pub clippy: bool,
+    /// Extra lint flags passed to clippy
+    pub clippy_args: Vec<String>,
+    /// Gates (`fmt`, `clippy`) whose failures are only warnings
+    pub advisory: Vec<String>,
+}
+
+impl LintGates {
+    /// Whether any gate is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.fmt || self.clippy
+    }
+
+    /// Whether failures of `gate` only produce a warning
+    pub fn is_advisory(&self, gate: &str) -> bool {
+        self.advisory.iter().any(|g| g == gate)
+    }
+
+    fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("lints"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.lints] {}", reason),
+            })
+        };
+        let flag = |key: &str| -> Result<bool> {
+            match table.get(key) {
+                None => Ok(false),
+                Some(value) => value.as_bool().ok_or_else(|| invalid(&format!("{} must be true or false", key))),
+            }
+        };
+        let strings = |key: &str| -> Result<Vec<String>> {
+            match table.get(key) {
+                None => Ok(Vec::new()),
+                Some(value) => value
+                    .as_array()
+                    .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+                    .ok_or_else(|| invalid(&format!("{} must be an array of strings", key))),
+            }
+        };
+
+        let advisory = strings("advisory")?;
+        if let Some(gate) = advisory.iter().find(|g| !["fmt", "clippy"].contains(&g.as_str())) {
+            return Err(invalid(&format!("unknown gate '{}' in advisory (expected fmt or clippy)", gate)));
+        }
+
+        Ok(Self {
+            fmt: flag("fmt")?,
+            clippy: flag("clippy")?,
+            clippy_args: strings("clippy-args")?,
+            advisory,
+        })
+    }
+}
+
+/// RustSec advisory check of `Cargo.lock`, read from
+/// `[package.metadata.release.audit]`:
+///
+/// ```toml
+/// [package.metadata.release.audit]
+/// warn-only = true        # report vulnerabilities without stopping the release
+///
+/// [[package.metadata.release.audit.ignore]]
+/// id = "RUSTSEC-2023-0071"
+/// until = "2026-12-31"    # the advisory blocks again after this date
+/// reason = "no fix upstream; the affected API is not used"
+/// ```
+///
+/// The check runs whenever the table is present.
+#[derive(Debug, Clone, Default)]
+pub struct AuditGate {
+    /// Vulnerabilities produce warnings instead of stopping the release
+    pub warn_only: bool,
+    /// Advisories that are accepted, possibly until a date
+    pub ignore: Vec<IgnoredAdvisory>,
+}
+
+/// Accepted advisory in `[[package.metadata.release.audit.ignore]]`
+#[derive(Debug, Clone)]
+pub struct IgnoredAdvisory {
+    /// Advisory ID, e.g. `RUSTSEC-2023-0071`
+    pub id: String,
+    /// Last day the advisory is ignored (forever when unset)
+    pub until: Option<chrono::NaiveDate>,
+    /// Why the advisory is accepted
+    pub reason: Option<String>,
+}
+
+impl IgnoredAdvisory {
+    /// Whether the ignore entry still applies on `today`
+    pub fn is_active(&self, today: chrono::NaiveDate) -> bool {
+        self.until.is_none_or(|until| today <= until)
+    }
+}
+
+impl AuditGate {
+    fn from_package(package: &toml::Value) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("audit"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.audit] {}", reason),
+            })
+        };
+
+        let mut gate = Self::default();
+        if let Some(value) = table.get("warn-only") {
+            gate.warn_only = value.as_bool().ok_or_else(|| invalid("warn-only must be true or false"))?;
+        }
+        let entries = match table.get("ignore") {
+            None => Vec::new(),
+            Some(value) => value
+                .as_array()
+                .cloned()
+                .ok_or_else(|| invalid("ignore must be an array of tables"))?,
+        };
+        for entry in &entries {
+            let id = entry
+                .get("id")
+                .and_then(|v| v.as_str())
+                .filter(|id| !id.trim().is_empty())
+                .ok_or_else(|| invalid("every ignore entry needs an advisory id"))?
+                .trim()
+                .to_string();
+            // Accept both `until = "2026-12-31"` and the TOML date `until = 2026-12-31`
+            let until = match entry.get("until") {
+                None => None,
+                Some(value) => {
+                    let text = match value {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Datetime(d) => d.to_string(),
+                        _ => return Err(invalid(&format!("until of {} must be a date (YYYY-MM-DD)", id))),
+                    };
+                    Some(
+                        chrono::NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+                            .map_err(|_| invalid(&format!("until of {} must be a date (YYYY-MM-DD), got '{}'", id, text)))?,
+                    )
+                }
+            };
+            let reason = match entry.get("reason") {
+                None => None,
+                Some(value) => Some(
+                    value
+                        .as_str()
+                        .ok_or_else(|| invalid(&format!("reason of {} must be a string", id)))?
+                        .to_string(),
+                ),
+            };
+            gate.ignore.push(IgnoredAdvisory { id, until, reason });
+        }
+        Ok(Some(gate))
+    }
+
+    /// The ignore entry for `id`, if any
+    pub fn ignored(&self, id: &str) -> Option<&IgnoredAdvisory> {
+        self.ignore.iter().find(|entry| entry.id == id)
+    }
+}
+
+/// License and crate-ban policy checked with `cargo deny`, read from
+/// `[package.metadata.release.deny]`:
+///
+/// ```toml
+/// [package.metadata.release.deny]
+/// warn-only = false                        # true: report violations without stopping the release
+/// checks = ["licenses", "bans"]            # default: both
+/// config = "deny.toml"                     # default: deny.toml in the repository
+///
+/// # Without a deny.toml, the policy can be given here instead:
+/// allow-licenses = ["MIT", "Apache-2.0"]
+/// deny-crates = ["openssl-sys"]
+/// ```
+///
+/// The check runs whenever the table is present.
+#[derive(Debug, Clone)]
+pub struct DenyGate {
+    /// Violations produce warnings instead of stopping the release
+    pub warn_only: bool,
+    /// cargo-deny checks to run (`licenses`, `bans`)
+    pub checks: Vec<String>,
+    /// Policy file relative to the repository (`None`: deny.toml if present)
+    pub config: Option<std::path::PathBuf>,
+    /// Licenses allowed when the policy is given inline
+    pub allow_licenses: Vec<String>,
+    /// Crates banned when the policy is given inline
+    pub deny_crates: Vec<String>,
+}
+
+impl DenyGate {
+    /// Checks that can be selected
+    pub const CHECKS: [&'static str; 2] = ["licenses", "bans"];
+
+    /// Whether the policy is given in Cargo.toml rather than a deny.toml
+    pub fn is_inline(&self) -> bool {
+        !self.allow_licenses.is_empty() || !self.deny_crates.is_empty()
+    }
+
+    fn from_package(package: &toml::Value) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("deny"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.deny] {}", reason),
+            })
+        };
+        let strings = |key: &str| -> Result<Vec<String>> {
+            match table.get(key) {
+                None => Ok(Vec::new()),
+                Some(value) => value
+                    .as_array()
+                    .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+                    .ok_or_else(|| invalid(&format!("{} must be an array of strings", key))),
+            }
+        };
+
+        let warn_only = match table.get("warn-only") {
+            None => false,
+            Some(value) => value.as_bool().ok_or_else(|| invalid("warn-only must be true or false"))?,
+        };
+        let mut checks = strings("checks")?;
+        if checks.is_empty() {
+            checks = Self::CHECKS.map(String::from).to_vec();
+        }
+        if let Some(check) = checks.iter().find(|c| !Self::CHECKS.contains(&c.as_str())) {
+            return Err(invalid(&format!("unknown check '{}' (expected licenses or bans)", check)));
+        }
+        let config = match table.get("config") {
+            None => None,
+            Some(value) => Some(value.as_str().ok_or_else(|| invalid("config must be a path"))?.into()),
+        };
+
+        let gate = Self {
+            warn_only,
+            checks,
+            config,
+            allow_licenses: strings("allow-licenses")?,
+            deny_crates: strings("deny-crates")?,
+        };
+        if gate.config.is_some() && gate.is_inline() {
+            return Err(invalid("sets config and an inline policy (allow-licenses, deny-crates); use one"));
+        }
+        Ok(Some(gate))
+    }
+}
+
+/// Per-target builds, read from `[[package.metadata.release.targets]]`:
+///
+/// ```toml
+/// [[package.metadata.release.targets]]
+/// target = "x86_64-unknown-linux-musl"
+/// features = ["vendored-openssl"]
+///
+/// [[package.metadata.release.targets]]
+/// target = "universal-apple-darwin"   # aarch64 + x86_64 joined with lipo
+/// profile = "dist"
+/// ```
+///
+/// Each entry is one artifact, `<bin>-<version>-<target>[-<name>].tar.gz`.
+#[derive(Debug, Clone)]
+pub struct BuildTarget {
+    /// Rust target triple, or `universal-apple-darwin`
+    pub target: String,
+    /// Cargo features to enable
+    pub features: Vec<String>,
+    /// Build with `--no-default-features`
+    pub no_default_features: bool,
+    /// Cargo profile (default `release`)
+    pub profile: String,
+    /// Suffix distinguishing several builds of one target
+    pub name: Option<String>,
+    /// Force `cargo` or `cross` (default: `cargo` on a matching host, else `cross`)
+    pub builder: Option<Builder>,
+}
+
+/// Tool that compiles a [`BuildTarget`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builder {
+    /// `cargo` on the host
+    Cargo,
+    /// `cross` in Docker
+    Cross,
+}
+
+impl Builder {
+    /// Executable to run
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Cross => "cross",
+        }
+    }
+}
+
+impl BuildTarget {
+    /// Label used for the artifact name and the release manifest
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{}-{}", self.target, name),
+            None => self.target.clone(),
+        }
+    }
+
+    fn from_package(package: &toml::Value) -> Result<Vec<Self>> {
+        let Some(value) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("targets"))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let invalid = |reason: String| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[[package.metadata.release.targets]] {}", reason),
+            })
+        };
+
+        let entries = value
+            .as_array()
+            .ok_or_else(|| invalid("must be an array of tables".to_string()))?;
+        let mut targets: Vec<Self> = Vec::new();
+
+        for entry in entries {
+            let string = |key: &str| -> Result<Option<String>> {
+                match entry.get(key) {
+                    None => Ok(None),
+                    Some(v) => v
+                        .as_str()
+                        .filter(|s| !s.is_empty())
+                        .map(|s| Some(s.to_string()))
+                        .ok_or_else(|| invalid(format!("{} must be a non-empty string", key))),
+                }
+            };
+
+            let target = string("target")?.ok_or_else(|| invalid("entries need a `target`".to_string()))?;
+            let features = match entry.get("features") {
+                None => Vec::new(),
+                Some(v) => v
+                    .as_array()
+                    .and_then(|arr| arr.iter().map(|f| f.as_str().map(String::from)).collect())
+                    .ok_or_else(|| invalid(format!("{}: features must be an array of strings", target)))?,
+            };
+            let no_default_features = match entry.get("no-default-features") {
+                None => false,
+                Some(v) => v.as_bool().ok_or_else(|| {
+                    invalid(format!("{}: no-default-features must be true or false", target))
+                })?,
+            };
+            let builder = match string("builder")?.as_deref() {
+                None => None,
+                Some("cargo") => Some(Builder::Cargo),
+                Some("cross") => Some(Builder::Cross),
+                Some(other) => {
+                    return Err(invalid(format!(
+                        "{}: builder must be \"cargo\" or \"cross\", not \"{}\"",
+                        target, other
+                    )));
+                }
+            };
+
+            let build = Self {
+                profile: string("profile")?.unwrap_or_else(|| "release".to_string()),
+                name: string("name")?,
+                target,
+                features,
+                no_default_features,
+                builder,
+            };
+            if build.target == "universal-apple-darwin" && build.builder == Some(Builder::Cross) {
+                return Err(invalid("universal-apple-darwin can only be built with cargo on macOS".to_string()));
+            }
+            if targets.iter().any(|t| t.label() == build.label()) {
+                return Err(invalid(format!(
+                    "{} appears twice; give the builds distinct `name`s",
+                    build.label()
+                )));
+            }
+            targets.push(build);
+        }
+
+        Ok(targets)
+    }
+}
+
+/// Custom package format, read from `[[package.metadata.release.bundlers]]`:
+///
+/// ```toml
+/// [[package.metadata.release.bundlers]]
+/// name = "internal-tarball"
+/// hosts = ["linux", "macos"]      # default: every host
+/// command = ["scripts/package.sh", "{binary}", "{version}", "{out_dir}"]
+/// artifacts = ["*.tar.zst"]       # files in {out_dir} to release (default: all)
+/// ```
+///
+/// The command runs in the release clone after the built-in bundles.
+#[derive(Debug, Clone)]
+pub struct CustomBundler {
+    /// Format name, used for the output directory and in the manifest
+    pub name: String,
+    /// Host operating systems (`std::env::consts::OS` names) it runs on
+    pub hosts: Vec<String>,
+    /// Program and arguments, with `{binary}`, `{binary_path}`, `{version}`,
+    /// `{arch}` and `{out_dir}` substituted
+    pub command: Vec<String>,
+    /// Globs selecting the files in the output directory that are released
+    pub artifacts: Vec<String>,
+}
+
+/// Names the release already uses for its own artifacts
+const RESERVED_BUNDLE_NAMES: [&str; 9] = [
+    "deb", "rpm", "appimage", "dmg", "exe", "msi", "msix", "mas", "shell-integration",
+];
+
+/// Host names accepted in `hosts`
+const BUNDLER_HOSTS: [&str; 6] = ["linux", "macos", "windows", "freebsd", "openbsd", "netbsd"];
+
+impl CustomBundler {
+    /// Whether the bundler runs on this host
+    pub fn supports_host(&self) -> bool {
+        self.hosts.is_empty() || self.hosts.iter().any(|host| host == std::env::consts::OS)
+    }
+
+    fn from_package(package: &toml::Value) -> Result<Vec<Self>> {
+        let Some(value) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("bundlers"))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let invalid = |reason: String| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("[[package.metadata.release.bundlers]] {}", reason),
+            })
+        };
+
+        let entries = value
+            .as_array()
+            .ok_or_else(|| invalid("must be an array of tables".to_string()))?;
+        let mut bundlers: Vec<Self> = Vec::new();
+
+        for entry in entries {
+            let name = entry
+                .get("name")
+                .and_then(|v| v.as_str())
+                .filter(|name| {
+                    !name.is_empty()
+                        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                })
+                .ok_or_else(|| invalid("entries need a `name` of lowercase letters, digits and dashes".to_string()))?
+                .to_string();
+            let strings = |key: &str| -> Result<Vec<String>> {
+                match entry.get(key) {
+                    None => Ok(Vec::new()),
+                    Some(v) => v
+                        .as_array()
+                        .and_then(|arr| arr.iter().map(|s| s.as_str().map(String::from)).collect())
+                        .ok_or_else(|| invalid(format!("{}: {} must be an array of strings", name, key))),
+                }
+            };
+
+            if RESERVED_BUNDLE_NAMES.contains(&name.as_str()) {
+                return Err(invalid(format!("{} is a built-in format; pick another name", name)));
+            }
+            if bundlers.iter().any(|b| b.name == name) {
+                return Err(invalid(format!("{} appears twice", name)));
+            }
+            let hosts = strings("hosts")?;
+            if let Some(host) = hosts.iter().find(|h| !BUNDLER_HOSTS.contains(&h.as_str())) {
+                return Err(invalid(format!(
+                    "{}: unknown host '{}' (expected {})",
+                    name,
+                    host,
+                    BUNDLER_HOSTS.join(", ")
+                )));
+            }
+            let command = strings("command")?;
+            if command.is_empty() {
+                return Err(invalid(format!("{}: command must be a non-empty array of strings", name)));
+            }
+            let mut artifacts = strings("artifacts")?;
+            if artifacts.is_empty() {
+                artifacts.push("*".to_string());
+            }
+
+            bundlers.push(Self {
+                name,
+                hosts,
+                command,
+                artifacts,
+            });
+        }
+
+        Ok(bundlers)
+    }
+}
+
+/// Feature set a crate is published with, read from `[package.metadata.release.publish]`:
+///
+/// ```toml
+/// [package.metadata.release.publish]
+/// features = ["rustls"]
+/// no-default-features = true
+/// all-features = false   # skip the --all-features check (mutually exclusive features)
+/// ```
+#[derive(Debug, Clone)]
+pub struct PublishFeatures {
+    /// Features passed to `cargo publish --features`
+    pub features: Vec<String>,
+    /// Publish with `--no-default-features`
+    pub no_default_features: bool,
+    /// Also check the crate with `--all-features`
+    pub check_all_features: bool,
+}
+
+impl Default for PublishFeatures {
+    fn default() -> Self {
+        Self {
+            features: Vec::new(),
+            no_default_features: false,
+            check_all_features: true,
+        }
+    }
+}
+
+impl PublishFeatures {
+    /// Parse `[package.metadata.release.publish]` of `package` (a `[package]` table)
+    pub fn from_package(package: &toml::Value) -> Result<Self> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("publish"))
+        else {
+            return Ok(Self::default());
+        };
+
+        let name = package.get("name").and_then(|n| n.as_str()).unwrap_or("package");
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: format!("{}: [package.metadata.release.publish] {}", name, reason),
+            })
+        };
+        let flag = |key: &str, default: bool| -> Result<bool> {
+            match table.get(key) {
+                None => Ok(default),
+                Some(v) => v
+                    .as_bool()
+                    .ok_or_else(|| invalid(&format!("{} must be true or false", key))),
+            }
+        };
+
+        Ok(Self {
+            features: match table.get("features") {
+                None => Vec::new(),
+                Some(v) => v
+                    .as_array()
+                    .and_then(|arr| arr.iter().map(|f| f.as_str().map(String::from)).collect())
+                    .ok_or_else(|| invalid("features must be an array of strings"))?,
+            },
+            no_default_features: flag("no-default-features", false)?,
+            check_all_features: flag("all-features", true)?,
+        })
+    }
+
+    /// Feature arguments for `cargo publish` / `cargo check`
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+        if !self.features.is_empty() {
+            args.push("--features".to_string());
+            args.push(self.features.join(","));
+        }
+        args
+    }
+}
+
+/// Parse `[package.metadata.release.timeouts]`
+///
+/// ```toml
+/// [package.metadata.release.timeouts]
+/// build = "20m"
+/// bundle = "1h30m"   # per platform, including notarization
+/// upload = 600       # plain integers are seconds
+/// ```
+fn timeouts_from_package(package: &toml::Value) -> Result<Vec<(TimedPhase, Duration)>> {
+    let Some(table) = package
+        .get("metadata")
+        .and_then(|m| m.get("release"))
+        .and_then(|r| r.get("timeouts"))
+        .and_then(|t| t.as_table())
+    else {
+        return Ok(Vec::new());
+    };
+
+    table
+        .iter()
+        .map(|(key, value)| {
+            let invalid = |reason: String| {
+                ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                    reason: format!("[package.metadata.release.timeouts] {}: {}", key, reason),
+                })
+            };
+            let phase = TimedPhase::from_name(key).ok_or_else(|| {
+                invalid("unknown phase (expected build, bundle, upload, github, registry or test)".to_string())
+            })?;
+            let duration = match value {
+                toml::Value::String(s) => parse_duration(s).map_err(invalid)?,
+                toml::Value::Integer(secs) if *secs > 0 => Duration::from_secs(*secs as u64),
+                _ => return Err(invalid("expected a duration like \"30m\" or seconds".to_string())),
+            };
+            Ok((phase, duration))
+        })
+        .collect()
+}
+
+/// Whether the package has a library but no binary target
+///
+/// Without `[[bin]]`, cargo infers binaries from `src/main.rs` and
+/// `src/bin/` (unless `autobins = false`).
+fn is_library_only(package: &toml::Value, manifest: &toml::Value, package_dir: &Path) -> bool {
+    let autobins = package.get("autobins").and_then(|v| v.as_bool()).unwrap_or(true);
+    let infers_binary =
+        autobins && (package_dir.join("src/main.rs").is_file() || package_dir.join("src/bin").is_dir());
+    let has_library = manifest.get("lib").is_some() || package_dir.join("src/lib.rs").is_file();
+    has_library && !infers_binary
 }
 
 /// Manifest with metadata and binary name
 pub struct Manifest {
     pub metadata: PackageMetadata,
-    pub binary_name: String,
+    /// Binary to build and bundle; `None` for a library-only crate
+    pub binary_name: Option<String>,
+}
+
+/// `[workspace.package].version` of the workspace a package inherits its version from
+///
+/// The workspace is the package's own manifest if it has a `[workspace]`
+/// table, otherwise the nearest parent directory whose Cargo.toml has one.
+fn workspace_package_version(manifest: &toml::Value, package_dir: &Path) -> Result<String> {
+    let version_of = |root: &toml::Value| {
+        root.get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+    let missing = |root: &Path| {
+        ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+            reason: format!(
+                "[package] sets version.workspace = true, but {} has no [workspace.package] version",
+                root.display()
+            ),
+        })
+    };
+
+    if manifest.get("workspace").is_some() {
+        return version_of(manifest).ok_or_else(|| missing(&package_dir.join("Cargo.toml")));
+    }
+
+    let start = package_dir.canonicalize().unwrap_or_else(|_| package_dir.to_path_buf());
+    for dir in start.ancestors().skip(1) {
+        let cargo_toml = dir.join("Cargo.toml");
+        let Ok(content) = std::fs::read_to_string(&cargo_toml) else {
+            continue;
+        };
+        let root: toml::Value = toml::from_str(&content).map_err(|e| {
+            ReleaseError::Cli(crate::error::CliError::ExecutionFailed {
+                command: "parse_cargo_toml".to_string(),
+                reason: format!("Failed to parse {}: {}", cargo_toml.display(), e),
+            })
+        })?;
+        if root.get("workspace").is_some() {
+            return version_of(&root).ok_or_else(|| missing(&cargo_toml));
+        }
+    }
+
+    Err(ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+        reason: "[package] sets version.workspace = true, but no workspace root was found above it".to_string(),
+    }))
+}
+
+/// The parts of `cargo metadata --no-deps` output used for binary discovery
+#[derive(Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+    version: String,
+    manifest_path: PathBuf,
+    targets: Vec<CargoTarget>,
+    #[serde(default)]
+    default_run: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoTarget {
+    name: String,
+    kind: Vec<String>,
+    #[serde(rename = "required-features", default)]
+    required_features: Vec<String>,
+}
+
+/// Name, version, binary and the binary's required features of a package
+struct PackageIdentity {
+    name: String,
+    version: String,
+    binary_name: Option<String>,
+    binary_features: Vec<String>,
+}
+
+/// Upper bound for `cargo metadata`, which can wait on the package cache lock
+const CARGO_METADATA_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Package identity as cargo resolves it; `None` when `cargo metadata` fails
+///
+/// The reason for falling back to Cargo.toml is shown in verbose mode.
+async fn cargo_metadata_identity(cargo_toml_path: &Path, config: &RuntimeConfig) -> Option<PackageIdentity> {
+    match resolve_with_cargo(cargo_toml_path).await {
+        Ok(identity) => Some(identity),
+        Err(reason) => {
+            config
+                .verbose_println(&format!(
+                    "   cargo metadata unavailable ({}), reading {} directly",
+                    reason,
+                    cargo_toml_path.display()
+                ))
+                .expect("Failed to write to stdout");
+            None
+        }
+    }
+}
+
+/// Name, version and binary from `cargo metadata`, or why it failed
+///
+/// The binary is `default-run` when set, otherwise the first binary target.
+async fn resolve_with_cargo(cargo_toml_path: &Path) -> std::result::Result<PackageIdentity, String> {
+    let output = crate::exec::Exec::new("cargo")
+        .args(["metadata", "--format-version", "1", "--no-deps", "--manifest-path"])
+        .arg(cargo_toml_path)
+        .timeout(CARGO_METADATA_TIMEOUT)
+        .run()
+        .await
+        .map_err(|e| e.to_string())?;
+    let metadata: CargoMetadata =
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("unexpected output: {}", e))?;
+
+    let wanted = cargo_toml_path
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", cargo_toml_path.display(), e))?;
+    let package = metadata
+        .packages
+        .into_iter()
+        .find(|p| p.manifest_path.canonicalize().is_ok_and(|path| path == wanted))
+        .ok_or_else(|| "the package is not in its output".to_string())?;
+
+    let binaries: Vec<&CargoTarget> = package
+        .targets
+        .iter()
+        .filter(|t| t.kind.iter().any(|kind| kind == "bin"))
+        .collect();
+    let binary = package
+        .default_run
+        .as_deref()
+        .and_then(|name| binaries.iter().find(|t| t.name == name))
+        .or_else(|| binaries.first());
+
+    Ok(PackageIdentity {
+        binary_name: binary.map(|t| t.name.clone()),
+        binary_features: binary.map(|t| t.required_features.clone()).unwrap_or_default(),
+        name: package.name,
+        version: package.version,
+    })
+}
+
+/// Package identity read from Cargo.toml alone (offline fallback)
+///
+/// Handles `version.workspace = true` and the first `[[bin]]`, and falls back
+/// to the package name for the binary unless the package is library-only.
+fn manifest_identity(manifest: &toml::Value, package: &toml::Value, package_dir: &Path) -> Result<PackageIdentity> {
+    let name = package
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: "Missing 'name' in [package]".to_string(),
+            })
+        })?
+        .to_string();
+
+    let version = match package.get("version") {
+        Some(toml::Value::String(version)) => version.clone(),
+        Some(toml::Value::Table(table)) if table.get("workspace") == Some(&toml::Value::Boolean(true)) => {
+            workspace_package_version(manifest, package_dir)?
+        }
+        _ => {
+            return Err(ReleaseError::Cli(crate::error::CliError::InvalidArguments {
+                reason: "Missing 'version' in [package]".to_string(),
+            }));
+        }
+    };
+
+    let first_bin = manifest
+        .get("bin")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first());
+    let binary_name = first_bin
+        .and_then(|first| first.get("name"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .or_else(|| (!is_library_only(package, manifest, package_dir)).then(|| name.clone()));
+    let binary_features = first_bin
+        .and_then(|first| first.get("required-features"))
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    Ok(PackageIdentity {
+        name,
+        version,
+        binary_name,
+        binary_features,
+    })
 }
 
 /// Load manifest from Cargo.toml
 ///
-/// Properly handles [[bin]] sections in Cargo.toml for binary discovery.
-/// Falls back to package name if no [[bin]] sections exist.
-pub fn load_manifest(cargo_toml_path: &Path) -> Result<Manifest> {
+/// Name, version and binary come from `cargo metadata`, or from Cargo.toml
+/// when cargo is unavailable; `binary_name` is `None` for a library crate.
+pub async fn load_manifest(cargo_toml_path: &Path, config: &RuntimeConfig) -> Result<Manifest> {
     // Step 1: Read file once
     let content = std::fs::read_to_string(cargo_toml_path).map_err(|e| {
         ReleaseError::Cli(crate::error::CliError::ExecutionFailed {
@@ -43,48 +1248,164 @@ pub fn load_manifest(cargo_toml_path: &Path) -> Result<Manifest> {
         })
     })?;
 
-    // Step 3: Extract package name
-    let name = package
-        .get("name")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| {
-            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
-                reason: "Missing 'name' in [package]".to_string(),
-            })
-        })?
-        .to_string();
-
-    // Step 4: Extract version
-    let version = package
-        .get("version")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| {
-            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
-                reason: "Missing 'version' in [package]".to_string(),
-            })
-        })?
-        .to_string();
+    // Steps 3-5: Name, version and binary, as cargo resolves them when possible
+    let package_dir = cargo_toml_path.parent().unwrap_or(Path::new("."));
+    let PackageIdentity {
+        name,
+        version,
+        binary_name,
+        binary_features,
+    } = match cargo_metadata_identity(cargo_toml_path, config).await {
+        Some(identity) => identity,
+        None => manifest_identity(&toml_value, package, package_dir)?,
+    };
 
-    // Step 5: Discover binary name from [[bin]] sections or fallback to package name
-    let binary_name = toml_value
-        .get("bin")
-        .and_then(|v| v.as_array())
-        .and_then(|arr| arr.first())
-        .and_then(|first| first.get("name"))
-        .and_then(|v| v.as_str())
-        .map(String::from)
-        .or_else(|| Some(name.clone()))
-        .ok_or_else(|| {
-            ReleaseError::Cli(crate::error::CliError::InvalidArguments {
-                reason: "No binary found in Cargo.toml".to_string(),
-            })
-        })?;
+    // Step 6: Optional release tool metadata
+    let shell_integration = ShellIntegration::from_package(package)?;
+    let debug_symbols = debug_symbols_from_package(package)?;
+    let timeouts = timeouts_from_package(package)?;
+    let release_notes = ReleaseNotesConfig::from_package(package)?;
+    let version_scheme = VersionScheme::from_package(package)?;
+    let version_files = crate::version_refs::from_package(package)?;
+    let asset_labels = AssetLabelRule::from_package(package)?;
+    let bsd = BsdTargets::from_package(package)?;
+    let retention = RetentionPolicy::from_package(package)?;
+    let targets = BuildTarget::from_package(package)?;
+    let bundlers = CustomBundler::from_package(package)?;
+    let store = crate::store::StoreConfig::from_package(package)?;
+    let mirrors = crate::mirror::MirrorConfig::from_package(package)?;
+    let encryption = crate::encryption::EncryptionConfig::from_package(package)?;
+    let compression = crate::compression::CompressionConfig::from_package(package)?;
+    let mac_app_store = MacAppStore::from_package(package, package_dir)?;
+    let tests = TestGate::from_package(package)?;
+    let lints = LintGates::from_package(package)?;
+    let audit = AuditGate::from_package(package)?;
+    let deny = DenyGate::from_package(package)?;
 
     Ok(Manifest {
         metadata: PackageMetadata {
             name,
             version,
+            binary_features,
+            shell_integration,
+            debug_symbols,
+            timeouts,
+            release_notes,
+            version_scheme,
+            version_files,
+            asset_labels,
+            bsd,
+            retention,
+            targets,
+            bundlers,
+            store,
+            mirrors,
+            encryption,
+            compression,
+            mac_app_store,
+            tests,
+            lints,
+            audit,
+            deny,
         },
         binary_name,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(path: &Path) -> toml::Value {
+        toml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Offline identity of `member` in the workspace at `dir`
+    fn identity(dir: &Path, member: &str) -> Result<PackageIdentity> {
+        let member_dir = dir.join(member);
+        let manifest = read(&member_dir.join("Cargo.toml"));
+        manifest_identity(&manifest, &manifest["package"], &member_dir)
+    }
+
+    #[test]
+    fn inherits_workspace_version() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"tool\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+        );
+        write(dir.path(), "tool/Cargo.toml", "[package]\nname = \"tool\"\nversion.workspace = true\n");
+
+        let member_dir = dir.path().join("tool");
+        let manifest = read(&member_dir.join("Cargo.toml"));
+        assert_eq!(workspace_package_version(&manifest, &member_dir).unwrap(), "1.2.3");
+        assert_eq!(identity(dir.path(), "tool").unwrap().version, "1.2.3");
+
+        // A package that is its own workspace root reads its own table
+        let root = read(&dir.path().join("Cargo.toml"));
+        assert_eq!(workspace_package_version(&root, dir.path()).unwrap(), "1.2.3");
+    }
+
+    #[test]
+    fn explicit_version_ignores_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"tool\"]\n\n[workspace.package]\nversion = \"1.2.3\"\n",
+        );
+        write(dir.path(), "tool/Cargo.toml", "[package]\nname = \"tool\"\nversion = \"0.4.0\"\n");
+
+        assert_eq!(identity(dir.path(), "tool").unwrap().version, "0.4.0");
+    }
+
+    #[test]
+    fn missing_workspace_version_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "Cargo.toml", "[workspace]\nmembers = [\"tool\"]\n");
+        write(dir.path(), "tool/Cargo.toml", "[package]\nname = \"tool\"\nversion.workspace = true\n");
+
+        let member_dir = dir.path().join("tool");
+        let manifest = read(&member_dir.join("Cargo.toml"));
+        let error = workspace_package_version(&manifest, &member_dir).unwrap_err().to_string();
+        assert!(error.contains("no [workspace.package] version"), "{}", error);
+        assert!(identity(dir.path(), "tool").is_err());
+    }
+
+    #[tokio::test]
+    async fn cargo_metadata_picks_default_run_and_its_features() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "Cargo.toml",
+            "[package]\nname = \"tool\"\nversion = \"0.3.0\"\nedition = \"2021\"\ndefault-run = \"server\"\n\n\
+             [features]\nnet = []\n\n\
+             [[bin]]\nname = \"client\"\npath = \"src/client.rs\"\n\n\
+             [[bin]]\nname = \"server\"\npath = \"src/server.rs\"\nrequired-features = [\"net\"]\n",
+        );
+        write(dir.path(), "src/client.rs", "fn main() {}");
+        write(dir.path(), "src/server.rs", "fn main() {}");
+
+        let identity = resolve_with_cargo(&dir.path().join("Cargo.toml")).await.unwrap();
+        assert_eq!(identity.name, "tool");
+        assert_eq!(identity.version, "0.3.0");
+        assert_eq!(identity.binary_name.as_deref(), Some("server"));
+        assert_eq!(identity.binary_features, ["net"]);
+    }
+
+    #[tokio::test]
+    async fn cargo_metadata_failure_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "Cargo.toml", "[package]\nname = \"tool\"\n");
+
+        let reason = resolve_with_cargo(&dir.path().join("Cargo.toml")).await.err().unwrap();
+        assert!(!reason.is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Named release profiles from `release.toml`.
+//!
+//! A profile redirects a run at other infrastructure without touching
+//! `Cargo.toml`, so a full rehearsal release is one flag away:
+//!
+//! ```toml
+//! # release.toml (repository root)
+//! [profile.staging]
+//! repository = "acme-staging/widget"   # GitHub repository receiving the release
+//! draft = true                         # leave the release unpublished
+//! sign = false                         # bundle without signing or notarization
+//! ```
+//!
+//! Selected with `--profile staging`. Keys left out keep their normal
+//! behavior (detected repository, published release, signed bundles).
+//!
+//! `prerelease` sets which releases are marked as a GitHub prerelease. At
+//! the top level of the file it applies to every run, inside a profile to
+//! runs with that profile; `--prerelease` overrides both:
+//!
+//! ```toml
+//! prerelease = "pre-tag"   # 0.x releases are stable, only -rc/-beta are not
+//! ```
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::github::PrereleasePolicy;
+use std::path::Path;
+
+/// File holding the profiles, relative to the repository root
+pub const PROFILE_FILE: &str = "release.toml";
+
+/// Overrides applied by a `[profile.<name>]` table
+#[derive(Debug, Clone)]
+pub struct ReleaseProfile {
+    /// Profile name (`None` for a run without `--profile`)
+    pub name: Option<String>,
+    /// `(owner, repo)` receiving the GitHub release instead of the detected one
+    pub repository: Option<(String, String)>,
+    /// Leave the GitHub release as a draft instead of publishing it
+    pub draft: bool,
+    /// Pass signing and notarization credentials to the bundler
+    pub sign: bool,
+    /// Which releases are marked as a prerelease (`None` for the default)
+    pub prerelease: Option<PrereleasePolicy>,
+}
+
+impl Default for ReleaseProfile {
+    fn default() -> Self {
+        Self {
+            name: None,
+            repository: None,
+            draft: false,
+            sign: true,
+            prerelease: None,
+        }
+    }
+}
+
+impl ReleaseProfile {
+    /// Settings for a run without `--profile`: the top-level keys of
+    /// `release.toml` in `repo_path`, if there is one
+    pub fn load_default(repo_path: &Path) -> Result<Self> {
+        let path = repo_path.join(PROFILE_FILE);
+        let invalid = |reason: String| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("{}: {}", path.display(), reason),
+            })
+        };
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(invalid(e.to_string())),
+        };
+        let document: toml::Value =
+            toml::from_str(&content).map_err(|e| invalid(format!("invalid TOML: {}", e)))?;
+        Ok(Self {
+            prerelease: prerelease_policy(document.get("prerelease"), "", &invalid)?,
+            ..Self::default()
+        })
+    }
+
+    /// Load profile `name` from `release.toml` in `repo_path`
+    pub fn load(repo_path: &Path, name: &str) -> Result<Self> {
+        let path = repo_path.join(PROFILE_FILE);
+        let invalid = |reason: String| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("{}: {}", path.display(), reason),
+            })
+        };
+
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                invalid(format!("not found (required by --profile {})", name))
+            } else {
+                invalid(e.to_string())
+            }
+        })?;
+        let document: toml::Value =
+            toml::from_str(&content).map_err(|e| invalid(format!("invalid TOML: {}", e)))?;
+        let top_level_prerelease = prerelease_policy(document.get("prerelease"), "", &invalid)?;
+
+        let profiles = document.get("profile").and_then(|p| p.as_table());
+        let Some(table) = profiles.and_then(|p| p.get(name)).and_then(|p| p.as_table()) else {
+            let mut known: Vec<&str> = profiles
+                .map(|p| p.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            known.sort_unstable();
+            return Err(invalid(if known.is_empty() {
+                format!("no [profile.{}] table (no profiles defined)", name)
+            } else {
+                format!("no [profile.{}] table (defined: {})", name, known.join(", "))
+            }));
+        };
+
+        let mut profile = Self {
+            name: Some(name.to_string()),
+            prerelease: top_level_prerelease,
+            ..Self::default()
+        };
+        let section = format!("[profile.{}]", name);
+
+        for (key, value) in table {
+            match key.as_str() {
+                "repository" => {
+                    let repository = value
+                        .as_str()
+                        .and_then(|s| s.split_once('/'))
+                        .filter(|(owner, repo)| {
+                            !owner.is_empty() && !repo.is_empty() && !repo.contains('/')
+                        })
+                        .ok_or_else(|| {
+                            invalid(format!("{} repository must be \"owner/repo\"", section))
+                        })?;
+                    profile.repository =
+                        Some((repository.0.to_string(), repository.1.to_string()));
+                }
+                "draft" => {
+                    profile.draft = value
+                        .as_bool()
+                        .ok_or_else(|| invalid(format!("{} draft must be true or false", section)))?;
+                }
+                "sign" => {
+                    profile.sign = value
+                        .as_bool()
+                        .ok_or_else(|| invalid(format!("{} sign must be true or false", section)))?;
+                }
+                "prerelease" => {
+                    profile.prerelease = prerelease_policy(Some(value), &format!("{} ", section), &invalid)?;
+                }
+                // Crates are published by `just publish` before this tool runs
+                "registry" => {
+                    return Err(invalid(format!(
+                        "{} registry is not supported: crates are published before the release \
+                         (pass --registry to cargo publish instead)",
+                        section
+                    )));
+                }
+                other => {
+                    return Err(invalid(format!(
+                        "{} has unknown key `{}` (expected repository, draft, sign, prerelease)",
+                        section, other
+                    )));
+                }
+            }
+        }
+
+        Ok(profile)
+    }
+
+    /// One-line summary of what the profile changes
+    pub fn describe(&self) -> String {
+        let mut changes = Vec::new();
+        if let Some((owner, repo)) = &self.repository {
+            changes.push(format!("repository {}/{}", owner, repo));
+        }
+        if self.draft {
+            changes.push("draft".to_string());
+        }
+        if !self.sign {
+            changes.push("unsigned".to_string());
+        }
+        if let Some(policy) = self.prerelease {
+            changes.push(format!("prerelease {}", policy_name(policy)));
+        }
+        if changes.is_empty() {
+            changes.push("no overrides".to_string());
+        }
+        format!("{} ({})", self.name.as_deref().unwrap_or("default"), changes.join(", "))
+    }
+}
+
+/// Parse a `prerelease` value, `None` when the key is absent
+fn prerelease_policy(
+    value: Option<&toml::Value>,
+    section: &str,
+    invalid: &impl Fn(String) -> ReleaseError,
+) -> Result<Option<PrereleasePolicy>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    value
+        .as_str()
+        .ok_or_else(|| format!("{}prerelease must be a string", section))
+        .and_then(|s| s.parse().map_err(|e| format!("{}{}", section, e)))
+        .map(Some)
+        .map_err(invalid)
+}
+
+/// Name of `policy` as written in `release.toml` and on the command line
+fn policy_name(policy: PrereleasePolicy) -> String {
+    clap::ValueEnum::to_possible_value(&policy)
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
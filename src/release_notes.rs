@@ -0,0 +1,371 @@
+//! Localized GitHub release notes from translated templates.
+//!
+//! Configured in `[package.metadata.release.notes]`:
+//!
+//! ```toml
+//! [package.metadata.release.notes]
+//! templates = "release-notes"     # directory of <locale>.md files
+//! locales = ["en", "ja", "de"]    # optional; default: every template found
+//! default-locale = "en"           # listed first; default "en"
+//! ```
+//!
+//! For each locale the template `<templates>/<version>/<locale>.md` is used if
+//! present, otherwise `<templates>/<locale>.md`. Templates may use `{package}`,
+//! `{version}` and `{tag}`. With more than one locale the release body gets a
+//! heading per language, default locale first; a single locale is used as-is.
+//!
+//! Workspace releases also get a section per crate, e.g.
+//! `## kodegen_tools_git 0.4.0`, listing the commits since the previous tag
+//! that touched the crate's directory (see [`crate_sections`]). With
+//! `--notes-source prs` the changelog lists the merged pull requests of those
+//! commits instead, grouped by label (see [`pull_request_sections`]).
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::exec::Exec;
+
+/// Release notes template settings
+#[derive(Debug, Clone)]
+pub struct ReleaseNotesConfig {
+    /// Template directory, relative to the package root
+    pub templates: PathBuf,
+    /// Locales to include, in order (empty = all templates found)
+    pub locales: Vec<String>,
+    /// Locale listed first
+    pub default_locale: String,
+}
+
+impl ReleaseNotesConfig {
+    /// Parse `[package.metadata.release.notes]`, if present
+    pub(crate) fn from_package(package: &toml::Value) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("notes"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.notes] {}", reason),
+            })
+        };
+
+        let templates = table
+            .get("templates")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| invalid("templates must be a directory path"))?;
+
+        let locales = match table.get("locales") {
+            None => Vec::new(),
+            Some(value) => value
+                .as_array()
+                .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+                .ok_or_else(|| invalid("locales must be an array of strings"))?,
+        };
+
+        let default_locale = match table.get("default-locale") {
+            None => "en".to_string(),
+            Some(value) => value
+                .as_str()
+                .ok_or_else(|| invalid("default-locale must be a string"))?
+                .to_string(),
+        };
+
+        Ok(Some(Self {
+            templates: PathBuf::from(templates),
+            locales,
+            default_locale,
+        }))
+    }
+
+    /// Render the release body for `version`, tagged `tag`
+    ///
+    /// Returns `None` when no template exists for any locale.
+    pub fn render(
+        &self,
+        package_root: &Path,
+        package: &str,
+        version: &semver::Version,
+        tag: &str,
+    ) -> Result<Option<String>> {
+        let dir = package_root.join(&self.templates);
+        let versioned = dir.join(version.to_string());
+
+        let mut locales = if self.locales.is_empty() {
+            discover_locales(&dir, &versioned)
+        } else {
+            self.locales.clone()
+        };
+        // Default locale first, everything else in configured/discovered order
+        locales.sort_by_key(|locale| *locale != self.default_locale);
+
+        let mut sections = Vec::new();
+        for locale in &locales {
+            let file = format!("{}.md", locale);
+            let path = [versioned.join(&file), dir.join(&file)]
+                .into_iter()
+                .find(|p| p.is_file());
+
+            let Some(path) = path else {
+                if !self.locales.is_empty() {
+                    return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                        reason: format!(
+                            "No release notes template for locale '{}' in {}",
+                            locale,
+                            dir.display()
+                        ),
+                    }));
+                }
+                continue;
+            };
+
+            let content = std::fs::read_to_string(&path)?
+                .replace("{package}", package)
+                .replace("{version}", &version.to_string())
+                .replace("{tag}", tag);
+            sections.push((locale.as_str(), content.trim().to_string()));
+        }
+
+        Ok(match sections.as_slice() {
+            [] => None,
+            [(_, only)] => Some(only.clone()),
+            _ => Some(
+                sections
+                    .iter()
+                    .map(|(locale, content)| format!("## {}\n\n{}", language_name(locale), content))
+                    .collect::<Vec<_>>()
+                    .join("\n\n"),
+            ),
+        })
+    }
+}
+
+/// Changelog of a workspace release grouped by crate
+///
+/// Lists the commits between the previous tag matching `tag_pattern` and
+/// `tag` (all of history for a first release) under a heading per crate they
+/// touched, crates sorted by name. A file belongs to the member whose
+/// directory contains it most closely; commits touching no member are listed
+/// last under "Other changes". Returns `None` outside a workspace, when
+/// `[workspace.metadata.release] crate-notes = false`, or without commits.
+pub fn crate_sections(repo: &Path, tag: &str, tag_pattern: &str) -> Result<Option<String>> {
+    if !crate::workspace::WorkspaceInfo::is_workspace_root(repo)? {
+        return Ok(None);
+    }
+    let workspace = crate::workspace::WorkspaceInfo::analyze(repo)?;
+    if !workspace.workspace_config.crate_notes() {
+        return Ok(None);
+    }
+
+    let range = match previous_tag(repo, tag, tag_pattern) {
+        Some(previous) => format!("{}..{}", previous, tag),
+        None => tag.to_string(),
+    };
+    let log = git(repo, &["log", "--no-merges", "--format=%x00%h %s", "--name-only", &range])?;
+
+    let mut packages: Vec<_> = workspace.packages.values().collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut sections: Vec<Vec<String>> = vec![Vec::new(); packages.len()];
+    let mut other = Vec::new();
+
+    for commit in log.split('\0').filter(|c| !c.trim().is_empty()) {
+        let mut lines = commit.lines();
+        let Some((hash, subject)) = lines.next().and_then(|line| line.split_once(' ')) else {
+            continue;
+        };
+        let entry = format!("- {} ({})", subject, hash);
+
+        let mut touched = vec![false; packages.len()];
+        for file in lines.map(Path::new).filter(|f| !f.as_os_str().is_empty()) {
+            let owner = packages
+                .iter()
+                .enumerate()
+                .filter(|(_, package)| file.starts_with(&package.path))
+                .max_by_key(|(_, package)| package.path.components().count());
+            if let Some((index, _)) = owner {
+                touched[index] = true;
+            }
+        }
+
+        if touched.iter().any(|t| *t) {
+            for (section, _) in sections.iter_mut().zip(&touched).filter(|(_, t)| **t) {
+                section.push(entry.clone());
+            }
+        } else {
+            other.push(entry);
+        }
+    }
+
+    let mut blocks: Vec<String> = packages
+        .iter()
+        .zip(&sections)
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(package, entries)| format!("## {} {}\n\n{}", package.name, package.version, entries.join("\n")))
+        .collect();
+    if !other.is_empty() {
+        blocks.push(format!("## Other changes\n\n{}", other.join("\n")));
+    }
+
+    Ok((!blocks.is_empty()).then(|| blocks.join("\n\n")))
+}
+
+/// Commits of a release, for matching them to pull requests
+#[derive(Debug, Clone)]
+pub struct ChangeRange {
+    /// Full hashes of the commits since the previous tag
+    pub commits: HashSet<String>,
+    /// Commit date of the previous tag (`None` for a first release)
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Commits between the previous tag matching `tag_pattern` and `tag`
+pub fn change_range(repo: &Path, tag: &str, tag_pattern: &str) -> Result<ChangeRange> {
+    let previous = previous_tag(repo, tag, tag_pattern);
+    let range = match &previous {
+        Some(previous) => format!("{}..{}", previous, tag),
+        None => tag.to_string(),
+    };
+    let commits = git(repo, &["rev-list", &range])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let since = match &previous {
+        Some(previous) => chrono::DateTime::parse_from_rfc3339(git(repo, &["log", "-1", "--format=%cI", previous])?.trim())
+            .ok()
+            .map(|date| date.with_timezone(&chrono::Utc)),
+        None => None,
+    };
+    Ok(ChangeRange { commits, since })
+}
+
+/// A merged pull request listed in the release notes
+#[derive(Debug, Clone)]
+pub struct PullRequestNote {
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    /// GitHub login of the author
+    pub author: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl PullRequestNote {
+    /// Section index: breaking changes, features, fixes, other
+    ///
+    /// Labels decide first (`breaking`, `feature`/`enhancement`, `fix`/`bug`);
+    /// otherwise a conventional title prefix (`feat:`, `fix(x):`, `feat!:`).
+    fn section(&self) -> usize {
+        let labels: Vec<String> = self.labels.iter().map(|l| l.to_ascii_lowercase()).collect();
+        let labeled = |words: &[&str]| labels.iter().any(|l| words.iter().any(|w| l.contains(w)));
+        if labeled(&["breaking"]) {
+            return 0;
+        }
+        if labeled(&["feature", "enhancement"]) {
+            return 1;
+        }
+        if labeled(&["fix", "bug"]) {
+            return 2;
+        }
+
+        let prefix = self
+            .title
+            .split_once(':')
+            .map(|(prefix, _)| prefix.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        match prefix {
+            p if p.ends_with('!') => 0,
+            p if p.starts_with("feat") => 1,
+            p if p.starts_with("fix") => 2,
+            _ => 3,
+        }
+    }
+}
+
+/// Changelog from merged pull requests, grouped into sections
+///
+/// Each entry links the pull request and names its author. Returns `None`
+/// without pull requests.
+pub fn pull_request_sections(pull_requests: &[PullRequestNote]) -> Option<String> {
+    const HEADINGS: [&str; 4] = ["Breaking Changes", "Features", "Fixes", "Other changes"];
+
+    let mut sorted: Vec<&PullRequestNote> = pull_requests.iter().collect();
+    sorted.sort_by_key(|pr| pr.number);
+    let mut sections: [Vec<String>; 4] = Default::default();
+    for pr in sorted {
+        let mut entry = format!("- {} ([#{}]({}))", pr.title, pr.number, pr.url);
+        if let Some(author) = &pr.author {
+            entry.push_str(&format!(" by @{}", author));
+        }
+        sections[pr.section()].push(entry);
+    }
+
+    let blocks: Vec<String> = HEADINGS
+        .iter()
+        .zip(&sections)
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(heading, entries)| format!("## {}\n\n{}", heading, entries.join("\n")))
+        .collect();
+    (!blocks.is_empty()).then(|| blocks.join("\n\n"))
+}
+
+/// Latest tag matching `tag_pattern` before `tag` (`None` for a first release)
+fn previous_tag(repo: &Path, tag: &str, tag_pattern: &str) -> Option<String> {
+    let parent = format!("{}^", tag);
+    git(repo, &["describe", "--tags", "--abbrev=0", "--match", tag_pattern, &parent])
+        .ok()
+        .map(|previous| previous.trim().to_string())
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<String> {
+    let output = Exec::new("git").args(args).current_dir(repo).run_blocking()?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Locales with a `<locale>.md` template, sorted
+fn discover_locales(dir: &Path, versioned: &Path) -> Vec<String> {
+    let mut locales = Vec::new();
+    for dir in [versioned, dir] {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file()
+                && path.extension().is_some_and(|ext| ext == "md")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                && !locales.iter().any(|l| l == stem)
+            {
+                locales.push(stem.to_string());
+            }
+        }
+    }
+    locales.sort();
+    locales
+}
+
+/// Heading for a locale: the language's own name where known, else the code
+fn language_name(locale: &str) -> String {
+    let name = match locale.to_ascii_lowercase().replace('_', "-").as_str() {
+        "en" | "en-us" | "en-gb" => "English",
+        "de" => "Deutsch",
+        "es" => "Español",
+        "fr" => "Français",
+        "it" => "Italiano",
+        "ja" => "日本語",
+        "ko" => "한국어",
+        "nl" => "Nederlands",
+        "pl" => "Polski",
+        "pt" | "pt-pt" => "Português",
+        "pt-br" => "Português (Brasil)",
+        "ru" => "Русский",
+        "tr" => "Türkçe",
+        "uk" => "Українська",
+        "zh" | "zh-cn" | "zh-hans" => "简体中文",
+        "zh-tw" | "zh-hant" => "繁體中文",
+        _ => return locale.to_string(),
+    };
+    name.to_string()
+}
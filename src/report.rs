@@ -0,0 +1,423 @@
+//! Human-readable report of a release run.
+//!
+//! Rendered from the run journal once the run ends, successfully or not:
+//! phase timings, test, lint, advisory and policy results, the uploaded artifacts
+//! with sizes, checksums and download URLs, mirror uploads, crates verified
+//! on crates.io, the Mac App Store pkg and Microsoft Store submission,
+//! warnings, and cleanup actions such as deleted drafts. Written as Markdown (`--report`) and/or HTML (`--report-html`), and
+//! optionally attached to the GitHub release.
+
+use crate::cli::RunJournal;
+use crate::cli::timeouts::format_duration;
+use crate::util::format_bytes;
+use std::fmt::Write;
+
+/// Asset name of the Markdown report on the GitHub release
+pub const REPORT_FILE_NAME: &str = "release-report.md";
+
+/// Asset name of the HTML report on the GitHub release
+pub const REPORT_HTML_FILE_NAME: &str = "release-report.html";
+
+/// Table cell, optionally linked
+struct Cell {
+    text: String,
+    link: Option<String>,
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self { text, link: None }
+    }
+}
+
+/// Content of a report section
+enum Block {
+    Table { headers: &'static [&'static str], rows: Vec<Vec<Cell>> },
+    List(Vec<String>),
+    Text(String),
+}
+
+/// Report of one run
+pub struct ReleaseReport {
+    title: String,
+    summary: Vec<(&'static str, Cell)>,
+    sections: Vec<(&'static str, Block)>,
+}
+
+impl ReleaseReport {
+    /// Build the report from the journal
+    ///
+    /// `subject` names the run when no release was created (the repository
+    /// source); `failure` is the error the run ended with, if any.
+    pub fn new(journal: &RunJournal, subject: &str, failure: Option<&str>) -> Self {
+        let manifest = journal.manifest();
+        let title = match manifest {
+            Some(manifest) => format!("Release report: {} {}", manifest.package, manifest.tag),
+            None => format!("Release report: {}", subject),
+        };
+
+        let mut summary = vec![(
+            "Outcome",
+            Cell::from(match failure {
+                None => "Succeeded".to_string(),
+                Some(error) => format!("Failed: {}", error),
+            }),
+        )];
+        if let Some(manifest) = manifest {
+            summary.push(("Repository", Cell::from(manifest.repository.clone())));
+            summary.push((
+                "Release",
+                Cell {
+                    text: manifest.html_url.clone(),
+                    link: Some(manifest.html_url.clone()),
+                },
+            ));
+            summary.push(("Version", Cell::from(manifest.version.to_string())));
+        }
+        if let Some(freeze) = journal.freeze_override() {
+            summary.push(("Freeze override", Cell::from(freeze.to_string())));
+        }
+        let phases = journal.phases();
+        let total: std::time::Duration = phases.iter().map(|p| p.duration).sum();
+        summary.push(("Duration", Cell::from(format_duration(total))));
+        summary.push(("Generated", Cell::from(chrono::Utc::now().to_rfc3339())));
+
+        let mut sections = vec![(
+            "Phases",
+            Block::Table {
+                headers: &["Phase", "Duration"],
+                rows: phases
+                    .iter()
+                    .map(|phase| vec![Cell::from(phase.name.clone()), Cell::from(format_duration(phase.duration))])
+                    .collect(),
+            },
+        )];
+
+        if let Some(tests) = journal.tests() {
+            sections.push((
+                "Tests",
+                Block::Table {
+                    headers: &["Command", "Passed", "Failed", "Ignored", "Source"],
+                    rows: vec![vec![
+                        Cell::from(tests.command.clone()),
+                        Cell::from(tests.passed.to_string()),
+                        Cell::from(tests.failed.to_string()),
+                        Cell::from(tests.ignored.to_string()),
+                        Cell::from(tests.source.clone()),
+                    ]],
+                },
+            ));
+            if !tests.failures.is_empty() {
+                sections.push(("Failed tests", Block::List(tests.failures.clone())));
+            }
+        }
+
+        let lints = journal.lints();
+        if !lints.is_empty() {
+            sections.push((
+                "Lints",
+                Block::Table {
+                    headers: &["Crate", "Gate", "Result", "Details"],
+                    rows: lints
+                        .iter()
+                        .map(|lint| {
+                            let result = match (lint.passed, lint.advisory) {
+                                (true, _) => "passed",
+                                (false, true) => "failed (advisory)",
+                                (false, false) => "failed",
+                            };
+                            vec![
+                                Cell::from(lint.package.clone()),
+                                Cell::from(lint.gate.to_string()),
+                                Cell::from(result.to_string()),
+                                Cell::from(lint.detail.clone()),
+                            ]
+                        })
+                        .collect(),
+                },
+            ));
+        }
+
+        let advisories = journal.advisories();
+        if !advisories.is_empty() {
+            sections.push((
+                "Security advisories",
+                Block::Table {
+                    headers: &["Advisory", "Crate", "Patched", "Result", "Notes"],
+                    rows: advisories
+                        .iter()
+                        .map(|advisory| {
+                            vec![
+                                Cell::from(format!("{}: {}", advisory.id, advisory.title)),
+                                Cell::from(format!("{} {}", advisory.package, advisory.version)),
+                                Cell::from(if advisory.patched.is_empty() {
+                                    "none".to_string()
+                                } else {
+                                    advisory.patched.join(", ")
+                                }),
+                                Cell::from(advisory.status.to_string()),
+                                Cell::from(advisory.note.clone()),
+                            ]
+                        })
+                        .collect(),
+                },
+            ));
+        }
+
+        let policy = journal.policy_findings();
+        if !policy.is_empty() {
+            sections.push((
+                "Dependency policy",
+                Block::Table {
+                    headers: &["Crate", "Violation", "Result"],
+                    rows: policy
+                        .iter()
+                        .map(|finding| {
+                            vec![
+                                Cell::from(format!("{} {}", finding.package, finding.version)),
+                                Cell::from(format!("{} ({})", finding.message, finding.code)),
+                                Cell::from(finding.status.to_string()),
+                            ]
+                        })
+                        .collect(),
+                },
+            ));
+        }
+
+        let artifacts = manifest.map(|m| m.artifacts.as_slice()).unwrap_or_default();
+        sections.push((
+            "Artifacts",
+            if artifacts.is_empty() {
+                Block::Text("No artifacts were uploaded.".to_string())
+            } else {
+                Block::Table {
+                    headers: &["Artifact", "Platform", "Size", "SHA-256"],
+                    rows: artifacts
+                        .iter()
+                        .map(|artifact| {
+                            vec![
+                                Cell {
+                                    text: artifact.name.clone(),
+                                    link: Some(artifact.download_url.clone()),
+                                },
+                                Cell::from(artifact.platform.clone()),
+                                Cell::from(format_bytes(artifact.size)),
+                                Cell::from(artifact.sha256.clone()),
+                            ]
+                        })
+                        .collect(),
+                }
+            },
+        ));
+
+        let mirrors = manifest.map(|m| m.mirrors.as_slice()).unwrap_or_default();
+        if !mirrors.is_empty() {
+            sections.push((
+                "Mirrors",
+                Block::Table {
+                    headers: &["Mirror", "Destination", "Status", "Uploaded", "Failed"],
+                    rows: mirrors
+                        .iter()
+                        .map(|mirror| {
+                            vec![
+                                Cell::from(mirror.name.clone()),
+                                Cell::from(mirror.destination.clone()),
+                                Cell::from(mirror.status.clone()),
+                                Cell::from(mirror.artifacts.len().to_string()),
+                                Cell::from(if mirror.failures.is_empty() {
+                                    "none".to_string()
+                                } else {
+                                    mirror
+                                        .failures
+                                        .iter()
+                                        .map(|failure| format!("{}: {}", failure.name, failure.error))
+                                        .collect::<Vec<_>>()
+                                        .join("; ")
+                                }),
+                            ]
+                        })
+                        .collect(),
+                },
+            ));
+        }
+
+        let crates = manifest.map(|m| m.crates_io.as_slice()).unwrap_or_default();
+        if !crates.is_empty() {
+            sections.push((
+                "Published crates",
+                Block::Table {
+                    headers: &["Crate", "Version", "Visible on crates.io"],
+                    rows: crates
+                        .iter()
+                        .map(|published| {
+                            vec![
+                                Cell {
+                                    text: published.name.clone(),
+                                    link: Some(format!(
+                                        "https://crates.io/crates/{}/{}",
+                                        published.name, published.version
+                                    )),
+                                },
+                                Cell::from(published.version.clone()),
+                                Cell::from(published.verified_at.to_rfc3339()),
+                            ]
+                        })
+                        .collect(),
+                },
+            ));
+        }
+
+        if let Some(package) = manifest.and_then(|m| m.mac_app_store.as_ref()) {
+            sections.push((
+                "Mac App Store",
+                Block::Table {
+                    headers: &["Package", "Size", "SHA-256", "Uploaded"],
+                    rows: vec![vec![
+                        Cell::from(package.name.clone()),
+                        Cell::from(format_bytes(package.size)),
+                        Cell::from(package.sha256.clone()),
+                        Cell::from(
+                            package
+                                .uploaded_at
+                                .map(|at| at.to_rfc3339())
+                                .unwrap_or_else(|| "not uploaded".to_string()),
+                        ),
+                    ]],
+                },
+            ));
+        }
+
+        if let Some(store) = manifest.and_then(|m| m.microsoft_store.as_ref()) {
+            sections.push((
+                "Microsoft Store",
+                Block::Table {
+                    headers: &["Product", "Submission", "Packages", "Status"],
+                    rows: vec![vec![
+                        Cell::from(store.app_id.clone()),
+                        Cell::from(store.submission_id.clone()),
+                        Cell::from(store.packages.join(", ")),
+                        Cell::from(store.status.clone()),
+                    ]],
+                },
+            ));
+        }
+
+        sections.push(("Warnings", list_or_none(journal.warnings())));
+        sections.push(("Cleanup actions", list_or_none(journal.actions())));
+
+        Self {
+            title,
+            summary,
+            sections,
+        }
+    }
+
+    /// Render as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title);
+        for (label, cell) in &self.summary {
+            let _ = writeln!(out, "- **{}:** {}", label, markdown_cell(cell));
+        }
+
+        for (heading, block) in &self.sections {
+            let _ = write!(out, "\n## {}\n\n", heading);
+            match block {
+                Block::Table { headers, rows } => {
+                    let _ = writeln!(out, "| {} |", headers.join(" | "));
+                    let _ = writeln!(out, "|{}", "---|".repeat(headers.len()));
+                    for row in rows {
+                        let cells: Vec<String> = row.iter().map(markdown_cell).collect();
+                        let _ = writeln!(out, "| {} |", cells.join(" | "));
+                    }
+                }
+                Block::List(items) => {
+                    for item in items {
+                        let _ = writeln!(out, "- {}", item.replace('\n', " "));
+                    }
+                }
+                Block::Text(text) => {
+                    let _ = writeln!(out, "{}", text);
+                }
+            }
+        }
+        out
+    }
+
+    /// Render as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+             <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse}}\
+             th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+             </head>\n<body>\n<h1>{0}</h1>\n<ul>\n",
+            escape_html(&self.title)
+        );
+        for (label, cell) in &self.summary {
+            let _ = writeln!(out, "<li><strong>{}:</strong> {}</li>", label, html_cell(cell));
+        }
+        out.push_str("</ul>\n");
+
+        for (heading, block) in &self.sections {
+            let _ = writeln!(out, "<h2>{}</h2>", heading);
+            match block {
+                Block::Table { headers, rows } => {
+                    out.push_str("<table>\n<tr>");
+                    for header in *headers {
+                        let _ = write!(out, "<th>{}</th>", header);
+                    }
+                    out.push_str("</tr>\n");
+                    for row in rows {
+                        out.push_str("<tr>");
+                        for cell in row {
+                            let _ = write!(out, "<td>{}</td>", html_cell(cell));
+                        }
+                        out.push_str("</tr>\n");
+                    }
+                    out.push_str("</table>\n");
+                }
+                Block::List(items) => {
+                    out.push_str("<ul>\n");
+                    for item in items {
+                        let _ = writeln!(out, "<li>{}</li>", escape_html(item));
+                    }
+                    out.push_str("</ul>\n");
+                }
+                Block::Text(text) => {
+                    let _ = writeln!(out, "<p>{}</p>", escape_html(text));
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn list_or_none(items: &[String]) -> Block {
+    if items.is_empty() {
+        Block::Text("None.".to_string())
+    } else {
+        Block::List(items.to_vec())
+    }
+}
+
+fn markdown_cell(cell: &Cell) -> String {
+    let text = cell.text.trim().replace('|', "\\|").replace('\n', " ");
+    match &cell.link {
+        Some(link) => format!("[{}]({})", text, link),
+        None => text,
+    }
+}
+
+fn html_cell(cell: &Cell) -> String {
+    match &cell.link {
+        Some(link) => format!("<a href=\"{}\">{}</a>", escape_html(link), escape_html(&cell.text)),
+        None => escape_html(&cell.text),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
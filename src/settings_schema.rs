@@ -0,0 +1,429 @@
+//! Strict schema checks of `[package.metadata.bundle]` and `release.toml`.
+//!
+//! The bundler reads its settings only when it packages a platform, and
+//! ignores keys it does not know: a misspelled `short_desription` or a
+//! `depends = "libc6"` that should be an array is silently dropped, and a
+//! missing script or template fails inside a platform tool after the build.
+//! `config validate` checks both files against the schema below and reports
+//! every problem with its line and column:
+//!
+//! - keys the bundler or the release tool does not know;
+//! - values of the wrong type, or outside a fixed set of choices;
+//! - referenced files (icons, scripts, templates, licenses) that do not exist,
+//!   relative to the package directory.
+//!
+//! The schema follows the bundler's settings; a key it gains has to be added
+//! here too.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+use toml::de::{DeTable, DeValue};
+
+/// Expected shape of a settings value
+enum Kind {
+    String,
+    Bool,
+    Integer,
+    /// Array of strings
+    Strings,
+    /// One of a fixed set of strings
+    Choice(&'static [&'static str]),
+    /// Path of an existing file, relative to the package directory
+    File,
+    /// Array of paths, each matching at least one file (`*` / `?` in the file name)
+    Files,
+    /// Table with exactly these keys (all optional)
+    Table(&'static [Field]),
+    /// Array of tables with exactly these keys (`[[name]]`)
+    Tables(&'static [Field]),
+    /// Table with arbitrary keys whose values are all of one kind
+    Map(&'static Kind),
+}
+
+struct Field {
+    key: &'static str,
+    kind: Kind,
+}
+
+const fn field(key: &'static str, kind: Kind) -> Field {
+    Field { key, kind }
+}
+
+/// `files`: destination path in the package -> source file
+const PACKAGE_FILES: Kind = Kind::Map(&Kind::File);
+
+const POSITION: &[Field] = &[field("x", Kind::Integer), field("y", Kind::Integer)];
+
+const DEB: &[Field] = &[
+    field("depends", Kind::Strings),
+    field("recommends", Kind::Strings),
+    field("provides", Kind::Strings),
+    field("conflicts", Kind::Strings),
+    field("replaces", Kind::Strings),
+    field("section", Kind::String),
+    field("priority", Kind::Choice(&["required", "important", "standard", "optional", "extra"])),
+    field("files", PACKAGE_FILES),
+    field("desktop_template", Kind::File),
+    field("changelog", Kind::File),
+    field("pre_install_script", Kind::File),
+    field("post_install_script", Kind::File),
+    field("pre_remove_script", Kind::File),
+    field("post_remove_script", Kind::File),
+];
+
+const RPM: &[Field] = &[
+    field("requires", Kind::Strings),
+    field("recommends", Kind::Strings),
+    field("provides", Kind::Strings),
+    field("conflicts", Kind::Strings),
+    field("obsoletes", Kind::Strings),
+    field("release", Kind::String),
+    field("epoch", Kind::Integer),
+    field("files", PACKAGE_FILES),
+    field("desktop_template", Kind::File),
+    field("pre_install_script", Kind::File),
+    field("post_install_script", Kind::File),
+    field("pre_remove_script", Kind::File),
+    field("post_remove_script", Kind::File),
+];
+
+const APPIMAGE: &[Field] = &[
+    field("bundle_media_framework", Kind::Bool),
+    field("files", PACKAGE_FILES),
+];
+
+const LINUX: &[Field] = &[
+    field("deb", Kind::Table(DEB)),
+    field("rpm", Kind::Table(RPM)),
+    field("appimage", Kind::Table(APPIMAGE)),
+];
+
+const DMG: &[Field] = &[
+    field("background", Kind::File),
+    field("window_size", Kind::Table(&[field("width", Kind::Integer), field("height", Kind::Integer)])),
+    field("window_position", Kind::Table(POSITION)),
+    field("app_position", Kind::Table(POSITION)),
+    field("application_folder_position", Kind::Table(POSITION)),
+];
+
+const MACOS: &[Field] = &[
+    field("frameworks", Kind::Strings),
+    field("minimum_system_version", Kind::String),
+    field("exception_domain", Kind::String),
+    field("license", Kind::File),
+    field("entitlements", Kind::File),
+    field("info_plist", Kind::File),
+    field("signing_identity", Kind::String),
+    field("provider_short_name", Kind::String),
+    field("hardened_runtime", Kind::Bool),
+    field("files", PACKAGE_FILES),
+    field("dmg", Kind::Table(DMG)),
+];
+
+const WIX: &[Field] = &[
+    field("template", Kind::File),
+    field("fragment_paths", Kind::Files),
+    field("language", Kind::Strings),
+    field("upgrade_code", Kind::String),
+    field("license", Kind::File),
+    field("banner_path", Kind::File),
+    field("dialog_image_path", Kind::File),
+];
+
+const NSIS: &[Field] = &[
+    field("template", Kind::File),
+    field("license", Kind::File),
+    field("installer_icon", Kind::File),
+    field("header_image", Kind::File),
+    field("sidebar_image", Kind::File),
+    field("install_mode", Kind::Choice(&["currentUser", "perMachine", "both"])),
+    field("languages", Kind::Strings),
+];
+
+const MSIX: &[Field] = &[
+    field("publisher", Kind::String),
+    field("publisher_display_name", Kind::String),
+    field("capabilities", Kind::Strings),
+    field("logo", Kind::File),
+];
+
+const WINDOWS: &[Field] = &[
+    field("digest_algorithm", Kind::Choice(&["sha256", "sha384", "sha512"])),
+    field("certificate_thumbprint", Kind::String),
+    field("timestamp_url", Kind::String),
+    field("tsp", Kind::Bool),
+    field("wix", Kind::Table(WIX)),
+    field("nsis", Kind::Table(NSIS)),
+    field("msix", Kind::Table(MSIX)),
+];
+
+/// `[package.metadata.bundle]`
+const BUNDLE: &[Field] = &[
+    field("name", Kind::String),
+    field("identifier", Kind::String),
+    field("publisher", Kind::String),
+    field("version", Kind::String),
+    field("icon", Kind::Files),
+    field("resources", Kind::Files),
+    field("copyright", Kind::String),
+    field("category", Kind::String),
+    field("short_description", Kind::String),
+    field("long_description", Kind::String),
+    field("homepage", Kind::String),
+    field("license_file", Kind::File),
+    field("external_bin", Kind::Strings),
+    field("linux", Kind::Table(LINUX)),
+    field("macos", Kind::Table(MACOS)),
+    field("windows", Kind::Table(WINDOWS)),
+];
+
+const PRERELEASE: Kind = Kind::Choice(&["zero-major", "pre-tag", "always", "never"]);
+
+/// `[profile.<name>]` in `release.toml`
+const PROFILE: &[Field] = &[
+    field("repository", Kind::String),
+    field("draft", Kind::Bool),
+    field("sign", Kind::Bool),
+    field("prerelease", PRERELEASE),
+];
+
+/// `[[freeze]]` in `release.toml`
+const FREEZE: &[Field] = &[
+    field("from", Kind::String),
+    field("until", Kind::String),
+    field("reason", Kind::String),
+];
+
+/// `release.toml`
+const RELEASE_TOML: &[Field] = &[
+    field("prerelease", PRERELEASE),
+    field("profile", Kind::Map(&Kind::Table(PROFILE))),
+    field("freeze", Kind::Tables(FREEZE)),
+];
+
+/// A problem found in a settings file
+#[derive(Debug, Clone)]
+pub struct SettingsProblem {
+    pub file: PathBuf,
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SettingsProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}: {}", self.file.display(), self.line, self.column, self.message)
+    }
+}
+
+/// Check the bundle settings in `package_dir/Cargo.toml` and, if present,
+/// `release.toml` in `repo_dir`
+///
+/// Returns every problem found, in file order; unreadable files are problems too.
+pub fn validate_settings(package_dir: &Path, repo_dir: &Path) -> Vec<SettingsProblem> {
+    let mut problems = Vec::new();
+
+    let cargo_toml = package_dir.join("Cargo.toml");
+    match std::fs::read_to_string(&cargo_toml) {
+        Ok(content) => {
+            let mut checker = Checker::new(&cargo_toml, &content, package_dir);
+            if let Some(root) = checker.parse() {
+                let bundle = root
+                    .get_ref()
+                    .get("package")
+                    .and_then(|package| table(package.get_ref()))
+                    .and_then(|package| package.get("metadata"))
+                    .and_then(|metadata| table(metadata.get_ref()))
+                    .and_then(|metadata| metadata.get("bundle"));
+                if let Some(bundle) = bundle {
+                    checker.check(bundle, &Kind::Table(BUNDLE), "package.metadata.bundle");
+                }
+            }
+            problems.append(&mut checker.finish());
+        }
+        Err(e) => problems.push(SettingsProblem {
+            file: cargo_toml,
+            line: 1,
+            column: 1,
+            message: format!("cannot be read: {}", e),
+        }),
+    }
+
+    let release_toml = repo_dir.join(crate::profile::PROFILE_FILE);
+    if let Ok(content) = std::fs::read_to_string(&release_toml) {
+        let mut checker = Checker::new(&release_toml, &content, repo_dir);
+        if let Some(root) = checker.parse() {
+            checker.check_table(root.get_ref(), RELEASE_TOML, "");
+        }
+        problems.append(&mut checker.finish());
+    }
+
+    problems
+}
+
+fn table<'a, 'i>(value: &'a DeValue<'i>) -> Option<&'a DeTable<'i>> {
+    match value {
+        DeValue::Table(table) => Some(table),
+        _ => None,
+    }
+}
+
+/// Walks one file, collecting problems with their positions
+struct Checker<'a> {
+    file: &'a Path,
+    content: &'a str,
+    /// Base directory of referenced files
+    base_dir: &'a Path,
+    problems: Vec<SettingsProblem>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(file: &'a Path, content: &'a str, base_dir: &'a Path) -> Self {
+        Self {
+            file,
+            content,
+            base_dir,
+            problems: Vec::new(),
+        }
+    }
+
+    /// Parse the file; a syntax error is reported at its position
+    fn parse(&mut self) -> Option<Spanned<DeTable<'a>>> {
+        match DeTable::parse(self.content) {
+            Ok(root) => Some(root),
+            Err(e) => {
+                let offset = e.span().map(|span| span.start).unwrap_or(0);
+                self.report(offset, format!("invalid TOML: {}", e.message()));
+                None
+            }
+        }
+    }
+
+    /// Problems found, by position (keys are walked in sorted order)
+    fn finish(mut self) -> Vec<SettingsProblem> {
+        self.problems.sort_by_key(|problem| (problem.line, problem.column));
+        self.problems
+    }
+
+    fn report(&mut self, offset: usize, message: String) {
+        let before = &self.content[..offset.min(self.content.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.problems.push(SettingsProblem {
+            file: self.file.to_path_buf(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message,
+        });
+    }
+
+    fn check_table(&mut self, table: &DeTable<'_>, fields: &[Field], path: &str) {
+        for (key, value) in table.iter() {
+            let key_path = join(path, key.get_ref());
+            match fields.iter().find(|field| field.key == key.get_ref()) {
+                Some(field) => self.check(value, &field.kind, &key_path),
+                None => {
+                    let expected: Vec<&str> = fields.iter().map(|field| field.key).collect();
+                    self.report(
+                        key.span().start,
+                        format!("unknown key `{}` (expected one of: {})", key_path, expected.join(", ")),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, value: &Spanned<DeValue<'_>>, kind: &Kind, path: &str) {
+        let offset = value.span().start;
+        match (kind, value.get_ref()) {
+            (Kind::String, DeValue::String(_))
+            | (Kind::Bool, DeValue::Boolean(_))
+            | (Kind::Integer, DeValue::Integer(_)) => {}
+            (Kind::Strings, DeValue::Array(items)) => {
+                for item in items {
+                    self.check(item, &Kind::String, path);
+                }
+            }
+            (Kind::Choice(choices), DeValue::String(s)) => {
+                if !choices.contains(&s.as_ref()) {
+                    self.report(
+                        offset,
+                        format!("`{}` is \"{}\", expected one of: {}", path, s, choices.join(", ")),
+                    );
+                }
+            }
+            (Kind::File, DeValue::String(s)) => {
+                if !self.base_dir.join(s.as_ref()).exists() {
+                    self.report(offset, format!("`{}` refers to {}, which does not exist", path, s));
+                }
+            }
+            (Kind::Files, DeValue::Array(items)) => {
+                for item in items {
+                    match item.get_ref() {
+                        DeValue::String(s) => {
+                            if crate::icons::matching_files(self.base_dir, s).is_empty() {
+                                self.report(item.span().start, format!("`{}` entry {} matches no file", path, s));
+                            }
+                        }
+                        other => self.mismatch(item.span().start, path, "a path", other),
+                    }
+                }
+            }
+            (Kind::Table(fields), DeValue::Table(table)) => self.check_table(table, fields, path),
+            (Kind::Tables(fields), DeValue::Array(items)) => {
+                for item in items {
+                    match item.get_ref() {
+                        DeValue::Table(table) => self.check_table(table, fields, path),
+                        other => self.mismatch(item.span().start, path, "a table", other),
+                    }
+                }
+            }
+            (Kind::Map(kind), DeValue::Table(table)) => {
+                for (key, value) in table.iter() {
+                    self.check(value, kind, &join(path, key.get_ref()));
+                }
+            }
+            (kind, other) => self.mismatch(offset, path, kind.describe(), other),
+        }
+    }
+
+    fn mismatch(&mut self, offset: usize, path: &str, expected: &str, found: &DeValue<'_>) {
+        let found = match found {
+            DeValue::String(_) => "a string",
+            DeValue::Integer(_) => "an integer",
+            DeValue::Float(_) => "a float",
+            DeValue::Boolean(_) => "a boolean",
+            DeValue::Datetime(_) => "a datetime",
+            DeValue::Array(_) => "an array",
+            DeValue::Table(_) => "a table",
+        };
+        self.report(offset, format!("`{}` must be {}, found {}", path, expected, found));
+    }
+}
+
+impl Kind {
+    fn describe(&self) -> &'static str {
+        match self {
+            Kind::String => "a string",
+            Kind::Bool => "true or false",
+            Kind::Integer => "an integer",
+            Kind::Strings => "an array of strings",
+            Kind::Choice(_) => "a string",
+            Kind::File => "a file path",
+            Kind::Files => "an array of paths",
+            Kind::Table(_) | Kind::Map(_) => "a table",
+            Kind::Tables(_) => "an array of tables",
+        }
+    }
+}
+
+/// Dotted key path, e.g. `package.metadata.bundle.linux.deb`
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
@@ -54,7 +54,7 @@ impl RepositorySource {
     }
 
     /// Resolve to local path (clone if GitHub)
-    pub async fn resolve(&self) -> Result<ResolvedRepo> {
+    pub async fn resolve(&self, auth: &crate::git_auth::GitAuth) -> Result<ResolvedRepo> {
         match self {
             Self::Local(path) => Ok(ResolvedRepo {
                 path: path.clone(),
@@ -72,31 +72,15 @@ impl RepositorySource {
                     .as_secs();
 
                 let temp_dir = std::env::temp_dir().join(format!("kodegen-release-{}", timestamp));
-                let remote_url = format!("git@github.com:{}/{}.git", owner, repo);
+                let ssh_url = format!("git@github.com:{}/{}.git", owner, repo);
+                let remote_url = auth.rewrite_url(&ssh_url, owner, repo).unwrap_or(ssh_url);
 
                 // Clone using git command
-                let output = tokio::process::Command::new("git")
-                    .args([
-                        "clone",
-                        "--single-branch",
-                        &remote_url,
-                        temp_dir.to_str().unwrap(),
-                    ])
-                    .output()
-                    .await
-                    .map_err(|e| {
-                        ReleaseError::Cli(CliError::ExecutionFailed {
-                            command: "git clone".to_string(),
-                            reason: e.to_string(),
-                        })
-                    })?;
-
-                if !output.status.success() {
-                    return Err(ReleaseError::Cli(CliError::ExecutionFailed {
-                        command: "git clone".to_string(),
-                        reason: String::from_utf8_lossy(&output.stderr).to_string(),
-                    }));
-                }
+                auth.git()
+                    .args(["clone", "--single-branch", &remote_url])
+                    .arg(&temp_dir)
+                    .run()
+                    .await?;
 
                 Ok(ResolvedRepo {
                     path: temp_dir,
@@ -113,6 +97,16 @@ pub struct ResolvedRepo {
     pub is_temp: bool,
 }
 
+impl ResolvedRepo {
+    /// The repository's `.env` file, for local checkouts only
+    ///
+    /// A clone of a remote repository carries whatever its authors committed,
+    /// so its `.env` must not override endpoints or credentials.
+    pub fn dotenv_path(&self) -> Option<PathBuf> {
+        (!self.is_temp).then(|| self.path.join(".env"))
+    }
+}
+
 impl Drop for ResolvedRepo {
     fn drop(&mut self) {
         if !self.is_temp {
@@ -0,0 +1,27 @@
+//! Layout of a cargo sparse registry index.
+//!
+//! Shared by the post-publish check and the test harness's local registry.
+
+/// Path of a crate's file in the index (`1/a`, `2/ab`, `3/a/abc`, `ab/cd/abcd...`)
+pub(crate) fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::index_path;
+
+    #[test]
+    fn index_paths() {
+        assert_eq!(index_path("a"), "1/a");
+        assert_eq!(index_path("ab"), "2/ab");
+        assert_eq!(index_path("abc"), "3/a/abc");
+        assert_eq!(index_path("Serde_JSON"), "se/rd/serde_json");
+    }
+}
@@ -0,0 +1,51 @@
+//! Append-only audit log for destructive release operations.
+//!
+//! Entries are JSON lines in `<data dir>/kodegen_bundler_release/audit.jsonl`
+//! (e.g. `~/.local/share/...` on Linux). The log lives outside the release
+//! clone so it survives temp-clone cleanup.
+
+use crate::error::{CliError, ReleaseError, Result};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Audit log location
+pub fn audit_log_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("kodegen_bundler_release").join("audit.jsonl"))
+}
+
+/// Append an audit entry for `action`
+///
+/// The entry records the time, the local user and `details`. Returns the log
+/// path. Fails if the entry cannot be written, so callers can refuse to
+/// perform an unaudited operation.
+pub fn append_audit_entry(action: &str, details: serde_json::Value) -> Result<PathBuf> {
+    let path = audit_log_path().ok_or_else(|| {
+        ReleaseError::Cli(CliError::ExecutionFailed {
+            command: "audit_log".to_string(),
+            reason: "Could not determine the local data directory for the audit log".to_string(),
+        })
+    })?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    let entry = serde_json::json!({
+        "timestamp": chrono::Utc::now(),
+        "action": action,
+        "user": user,
+        "details": details,
+    });
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    file.sync_all()?;
+
+    Ok(path)
+}
@@ -1,16 +1,38 @@
 //! State management for release operations.
 
+mod audit;
 mod manager;
 mod release_state;
 
+pub use audit::append_audit_entry;
 pub use manager::{SaveStateResult, StateManager};
 pub use release_state::{ReleaseConfig, ReleasePhase, ReleaseState};
 
 use crate::error::Result;
 
 fn create_state_manager(temp_dir: &std::path::Path) -> Result<StateManager> {
-    let state_file = temp_dir.join(".cyrup_release_state.json");
-    StateManager::new(state_file)
+    StateManager::new(state_file_path(temp_dir))
+}
+
+/// Path of the state file in the given directory
+pub fn state_file_path(dir: &std::path::Path) -> std::path::PathBuf {
+    dir.join(".cyrup_release_state.json")
+}
+
+/// Directory that keeps the state of an aborted release of `package` at
+/// `version`, since the temp clone holding the state is deleted on exit
+pub fn aborted_state_dir(package: &str, version: &semver::Version) -> Result<std::path::PathBuf> {
+    let dir = kodegen_config::KodegenConfig::state_dir()
+        .map_err(|e| {
+            crate::error::ReleaseError::Cli(crate::error::CliError::ExecutionFailed {
+                command: "get_state_dir".to_string(),
+                reason: e.to_string(),
+            })
+        })?
+        .join("aborted_releases")
+        .join(format!("{}-{}", package, version));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
 }
 
 /// Save release state to the given temp directory
@@ -27,3 +49,18 @@ pub fn cleanup_release_state(temp_dir: &std::path::Path) -> Result<()> {
     let manager = create_state_manager(temp_dir)?;
     manager.cleanup_state()
 }
+
+/// Load the release state saved in the given temp directory, if there is one
+pub async fn load_release_state(temp_dir: &std::path::Path) -> Result<Option<ReleaseState>> {
+    let mut manager = create_state_manager(temp_dir)?;
+    if !manager.state_exists() {
+        return Ok(None);
+    }
+    Ok(Some(manager.load_state().await?.state))
+}
+
+/// Remove the state lock in the given temp directory, whoever holds it
+pub fn force_unlock_release_state(temp_dir: &std::path::Path) -> Result<()> {
+    let mut manager = create_state_manager(temp_dir)?;
+    manager.force_unlock()
+}
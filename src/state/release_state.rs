@@ -56,6 +56,31 @@ pub enum ReleasePhase {
     Failed,
 }
 
+impl ReleasePhase {
+    /// Phases that `--skip` / `--only` can select, in execution order
+    pub const SELECTABLE: [ReleasePhase; 5] = [
+        ReleasePhase::GitHubRelease,
+        ReleasePhase::Building,
+        ReleasePhase::Bundling,
+        ReleasePhase::Uploading,
+        ReleasePhase::GitHubPublish,
+    ];
+
+    /// Name used by `--skip` / `--only`
+    pub fn cli_name(self) -> &'static str {
+        match self {
+            ReleasePhase::Validation => "validation",
+            ReleasePhase::GitHubRelease => "github-release",
+            ReleasePhase::Building => "build",
+            ReleasePhase::Bundling => "bundles",
+            ReleasePhase::Uploading => "upload",
+            ReleasePhase::GitHubPublish => "github-publish",
+            ReleasePhase::Completed => "completed",
+            ReleasePhase::Failed => "failed",
+        }
+    }
+}
+
 /// Checkpoint in the release process
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReleaseCheckpoint {
@@ -154,6 +179,15 @@ impl ReleaseState {
         self.updated_at = chrono::Utc::now();
     }
 
+    /// Record that the run was stopped by `signal` after `current_phase`
+    ///
+    /// The marker is filed under [`ReleasePhase::Failed`], so it does not
+    /// count as completing the interrupted phase.
+    pub fn mark_aborted(&mut self, signal: &str) {
+        let data = serde_json::json!({ "signal": signal, "after_phase": self.current_phase });
+        self.add_checkpoint("aborted".to_string(), ReleasePhase::Failed, Some(data));
+    }
+
     /// Check if a specific phase has been completed
     pub fn has_completed(&self, phase: ReleasePhase) -> bool {
         self.checkpoints.iter().any(|cp| cp.phase == phase)
@@ -0,0 +1,318 @@
+//! Microsoft Store submissions through the Partner Center API.
+//!
+//! MSIX bundles of a release can be added to the Store product configured in
+//! `[package.metadata.release.store]`. The product's pending submission is
+//! used (or a new one is created from the last published submission), the
+//! packages are uploaded as one ZIP to the submission's blob URL, and the
+//! submission is committed for certification unless `commit = false`.
+//!
+//! Credentials come from an Azure AD application associated with the Partner
+//! Center account: `PARTNER_CENTER_TENANT_ID`, `PARTNER_CENTER_CLIENT_ID` and
+//! `PARTNER_CENTER_CLIENT_SECRET`.
+
+use crate::error::{CliError, ReleaseError, Result};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Partner Center submission API
+const API_BASE: &str = "https://manage.devcenter.microsoft.com/v1.0/my/applications";
+
+/// Resource the Azure AD token is requested for
+const API_RESOURCE: &str = "https://manage.devcenter.microsoft.com";
+
+/// Store product to submit MSIX packages to, read from
+/// `[package.metadata.release.store]`:
+///
+/// ```toml
+/// [package.metadata.release.store]
+/// app-id = "9NBLGGH4R315"  # Store ID of the product in Partner Center
+/// commit = false           # leave the submission pending (default: commit it)
+/// ```
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    /// Store ID of the product
+    pub app_id: String,
+    /// Commit the submission for certification after uploading
+    pub commit: bool,
+}
+
+impl StoreConfig {
+    pub fn from_package(package: &toml::Value) -> Result<Option<Self>> {
+        let Some(table) = package
+            .get("metadata")
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("store"))
+        else {
+            return Ok(None);
+        };
+
+        let invalid = |reason: &str| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release.store] {}", reason),
+            })
+        };
+
+        let app_id = table
+            .get("app-id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.trim().is_empty())
+            .ok_or_else(|| invalid("app-id must be the product's Store ID"))?
+            .trim()
+            .to_string();
+        let commit = match table.get("commit") {
+            None => true,
+            Some(value) => value.as_bool().ok_or_else(|| invalid("commit must be true or false"))?,
+        };
+
+        Ok(Some(Self { app_id, commit }))
+    }
+}
+
+/// Status of a submission after a commit
+#[derive(Debug, Clone)]
+pub struct SubmissionStatus {
+    /// `CommitStarted`, `PreProcessing`, `Certification`, `CommitFailed`, ...
+    pub status: String,
+    /// Error details reported by Partner Center
+    pub errors: Vec<String>,
+}
+
+/// Authenticated Partner Center client for one Store product
+pub struct PartnerCenterClient {
+    http: reqwest::Client,
+    token: String,
+    app_id: String,
+}
+
+impl PartnerCenterClient {
+    /// Get an Azure AD token with the client credentials from the environment
+    pub async fn connect(env_config: &crate::EnvConfig, app_id: &str) -> Result<Self> {
+        let var = |name: &str| {
+            env_config.get(name).ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: format!("{} is not set", name),
+                })
+            })
+        };
+        let tenant = var("PARTNER_CENTER_TENANT_ID")?;
+        let client_id = var("PARTNER_CENTER_CLIENT_ID")?;
+        let client_secret = var("PARTNER_CENTER_CLIENT_SECRET")?;
+
+        let http = reqwest::Client::builder()
+            .user_agent("kodegen_bundler_release")
+            .timeout(Duration::from_secs(300))
+            .build()
+            .map_err(api_error)?;
+
+        let response = http
+            .post(format!("https://login.microsoftonline.com/{}/oauth2/token", tenant))
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("resource", API_RESOURCE),
+            ])
+            .send()
+            .await
+            .map_err(api_error)?;
+        let body = json_response(response, "Azure AD token request").await?;
+        let token = body
+            .get("access_token")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| api_failure("Azure AD token response has no access_token"))?
+            .to_string();
+
+        Ok(Self {
+            http,
+            token,
+            app_id: app_id.to_string(),
+        })
+    }
+
+    /// The product's pending submission, or a new one
+    ///
+    /// Returns the submission and whether it was created.
+    pub async fn pending_or_new_submission(&self) -> Result<(Value, bool)> {
+        let application = self.request(reqwest::Method::GET, "", None).await?;
+        let pending = application
+            .get("pendingApplicationSubmission")
+            .and_then(|s| s.get("id"))
+            .and_then(|id| id.as_str());
+
+        match pending {
+            Some(id) => Ok((self.submission(id).await?, false)),
+            None => Ok((self.request(reqwest::Method::POST, "/submissions", None).await?, true)),
+        }
+    }
+
+    /// Fetch a submission
+    pub async fn submission(&self, id: &str) -> Result<Value> {
+        self.request(reqwest::Method::GET, &format!("/submissions/{}", id), None)
+            .await
+    }
+
+    /// Replace a submission with `submission`
+    pub async fn update_submission(&self, id: &str, submission: &Value) -> Result<()> {
+        self.request(reqwest::Method::PUT, &format!("/submissions/{}", id), Some(submission))
+            .await
+            .map(|_| ())
+    }
+
+    /// Upload the ZIP of new packages to a submission's `fileUploadUrl`
+    pub async fn upload_packages(&self, upload_url: &str, zip: Vec<u8>) -> Result<()> {
+        // The SAS token is returned unescaped; '+' would be read as a space
+        let response = self
+            .http
+            .put(upload_url.replace('+', "%2B"))
+            .header("x-ms-blob-type", "BlockBlob")
+            .body(zip)
+            .send()
+            .await
+            .map_err(api_error)?;
+        if !response.status().is_success() {
+            return Err(api_failure(&format!(
+                "package upload failed with HTTP {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Commit a submission for certification
+    pub async fn commit(&self, id: &str) -> Result<()> {
+        self.request(reqwest::Method::POST, &format!("/submissions/{}/commit", id), None)
+            .await
+            .map(|_| ())
+    }
+
+    /// Status of a committed submission
+    pub async fn status(&self, id: &str) -> Result<SubmissionStatus> {
+        let body = self
+            .request(reqwest::Method::GET, &format!("/submissions/{}/status", id), None)
+            .await?;
+        let errors = body
+            .get("statusDetails")
+            .and_then(|d| d.get("errors"))
+            .and_then(|e| e.as_array())
+            .map(|errors| {
+                errors
+                    .iter()
+                    .map(|error| {
+                        let field = |key: &str| error.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+                        format!("{}: {}", field("code"), field("details"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(SubmissionStatus {
+            status: body
+                .get("status")
+                .and_then(|s| s.as_str())
+                .unwrap_or("Unknown")
+                .to_string(),
+            errors,
+        })
+    }
+
+    async fn request(&self, method: reqwest::Method, path: &str, body: Option<&Value>) -> Result<Value> {
+        let operation = format!("{} {}", method, path_or_product(path));
+        let mut request = self
+            .http
+            .request(method, format!("{}/{}{}", API_BASE, self.app_id, path))
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request.send().await.map_err(api_error)?;
+        json_response(response, &operation).await
+    }
+}
+
+fn path_or_product(path: &str) -> &str {
+    if path.is_empty() { "product" } else { path }
+}
+
+/// Parse a JSON response, turning an error status into a readable failure
+async fn json_response(response: reqwest::Response, operation: &str) -> Result<Value> {
+    let status = response.status();
+    let text = response.text().await.map_err(api_error)?;
+    if !status.is_success() {
+        let detail: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+        let message = detail
+            .get("message")
+            .or_else(|| detail.get("error_description"))
+            .and_then(|m| m.as_str())
+            .unwrap_or(text.as_str());
+        return Err(api_failure(&format!("{} failed with HTTP {}: {}", operation, status, message.trim())));
+    }
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&text).map_err(|e| api_failure(&format!("{} returned invalid JSON: {}", operation, e)))
+}
+
+fn api_error(error: reqwest::Error) -> ReleaseError {
+    api_failure(&error.to_string())
+}
+
+fn api_failure(reason: &str) -> ReleaseError {
+    ReleaseError::Cli(CliError::ExecutionFailed {
+        command: "Partner Center".to_string(),
+        reason: reason.to_string(),
+    })
+}
+
+/// Uncompressed ZIP archive of `(name, contents)` entries
+///
+/// MSIX packages are already compressed, so entries are stored as-is.
+/// Archives over 4 GiB (ZIP64) are not supported.
+pub fn stored_zip(entries: &[(String, Vec<u8>)]) -> Vec<u8> {
+    // 1980-01-01, the earliest DOS date
+    const DOS_DATE: u16 = 0x21;
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, contents) in entries {
+        let mut crc = flate2::Crc::new();
+        crc.update(contents);
+        let offset = out.len() as u32;
+
+        // Fields shared by the local header and the central directory entry:
+        // version needed, flags, method, time, date, CRC, sizes, name length
+        let mut common = Vec::new();
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&DOS_DATE.to_le_bytes());
+        common.extend_from_slice(&crc.sum().to_le_bytes());
+        common.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+
+        out.extend_from_slice(b"PK\x03\x04");
+        out.extend_from_slice(&common);
+        out.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(contents);
+
+        central.extend_from_slice(b"PK\x01\x02");
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&common);
+        central.extend_from_slice(&[0; 12]); // extra, comment, disk, attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(b"PK\x05\x06");
+    out.extend_from_slice(&[0; 4]); // disk numbers
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    out
+}
@@ -0,0 +1,126 @@
+//! Throwaway git remote standing in for a GitHub repository.
+//!
+//! The remote is a bare repository at `<root>/<owner>/<repo>.git`. Working
+//! copies keep `https://github.com/<owner>/<repo>.git` as their origin, so
+//! the tool still detects the GitHub repository, and git is told through
+//! `GIT_CONFIG_*` variables ([`GitRemote::env`]) to fetch and push that URL
+//! (and its SSH form) from the bare repository instead.
+
+use super::TempDir;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Identity for commits made by the harness and by the tool under test
+pub(super) const GIT_IDENTITY: [(&str, &str); 4] = [
+    ("GIT_AUTHOR_NAME", "Release Harness"),
+    ("GIT_AUTHOR_EMAIL", "harness@example.com"),
+    ("GIT_COMMITTER_NAME", "Release Harness"),
+    ("GIT_COMMITTER_EMAIL", "harness@example.com"),
+];
+
+/// Bare repository served in place of `github.com/<owner>/<repo>`
+pub struct GitRemote {
+    root: TempDir,
+    owner: String,
+    repo: String,
+}
+
+impl GitRemote {
+    /// Create an empty bare repository whose default branch is `main`
+    pub fn new(owner: &str, repo: &str) -> io::Result<Self> {
+        let root = TempDir::new("remote")?;
+        let remote = Self {
+            root,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        };
+        std::fs::create_dir_all(remote.path())?;
+        git(&remote.path(), &["init", "--bare", "--quiet", "--initial-branch=main"])?;
+        Ok(remote)
+    }
+
+    /// The bare repository
+    pub fn path(&self) -> PathBuf {
+        self.root.path().join(&self.owner).join(format!("{}.git", self.repo))
+    }
+
+    /// Origin URL for working copies
+    pub fn url(&self) -> String {
+        format!("https://github.com/{}/{}.git", self.owner, self.repo)
+    }
+
+    /// Variables redirecting github.com remotes to the bare repository
+    pub fn env(&self) -> Vec<(String, String)> {
+        let base = format!("file://{}/", self.root.path().display());
+        let mut vars = vec![("GIT_CONFIG_COUNT".to_string(), "2".to_string())];
+        for (index, prefix) in ["https://github.com/", "git@github.com:"].into_iter().enumerate() {
+            vars.push((format!("GIT_CONFIG_KEY_{}", index), format!("url.{}.insteadOf", base)));
+            vars.push((format!("GIT_CONFIG_VALUE_{}", index), prefix.to_string()));
+        }
+        vars.extend(GIT_IDENTITY.iter().map(|(name, value)| (name.to_string(), value.to_string())));
+        vars
+    }
+
+    /// Create a working copy at `dir` with a minimal binary crate, and push it
+    ///
+    /// `extra_manifest` is appended to the generated Cargo.toml, e.g. a
+    /// `[package.metadata.release]` table.
+    pub fn seed_package(&self, dir: &Path, name: &str, version: &str, extra_manifest: &str) -> io::Result<()> {
+        std::fs::create_dir_all(dir.join("src"))?;
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"{version}\"\nedition = \"2021\"\n\
+                 license = \"MIT\"\ndescription = \"Release harness package\"\n\
+                 repository = \"https://github.com/{}/{}\"\n\n{extra_manifest}",
+                self.owner, self.repo
+            ),
+        )?;
+        std::fs::write(dir.join("src/main.rs"), "fn main() {\n    println!(\"hello\");\n}\n")?;
+        std::fs::write(dir.join(".gitignore"), "/target\n")?;
+
+        self.git_in(dir, &["init", "--quiet", "--initial-branch=main"])?;
+        self.git_in(dir, &["remote", "add", "origin", &self.url()])?;
+        self.git_in(dir, &["add", "-A"])?;
+        self.git_in(dir, &["commit", "--quiet", "-m", "Initial commit"])?;
+        self.git_in(dir, &["push", "--quiet", "-u", "origin", "main"])
+    }
+
+    /// Tags pushed to the remote
+    pub fn tags(&self) -> io::Result<Vec<String>> {
+        let output = git(&self.path(), &["tag", "--list"])?;
+        Ok(output.lines().map(String::from).collect())
+    }
+
+    /// Commit `rev` points to on the remote
+    pub fn rev_parse(&self, rev: &str) -> io::Result<String> {
+        git(&self.path(), &["rev-parse", rev]).map(|sha| sha.trim().to_string())
+    }
+
+    /// Run git in `dir` with the redirection in place
+    pub fn git_in(&self, dir: &Path, args: &[&str]) -> io::Result<()> {
+        let mut command = Command::new("git");
+        command.args(args).current_dir(dir).envs(self.env());
+        run(command).map(drop)
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(dir);
+    run(command)
+}
+
+/// Run `command`, failing on a non-zero exit, and return its stdout
+fn run(mut command: Command) -> io::Result<String> {
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{:?} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
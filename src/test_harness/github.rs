@@ -0,0 +1,592 @@
+//! In-memory stand-in for the GitHub releases API.
+//!
+//! Serves the endpoints a release uses, for one repository:
+//!
+//! - releases: create, list, get (by ID and by tag), update, delete;
+//! - release assets: upload, list, get/download, rename, delete;
+//! - closed pull requests, for release notes ([`MockGitHub::add_pull`]);
+//! - commit statuses ([`MockGitHub::statuses`]);
+//! - branches: list, delete, and comparison with a tag ([`MockGitHub::add_branch`]);
+//! - public downloads of uploaded assets (`browser_download_url`).
+//!
+//! As on GitHub, looking a release up by tag does not find drafts. Point the
+//! tool at it with `GITHUB_API_URL` ([`MockGitHub::env`]); any token works.
+
+use super::http::{self, Request, Response};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A release held by the mock
+#[derive(Debug, Clone)]
+pub struct MockRelease {
+    pub id: u64,
+    pub tag_name: String,
+    pub target_commitish: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub assets: Vec<MockAsset>,
+}
+
+/// An uploaded release asset
+#[derive(Debug, Clone)]
+pub struct MockAsset {
+    pub id: u64,
+    pub name: String,
+    pub label: Option<String>,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// A commit status posted to the mock
+#[derive(Debug, Clone)]
+pub struct MockStatus {
+    pub sha: String,
+    pub context: String,
+    pub state: String,
+    pub description: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    /// Oldest first
+    releases: Vec<MockRelease>,
+    pulls: Vec<serde_json::Value>,
+    /// In the order posted
+    statuses: Vec<MockStatus>,
+    /// Branch names with the number of commits beyond the same-named tag
+    branches: Vec<(String, u64)>,
+}
+
+/// Mock GitHub API for `owner/repo`, serving until the process exits
+#[derive(Clone)]
+pub struct MockGitHub {
+    addr: SocketAddr,
+    owner: String,
+    repo: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockGitHub {
+    /// Start the mock on a free local port
+    pub fn start(owner: &str, repo: &str) -> io::Result<Self> {
+        let listener = http::bind()?;
+        let mock = Self {
+            addr: listener.local_addr()?,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            state: Arc::new(Mutex::new(State::default())),
+        };
+        let handler = mock.clone();
+        http::serve(listener, Arc::new(move |request| handler.route(request)));
+        Ok(mock)
+    }
+
+    /// API root, the value of `GITHUB_API_URL`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Variables pointing the tool at this mock
+    pub fn env(&self) -> Vec<(String, String)> {
+        vec![("GITHUB_API_URL".to_string(), self.url())]
+    }
+
+    /// Releases, newest first (drafts included)
+    pub fn releases(&self) -> Vec<MockRelease> {
+        self.lock().releases.iter().rev().cloned().collect()
+    }
+
+    /// Release whose tag is `tag`, drafts included
+    pub fn release(&self, tag: &str) -> Option<MockRelease> {
+        self.lock().releases.iter().rev().find(|r| r.tag_name == tag).cloned()
+    }
+
+    /// Commit statuses, in the order posted
+    pub fn statuses(&self) -> Vec<MockStatus> {
+        self.lock().statuses.clone()
+    }
+
+    /// Add a published release of `tag` without assets
+    pub fn add_release(&self, tag: &str, prerelease: bool) -> u64 {
+        let mut state = self.lock();
+        state.next_id += 1;
+        let release = MockRelease {
+            id: state.next_id,
+            tag_name: tag.to_string(),
+            target_commitish: "main".to_string(),
+            name: Some(tag.to_string()),
+            body: None,
+            draft: false,
+            prerelease,
+            assets: Vec::new(),
+        };
+        state.releases.push(release);
+        state.next_id
+    }
+
+    /// Add asset `name` to release `release_id`; `None` if there is no such release
+    pub fn add_asset(&self, release_id: u64, name: &str, content: &[u8]) -> Option<u64> {
+        let mut state = self.lock();
+        state.next_id += 1;
+        let asset_id = state.next_id;
+        let release = state.releases.iter_mut().find(|r| r.id == release_id)?;
+        release.assets.push(MockAsset {
+            id: asset_id,
+            name: name.to_string(),
+            label: None,
+            content_type: "application/octet-stream".to_string(),
+            content: content.to_vec(),
+        });
+        Some(asset_id)
+    }
+
+    /// Add a branch with `commits_beyond_tag` commits its same-named tag lacks
+    pub fn add_branch(&self, name: &str, commits_beyond_tag: u64) {
+        self.lock().branches.push((name.to_string(), commits_beyond_tag));
+    }
+
+    /// Branch names
+    pub fn branches(&self) -> Vec<String> {
+        self.lock().branches.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Add a merged pull request whose merge commit is `merge_commit_sha`
+    pub fn add_pull(&self, title: &str, author: &str, labels: &[&str], merge_commit_sha: &str) -> u64 {
+        let now = now();
+        let mut state = self.lock();
+        let number = state.pulls.len() as u64 + 1;
+        let html_url = format!("https://github.com/{}/{}/pull/{}", self.owner, self.repo, number);
+        state.pulls.insert(
+            0,
+            serde_json::json!({
+                "number": number,
+                "title": title,
+                "html_url": html_url,
+                "user": { "login": author },
+                "labels": labels.iter().map(|name| serde_json::json!({ "name": name })).collect::<Vec<_>>(),
+                "updated_at": now,
+                "merged_at": now,
+                "merge_commit_sha": merge_commit_sha,
+            }),
+        );
+        number
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        let segments = request.segments();
+        let method = request.method.as_str();
+        let repo = [self.owner.as_str(), self.repo.as_str()];
+
+        match segments.as_slice() {
+            ["repos", owner, name, rest @ ..] if [*owner, *name] == repo => match (method, rest) {
+                ("GET", ["releases"]) => self.list_releases(),
+                ("POST", ["releases"]) => self.create_release(request),
+                ("GET", ["releases", "tags", tag]) => self.release_by_tag(tag),
+                ("GET", ["releases", "assets", id]) => self.get_asset(request, id),
+                ("PATCH", ["releases", "assets", id]) => self.update_asset(request, id),
+                ("DELETE", ["releases", "assets", id]) => self.delete_asset(id),
+                ("GET", ["releases", id]) => self.get_release(id),
+                ("PATCH", ["releases", id]) => self.update_release(request, id),
+                ("DELETE", ["releases", id]) => self.delete_release(id),
+                ("GET", ["releases", id, "assets"]) => self.list_assets(request, id),
+                ("GET", ["pulls"]) => self.list_pulls(request),
+                ("POST", ["statuses", sha]) => self.create_status(request, sha),
+                ("GET", ["branches"]) => self.list_branches(request),
+                ("GET", ["compare", spec @ ..]) => self.compare(&spec.join("/")),
+                ("DELETE", ["git", "refs", "heads", name]) => self.delete_branch(name),
+                _ => Response::not_found(),
+            },
+            ["uploads", "repos", owner, name, "releases", id, "assets"]
+                if method == "POST" && [*owner, *name] == repo =>
+            {
+                self.upload_asset(request, id)
+            }
+            ["download", tag, file] if method == "GET" => self.download(tag, file),
+            _ => Response::not_found(),
+        }
+    }
+
+    fn list_releases(&self) -> Response {
+        let state = self.lock();
+        let releases: Vec<_> = state.releases.iter().rev().map(|r| self.release_json(r)).collect();
+        Response::json(200, &serde_json::Value::Array(releases))
+    }
+
+    fn create_release(&self, request: &Request) -> Response {
+        let Ok(body) = serde_json::from_slice::<serde_json::Value>(&request.body) else {
+            return unprocessable("body is not JSON");
+        };
+        let Some(tag_name) = body.get("tag_name").and_then(|v| v.as_str()) else {
+            return unprocessable("tag_name is missing");
+        };
+
+        let mut state = self.lock();
+        if state.releases.iter().any(|r| r.tag_name == tag_name && !r.draft) {
+            return unprocessable("tag_name already_exists");
+        }
+        state.next_id += 1;
+        let release = MockRelease {
+            id: state.next_id,
+            tag_name: tag_name.to_string(),
+            target_commitish: str_field(&body, "target_commitish").unwrap_or_else(|| "main".to_string()),
+            name: str_field(&body, "name"),
+            body: str_field(&body, "body"),
+            draft: body.get("draft").and_then(|v| v.as_bool()).unwrap_or(false),
+            prerelease: body.get("prerelease").and_then(|v| v.as_bool()).unwrap_or(false),
+            assets: Vec::new(),
+        };
+        let json = self.release_json(&release);
+        state.releases.push(release);
+        Response::json(201, &json)
+    }
+
+    fn create_status(&self, request: &Request, sha: &str) -> Response {
+        let Ok(body) = serde_json::from_slice::<serde_json::Value>(&request.body) else {
+            return unprocessable("body is not JSON");
+        };
+        let Some(state) = str_field(&body, "state") else {
+            return unprocessable("state is missing");
+        };
+        let status = MockStatus {
+            sha: sha.to_string(),
+            context: str_field(&body, "context").unwrap_or_else(|| "default".to_string()),
+            state,
+            description: str_field(&body, "description"),
+        };
+        let json = serde_json::json!({
+            "state": status.state,
+            "context": status.context,
+            "description": status.description,
+        });
+        self.lock().statuses.push(status);
+        Response::json(201, &json)
+    }
+
+    fn release_by_tag(&self, tag: &str) -> Response {
+        let state = self.lock();
+        match state.releases.iter().rev().find(|r| r.tag_name == tag && !r.draft) {
+            Some(release) => Response::json(200, &self.release_json(release)),
+            None => Response::not_found(),
+        }
+    }
+
+    fn get_release(&self, id: &str) -> Response {
+        let state = self.lock();
+        match find_release(&state, id) {
+            Some(release) => Response::json(200, &self.release_json(release)),
+            None => Response::not_found(),
+        }
+    }
+
+    fn update_release(&self, request: &Request, id: &str) -> Response {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        let mut state = self.lock();
+        let Some(release) = find_release_mut(&mut state, id) else {
+            return Response::not_found();
+        };
+        if let Some(tag_name) = str_field(&body, "tag_name") {
+            release.tag_name = tag_name;
+        }
+        if let Some(name) = str_field(&body, "name") {
+            release.name = Some(name);
+        }
+        if let Some(text) = str_field(&body, "body") {
+            release.body = Some(text);
+        }
+        if let Some(draft) = body.get("draft").and_then(|v| v.as_bool()) {
+            release.draft = draft;
+        }
+        if let Some(prerelease) = body.get("prerelease").and_then(|v| v.as_bool()) {
+            release.prerelease = prerelease;
+        }
+        let release = release.clone();
+        Response::json(200, &self.release_json(&release))
+    }
+
+    fn delete_release(&self, id: &str) -> Response {
+        let mut state = self.lock();
+        let before = state.releases.len();
+        state.releases.retain(|r| Some(r.id) != id.parse().ok());
+        if state.releases.len() == before {
+            return Response::not_found();
+        }
+        Response::empty(204)
+    }
+
+    fn list_assets(&self, request: &Request, id: &str) -> Response {
+        let state = self.lock();
+        let per_page: usize = request.query("per_page").and_then(|v| v.parse().ok()).unwrap_or(30);
+        let page: usize = request.query("page").and_then(|v| v.parse().ok()).unwrap_or(1);
+        match find_release(&state, id) {
+            Some(release) => Response::json(
+                200,
+                &serde_json::Value::Array(
+                    release
+                        .assets
+                        .iter()
+                        .skip(page.saturating_sub(1) * per_page)
+                        .take(per_page)
+                        .map(|a| self.asset_json(release, a))
+                        .collect(),
+                ),
+            ),
+            None => Response::not_found(),
+        }
+    }
+
+    fn upload_asset(&self, request: &Request, id: &str) -> Response {
+        let Some(name) = request.query("name") else {
+            return unprocessable("name is missing");
+        };
+        let mut state = self.lock();
+        state.next_id += 1;
+        let asset_id = state.next_id;
+        let Some(release) = find_release_mut(&mut state, id) else {
+            return Response::not_found();
+        };
+        if release.assets.iter().any(|a| a.name == name) {
+            return unprocessable("already_exists");
+        }
+        release.assets.push(MockAsset {
+            id: asset_id,
+            name: name.to_string(),
+            label: request.query("label").map(String::from),
+            content_type: request
+                .header("content-type")
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+            content: request.body.clone(),
+        });
+        let release = release.clone();
+        let asset = release.assets.last().expect("asset was just added");
+        Response::json(201, &self.asset_json(&release, asset))
+    }
+
+    fn get_asset(&self, request: &Request, id: &str) -> Response {
+        let state = self.lock();
+        let Some((release, asset)) = find_asset(&state, id) else {
+            return Response::not_found();
+        };
+        if request.header("accept") == Some("application/octet-stream") {
+            return Response::bytes(&asset.content_type, asset.content.clone());
+        }
+        Response::json(200, &self.asset_json(release, asset))
+    }
+
+    fn update_asset(&self, request: &Request, id: &str) -> Response {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap_or_default();
+        let mut state = self.lock();
+        let Some(release) = state
+            .releases
+            .iter_mut()
+            .find(|r| r.assets.iter().any(|a| Some(a.id) == id.parse().ok()))
+        else {
+            return Response::not_found();
+        };
+        let asset = release
+            .assets
+            .iter_mut()
+            .find(|a| Some(a.id) == id.parse().ok())
+            .expect("release has the asset");
+        if let Some(name) = str_field(&body, "name") {
+            asset.name = name;
+        }
+        if body.get("label").is_some() {
+            asset.label = str_field(&body, "label");
+        }
+        let asset = asset.clone();
+        let release = release.clone();
+        Response::json(200, &self.asset_json(&release, &asset))
+    }
+
+    fn delete_asset(&self, id: &str) -> Response {
+        let mut state = self.lock();
+        let id: Option<u64> = id.parse().ok();
+        for release in &mut state.releases {
+            let before = release.assets.len();
+            release.assets.retain(|a| Some(a.id) != id);
+            if release.assets.len() != before {
+                return Response::empty(204);
+            }
+        }
+        Response::not_found()
+    }
+
+    fn list_pulls(&self, request: &Request) -> Response {
+        let state = self.lock();
+        let per_page: usize = request.query("per_page").and_then(|v| v.parse().ok()).unwrap_or(30);
+        let page: usize = request.query("page").and_then(|v| v.parse().ok()).unwrap_or(1);
+        let pulls = state
+            .pulls
+            .iter()
+            .skip(page.saturating_sub(1) * per_page)
+            .take(per_page)
+            .cloned()
+            .collect();
+        Response::json(200, &serde_json::Value::Array(pulls))
+    }
+
+    fn list_branches(&self, request: &Request) -> Response {
+        let state = self.lock();
+        let per_page: usize = request.query("per_page").and_then(|v| v.parse().ok()).unwrap_or(30);
+        let page: usize = request.query("page").and_then(|v| v.parse().ok()).unwrap_or(1);
+        let branches = state
+            .branches
+            .iter()
+            .skip(page.saturating_sub(1) * per_page)
+            .take(per_page)
+            .map(|(name, _)| serde_json::json!({ "name": name }))
+            .collect();
+        Response::json(200, &serde_json::Value::Array(branches))
+    }
+
+    /// `base...head`; as in git, a bare name that is both a tag and a branch
+    /// means the tag
+    fn compare(&self, spec: &str) -> Response {
+        let Some((base, head)) = spec.split_once("...") else {
+            return Response::not_found();
+        };
+        let state = self.lock();
+        let resolve = |rev: &str| -> Option<(bool, String)> {
+            let is_tag = |name: &str| state.releases.iter().any(|r| r.tag_name == name);
+            let is_branch = |name: &str| state.branches.iter().any(|(b, _)| b == name);
+            match (rev.strip_prefix("refs/tags/"), rev.strip_prefix("refs/heads/")) {
+                (Some(tag), _) => is_tag(tag).then(|| (true, tag.to_string())),
+                (_, Some(branch)) => is_branch(branch).then(|| (false, branch.to_string())),
+                _ if is_tag(rev) => Some((true, rev.to_string())),
+                _ => is_branch(rev).then(|| (false, rev.to_string())),
+            }
+        };
+        let (Some(base), Some(head)) = (resolve(base), resolve(head)) else {
+            return Response::not_found();
+        };
+        let ahead_by = match (&base, &head) {
+            ((true, tag), (false, branch)) if tag == branch => state
+                .branches
+                .iter()
+                .find(|(name, _)| name == branch)
+                .map_or(0, |(_, ahead)| *ahead),
+            _ => 0,
+        };
+        Response::json(200, &serde_json::json!({ "ahead_by": ahead_by, "behind_by": 0 }))
+    }
+
+    fn delete_branch(&self, name: &str) -> Response {
+        let mut state = self.lock();
+        let before = state.branches.len();
+        state.branches.retain(|(branch, _)| branch != name);
+        if state.branches.len() == before {
+            return Response::json(422, &serde_json::json!({ "message": "Reference does not exist" }));
+        }
+        Response::empty(204)
+    }
+
+    fn download(&self, tag: &str, file: &str) -> Response {
+        let state = self.lock();
+        state
+            .releases
+            .iter()
+            .rev()
+            .filter(|r| r.tag_name == tag && !r.draft)
+            .flat_map(|r| &r.assets)
+            .find(|a| a.name == file)
+            .map(|a| Response::bytes(&a.content_type, a.content.clone()))
+            .unwrap_or_else(Response::not_found)
+    }
+
+    fn release_json(&self, release: &MockRelease) -> serde_json::Value {
+        let api = format!("{}/repos/{}/{}/releases/{}", self.url(), self.owner, self.repo, release.id);
+        serde_json::json!({
+            "url": api,
+            "html_url": format!("{}/{}/{}/releases/tag/{}", self.url(), self.owner, self.repo, release.tag_name),
+            "assets_url": format!("{}/assets", api),
+            "upload_url": format!(
+                "{}/uploads/repos/{}/{}/releases/{}/assets{{?name,label}}",
+                self.url(), self.owner, self.repo, release.id
+            ),
+            "tarball_url": null,
+            "zipball_url": null,
+            "id": release.id,
+            "node_id": format!("RE_{}", release.id),
+            "tag_name": release.tag_name,
+            "target_commitish": release.target_commitish,
+            "name": release.name,
+            "body": release.body,
+            "draft": release.draft,
+            "prerelease": release.prerelease,
+            "created_at": now(),
+            "published_at": if release.draft { None } else { Some(now()) },
+            "author": null,
+            "assets": release.assets.iter().map(|a| self.asset_json(release, a)).collect::<Vec<_>>(),
+        })
+    }
+
+    fn asset_json(&self, release: &MockRelease, asset: &MockAsset) -> serde_json::Value {
+        serde_json::json!({
+            "url": format!("{}/repos/{}/{}/releases/assets/{}", self.url(), self.owner, self.repo, asset.id),
+            "browser_download_url": format!("{}/download/{}/{}", self.url(), release.tag_name, asset.name),
+            "id": asset.id,
+            "node_id": format!("RA_{}", asset.id),
+            "name": asset.name,
+            "label": asset.label,
+            "state": "uploaded",
+            "content_type": asset.content_type,
+            "size": asset.content.len(),
+            "digest": format!("sha256:{}", sha256_hex(&asset.content)),
+            "download_count": 0,
+            "created_at": now(),
+            "updated_at": now(),
+            "uploader": null,
+        })
+    }
+}
+
+fn find_release<'a>(state: &'a State, id: &str) -> Option<&'a MockRelease> {
+    let id: u64 = id.parse().ok()?;
+    state.releases.iter().find(|r| r.id == id)
+}
+
+fn find_release_mut<'a>(state: &'a mut State, id: &str) -> Option<&'a mut MockRelease> {
+    let id: u64 = id.parse().ok()?;
+    state.releases.iter_mut().find(|r| r.id == id)
+}
+
+fn find_asset<'a>(state: &'a State, id: &str) -> Option<(&'a MockRelease, &'a MockAsset)> {
+    let id: u64 = id.parse().ok()?;
+    state
+        .releases
+        .iter()
+        .find_map(|r| r.assets.iter().find(|a| a.id == id).map(|a| (r, a)))
+}
+
+fn str_field(body: &serde_json::Value, name: &str) -> Option<String> {
+    body.get(name).and_then(|v| v.as_str()).map(String::from)
+}
+
+fn unprocessable(message: &str) -> Response {
+    Response::json(
+        422,
+        &serde_json::json!({
+            "message": "Validation Failed",
+            "errors": [{ "message": message }],
+            "documentation_url": "https://docs.github.com/rest",
+        }),
+    )
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(content).iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,111 @@
+//! Hermetic end-to-end releases (`test-harness` feature).
+//!
+//! A release talks to three services: the git remote, the GitHub API and the
+//! crates.io index. [`TestHarness`] starts local stand-ins for all three and
+//! hands out the environment that points the tool at them:
+//!
+//! - [`GitRemote`]: a bare repository served in place of github.com;
+//! - [`MockGitHub`]: an in-memory releases API (`GITHUB_API_URL`);
+//! - [`LocalRegistry`]: a sparse index (`KODEGEN_REGISTRY_INDEX`).
+//!
+//! ```no_run
+//! use kodegen_bundler_release::test_harness::TestHarness;
+//!
+//! let harness = TestHarness::start("acme", "widget")?;
+//! let package = harness.seed_package("widget", "0.1.0", "")?;
+//! let status = std::process::Command::new("kodegen_bundler_release")
+//!     .args([".", "--skip", "build"])
+//!     .current_dir(&package)
+//!     .envs(harness.env())
+//!     .status()?;
+//! assert!(status.success());
+//! assert!(!harness.github.release("v0.1.0").unwrap().draft);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Building bundles still runs the real `kodegen_bundler_bundle`; skip the
+//! `build` phase to stay fully offline.
+
+mod git_remote;
+mod github;
+mod http;
+mod registry;
+
+pub use git_remote::GitRemote;
+pub use github::{MockAsset, MockGitHub, MockRelease, MockStatus};
+pub use registry::LocalRegistry;
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Token the harness gives the tool; the mock accepts any
+pub const TEST_TOKEN: &str = "ghp_harness";
+
+/// A git remote, mock GitHub API and local registry for one repository
+pub struct TestHarness {
+    pub remote: GitRemote,
+    pub github: MockGitHub,
+    pub registry: LocalRegistry,
+    /// Working copies live here
+    workdir: TempDir,
+}
+
+impl TestHarness {
+    /// Start the services for `owner/repo`
+    pub fn start(owner: &str, repo: &str) -> io::Result<Self> {
+        Ok(Self {
+            remote: GitRemote::new(owner, repo)?,
+            github: MockGitHub::start(owner, repo)?,
+            registry: LocalRegistry::start()?,
+            workdir: TempDir::new("work")?,
+        })
+    }
+
+    /// Create and push a minimal binary crate; returns its working copy
+    pub fn seed_package(&self, name: &str, version: &str, extra_manifest: &str) -> io::Result<PathBuf> {
+        let dir = self.workdir.path().join(name);
+        self.remote.seed_package(&dir, name, version, extra_manifest)?;
+        Ok(dir)
+    }
+
+    /// Environment for the tool under test
+    ///
+    /// Besides the service endpoints this sets a GitHub token, skips the
+    /// shell rc and keychain lookups, and gives git an identity.
+    pub fn env(&self) -> Vec<(String, String)> {
+        let mut vars = self.remote.env();
+        vars.extend(self.github.env());
+        vars.extend(self.registry.env());
+        vars.extend(
+            [
+                ("GH_TOKEN", TEST_TOKEN),
+                ("KODEGEN_SKIP_SHELL_RC", "1"),
+                ("KODEGEN_CI", "true"),
+                ("KODEGEN_GIT_AUTH", "ssh"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        vars
+    }
+}
+
+/// Directory under the system temp dir, removed on drop
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    pub(crate) fn new(label: &str) -> io::Result<Self> {
+        let path = std::env::temp_dir().join(format!("kodegen-harness-{}-{}", label, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
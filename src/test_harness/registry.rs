@@ -0,0 +1,95 @@
+//! Local sparse registry index.
+//!
+//! Serves a cargo sparse index (`config.json` plus one file per crate) from
+//! memory. [`LocalRegistry::publish`] lists a version, standing in for
+//! crates.io accepting an upload; the tool's post-publish check polls it
+//! through `KODEGEN_REGISTRY_INDEX` ([`LocalRegistry::env`]).
+
+use super::http::{self, Request, Response};
+use crate::sparse_index::index_path;
+use std::collections::BTreeMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// Local sparse index, serving until the process exits
+#[derive(Clone)]
+pub struct LocalRegistry {
+    addr: SocketAddr,
+    /// Index file lines by lowercase crate name
+    crates: Arc<Mutex<BTreeMap<String, Vec<String>>>>,
+}
+
+impl LocalRegistry {
+    /// Start the index on a free local port
+    pub fn start() -> io::Result<Self> {
+        let listener = http::bind()?;
+        let registry = Self {
+            addr: listener.local_addr()?,
+            crates: Arc::default(),
+        };
+        let handler = registry.clone();
+        http::serve(listener, Arc::new(move |request| handler.route(request)));
+        Ok(registry)
+    }
+
+    /// Index root, the value of `KODEGEN_REGISTRY_INDEX`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Variables pointing the tool at this index
+    pub fn env(&self) -> Vec<(String, String)> {
+        vec![("KODEGEN_REGISTRY_INDEX".to_string(), self.url())]
+    }
+
+    /// List `version` of `name` in the index
+    pub fn publish(&self, name: &str, version: &str) {
+        let entry = serde_json::json!({
+            "name": name,
+            "vers": version,
+            "deps": [],
+            "cksum": "0".repeat(64),
+            "features": {},
+            "yanked": false,
+        });
+        self.crates
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(entry.to_string());
+    }
+
+    /// Versions of `name` in the index
+    pub fn versions(&self, name: &str) -> Vec<String> {
+        let crates = self.crates.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        crates
+            .get(&name.to_lowercase())
+            .into_iter()
+            .flatten()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter_map(|entry| entry.get("vers").and_then(|v| v.as_str()).map(String::from))
+            .collect()
+    }
+
+    fn route(&self, request: &Request) -> Response {
+        if request.method != "GET" {
+            return Response::empty(405);
+        }
+        if request.path == "/config.json" {
+            return Response::json(200, &serde_json::json!({ "dl": format!("{}/crates", self.url()) }));
+        }
+
+        let crates = self.crates.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(name) = request.segments().last().map(|name| name.to_lowercase()) else {
+            return Response::not_found();
+        };
+        match crates.get(&name) {
+            Some(lines) if request.path == format!("/{}", index_path(&name)) => {
+                Response::bytes("text/plain", format!("{}\n", lines.join("\n")).into_bytes())
+            }
+            _ => Response::not_found(),
+        }
+    }
+}
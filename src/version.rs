@@ -0,0 +1,382 @@
+//! Version schemes: SemVer and calendar versioning (CalVer).
+//!
+//! Cargo only accepts SemVer-shaped versions, so a CalVer release is still a
+//! `semver::Version` internally (`2024.10.3`). State files, artifact names and
+//! version comparisons therefore work unchanged for both schemes. The scheme
+//! adds what differs:
+//!
+//! - validation that the version actually fits the calendar format
+//! - the next version (date-driven for CalVer)
+//! - tag naming, including zero padding (`v2024.01.3` for `YYYY.0M.MICRO`)
+//!
+//! Configured in Cargo.toml:
+//!
+//! ```toml
+//! [package.metadata.release]
+//! version-scheme = "calver:YYYY.MM.MICRO"   # or "semver" (default), "calver:YY.0M.PATCH"
+//! tag-format = "{name}-v{version}"          # default "v{version}"
+//! ```
+//!
+//! `tag-prefix = "x"` is shorthand for `tag-format = "x{version}"`.
+
+#![allow(dead_code)] // Public API - items may be used by external consumers
+
+use crate::error::{CliError, ReleaseError, Result};
+use chrono::Datelike;
+use semver::Version;
+
+/// Version component to increment for SemVer releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
+
+/// One segment of a CalVer format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CalVerToken {
+    /// Full year, e.g. 2024
+    FullYear,
+    /// Short year, e.g. 24 (`YY`) or 24/06 (`0Y`)
+    ShortYear { padded: bool },
+    /// Month, e.g. 1 (`MM`) or 01 (`0M`)
+    Month { padded: bool },
+    /// ISO week, e.g. 3 (`WW`) or 03 (`0W`)
+    Week { padded: bool },
+    /// Day of month, e.g. 7 (`DD`) or 07 (`0D`)
+    Day { padded: bool },
+    /// Release counter within the date (`MICRO` or `PATCH`)
+    Micro,
+}
+
+impl CalVerToken {
+    fn parse(token: &str) -> Option<Self> {
+        Some(match token {
+            "YYYY" => Self::FullYear,
+            "YY" => Self::ShortYear { padded: false },
+            "0Y" => Self::ShortYear { padded: true },
+            "MM" => Self::Month { padded: false },
+            "0M" => Self::Month { padded: true },
+            "WW" => Self::Week { padded: false },
+            "0W" => Self::Week { padded: true },
+            "DD" => Self::Day { padded: false },
+            "0D" => Self::Day { padded: true },
+            "MICRO" | "PATCH" => Self::Micro,
+            _ => return None,
+        })
+    }
+
+    /// Value of a date token for `date`
+    ///
+    /// With `week_based`, years are ISO week-years, so 2024-12-30 (week 1 of
+    /// 2025) is `2025.01` rather than `2024.01`.
+    fn value_for(self, date: chrono::NaiveDate, week_based: bool) -> u64 {
+        let year = if week_based { date.iso_week().year() } else { date.year() };
+        match self {
+            Self::FullYear => year as u64,
+            Self::ShortYear { .. } => (year % 100) as u64,
+            Self::Month { .. } => date.month() as u64,
+            Self::Week { .. } => date.iso_week().week() as u64,
+            Self::Day { .. } => date.day() as u64,
+            Self::Micro => 0,
+        }
+    }
+
+    fn valid_range(self) -> std::ops::RangeInclusive<u64> {
+        match self {
+            Self::FullYear => 1970..=9999,
+            Self::ShortYear { .. } => 0..=99,
+            Self::Month { .. } => 1..=12,
+            Self::Week { .. } => 1..=53,
+            Self::Day { .. } => 1..=31,
+            Self::Micro => 0..=u64::MAX,
+        }
+    }
+
+    fn render(self, value: u64) -> String {
+        match self {
+            Self::ShortYear { padded: true }
+            | Self::Month { padded: true }
+            | Self::Week { padded: true }
+            | Self::Day { padded: true } => format!("{:02}", value),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Tag template when none is configured
+pub const DEFAULT_TAG_FORMAT: &str = "v{version}";
+
+/// How versions are formed and tagged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionScheme {
+    kind: SchemeKind,
+    /// Tag template with `{version}` (package name already substituted)
+    tag_format: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SchemeKind {
+    SemVer,
+    CalVer {
+        format: String,
+        tokens: [CalVerToken; 3],
+    },
+}
+
+impl Default for VersionScheme {
+    fn default() -> Self {
+        Self {
+            kind: SchemeKind::SemVer,
+            tag_format: DEFAULT_TAG_FORMAT.to_string(),
+        }
+    }
+}
+
+impl VersionScheme {
+    /// Parse a scheme like `semver` or `calver:YYYY.0M.MICRO`
+    ///
+    /// Tags use [`DEFAULT_TAG_FORMAT`]; see [`Self::with_tag_format`].
+    pub fn parse(scheme: &str) -> Result<Self> {
+        let invalid = |reason: String| {
+            ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("Invalid version-scheme '{}': {}", scheme, reason),
+            })
+        };
+
+        let kind = match scheme.split_once(':') {
+            None if scheme.eq_ignore_ascii_case("semver") => SchemeKind::SemVer,
+            Some((name, format)) if name.eq_ignore_ascii_case("calver") => {
+                let parsed: Vec<CalVerToken> = format
+                    .split('.')
+                    .map(|t| {
+                        CalVerToken::parse(t).ok_or_else(|| {
+                            invalid(format!(
+                                "unknown token '{}' (use YYYY, YY, 0Y, MM, 0M, WW, 0W, DD, 0D, MICRO)",
+                                t
+                            ))
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+
+                let tokens: [CalVerToken; 3] = parsed.try_into().map_err(|_| {
+                    invalid("Cargo versions have exactly three segments".to_string())
+                })?;
+                if tokens[..2].contains(&CalVerToken::Micro) {
+                    return Err(invalid("MICRO/PATCH must be the last segment".to_string()));
+                }
+                if !matches!(tokens[0], CalVerToken::FullYear | CalVerToken::ShortYear { .. }) {
+                    return Err(invalid("the first segment must be a year".to_string()));
+                }
+
+                SchemeKind::CalVer {
+                    format: format.to_string(),
+                    tokens,
+                }
+            }
+            _ => return Err(invalid("expected 'semver' or 'calver:<FORMAT>'".to_string())),
+        };
+
+        Ok(Self {
+            kind,
+            tag_format: DEFAULT_TAG_FORMAT.to_string(),
+        })
+    }
+
+    /// Use `format` for tags, e.g. `{name}-v{version}` or `{version}`
+    ///
+    /// `{name}` is replaced with `package_name`; `{version}` is required.
+    pub fn with_tag_format(mut self, format: &str, package_name: &str) -> Result<Self> {
+        if !format.contains("{version}") {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("tag-format '{}' must contain {{version}}", format),
+            }));
+        }
+        self.tag_format = format.replace("{name}", package_name);
+        Ok(self)
+    }
+
+    /// Read `version-scheme` and `tag-format` / `tag-prefix` from `[package.metadata.release]`
+    pub(crate) fn from_package(package: &toml::Value) -> Result<Self> {
+        let release = package.get("metadata").and_then(|m| m.get("release"));
+        let get_str = |key: &str| -> Result<Option<&str>> {
+            match release.and_then(|r| r.get(key)) {
+                None => Ok(None),
+                Some(v) => v.as_str().map(Some).ok_or_else(|| {
+                    ReleaseError::Cli(CliError::InvalidArguments {
+                        reason: format!("[package.metadata.release] {} must be a string", key),
+                    })
+                }),
+            }
+        };
+
+        let scheme = match get_str("version-scheme")? {
+            Some(scheme) => Self::parse(scheme)?,
+            None => Self::default(),
+        };
+
+        let name = package.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+        match (get_str("tag-format")?, get_str("tag-prefix")?) {
+            (Some(_), Some(_)) => Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: "[package.metadata.release] sets both tag-format and tag-prefix; use one"
+                    .to_string(),
+            })),
+            (Some(format), None) => scheme.with_tag_format(format, name),
+            (None, Some(prefix)) => scheme.with_tag_format(&format!("{}{{version}}", prefix), name),
+            (None, None) => Ok(scheme),
+        }
+    }
+
+    /// Whether this is a calendar scheme
+    pub fn is_calver(&self) -> bool {
+        matches!(self.kind, SchemeKind::CalVer { .. })
+    }
+
+    /// Check that `version` fits the scheme
+    pub fn validate(&self, version: &Version) -> Result<()> {
+        let SchemeKind::CalVer { format, tokens } = &self.kind else {
+            return Ok(());
+        };
+
+        for (token, value) in tokens.iter().zip(segments(version)) {
+            if !token.valid_range().contains(&value) {
+                return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: format!(
+                        "Version {} does not match CalVer format {} ({} is out of range)",
+                        version, format, value
+                    ),
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tag for a release of `version`, e.g. `v1.2.3`, `mytool-v1.2.3` or `v2024.01.3`
+    pub fn tag_name(&self, version: &Version) -> String {
+        let rendered = match &self.kind {
+            SchemeKind::SemVer => version.to_string(),
+            SchemeKind::CalVer { tokens, .. } => {
+                let mut rendered = tokens
+                    .iter()
+                    .zip(segments(version))
+                    .map(|(token, value)| token.render(value))
+                    .collect::<Vec<_>>()
+                    .join(".");
+                if !version.pre.is_empty() {
+                    rendered.push('-');
+                    rendered.push_str(version.pre.as_str());
+                }
+                rendered
+            }
+        };
+        self.tag_format.replace("{version}", &rendered)
+    }
+
+    /// Version part of a tag of this scheme, e.g. `1.2.3` for `mytool-v1.2.3`
+    pub fn version_in_tag<'t>(&self, tag: &'t str) -> Option<&'t str> {
+        let (prefix, suffix) = self.tag_format.split_once("{version}")?;
+        tag.strip_prefix(prefix)?
+            .strip_suffix(suffix)
+            .filter(|version| !version.is_empty())
+    }
+
+    /// Glob matching every tag of this scheme, e.g. `v*` or `mytool-v*`
+    pub fn tag_pattern(&self) -> String {
+        self.tag_format.replace("{version}", "*")
+    }
+
+    /// Version following `current`
+    ///
+    /// SemVer increments the `bump` component. CalVer ignores `bump`: a new
+    /// date starts at micro 0, and a second release on the same date increments
+    /// the micro segment.
+    pub fn next(&self, current: &Version, bump: Bump, today: chrono::NaiveDate) -> Result<Version> {
+        let SchemeKind::CalVer { format, tokens } = &self.kind else {
+            return Ok(match bump {
+                Bump::Major => Version::new(current.major + 1, 0, 0),
+                Bump::Minor => Version::new(current.major, current.minor + 1, 0),
+                Bump::Patch => Version::new(current.major, current.minor, current.patch + 1),
+            });
+        };
+
+        let current_segments = segments(current);
+        let week_based = tokens.iter().any(|token| matches!(token, CalVerToken::Week { .. }));
+        let mut next = [0u64; 3];
+        for (i, token) in tokens.iter().enumerate() {
+            next[i] = token.value_for(today, week_based);
+        }
+
+        let same_date = tokens
+            .iter()
+            .zip(next.iter().zip(current_segments.iter()))
+            .filter(|(token, _)| **token != CalVerToken::Micro)
+            .all(|(_, (a, b))| a == b);
+
+        if same_date {
+            if tokens[2] != CalVerToken::Micro {
+                return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: format!(
+                        "{} was already released today and format {} has no MICRO segment",
+                        current, format
+                    ),
+                }));
+            }
+            next[2] = current_segments[2] + 1;
+        } else if next < current_segments {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!(
+                    "Current version {} is ahead of today's date in format {}",
+                    current, format
+                ),
+            }));
+        }
+
+        Ok(Version::new(next[0], next[1], next[2]))
+    }
+}
+
+fn segments(version: &Version) -> [u64; 3] {
+    [version.major, version.minor, version.patch]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(scheme: &str, current: &str, today: &str) -> Result<Version> {
+        VersionScheme::parse(scheme)?.next(
+            &Version::parse(current).unwrap(),
+            Bump::Patch,
+            chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d").unwrap(),
+        )
+    }
+
+    #[test]
+    fn calver_starts_each_date_at_micro_zero() {
+        assert_eq!(next("calver:YYYY.MM.MICRO", "2026.9.4", "2026-10-17").unwrap(), Version::new(2026, 10, 0));
+        assert_eq!(next("calver:YYYY.MM.MICRO", "2026.10.4", "2026-10-17").unwrap(), Version::new(2026, 10, 5));
+        assert!(next("calver:YYYY.MM.DD", "2026.10.17", "2026-10-17").is_err());
+        assert!(next("calver:YYYY.MM.MICRO", "2026.11.0", "2026-10-17").is_err());
+    }
+
+    #[test]
+    fn calver_weeks_use_the_iso_week_year() {
+        // 2024-12-30 is in week 1 of 2025; 2027-01-01 is in week 53 of 2026
+        assert_eq!(next("calver:YYYY.WW.MICRO", "2024.52.0", "2024-12-30").unwrap(), Version::new(2025, 1, 0));
+        assert_eq!(next("calver:YY.0W.MICRO", "26.52.0", "2027-01-01").unwrap(), Version::new(26, 53, 0));
+        assert_eq!(next("calver:YYYY.WW.MICRO", "2025.1.0", "2025-01-02").unwrap(), Version::new(2025, 1, 1));
+
+        // Month formats keep the calendar year
+        assert_eq!(next("calver:YYYY.MM.MICRO", "2024.11.0", "2024-12-30").unwrap(), Version::new(2024, 12, 0));
+    }
+
+    #[test]
+    fn calver_tags_are_padded_and_validated() {
+        let scheme = VersionScheme::parse("calver:YY.0M.PATCH").unwrap();
+        assert_eq!(scheme.tag_name(&Version::new(26, 1, 3)), "v26.01.3");
+        assert!(scheme.validate(&Version::new(26, 1, 3)).is_ok());
+        assert!(scheme.validate(&Version::new(26, 13, 0)).is_err());
+        assert!(VersionScheme::parse("calver:MM.YYYY.MICRO").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Stale version references in documentation.
+//!
+//! `just publish` bumps Cargo.toml, but install instructions, badge URLs and
+//! doc headers that spell out the version are easy to miss. The files listed
+//! in `[package.metadata.release] version-files` are scanned for the previous
+//! release's version before anything is released:
+//!
+//! ```toml
+//! [package.metadata.release]
+//! version-files = ["README.md", "docs/*.md"]
+//! ```
+//!
+//! The previous version comes from the latest earlier tag of the same tag
+//! format. A reference only counts as a whole version, so `1.2.3` does not
+//! match `11.2.3` or `1.2.30`. `*` and `?` work in file names.
+
+use crate::error::{CliError, ReleaseError, Result};
+use crate::version::VersionScheme;
+use std::path::Path;
+
+/// A line that still mentions the previous version
+#[derive(Debug, Clone)]
+pub struct StaleReference {
+    /// File relative to the package root
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    /// The line, trimmed
+    pub text: String,
+}
+
+/// Read `[package.metadata.release] version-files`
+pub(crate) fn from_package(package: &toml::Value) -> Result<Vec<String>> {
+    match package
+        .get("metadata")
+        .and_then(|m| m.get("release"))
+        .and_then(|r| r.get("version-files"))
+    {
+        None => Ok(Vec::new()),
+        Some(value) => value
+            .as_array()
+            .and_then(|arr| arr.iter().map(|v| v.as_str().map(String::from)).collect())
+            .ok_or_else(|| {
+                ReleaseError::Cli(CliError::InvalidArguments {
+                    reason: "[package.metadata.release] version-files must be an array of paths".to_string(),
+                })
+            }),
+    }
+}
+
+/// Previous release's version, from the latest tag before `tag`
+///
+/// `None` for a first release or outside a git repository.
+pub fn previous_version(repo: &Path, scheme: &VersionScheme, tag: &str) -> Option<String> {
+    let previous = crate::exec::Exec::new("git")
+        .args(["describe", "--tags", "--abbrev=0", "--match", &scheme.tag_pattern(), "--exclude", tag, "HEAD"])
+        .current_dir(repo)
+        .stdout_blocking()
+        .ok()?;
+    scheme.version_in_tag(&previous).map(String::from)
+}
+
+/// Lines of `files` that mention `old_version`
+///
+/// Fails when an entry matches no file.
+pub fn find_stale(package_dir: &Path, files: &[String], old_version: &str) -> Result<Vec<StaleReference>> {
+    let mut stale = Vec::new();
+    for entry in files {
+        let matched = crate::icons::matching_files(package_dir, entry);
+        if matched.is_empty() {
+            return Err(ReleaseError::Cli(CliError::InvalidArguments {
+                reason: format!("[package.metadata.release] version-files: {} matches no file", entry),
+            }));
+        }
+        for file in matched {
+            let content = std::fs::read_to_string(&file)?;
+            let display = file.strip_prefix(package_dir).unwrap_or(&file).display().to_string();
+            for (index, line) in content.lines().enumerate() {
+                if mentions(line, old_version) {
+                    stale.push(StaleReference {
+                        file: display.clone(),
+                        line: index + 1,
+                        text: line.trim().to_string(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(stale)
+}
+
+/// Whether `line` contains `version` as a whole version
+fn mentions(line: &str, version: &str) -> bool {
+    let is_version_char = |c: char| c.is_ascii_digit() || c == '.';
+    line.match_indices(version).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let mut after = line[start + version.len()..].chars();
+        let next = after.next();
+        let continues = match next {
+            Some(c) if c.is_ascii_digit() => true,
+            // `1.2.3.4`, but not the full stop ending a sentence
+            Some('.') => after.next().is_some_and(|c| c.is_ascii_digit()),
+            _ => false,
+        };
+        !before.is_some_and(is_version_char) && !continues
+    })
+}
@@ -39,6 +39,38 @@ pub struct WorkspaceConfig {
     pub package: Option<WorkspacePackage>,
     /// Workspace dependencies
     pub dependencies: Option<HashMap<String, toml::Value>>,
+    /// Tool metadata (`[workspace.metadata]`)
+    #[serde(default)]
+    pub metadata: Option<toml::Value>,
+}
+
+impl WorkspaceConfig {
+    /// Packages listed in `[workspace.metadata.release] skip-publish`
+    ///
+    /// These are still version-bumped but never published to crates.io.
+    pub fn skip_publish(&self) -> Vec<String> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("skip-publish"))
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether release notes get a section per crate (`[workspace.metadata.release] crate-notes`, default true)
+    pub fn crate_notes(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("release"))
+            .and_then(|r| r.get("crate-notes"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+    }
 }
 
 /// Workspace package configuration
@@ -109,6 +141,13 @@ impl PackageConfig {
             _ => true, // Default is publishable
         }
     }
+
+    /// Feature set from `[package.metadata.release.publish]`
+    pub fn publish_features(&self) -> Result<crate::metadata::PublishFeatures> {
+        let mut table: toml::map::Map<String, toml::Value> = self.other.clone().into_iter().collect();
+        table.insert("name".to_string(), toml::Value::String(self.name.clone()));
+        crate::metadata::PublishFeatures::from_package(&toml::Value::Table(table))
+    }
 }
 
 /// Dependency specification
@@ -128,6 +167,8 @@ pub struct DependencySpec {
     pub optional: Option<bool>,
     /// Default features
     pub default_features: Option<bool>,
+    /// Inherited from `[workspace.dependencies]` (`workspace = true`)
+    pub workspace: Option<bool>,
 }
 
 impl WorkspaceInfo {
@@ -154,6 +195,17 @@ impl WorkspaceInfo {
         })
     }
 
+    /// Check whether `dir` contains a Cargo.toml with a `[workspace]` table
+    pub fn is_workspace_root<P: AsRef<Path>>(dir: P) -> Result<bool> {
+        let cargo_toml = dir.as_ref().join("Cargo.toml");
+        if !cargo_toml.exists() {
+            return Ok(false);
+        }
+        let content = std::fs::read_to_string(&cargo_toml)?;
+        let parsed: toml::Value = toml::from_str(&content)?;
+        Ok(parsed.get("workspace").is_some())
+    }
+
     /// Find the workspace root directory
     fn find_workspace_root<P: AsRef<Path>>(start_dir: P) -> Result<PathBuf> {
         // Try canonicalization, fall back to absolute path for network mounts
@@ -441,6 +493,7 @@ impl WorkspaceInfo {
                 features: None,
                 optional: None,
                 default_features: None,
+                workspace: None,
             }),
             toml::Value::Table(table) => {
                 let spec: DependencySpec =
@@ -0,0 +1,105 @@
+//! DOT and Mermaid rendering of the workspace dependency graph.
+//!
+//! Packages are grouped by publish tier. An edge points from a dependency to
+//! the package depending on it, so edges follow the publish order. Packages
+//! that are not published are drawn dashed, outside the tiers.
+
+use crate::workspace::analyzer::WorkspaceInfo;
+use crate::workspace::publish_plan::PublishPlan;
+use std::fmt::Write;
+
+/// Graph description language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT (`dot -Tsvg`)
+    #[default]
+    Dot,
+    /// Mermaid flowchart (renders in GitHub Markdown)
+    Mermaid,
+}
+
+/// Render the dependency graph of `workspace` with the tiers of `plan`
+pub fn render_graph(workspace: &WorkspaceInfo, plan: &PublishPlan, format: GraphFormat) -> String {
+    let mut edges: Vec<(&str, &str)> = workspace
+        .internal_dependencies
+        .iter()
+        .flat_map(|(name, deps)| {
+            deps.iter()
+                .filter(move |dep| *dep != name)
+                .map(move |dep| (dep.as_str(), name.as_str()))
+        })
+        .collect();
+    edges.sort();
+    edges.dedup();
+
+    match format {
+        GraphFormat::Dot => render_dot(plan, &edges),
+        GraphFormat::Mermaid => render_mermaid(plan, &edges),
+    }
+}
+
+fn render_dot(plan: &PublishPlan, edges: &[(&str, &str)]) -> String {
+    let mut out = String::from("digraph publish_order {\n    rankdir=LR;\n    node [shape=box];\n");
+    for (index, tier) in plan.tiers.iter().enumerate() {
+        let _ = writeln!(out, "    subgraph cluster_tier{} {{", index);
+        let _ = writeln!(out, "        label=\"Tier {}\";", index);
+        for name in tier {
+            let _ = writeln!(out, "        {:?};", name);
+        }
+        out.push_str("    }\n");
+    }
+    for skipped in &plan.skipped {
+        let _ = writeln!(
+            out,
+            "    {:?} [style=dashed, label={:?}];",
+            skipped.name,
+            format!("{}\n({})", skipped.name, skipped.reason)
+        );
+    }
+    for (from, to) in edges {
+        let _ = writeln!(out, "    {:?} -> {:?};", from, to);
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(plan: &PublishPlan, edges: &[(&str, &str)]) -> String {
+    // Crate names are not valid Mermaid ids everywhere, so nodes are numbered
+    let mut ids: Vec<&str> = plan
+        .publish_order()
+        .chain(plan.skipped.iter().map(|s| s.name.as_str()))
+        .collect();
+    for (from, to) in edges {
+        for name in [*from, *to] {
+            if !ids.contains(&name) {
+                ids.push(name);
+            }
+        }
+    }
+    let id = |name: &str| ids.iter().position(|n| *n == name).unwrap_or_default();
+
+    let mut out = String::from("flowchart LR\n");
+    for (index, tier) in plan.tiers.iter().enumerate() {
+        let _ = writeln!(out, "    subgraph tier{}[\"Tier {}\"]", index, index);
+        for name in tier {
+            let _ = writeln!(out, "        n{}[\"{}\"]", id(name), name);
+        }
+        out.push_str("    end\n");
+    }
+    for skipped in &plan.skipped {
+        let _ = writeln!(
+            out,
+            "    n{}[\"{}<br/>({})\"]:::skipped",
+            id(&skipped.name),
+            skipped.name,
+            skipped.reason
+        );
+    }
+    for (from, to) in edges {
+        let _ = writeln!(out, "    n{} --> n{}", id(from), id(to));
+    }
+    if !plan.skipped.is_empty() {
+        out.push_str("    classDef skipped stroke-dasharray: 5 5\n");
+    }
+    out
+}
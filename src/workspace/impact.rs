@@ -0,0 +1,182 @@
+//! Release impact of bumping one workspace crate.
+//!
+//! Walks the internal dependency graph backwards from the bumped crate. A
+//! dependent whose version requirement no longer matches the new version
+//! needs its requirement updated and, when publishable, a republish (as a
+//! patch release); that republish is followed to its own dependents the same
+//! way. Dependents whose requirement still matches are affected (they build
+//! against the new version) but need nothing. Dev-dependencies are left out,
+//! as in the publish order.
+
+use crate::error::{Result, WorkspaceError};
+use crate::workspace::analyzer::WorkspaceInfo;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+
+/// What a bump means for the rest of the workspace
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpactReport {
+    /// Bumped crate
+    pub package: String,
+    pub current_version: String,
+    pub new_version: String,
+    /// Every member depending on the bumped crate, nearest first, then by name
+    pub dependents: Vec<DependentImpact>,
+}
+
+/// One member affected by the bump
+#[derive(Debug, Clone, Serialize)]
+pub struct DependentImpact {
+    pub name: String,
+    /// Shortest dependency chain from the bumped crate, e.g. `[core, cli, app]`
+    pub path: Vec<String>,
+    /// Requirements on members that get a new version
+    pub requirements: Vec<RequirementChange>,
+    /// Version of the republish (next patch), `None` when not republished
+    pub republish_version: Option<String>,
+    /// `publish = false`: the manifest still needs updates, but nothing is published
+    pub publishable: bool,
+}
+
+/// A dependent's requirement on a member that gets a new version
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementChange {
+    pub dependency: String,
+    /// Current requirement (`None` for a path-only dependency)
+    pub requirement: Option<String>,
+    /// Requirement to write instead, when the current one rejects the new version
+    pub update: Option<String>,
+}
+
+impl DependentImpact {
+    /// Whether the member's manifest has to change
+    pub fn needs_update(&self) -> bool {
+        self.requirements.iter().any(|requirement| requirement.update.is_some())
+    }
+}
+
+impl ImpactReport {
+    /// Impact of releasing `package` as `new_version`
+    pub fn analyze(workspace: &WorkspaceInfo, package: &str, new_version: &Version) -> Result<Self> {
+        let bumped = workspace.get_package(package)?;
+
+        // Reverse runtime edges: crate -> members depending on it
+        let mut reverse: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (name, deps) in &workspace.internal_dependencies {
+            for dep in deps {
+                if dep != name {
+                    reverse.entry(dep.as_str()).or_default().push(name.as_str());
+                }
+            }
+        }
+        let dependents_of = |name: &str| reverse.get(name).cloned().unwrap_or_default();
+
+        // Affected members, breadth-first so each gets its shortest chain
+        let mut reports: BTreeMap<&str, DependentImpact> = BTreeMap::new();
+        let mut queue = VecDeque::from([(bumped.name.as_str(), vec![bumped.name.clone()])]);
+        while let Some((dependency, path)) = queue.pop_front() {
+            for dependent in dependents_of(dependency) {
+                if dependent == bumped.name || reports.contains_key(dependent) {
+                    continue;
+                }
+                let mut dependent_path = path.clone();
+                dependent_path.push(dependent.to_string());
+                queue.push_back((dependent, dependent_path.clone()));
+                reports.insert(
+                    dependent,
+                    DependentImpact {
+                        name: dependent.to_string(),
+                        path: dependent_path,
+                        requirements: Vec::new(),
+                        republish_version: None,
+                        publishable: workspace.get_package(dependent)?.config.is_publishable(),
+                    },
+                );
+            }
+        }
+
+        // Follow new versions: each republish is checked against its own dependents
+        let mut new_versions: BTreeMap<&str, Version> = BTreeMap::from([(bumped.name.as_str(), new_version.clone())]);
+        let mut republished = VecDeque::from([bumped.name.as_str()]);
+        while let Some(dependency) = republished.pop_front() {
+            let version = new_versions[dependency].clone();
+            for dependent in dependents_of(dependency) {
+                let Some(report) = reports.get_mut(dependent) else {
+                    continue;
+                };
+                let requirement = requirement_on(workspace, dependent, dependency);
+                let update = match &requirement {
+                    Some(requirement) => {
+                        let req = VersionReq::parse(requirement).map_err(|e| WorkspaceError::InvalidPackage {
+                            package: dependent.to_string(),
+                            reason: format!("invalid requirement `{}` on {}: {}", requirement, dependency, e),
+                        })?;
+                        (!req.matches(&version)).then(|| updated_requirement(requirement, &version))
+                    }
+                    None => None,
+                };
+                let needs_republish = update.is_some() && report.publishable && report.republish_version.is_none();
+                report.requirements.push(RequirementChange {
+                    dependency: dependency.to_string(),
+                    requirement,
+                    update,
+                });
+
+                if needs_republish {
+                    let member = workspace.get_package(dependent)?;
+                    let current = Version::parse(&member.version).map_err(|e| WorkspaceError::InvalidPackage {
+                        package: dependent.to_string(),
+                        reason: format!("invalid version {}: {}", member.version, e),
+                    })?;
+                    let next = Version::new(current.major, current.minor, current.patch + 1);
+                    report.republish_version = Some(next.to_string());
+                    new_versions.insert(dependent, next);
+                    republished.push_back(dependent);
+                }
+            }
+        }
+
+        let mut dependents: Vec<DependentImpact> = reports.into_values().collect();
+        dependents.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(Self {
+            package: bumped.name.clone(),
+            current_version: bumped.version.clone(),
+            new_version: new_version.to_string(),
+            dependents,
+        })
+    }
+
+    /// Members that have to be published again, in report order
+    pub fn republished(&self) -> impl Iterator<Item = &DependentImpact> {
+        self.dependents.iter().filter(|dependent| dependent.republish_version.is_some())
+    }
+}
+
+/// Version requirement of `dependent` on `dependency`, resolving `workspace = true`
+fn requirement_on(workspace: &WorkspaceInfo, dependent: &str, dependency: &str) -> Option<String> {
+    let package = workspace.packages.get(dependent)?;
+    let spec = package
+        .all_dependencies
+        .get(dependency)
+        .or_else(|| package.all_dependencies.get(&format!("build:{}", dependency)))?;
+
+    if spec.workspace == Some(true) {
+        let inherited = workspace.workspace_config.dependencies.as_ref()?.get(dependency)?;
+        return match inherited {
+            toml::Value::String(version) => Some(version.clone()),
+            other => other.get("version").and_then(|v| v.as_str()).map(str::to_string),
+        };
+    }
+    spec.version.clone()
+}
+
+/// `requirement` moved to `version`, keeping a leading `=`, `^` or `~`
+fn updated_requirement(requirement: &str, version: &Version) -> String {
+    let requirement = requirement.trim();
+    match requirement.chars().next() {
+        Some(op @ ('=' | '^' | '~')) if !requirement.contains(',') => format!("{}{}", op, version),
+        _ => version.to_string(),
+    }
+}
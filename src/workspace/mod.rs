@@ -1,6 +1,14 @@
 //! Workspace analysis.
 
 mod analyzer;
+mod graph;
+mod impact;
+mod publish_plan;
 mod validator;
 
-pub use analyzer::SharedWorkspaceInfo;
+pub use analyzer::{SharedWorkspaceInfo, WorkspaceInfo};
+pub use graph::{GraphFormat, render_graph};
+pub use impact::ImpactReport;
+#[allow(unused_imports)] // Public API - preserved for external consumers
+pub use publish_plan::{PublishPlan, SkipReason, SkippedPackage};
+pub use validator::WorkspaceValidator;
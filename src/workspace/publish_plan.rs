@@ -0,0 +1,160 @@
+//! Dependency-ordered publish planning for workspace releases.
+
+use crate::error::{Result, WorkspaceError};
+use crate::workspace::analyzer::WorkspaceInfo;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Why a package is left out of the publish order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// `publish = false` (or `publish = []`) in the package's Cargo.toml
+    PublishDisabled,
+    /// Listed in `[workspace.metadata.release] skip-publish` or passed explicitly
+    SkipList,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::PublishDisabled => write!(f, "publish = false"),
+            SkipReason::SkipList => write!(f, "in skip list"),
+        }
+    }
+}
+
+/// A package left out of the publish order
+#[derive(Debug, Clone)]
+pub struct SkippedPackage {
+    pub name: String,
+    pub reason: SkipReason,
+}
+
+/// Publish plan for a workspace
+///
+/// Only packages in `tiers` are expected on crates.io; skipped packages still
+/// constrain the order of the packages depending on them.
+#[derive(Debug, Clone)]
+pub struct PublishPlan {
+    /// Packages to publish, grouped into dependency tiers (tier N only depends on tiers < N)
+    pub tiers: Vec<Vec<String>>,
+    /// Packages excluded from publishing, sorted by name
+    pub skipped: Vec<SkippedPackage>,
+}
+
+impl PublishPlan {
+    /// Build a publish plan from workspace analysis
+    ///
+    /// `extra_skip` is merged with the workspace-configured skip list. Naming a
+    /// package that is not in the workspace is an error, to catch typos.
+    pub fn build(workspace: &WorkspaceInfo, extra_skip: &[String]) -> Result<Self> {
+        let mut skip_list = workspace.workspace_config.skip_publish();
+        skip_list.extend(extra_skip.iter().cloned());
+
+        for name in &skip_list {
+            if !workspace.has_package(name) {
+                return Err(WorkspaceError::PackageNotFound { name: name.clone() }.into());
+            }
+        }
+
+        let mut skipped: Vec<SkippedPackage> = workspace
+            .packages
+            .values()
+            .filter_map(|package| {
+                let reason = if !package.config.is_publishable() {
+                    SkipReason::PublishDisabled
+                } else if skip_list.contains(&package.name) {
+                    SkipReason::SkipList
+                } else {
+                    return None;
+                };
+                Some(SkippedPackage {
+                    name: package.name.clone(),
+                    reason,
+                })
+            })
+            .collect();
+        skipped.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Order the full graph so skipped packages still constrain their dependents
+        let tiers = Self::dependency_tiers(workspace)?
+            .into_iter()
+            .map(|tier| {
+                tier.into_iter()
+                    .filter(|name| !skipped.iter().any(|s| &s.name == name))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|tier| !tier.is_empty())
+            .collect();
+
+        Ok(Self { tiers, skipped })
+    }
+
+    /// Group all workspace packages into dependency tiers (Kahn's algorithm)
+    fn dependency_tiers(workspace: &WorkspaceInfo) -> Result<Vec<Vec<String>>> {
+        let mut remaining: HashMap<&str, BTreeSet<&str>> = workspace
+            .packages
+            .keys()
+            .map(|name| {
+                let deps = workspace
+                    .internal_dependencies
+                    .get(name)
+                    .map(|deps| deps.iter().map(String::as_str).filter(|d| d != name).collect())
+                    .unwrap_or_default();
+                (name.as_str(), deps)
+            })
+            .collect();
+
+        let mut tiers = Vec::new();
+        while !remaining.is_empty() {
+            let mut ready: Vec<&str> = remaining
+                .iter()
+                .filter(|(_, deps)| deps.is_empty())
+                .map(|(name, _)| *name)
+                .collect();
+
+            if ready.is_empty() {
+                let mut cycle: Vec<&str> = remaining.keys().copied().collect();
+                cycle.sort();
+                return Err(WorkspaceError::InvalidStructure {
+                    reason: format!("Circular dependency between packages: {}", cycle.join(", ")),
+                }
+                .into());
+            }
+
+            ready.sort();
+            for name in &ready {
+                remaining.remove(name);
+            }
+            for deps in remaining.values_mut() {
+                for name in &ready {
+                    deps.remove(name);
+                }
+            }
+            tiers.push(ready.into_iter().map(str::to_string).collect());
+        }
+
+        Ok(tiers)
+    }
+
+    /// Packages to publish, in order
+    pub fn publish_order(&self) -> impl Iterator<Item = &str> {
+        self.tiers.iter().flatten().map(String::as_str)
+    }
+
+    /// Human-readable summary lines for CLI output
+    pub fn report_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .tiers
+            .iter()
+            .enumerate()
+            .map(|(i, tier)| format!("Tier {}: [{}]", i, tier.join(", ")))
+            .collect();
+
+        for skipped in &self.skipped {
+            lines.push(format!("Skipping publish of {} ({})", skipped.name, skipped.reason));
+        }
+
+        lines
+    }
+}
@@ -3,7 +3,10 @@
 
 use crate::error::Result;
 use crate::workspace::SharedWorkspaceInfo;
+use crate::workspace::analyzer::{DependencySpec, PackageInfo};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Workspace validator
 #[derive(Debug)]
@@ -45,6 +48,9 @@ impl WorkspaceValidator {
         // Version consistency validation
         self.validate_version_consistency(&mut checks, &mut critical_errors, &mut warnings);
 
+        // Publish-breaking patterns
+        self.validate_publish_blockers_into(&mut checks, &mut critical_errors, &mut warnings);
+
         let success = critical_errors.is_empty();
 
         Ok(ValidationResult {
@@ -55,6 +61,231 @@ impl WorkspaceValidator {
         })
     }
 
+    /// Check only for patterns that would break `cargo publish`
+    ///
+    /// Runs before a release starts; unlike [`Self::validate`] this does not
+    /// require members to share the workspace version.
+    pub fn validate_publish_blockers(&self) -> ValidationResult {
+        let mut checks = Vec::new();
+        let mut critical_errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        self.validate_publish_blockers_into(&mut checks, &mut critical_errors, &mut warnings);
+
+        ValidationResult {
+            success: critical_errors.is_empty(),
+            checks,
+            critical_errors,
+            warnings,
+        }
+    }
+
+    fn validate_publish_blockers_into(
+        &self,
+        checks: &mut Vec<ValidationCheck>,
+        critical_errors: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        self.validate_dependency_cycles(checks, critical_errors);
+        self.validate_dependency_specs(checks, critical_errors, warnings);
+    }
+
+    /// Detect cycles between workspace members, including through dev-dependencies
+    ///
+    /// Cargo tolerates dev-dependency cycles for builds, but a versioned
+    /// dev-dependency on a crate that is not yet published makes the cycle
+    /// impossible to publish in any order.
+    fn validate_dependency_cycles(
+        &self,
+        checks: &mut Vec<ValidationCheck>,
+        critical_errors: &mut Vec<String>,
+    ) {
+        let start_time = std::time::Instant::now();
+
+        // Edges between members over all dependency kinds
+        let mut edges: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for (package_name, package_info) in &self.workspace.packages {
+            let targets = edges.entry(package_name.as_str()).or_default();
+            for key in package_info.all_dependencies.keys() {
+                let (_, dep_name) = split_dependency_key(key);
+                if dep_name != package_name
+                    && let Some((name, _)) = self.workspace.packages.get_key_value(dep_name)
+                {
+                    targets.insert(name.as_str());
+                }
+            }
+        }
+
+        let cycles: Vec<String> = strongly_connected_components(&edges)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let via_dev = component.iter().any(|name| {
+                    self.workspace.packages[*name]
+                        .all_dependencies
+                        .keys()
+                        .filter(|key| key.starts_with("dev:"))
+                        .any(|key| component.contains(&split_dependency_key(key).1))
+                });
+                format!(
+                    "Dependency cycle between {}{}",
+                    component.join(", "),
+                    if via_dev { " (via dev-dependencies)" } else { "" }
+                )
+            })
+            .collect();
+
+        let duration = start_time.elapsed().as_millis() as u64;
+
+        checks.push(ValidationCheck {
+            name: "Dependency Cycles".to_string(),
+            passed: cycles.is_empty(),
+            message: if cycles.is_empty() {
+                "No dependency cycles between workspace members".to_string()
+            } else {
+                cycles.join("; ")
+            },
+            critical: true,
+            duration_ms: duration,
+        });
+        critical_errors.extend(cycles);
+    }
+
+    /// Check dependency specifications of publishable members
+    ///
+    /// Wildcard versions and path dependencies without a version are critical.
+    /// Path dependencies on crates outside the workspace only warn, since they
+    /// publish fine as long as that crate is already on crates.io.
+    fn validate_dependency_specs(
+        &self,
+        checks: &mut Vec<ValidationCheck>,
+        critical_errors: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        let start_time = std::time::Instant::now();
+
+        let member_dirs: HashMap<PathBuf, &str> = self
+            .workspace
+            .packages
+            .values()
+            .map(|p| (normalize_path(&p.absolute_path), p.name.as_str()))
+            .collect();
+
+        let mut problems = Vec::new();
+        let mut package_names: Vec<&String> = self.workspace.packages.keys().collect();
+        package_names.sort();
+
+        for package_name in package_names {
+            let package_info = &self.workspace.packages[package_name];
+            if !package_info.config.is_publishable() {
+                continue;
+            }
+
+            let mut dependency_keys: Vec<&String> = package_info.all_dependencies.keys().collect();
+            dependency_keys.sort();
+
+            for key in dependency_keys {
+                let (kind, dep_name) = split_dependency_key(key);
+                let is_dev = kind == "dev-dependency";
+                let (version, path) =
+                    self.resolve_dependency(package_info, dep_name, &package_info.all_dependencies[key]);
+
+                if let Some(version) = &version
+                    && version.contains('*')
+                {
+                    problems.push((
+                        true,
+                        format!(
+                            "'{}' {} '{}' uses wildcard version '{}' (rejected by crates.io)",
+                            package_name, kind, dep_name, version
+                        ),
+                    ));
+                }
+
+                // Path-only dev-dependencies are stripped by cargo publish
+                let Some(path) = path.filter(|_| !is_dev) else {
+                    continue;
+                };
+
+                if version.is_none() {
+                    problems.push((
+                        true,
+                        format!(
+                            "'{}' {} '{}' is a path dependency without a version",
+                            package_name, kind, dep_name
+                        ),
+                    ));
+                }
+
+                if !member_dirs.contains_key(&normalize_path(&path)) {
+                    problems.push((
+                        false,
+                        format!(
+                            "'{}' {} '{}' references {} by path, which is not a workspace member",
+                            package_name,
+                            kind,
+                            dep_name,
+                            path.display()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let duration = start_time.elapsed().as_millis() as u64;
+        let critical: Vec<String> = problems.iter().filter(|(c, _)| *c).map(|(_, m)| m.clone()).collect();
+
+        checks.push(ValidationCheck {
+            name: "Publishable Dependencies".to_string(),
+            passed: critical.is_empty(),
+            message: if problems.is_empty() {
+                "All dependencies of publishable packages are publishable".to_string()
+            } else {
+                problems.iter().map(|(_, m)| m.as_str()).collect::<Vec<_>>().join("; ")
+            },
+            critical: true,
+            duration_ms: duration,
+        });
+
+        critical_errors.extend(critical);
+        warnings.extend(problems.into_iter().filter(|(c, _)| !*c).map(|(_, m)| m));
+    }
+
+    /// Resolve a dependency's effective version and absolute path
+    ///
+    /// `workspace = true` entries are looked up in `[workspace.dependencies]`,
+    /// whose paths are relative to the workspace root.
+    fn resolve_dependency(
+        &self,
+        package_info: &PackageInfo,
+        dep_name: &str,
+        spec: &DependencySpec,
+    ) -> (Option<String>, Option<PathBuf>) {
+        if spec.workspace != Some(true) {
+            let path = spec.path.as_ref().map(|p| package_info.absolute_path.join(p));
+            return (spec.version.clone(), path);
+        }
+
+        let inherited = self
+            .workspace
+            .workspace_config
+            .dependencies
+            .as_ref()
+            .and_then(|deps| deps.get(dep_name));
+
+        match inherited {
+            Some(toml::Value::String(version)) => (Some(version.clone()), None),
+            Some(toml::Value::Table(table)) => (
+                table.get("version").and_then(|v| v.as_str()).map(str::to_string),
+                table
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .map(|p| self.workspace.root.join(p)),
+            ),
+            _ => (None, None),
+        }
+    }
+
     /// Validate version consistency across packages
     fn validate_version_consistency(
         &self,
@@ -120,6 +351,85 @@ impl WorkspaceValidator {
     }
 }
 
+/// Split an `all_dependencies` key into its kind and dependency name
+fn split_dependency_key(key: &str) -> (&'static str, &str) {
+    if let Some(name) = key.strip_prefix("dev:") {
+        ("dev-dependency", name)
+    } else if let Some(name) = key.strip_prefix("build:") {
+        ("build-dependency", name)
+    } else {
+        ("dependency", key)
+    }
+}
+
+/// Canonicalize a path for comparison, falling back to the path as given
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Tarjan's algorithm; each component is returned sorted by name
+fn strongly_connected_components<'a>(
+    edges: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+) -> Vec<Vec<&'a str>> {
+    struct State<'a> {
+        index: usize,
+        indices: HashMap<&'a str, usize>,
+        lowlinks: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<&'a str>>,
+    }
+
+    fn visit<'a>(node: &'a str, edges: &BTreeMap<&'a str, BTreeSet<&'a str>>, state: &mut State<'a>) {
+        state.indices.insert(node, state.index);
+        state.lowlinks.insert(node, state.index);
+        state.index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for &next in edges.get(node).into_iter().flatten() {
+            if !state.indices.contains_key(next) {
+                visit(next, edges, state);
+                let low = state.lowlinks[node].min(state.lowlinks[next]);
+                state.lowlinks.insert(node, low);
+            } else if state.on_stack.contains(next) {
+                let low = state.lowlinks[node].min(state.indices[next]);
+                state.lowlinks.insert(node, low);
+            }
+        }
+
+        if state.lowlinks[node] == state.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort();
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: 0,
+        indices: HashMap::new(),
+        lowlinks: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+
+    for &node in edges.keys() {
+        if !state.indices.contains_key(node) {
+            visit(node, edges, &mut state);
+        }
+    }
+
+    state.components
+}
+
 impl ValidationResult {
     pub fn failed_checks(&self) -> Vec<&ValidationCheck> {
         self.checks.iter().filter(|check| !check.passed).collect()
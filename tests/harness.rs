@@ -0,0 +1,60 @@
+//! End-to-end release against the local stand-ins of the test harness.
+
+use kodegen_bundler_release::test_harness::TestHarness;
+use std::process::Command;
+
+#[test]
+fn releases_to_the_mock_github() {
+    let harness = TestHarness::start("acme", "widget").unwrap();
+    let package = harness.seed_package("widget", "0.1.0", "").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_kodegen_bundler_release"))
+        .args([".", "--skip", "build"])
+        .current_dir(&package)
+        .envs(harness.env())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "release failed:\n{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let release = harness.github.release("v0.1.0").expect("no release for v0.1.0");
+    assert!(!release.draft);
+}
+
+#[test]
+fn keeps_release_branches_ahead_of_their_tag() {
+    let harness = TestHarness::start("acme", "widget").unwrap();
+    let package = harness
+        .seed_package(
+            "widget",
+            "0.1.0",
+            "[package.metadata.release.retention]\nkeep-release-branches = 1\n",
+        )
+        .unwrap();
+    for tag in ["v0.0.1", "v0.0.2"] {
+        harness.github.add_release(tag, false);
+    }
+    harness.github.add_branch("v0.0.1", 2);
+    harness.github.add_branch("v0.0.2", 0);
+    harness.github.add_branch("main", 0);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_kodegen_bundler_release"))
+        .args([".", "--skip", "build"])
+        .current_dir(&package)
+        .envs(harness.env())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "release failed:\n{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // v0.0.2 is fully in its tag; v0.0.1 has commits the tag lacks
+    assert_eq!(harness.github.branches(), ["v0.0.1", "main"]);
+}